@@ -0,0 +1,71 @@
+//! # 解析器基准测试
+//!
+//! 测量最常用的几种语言解析器的解析耗时
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fuck_shit_code::{create_parser_for_language, LanguageType};
+use std::path::Path;
+
+/// 每个样本重复的函数数量
+const FUNCTIONS_PER_SAMPLE: usize = 200;
+
+/// 生成由大量相似函数组成的样本源码
+///
+/// # Arguments
+/// * `template` - 单个函数的模板，`{}` 会被替换为序号
+///
+/// # Returns
+/// * `String` - 样本源码
+fn sample(template: &str) -> String {
+    (0..FUNCTIONS_PER_SAMPLE)
+        .map(|i| template.replace("{}", &i.to_string()))
+        .collect()
+}
+
+/// 测量指定语言解析样本源码的耗时
+///
+/// # Arguments
+/// * `c` - criterion 上下文
+/// * `name` - 基准名称
+/// * `language` - 语言类型
+/// * `file_name` - 传给解析器的文件名
+/// * `source` - 样本源码
+fn bench_language(
+    c: &mut Criterion,
+    name: &str,
+    language: LanguageType,
+    file_name: &str,
+    source: &str,
+) {
+    let parser = create_parser_for_language(language);
+    let path = Path::new(file_name);
+
+    c.bench_function(name, |b| {
+        b.iter(|| parser.parse(path, black_box(source)).unwrap())
+    });
+}
+
+fn parsers(c: &mut Criterion) {
+    let javascript = sample(
+        "// 处理第 {} 项\nfunction handle{}(items, options) {\n  const result = items.filter((item) => item.id > {});\n  if (options && options.strict) {\n    return result.map((item) => item.value);\n  }\n  return result;\n}\n\n",
+    );
+    let python = sample(
+        "def handle_{}(items, options=None):\n    \"\"\"处理第 {} 项\"\"\"\n    result = [item for item in items if item.id > {}]\n    if options and options.strict:\n        return [item.value for item in result]\n    return result\n\n\n",
+    );
+    let c_source = sample(
+        "/* 处理第 {} 项 */\nint handle_{}(const int *items, int count) {\n    int total = 0;\n    for (int i = 0; i < count; i++) {\n        if (items[i] > {} && items[i] % 2 == 0) {\n            total += items[i];\n        }\n    }\n    return total;\n}\n\n",
+    );
+
+    bench_language(
+        c,
+        "parse_javascript",
+        LanguageType::JavaScript,
+        "bench.js",
+        &javascript,
+    );
+    bench_language(c, "parse_python", LanguageType::Python, "bench.py", &python);
+    bench_language(c, "parse_c", LanguageType::C, "bench.c", &c_source);
+}
+
+criterion_group!(benches, parsers);
+criterion_main!(benches);
@@ -2,22 +2,40 @@
 //!
 //! 核心分析逻辑的实现，负责协调各个组件完成代码分析
 
-use crate::common::find_source_files;
+use crate::common::{
+    check_file_extension, check_file_size, decode_source, find_source_files, read_file_manifest,
+    stable_hash, unsupported_extensions, LanguageType, SkipReason, SkippedFile, SourceFiles,
+    StableHasher,
+};
 use crate::config::AnalysisConfig;
 use crate::error::{AppError, AppResult};
 use crate::i18n::{Language, Translator};
-use crate::metrics::{MetricFactory, MetricResult};
-use crate::parser::{create_parser_for_file, ParseResult};
+use crate::metrics::{
+    sanitize_score, Issue, IssueKind, Metric, MetricFactory, MetricResult, DUPLICATION_METRIC_NAME,
+};
+use crate::parser::{
+    create_parser_for_file, detect_anonymous_functions, uses_generic_parser, BaseParseResult,
+    Function, ParseResult,
+};
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use super::result::{AnalysisResult, FileAnalysisResult};
+use super::bench::ParseBenchmark;
+use super::cache::{CacheStats, ParseCache};
+use super::changes::ChangedLines;
+use super::clones::{compute_shingles, find_cross_file_clones, CloneBlock, Shingle};
+use super::dead_code::{collect_symbols, find_unreferenced, DeadCodeCandidate, FileSymbols};
+use super::result::{AnalysisResult, FileAnalysisResult, LanguageScore, ParameterDistribution};
+use super::todo::stale_todo_issues;
+
+/// 内存中源码在报告中显示的路径（不含扩展名）
+const MEMORY_SOURCE_PATH: &str = "<memory>";
 
 /// 代码分析器主结构
 pub struct CodeAnalyzer {
@@ -31,6 +49,13 @@ pub struct CodeAnalyzer {
     metric_factory: MetricFactory,
 }
 
+impl Default for CodeAnalyzer {
+    /// 创建默认分析器
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CodeAnalyzer {
     /// 创建新的代码分析器实例
     ///
@@ -53,7 +78,29 @@ impl CodeAnalyzer {
     /// * `language` - 语言设置
     pub fn set_language(&mut self, language: Language) {
         self.translator = Translator::new(language);
-        self.metric_factory = MetricFactory::new(self.translator.clone());
+        self.metric_factory.set_translator(self.translator.clone());
+    }
+
+    /// 注册自定义度量指标
+    ///
+    /// # Arguments
+    /// * `metric` - 自定义指标
+    ///
+    /// # Returns
+    /// * `AppResult<()>` - 名称或标识与已有指标重复时返回配置错误
+    pub fn register_metric<M>(&mut self, metric: M) -> AppResult<()>
+    where
+        M: Metric + Send + Sync + 'static,
+    {
+        self.metric_factory.register_metric(Arc::new(metric))
+    }
+
+    /// 列出可选择的指标（含已注册的自定义指标）
+    ///
+    /// # Returns
+    /// * `Vec<(String, String, String)>` - （选择用的名称、指标名称、描述）
+    pub fn available_metrics(&self) -> Vec<(String, String, String)> {
+        self.metric_factory.available_metrics()
     }
 
     /// 设置静默模式
@@ -76,28 +123,220 @@ impl CodeAnalyzer {
         &self,
         path: &Path,
         config: &AnalysisConfig,
+    ) -> AppResult<AnalysisResult> {
+        self.analyze_paths_with_config(&[path.to_path_buf()], config)
+    }
+
+    /// 使用配置分析多个路径，结果汇总为一份
+    ///
+    /// 各路径分别查找源文件后合并，同一文件出现在多个路径下时只分析一次。
+    ///
+    /// # Arguments
+    /// * `paths` - 分析路径（目录或文件）
+    /// * `config` - 分析配置
+    ///
+    /// # Returns
+    /// * `AppResult<AnalysisResult>` - 分析结果
+    pub fn analyze_paths_with_config(
+        &self,
+        paths: &[PathBuf],
+        config: &AnalysisConfig,
     ) -> AppResult<AnalysisResult> {
         // 验证路径
-        self.validate_path(path)?;
+        for path in paths {
+            self.validate_path(path)?;
+        }
+
+        let unknown_metrics = self.metric_factory.unknown_metric_names(config);
+        if !unknown_metrics.is_empty() {
+            return Err(AppError::ConfigError(format!(
+                "未知的指标: {}（可用 --list-metrics 查看可选的指标）",
+                unknown_metrics.join(", ")
+            )));
+        }
+
+        for key in self.metric_factory.unknown_weight_keys(config) {
+            log::warn!("未知的指标 {}，忽略其权重配置", key);
+        }
+
+        // 读取 git 修改范围（不在 git 仓库中时退回到分析全部文件）
+        let changes = match &config.since {
+            Some(since) => self.load_changes(paths, since),
+            None => None,
+        };
+
+        let mut result = self.analyze_paths(paths, config, changes.as_ref())?;
+
+        if config.changed_lines_only {
+            if let Some(changes) = &changes {
+                changes.retain_changed_issues(&mut result);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// 读取各分析路径所在仓库相对于指定版本的修改
+    ///
+    /// # Arguments
+    /// * `paths` - 分析路径
+    /// * `since` - 比较的版本
+    ///
+    /// # Returns
+    /// * `Option<ChangedLines>` - 修改范围（所有路径都不在 git 仓库中时为 None）
+    fn load_changes(&self, paths: &[PathBuf], since: &str) -> Option<ChangedLines> {
+        let mut changes: Option<ChangedLines> = None;
+
+        for path in paths {
+            match ChangedLines::load(path, since) {
+                Ok(loaded) => changes
+                    .get_or_insert_with(ChangedLines::default)
+                    .extend(loaded),
+                Err(_) => log::warn!(
+                    "{} 不在 git 仓库中，无法按 {} 筛选修改过的文件，将分析全部文件",
+                    path.display(),
+                    since
+                ),
+            }
+        }
+
+        changes
+    }
 
-        // 处理单文件情况
-        if path.is_file() {
-            return self.analyze_single_file(path);
+    /// 分析路径（只分析修改过的文件时跳过未修改的文件）
+    ///
+    /// # Arguments
+    /// * `paths` - 分析路径
+    /// * `config` - 分析配置
+    /// * `changes` - 修改范围
+    ///
+    /// # Returns
+    /// * `AppResult<AnalysisResult>` - 分析结果
+    fn analyze_paths(
+        &self,
+        paths: &[PathBuf],
+        config: &AnalysisConfig,
+        changes: Option<&ChangedLines>,
+    ) -> AppResult<AnalysisResult> {
+        let is_changed = |file: &Path| changes.is_none_or(|c| c.contains_file(file));
+
+        // 处理单文件情况（被扩展名或大小过滤掉时走通用流程，记录跳过原因）
+        if let [path] = paths {
+            if config.files_from.is_none()
+                && path.is_file()
+                && is_changed(path)
+                && check_file_extension(path, &config.extensions).is_ok()
+                && check_file_size(path, config.min_file_size, config.max_file_size).is_ok()
+            {
+                return self.analyze_single_file(path, config);
+            }
         }
 
-        // 搜索源文件
-        let files = self.find_files(path, config)?;
+        // 搜索源文件（提供文件清单时直接使用清单）
+        let mut search = if let Some(manifest) = &config.files_from {
+            read_file_manifest(manifest, &config.extensions)
+                .map_err(|e| AppError::ConfigError(format!("{:#}", e)))?
+        } else {
+            self.search_paths(paths, config)?
+        };
+        let (files, unchanged): (Vec<PathBuf>, Vec<PathBuf>) =
+            search.files.into_iter().partition(|file| is_changed(file));
+        search.skipped.extend(
+            unchanged
+                .iter()
+                .map(|file| SkippedFile::new(file, SkipReason::Unchanged)),
+        );
+
+        // 跳过大小超出范围的文件（如巨大的压缩文件）
+        let mut files_in_range = Vec::with_capacity(files.len());
+        let mut out_of_range = 0;
+        for file in files {
+            match check_file_size(&file, config.min_file_size, config.max_file_size) {
+                Ok(()) => files_in_range.push(file),
+                Err(skipped) => {
+                    search.skipped.push(skipped);
+                    out_of_range += 1;
+                }
+            }
+        }
+        let files = files_in_range;
+        if out_of_range > 0 {
+            log::warn!(
+                "已跳过 {} 个大小不在 {}-{} 字节范围内的文件",
+                out_of_range,
+                config.min_file_size,
+                config.max_file_size
+            );
+        }
 
         // 检查是否为空项目
         if files.is_empty() {
-            return Ok(self.create_empty_result());
+            let mut result = self.create_empty_result();
+            result.run_id = compute_run_id(&mut [], config);
+            result.skipped_files = sorted_skipped(search.skipped);
+            return Ok(result);
         }
 
         // 分析文件
-        let file_results = self.analyze_files_parallel(&files)?;
+        let cache = config.cache_dir.as_deref().map(ParseCache::open);
+        let (file_results, failed) = self.analyze_files_parallel(&files, config, cache.as_ref())?;
+        search.skipped.extend(failed);
 
         // 汇总结果
-        self.aggregate_results(file_results)
+        let mut result = self.aggregate_results(file_results, config)?;
+        result.skipped_files = sorted_skipped(search.skipped);
+        result.cache_stats = finish_cache(cache);
+        Ok(result)
+    }
+
+    /// 重复解析目标文件并测量解析吞吐量（不计算指标）
+    ///
+    /// 文件内容只读取一次，计时只覆盖解析；解析失败的文件不计入。
+    ///
+    /// # Arguments
+    /// * `paths` - 分析路径
+    /// * `config` - 分析配置
+    /// * `iterations` - 解析轮数
+    ///
+    /// # Returns
+    /// * `AppResult<ParseBenchmark>` - 测量结果
+    pub fn bench_parse(
+        &self,
+        paths: &[PathBuf],
+        config: &AnalysisConfig,
+        iterations: usize,
+    ) -> AppResult<ParseBenchmark> {
+        for path in paths {
+            self.validate_path(path)?;
+        }
+
+        let files = self.search_paths(paths, config)?.files;
+        let sources: Vec<(PathBuf, String)> = files
+            .into_iter()
+            .filter_map(|file| {
+                self.read_file(&file, config)
+                    .ok()
+                    .map(|content| (file, content))
+            })
+            .filter(|(file, content)| self.parse_file(file, content, config, None).is_ok())
+            .collect();
+
+        let start = Instant::now();
+        for _ in 0..iterations {
+            for (file, content) in &sources {
+                let _ = self.parse_file(file, content, config, None);
+            }
+        }
+
+        Ok(ParseBenchmark {
+            files: sources.len(),
+            lines: sources
+                .iter()
+                .map(|(_, content)| content.lines().count())
+                .sum(),
+            iterations,
+            elapsed: start.elapsed(),
+        })
     }
 
     /// 使用排除模式进行分析（向后兼容）
@@ -138,6 +377,48 @@ impl CodeAnalyzer {
         Ok(())
     }
 
+    /// 在多个路径中查找源文件并合并
+    ///
+    /// 直接指定的文件不做语言过滤；同一文件（按规范化路径判断）只保留第一次出现。
+    ///
+    /// # Arguments
+    /// * `paths` - 分析路径（目录或文件）
+    /// * `config` - 配置
+    ///
+    /// # Returns
+    /// * `AppResult<SourceFiles>` - 找到的文件和被跳过的文件
+    fn search_paths(&self, paths: &[PathBuf], config: &AnalysisConfig) -> AppResult<SourceFiles> {
+        let mut merged = SourceFiles::default();
+        let mut seen = HashSet::new();
+
+        for path in paths {
+            let search = if path.is_file() {
+                match check_file_extension(path, &config.extensions) {
+                    Ok(()) => SourceFiles {
+                        files: vec![path.clone()],
+                        skipped: Vec::new(),
+                    },
+                    Err(skipped) => SourceFiles {
+                        files: Vec::new(),
+                        skipped: vec![skipped],
+                    },
+                }
+            } else {
+                self.find_files(path, config)?
+            };
+
+            for file in search.files {
+                let key = fs::canonicalize(&file).unwrap_or_else(|_| file.clone());
+                if seen.insert(key) {
+                    merged.files.push(file);
+                }
+            }
+            merged.skipped.extend(search.skipped);
+        }
+
+        Ok(merged)
+    }
+
     /// 查找源文件
     ///
     /// # Arguments
@@ -145,16 +426,23 @@ impl CodeAnalyzer {
     /// * `config` - 配置
     ///
     /// # Returns
-    /// * `AppResult<Vec<PathBuf>>` - 找到的文件列表
-    fn find_files(&self, path: &Path, config: &AnalysisConfig) -> AppResult<Vec<PathBuf>> {
+    /// * `AppResult<SourceFiles>` - 找到的文件和被跳过的文件
+    fn find_files(&self, path: &Path, config: &AnalysisConfig) -> AppResult<SourceFiles> {
         if !self.silent {
             self.print_search_progress();
         }
 
-        let files = find_source_files(
+        for ext in unsupported_extensions(&config.extensions) {
+            log::warn!("不支持的扩展名 .{}，不会分析任何此类文件", ext);
+        }
+
+        let search = find_source_files(
             path,
             &config.include_patterns,
             &config.exclude_patterns,
+            &config.extensions,
+            config.use_ignore_files,
+            config.respect_gitignore,
             |count| {
                 if !self.silent {
                     self.update_search_progress(count);
@@ -163,15 +451,15 @@ impl CodeAnalyzer {
         )?;
 
         if !self.silent {
-            self.print_files_found(files.len());
+            self.print_files_found(search.files.len());
         }
 
-        Ok(files)
+        Ok(search)
     }
 
     /// 打印搜索进度
     fn print_search_progress(&self) {
-        print!(
+        eprint!(
             "🔍 {}",
             self.translator.translate("analyzer.searching_files")
         );
@@ -182,7 +470,7 @@ impl CodeAnalyzer {
     /// # Arguments
     /// * `count` - 当前文件数
     fn update_search_progress(&self, count: usize) {
-        print!(
+        eprint!(
             "\r🔍 {} {}",
             self.translator.translate("analyzer.searching_files"),
             count
@@ -194,7 +482,7 @@ impl CodeAnalyzer {
     /// # Arguments
     /// * `count` - 文件数量
     fn print_files_found(&self, count: usize) {
-        println!(
+        eprintln!(
             "\r{}\r📂 {}: {}",
             " ".repeat(80),
             self.translator.translate("analyzer.files_found"),
@@ -218,48 +506,54 @@ impl CodeAnalyzer {
             total_files: 0,
             total_lines: 0,
             is_empty: true,
+            run_id: String::new(),
+            parameter_distribution: ParameterDistribution::default(),
+            analyzed_files: Vec::new(),
+            skipped_files: Vec::new(),
+            languages: Vec::new(),
+            cache_stats: None,
         }
     }
 
     /// 打印空项目消息
     fn print_empty_project_message(&self) {
-        println!();
-        println!(
+        eprintln!();
+        eprintln!(
             "  {}",
             "╔════════════════════════════════════════╗".bright_yellow()
         );
-        println!(
+        eprintln!(
             "  {}",
             "║       🏜️  荒芜代码检测器  🏜️         ║".bright_yellow()
         );
-        println!(
+        eprintln!(
             "  {}",
             "╚════════════════════════════════════════╝".bright_yellow()
         );
-        println!();
+        eprintln!();
 
         match self.translator.get_language() {
             Language::ZhCN => self.print_empty_message_zh(),
             Language::EnUS => self.print_empty_message_en(),
         }
 
-        println!();
+        eprintln!();
     }
 
     /// 打印中文空项目消息
     fn print_empty_message_zh(&self) {
-        println!("  {}", "😅 检测到一片荒芜...".bright_cyan());
-        println!("  {}", "📭 这里空空如也，连一行代码都没有！".yellow());
-        println!();
-        println!("  {}", "建议：".bright_magenta());
-        println!(
+        eprintln!("  {}", "😅 检测到一片荒芜...".bright_cyan());
+        eprintln!("  {}", "📭 这里空空如也，连一行代码都没有！".yellow());
+        eprintln!();
+        eprintln!("  {}", "建议：".bright_magenta());
+        eprintln!(
             "  {}",
             "1. 🎯 快去写点代码吧，不然我没东西可以吐槽了".green()
         );
-        println!("  {}", "2. 💡 或者检查一下路径是否正确？".green());
-        println!("  {}", "3. 🤔 也可能是排除规则太严格了？".green());
-        println!();
-        println!(
+        eprintln!("  {}", "2. 💡 或者检查一下路径是否正确？".green());
+        eprintln!("  {}", "3. 🤔 也可能是排除规则太严格了？".green());
+        eprintln!();
+        eprintln!(
             "  {}",
             "💭 记住：空的项目是最干净的，但也是最没用的！".bright_blue()
         );
@@ -267,24 +561,24 @@ impl CodeAnalyzer {
 
     /// 打印英文空项目消息
     fn print_empty_message_en(&self) {
-        println!("  {}", "😅 Detected a wasteland...".bright_cyan());
-        println!(
+        eprintln!("  {}", "😅 Detected a wasteland...".bright_cyan());
+        eprintln!(
             "  {}",
             "📭 It's empty here, not even a single line of code!".yellow()
         );
-        println!();
-        println!("  {}", "Suggestions:".bright_magenta());
-        println!(
+        eprintln!();
+        eprintln!("  {}", "Suggestions:".bright_magenta());
+        eprintln!(
             "  {}",
             "1. 🎯 Go write some code, or I have nothing to roast!".green()
         );
-        println!("  {}", "2. 💡 Or check if the path is correct?".green());
-        println!(
+        eprintln!("  {}", "2. 💡 Or check if the path is correct?".green());
+        eprintln!(
             "  {}",
             "3. 🤔 Maybe the exclusion rules are too strict?".green()
         );
-        println!();
-        println!(
+        eprintln!();
+        eprintln!(
             "  {}",
             "💭 Remember: Empty projects are the cleanest, but also the most useless!"
                 .bright_blue()
@@ -295,74 +589,233 @@ impl CodeAnalyzer {
     ///
     /// # Arguments
     /// * `path` - 文件路径
+    /// * `config` - 分析配置
     ///
     /// # Returns
     /// * `AppResult<AnalysisResult>` - 分析结果
-    fn analyze_single_file(&self, path: &Path) -> AppResult<AnalysisResult> {
+    fn analyze_single_file(
+        &self,
+        path: &Path,
+        config: &AnalysisConfig,
+    ) -> AppResult<AnalysisResult> {
         // 读取文件内容
-        let content = self.read_file(path)?;
+        let content = self.read_file(path, config)?;
+
+        self.analyze_source(path, &content, config)
+    }
+
+    /// 按语言分析内存中的源码（如编辑器中未保存的缓冲区），不访问文件系统
+    ///
+    /// 报告中的路径为 `<memory>.<扩展名>`；不支持的语言使用通用解析器，路径为 `<memory>`。
+    ///
+    /// # Arguments
+    /// * `content` - 源码内容
+    /// * `language` - 源码语言
+    /// * `config` - 分析配置
+    ///
+    /// # Returns
+    /// * `AppResult<AnalysisResult>` - 只包含一个文件的分析结果
+    pub fn analyze_string(
+        &self,
+        content: &str,
+        language: LanguageType,
+        config: &AnalysisConfig,
+    ) -> AppResult<AnalysisResult> {
+        let path = match language.primary_extension() {
+            Some(ext) => PathBuf::from(format!("{}.{}", MEMORY_SOURCE_PATH, ext)),
+            None => PathBuf::from(MEMORY_SOURCE_PATH),
+        };
+
+        self.analyze_source(&path, content, config)
+    }
+
+    /// 分析内存中的源码（如标准输入读取的代码片段）
+    ///
+    /// `path` 不需要真实存在，只用于按扩展名选择解析器和在报告中显示。
+    ///
+    /// # Arguments
+    /// * `path` - 源码对应的路径
+    /// * `content` - 源码内容
+    /// * `config` - 分析配置
+    ///
+    /// # Returns
+    /// * `AppResult<AnalysisResult>` - 只包含一个文件的分析结果
+    pub fn analyze_source(
+        &self,
+        path: &Path,
+        content: &str,
+        config: &AnalysisConfig,
+    ) -> AppResult<AnalysisResult> {
+        let content = content.strip_prefix('\u{feff}').unwrap_or(content);
 
         // 解析文件
-        let parse_result = self.parse_file(path, &content)?;
+        let cache = config.cache_dir.as_deref().map(ParseCache::open);
+        let parse_result = self.parse_file(path, content, config, cache.as_ref())?;
 
         // 分析指标
-        let metrics = self.analyze_metrics(&*parse_result);
+        let all_metrics = self.metric_factory.create_all_metrics(config);
+        let mut metrics = self.analyze_metrics(&*parse_result, &all_metrics);
 
         // 计算得分
         let file_score = self.calculate_score(&metrics);
 
         // 收集问题
-        let issues = self.collect_issues(&metrics);
+        let (issues, dropped_issues) =
+            self.collect_file_issues(path, content, &mut metrics, config);
 
         // 创建结果
-        Ok(self.create_single_file_result(
+        let mut result = self.create_single_file_result(
             path,
             file_score,
             metrics,
             issues,
             parse_result.get_total_lines(),
-        ))
+            parse_result.get_language(),
+        );
+        result.run_id = compute_run_id(
+            &mut [(path.display().to_string(), stable_hash(content.as_bytes()))],
+            config,
+        );
+        result.parameter_distribution =
+            ParameterDistribution::from_functions(parse_result.get_functions());
+        result.files_analyzed[0].dropped_issues = dropped_issues;
+        result.cache_stats = finish_cache(cache);
+
+        Ok(result)
     }
 
     /// 读取文件内容
     ///
+    /// 去除 UTF-8 BOM，启用 `encoding` 特性时会把非 UTF-8 编码转换为 UTF-8；
+    /// 无法解码时，开启 `lossy_utf8` 则把无效字节替换为 U+FFFD 后继续分析
+    ///
     /// # Arguments
     /// * `path` - 文件路径
+    /// * `config` - 分析配置
     ///
     /// # Returns
     /// * `AppResult<String>` - 文件内容
-    fn read_file(&self, path: &Path) -> AppResult<String> {
-        fs::read_to_string(path).map_err(|e| AppError::Io(e))
+    fn read_file(&self, path: &Path, config: &AnalysisConfig) -> AppResult<String> {
+        let bytes = fs::read(path)?;
+        let (content, encoding) = match decode_source(&bytes) {
+            Ok(decoded) => decoded,
+            Err(e) if config.lossy_utf8 && e.kind() == std::io::ErrorKind::InvalidData => {
+                log::warn!("{} 不是有效的 UTF-8，已按有损方式解码", path.display());
+                (
+                    String::from_utf8_lossy(&bytes).into_owned(),
+                    "UTF-8 (lossy)",
+                )
+            }
+            Err(e) => return Err(e.into()),
+        };
+        log::debug!("{} 的编码: {}", path.display(), encoding);
+
+        Ok(content)
     }
 
-    /// 解析文件
+    /// 解析文件（启用缓存时优先复用缓存的解析结果）
     ///
     /// # Arguments
     /// * `path` - 文件路径
     /// * `content` - 文件内容
+    /// * `config` - 分析配置
+    /// * `cache` - 解析缓存
     ///
     /// # Returns
     /// * `AppResult<Box<dyn ParseResult>>` - 解析结果
-    fn parse_file(&self, path: &Path, content: &str) -> AppResult<Box<dyn ParseResult>> {
-        let parser = create_parser_for_file(path);
-        parser
-            .parse(path, content)
-            .map_err(|e| AppError::ParseError(e.to_string()))
+    fn parse_file(
+        &self,
+        path: &Path,
+        content: &str,
+        config: &AnalysisConfig,
+        cache: Option<&ParseCache>,
+    ) -> AppResult<Box<dyn ParseResult>> {
+        let parse_result = match cache.and_then(|cache| cache.get(path, content)) {
+            Some(cached) => Box::new(cached) as Box<dyn ParseResult>,
+            None => {
+                let start = Instant::now();
+                let parse_result = create_parser_for_file(path)
+                    .parse(path, content)
+                    .map_err(|e| AppError::ParseError(e.to_string()))?;
+                if let Some(cache) = cache {
+                    cache.insert(path, content, &*parse_result, start.elapsed());
+                }
+                parse_result
+            }
+        };
+
+        Ok(self.apply_anonymous_policy(path, parse_result, config))
+    }
+
+    /// 按配置统一计入或排除匿名函数，并按需填充函数体
+    ///
+    /// 各语言解析器只负责具名函数，匿名函数在这里统一检测，保证命名和统计方式一致。
+    ///
+    /// # Arguments
+    /// * `path` - 文件路径
+    /// * `parse_result` - 解析器的结果
+    /// * `config` - 分析配置
+    ///
+    /// # Returns
+    /// * `Box<dyn ParseResult>` - 调整后的解析结果
+    fn apply_anonymous_policy(
+        &self,
+        path: &Path,
+        parse_result: Box<dyn ParseResult>,
+        config: &AnalysisConfig,
+    ) -> Box<dyn ParseResult> {
+        let language = parse_result.get_language();
+        let mut functions: Vec<Function> = parse_result
+            .get_functions()
+            .iter()
+            .filter(|f| !f.is_anonymous())
+            .cloned()
+            .collect();
+
+        if config.include_anonymous {
+            let anonymous = detect_anonymous_functions(path, parse_result.get_source(), language);
+            for func in anonymous {
+                // 与具名函数同一行开始的（如 `const f = () => {}`）已由解析器统计
+                if !functions.iter().any(|f| f.start_line == func.start_line) {
+                    functions.push(func);
+                }
+            }
+            functions.sort_by_key(|f| f.start_line);
+        }
+
+        if config.capture_function_bodies {
+            let source = parse_result.get_source();
+            functions = functions.into_iter().map(|f| f.with_body(source)).collect();
+        }
+
+        Box::new(BaseParseResult {
+            functions,
+            comment_lines: parse_result.get_comment_lines(),
+            total_lines: parse_result.get_total_lines(),
+            source: parse_result.get_source().to_string(),
+            language,
+        })
     }
 
     /// 分析指标
     ///
     /// # Arguments
     /// * `parse_result` - 解析结果
+    /// * `metrics` - 本次分析使用的度量指标
     ///
     /// # Returns
     /// * `HashMap<String, MetricResult>` - 指标结果
-    fn analyze_metrics(&self, parse_result: &dyn ParseResult) -> HashMap<String, MetricResult> {
-        let metrics = self.metric_factory.create_all_metrics();
+    fn analyze_metrics(
+        &self,
+        parse_result: &dyn ParseResult,
+        metrics: &[Box<dyn Metric + Send + Sync>],
+    ) -> HashMap<String, MetricResult> {
         let mut results = HashMap::new();
 
         for metric in metrics {
-            let result = metric.analyze(parse_result);
+            let mut result = metric.analyze(parse_result);
+            // 自定义指标可能直接构造结果，这里统一保证得分有效
+            result.score = sanitize_score(result.score);
             results.insert(metric.name().to_string(), result);
         }
 
@@ -392,18 +845,66 @@ impl CodeAnalyzer {
         }
     }
 
-    /// 收集问题
+    /// 收集文件的全部问题（指标问题和陈旧 TODO）
+    ///
+    /// 问题从指标结果中移出，每项指标（以及 TODO）只保留最严重的
+    /// `max_retained_issues` 个，避免病态输入占用大量内存。
     ///
     /// # Arguments
+    /// * `path` - 文件路径
+    /// * `content` - 文件内容
     /// * `metrics` - 指标结果
+    /// * `config` - 分析配置
+    ///
+    /// # Returns
+    /// * `(Vec<Issue>, usize)` - (保留的问题, 被丢弃的问题数)
+    fn collect_file_issues(
+        &self,
+        path: &Path,
+        content: &str,
+        metrics: &mut HashMap<String, MetricResult>,
+        config: &AnalysisConfig,
+    ) -> (Vec<Issue>, usize) {
+        let limit = config.max_retained_issues;
+        let mut issues = Vec::new();
+        let mut dropped = 0;
+
+        for (name, result) in metrics.iter_mut() {
+            let mut metric_issues = std::mem::take(&mut result.issues);
+            dropped += retain_most_severe(&mut metric_issues, limit);
+            issues.extend(
+                metric_issues
+                    .into_iter()
+                    .map(|issue| issue.with_metric(name.as_str())),
+            );
+        }
+
+        let mut todo_issues = self.collect_todo_issues(path, content, config);
+        dropped += retain_most_severe(&mut todo_issues, limit);
+        issues.extend(todo_issues);
+
+        (issues, dropped)
+    }
+
+    /// 收集陈旧 TODO/FIXME 问题（未配置天数时不检测）
+    ///
+    /// # Arguments
+    /// * `path` - 文件路径
+    /// * `content` - 文件内容
+    /// * `config` - 分析配置
     ///
     /// # Returns
-    /// * `Vec<String>` - 问题列表
-    fn collect_issues(&self, metrics: &HashMap<String, MetricResult>) -> Vec<String> {
-        metrics
-            .values()
-            .flat_map(|result| result.issues.clone())
-            .collect()
+    /// * `Vec<Issue>` - 问题列表
+    fn collect_todo_issues(
+        &self,
+        path: &Path,
+        content: &str,
+        config: &AnalysisConfig,
+    ) -> Vec<Issue> {
+        match config.stale_todo_days {
+            Some(days) => stale_todo_issues(path, content, days),
+            None => Vec::new(),
+        }
     }
 
     /// 创建单文件结果
@@ -414,6 +915,7 @@ impl CodeAnalyzer {
     /// * `metrics` - 指标结果
     /// * `issues` - 问题列表
     /// * `lines` - 行数
+    /// * `language` - 文件的语言
     ///
     /// # Returns
     /// * `AnalysisResult` - 分析结果
@@ -422,20 +924,34 @@ impl CodeAnalyzer {
         path: &Path,
         score: f64,
         metrics: HashMap<String, MetricResult>,
-        issues: Vec<String>,
+        issues: Vec<Issue>,
         lines: usize,
+        language: LanguageType,
     ) -> AnalysisResult {
+        let files_analyzed = vec![FileAnalysisResult {
+            file_path: path.display().to_string(),
+            file_score: score,
+            lines,
+            metrics: metrics.clone(),
+            issues,
+            dropped_issues: 0,
+            generic_parser: uses_generic_parser(path),
+            language,
+        }];
+
         AnalysisResult {
             code_quality_score: score,
             metrics,
-            files_analyzed: vec![FileAnalysisResult {
-                file_path: path.display().to_string(),
-                file_score: score,
-                issues,
-            }],
+            languages: LanguageScore::from_files(&files_analyzed),
+            files_analyzed,
             total_files: 1,
             total_lines: lines,
             is_empty: false,
+            run_id: String::new(),
+            parameter_distribution: ParameterDistribution::default(),
+            analyzed_files: vec![path.display().to_string()],
+            skipped_files: Vec::new(),
+            cache_stats: None,
         }
     }
 
@@ -443,18 +959,32 @@ impl CodeAnalyzer {
     ///
     /// # Arguments
     /// * `files` - 文件列表
+    /// * `config` - 分析配置
+    /// * `cache` - 解析缓存
     ///
     /// # Returns
-    /// * `AppResult<Vec<FileAnalysisData>>` - 分析数据列表
-    fn analyze_files_parallel(&self, files: &[PathBuf]) -> AppResult<Vec<FileAnalysisData>> {
+    /// * `AppResult<(Vec<FileAnalysisData>, Vec<SkippedFile>)>` - 分析数据列表和分析失败的文件
+    fn analyze_files_parallel(
+        &self,
+        files: &[PathBuf],
+        config: &AnalysisConfig,
+        cache: Option<&ParseCache>,
+    ) -> AppResult<(Vec<FileAnalysisData>, Vec<SkippedFile>)> {
         let results = Arc::new(Mutex::new(Vec::new()));
+        let failed = Mutex::new(Vec::new());
         let progress = self.create_progress_bar(files.len());
+        let metrics = self.metric_factory.create_all_metrics(config);
 
         // 并行处理文件
         files.par_iter().for_each(|file| {
-            if let Ok(data) = self.analyze_file_safe(file) {
-                let mut res = results.lock().unwrap();
-                res.push(data);
+            match self.analyze_file_safe(file, config, &metrics, cache) {
+                Ok(data) => results.lock().unwrap().push(data),
+                Err(e) => {
+                    log::warn!("无法分析 {}，已跳过: {}", file.display(), e);
+                    failed.lock().unwrap().push(
+                        SkippedFile::new(file, SkipReason::Failed).with_detail(e.to_string()),
+                    );
+                }
             }
 
             if let Some(ref pb) = progress {
@@ -466,10 +996,15 @@ impl CodeAnalyzer {
             pb.finish_and_clear();
         }
 
-        Arc::try_unwrap(results)
+        let results = Arc::try_unwrap(results)
             .map_err(|_| AppError::Other("Failed to unwrap results".to_string()))?
             .into_inner()
-            .map_err(|_| AppError::Other("Failed to get inner mutex".to_string()))
+            .map_err(|_| AppError::Other("Failed to get inner mutex".to_string()))?;
+        let failed = failed
+            .into_inner()
+            .map_err(|_| AppError::Other("Failed to get inner mutex".to_string()))?;
+
+        Ok((results, failed))
     }
 
     /// 创建进度条
@@ -502,20 +1037,44 @@ impl CodeAnalyzer {
     ///
     /// # Arguments
     /// * `file` - 文件路径
+    /// * `config` - 分析配置
+    /// * `all_metrics` - 本次分析使用的度量指标
+    /// * `cache` - 解析缓存
     ///
     /// # Returns
     /// * `AppResult<FileAnalysisData>` - 分析数据
-    fn analyze_file_safe(&self, file: &PathBuf) -> AppResult<FileAnalysisData> {
-        let content = self.read_file(file)?;
-        let parse_result = self.parse_file(file, &content)?;
-        let metrics = self.analyze_metrics(&*parse_result);
-        let issues = self.collect_issues(&metrics);
+    fn analyze_file_safe(
+        &self,
+        file: &PathBuf,
+        config: &AnalysisConfig,
+        all_metrics: &[Box<dyn Metric + Send + Sync>],
+        cache: Option<&ParseCache>,
+    ) -> AppResult<FileAnalysisData> {
+        let content = self.read_file(file, config)?;
+        let parse_result = self.parse_file(file, &content, config, cache)?;
+        let mut metrics = self.analyze_metrics(&*parse_result, all_metrics);
+        let (issues, dropped_issues) =
+            self.collect_file_issues(file, &content, &mut metrics, config);
 
         Ok(FileAnalysisData {
             path: file.clone(),
             metrics,
             issues,
+            dropped_issues,
             lines: parse_result.get_total_lines(),
+            language: parse_result.get_language(),
+            content_hash: stable_hash(content.as_bytes()),
+            parameter_distribution: ParameterDistribution::from_functions(
+                parse_result.get_functions(),
+            ),
+            shingles: compute_shingles(
+                &content,
+                parse_result.get_language(),
+                config.clone_min_lines,
+            ),
+            symbols: config
+                .dead_code
+                .then(|| collect_symbols(parse_result.get_functions(), &content)),
         })
     }
 
@@ -523,13 +1082,28 @@ impl CodeAnalyzer {
     ///
     /// # Arguments
     /// * `file_results` - 文件分析数据
+    /// * `config` - 分析配置
     ///
     /// # Returns
     /// * `AppResult<AnalysisResult>` - 汇总结果
-    fn aggregate_results(&self, file_results: Vec<FileAnalysisData>) -> AppResult<AnalysisResult> {
+    fn aggregate_results(
+        &self,
+        mut file_results: Vec<FileAnalysisData>,
+        config: &AnalysisConfig,
+    ) -> AppResult<AnalysisResult> {
+        self.apply_cross_file_clones(&mut file_results, config);
+        self.apply_dead_code(&mut file_results, config);
+
+        let mut fingerprints: Vec<(String, u64)> = file_results
+            .iter()
+            .map(|data| (data.path.display().to_string(), data.content_hash))
+            .collect();
+        let run_id = compute_run_id(&mut fingerprints, config);
+
         let mut total_lines = 0;
-        let mut all_metrics: HashMap<String, Vec<MetricResult>> = HashMap::new();
+        let mut all_metrics: HashMap<String, Vec<(MetricResult, usize)>> = HashMap::new();
         let mut files_analyzed = Vec::new();
+        let mut parameter_distribution = ParameterDistribution::default();
 
         // 处理每个文件的结果
         for data in file_results {
@@ -538,15 +1112,24 @@ impl CodeAnalyzer {
             files_analyzed.push(FileAnalysisResult {
                 file_path: data.path.display().to_string(),
                 file_score,
+                lines: data.lines,
+                metrics: data.metrics.clone(),
                 issues: data.issues,
+                dropped_issues: data.dropped_issues,
+                generic_parser: uses_generic_parser(&data.path),
+                language: data.language,
             });
 
             // 收集指标
             for (name, result) in data.metrics {
-                all_metrics.entry(name).or_default().push(result);
+                all_metrics
+                    .entry(name)
+                    .or_default()
+                    .push((result, data.lines));
             }
 
             total_lines += data.lines;
+            parameter_distribution.merge(&data.parameter_distribution);
         }
 
         // 计算平均指标
@@ -556,6 +1139,12 @@ impl CodeAnalyzer {
         let code_quality_score = self.calculate_score(&aggregated_metrics);
 
         let total_files = files_analyzed.len();
+        let mut analyzed_files: Vec<String> = files_analyzed
+            .iter()
+            .map(|file| file.file_path.clone())
+            .collect();
+        analyzed_files.sort();
+        let languages = LanguageScore::from_files(&files_analyzed);
 
         Ok(AnalysisResult {
             code_quality_score,
@@ -564,27 +1153,124 @@ impl CodeAnalyzer {
             total_files,
             total_lines,
             is_empty: false,
+            run_id,
+            parameter_distribution,
+            analyzed_files,
+            skipped_files: Vec::new(),
+            languages,
+            cache_stats: None,
         })
     }
 
+    /// 检测跨文件重复代码块，并合并到各文件的代码重复度指标
+    ///
+    /// 重复代码块作为问题记录在涉及的每个文件上；该文件的重复度得分
+    /// 至少为重复行数占文件行数的比例。未选择代码重复度指标时不检测。
+    ///
+    /// # Arguments
+    /// * `file_results` - 文件分析数据
+    /// * `config` - 分析配置
+    fn apply_cross_file_clones(
+        &self,
+        file_results: &mut [FileAnalysisData],
+        config: &AnalysisConfig,
+    ) {
+        let selected = file_results
+            .iter()
+            .any(|data| data.metrics.contains_key(DUPLICATION_METRIC_NAME));
+        if config.clone_min_lines == 0 || file_results.len() < 2 || !selected {
+            return;
+        }
+
+        let fingerprints: Vec<(String, Vec<Shingle>)> = file_results
+            .iter_mut()
+            .map(|data| {
+                (
+                    data.path.display().to_string(),
+                    std::mem::take(&mut data.shingles),
+                )
+            })
+            .collect();
+        let clones = find_cross_file_clones(&fingerprints);
+
+        for (data, blocks) in file_results.iter_mut().zip(clones) {
+            let Some(metric) = data.metrics.get_mut(DUPLICATION_METRIC_NAME) else {
+                continue;
+            };
+            if blocks.is_empty() {
+                continue;
+            }
+
+            let duplicated_lines: usize = blocks
+                .iter()
+                .map(|block| block.end_line - block.start_line + 1)
+                .sum();
+            if data.lines > 0 {
+                let ratio = (duplicated_lines as f64 / data.lines as f64).min(1.0);
+                metric.score = metric.score.max(ratio);
+            }
+
+            let mut issues: Vec<Issue> = blocks.iter().map(clone_issue).collect();
+            data.dropped_issues += retain_most_severe(&mut issues, config.max_retained_issues);
+            data.issues.extend(issues);
+        }
+    }
+
+    /// 报告在所有分析文件中都没有被引用的非导出函数
+    ///
+    /// 仅在启用 `dead_code` 时执行；疑似无用函数只作为问题报告，不影响得分。
+    ///
+    /// # Arguments
+    /// * `file_results` - 文件分析数据
+    /// * `config` - 分析配置
+    fn apply_dead_code(&self, file_results: &mut [FileAnalysisData], config: &AnalysisConfig) {
+        if !config.dead_code {
+            return;
+        }
+
+        let symbols: Vec<FileSymbols> = file_results
+            .iter_mut()
+            .map(|data| data.symbols.take().unwrap_or_default())
+            .collect();
+        let unreferenced = find_unreferenced(&symbols);
+
+        for (data, candidates) in file_results.iter_mut().zip(unreferenced) {
+            let mut issues: Vec<Issue> = candidates.into_iter().map(dead_code_issue).collect();
+            data.dropped_issues += retain_most_severe(&mut issues, config.max_retained_issues);
+            data.issues.extend(issues);
+        }
+    }
+
     /// 计算平均指标
     ///
+    /// 各文件的指标得分按文件行数加权平均，避免大量小文件稀释大文件的问题；
+    /// 所有文件都没有代码行时退化为等权平均。
+    ///
     /// # Arguments
-    /// * `all_metrics` - 所有指标
+    /// * `all_metrics` - 所有指标（指标结果及所在文件的行数）
     ///
     /// # Returns
     /// * `HashMap<String, MetricResult>` - 平均指标
     fn calculate_average_metrics(
         &self,
-        all_metrics: HashMap<String, Vec<MetricResult>>,
+        all_metrics: HashMap<String, Vec<(MetricResult, usize)>>,
     ) -> HashMap<String, MetricResult> {
         let mut aggregated = HashMap::new();
 
         for (name, results) in all_metrics {
             if !results.is_empty() {
-                let avg_score = results.iter().map(|r| r.score).sum::<f64>() / results.len() as f64;
-
-                let first = &results[0];
+                let total_lines: usize = results.iter().map(|(_, lines)| lines).sum();
+                let avg_score = if total_lines > 0 {
+                    results
+                        .iter()
+                        .map(|(r, lines)| r.score * *lines as f64)
+                        .sum::<f64>()
+                        / total_lines as f64
+                } else {
+                    results.iter().map(|(r, _)| r.score).sum::<f64>() / results.len() as f64
+                };
+
+                let first = &results[0].0;
 
                 aggregated.insert(
                     name,
@@ -612,8 +1298,739 @@ struct FileAnalysisData {
     metrics: HashMap<String, MetricResult>,
 
     /// 问题列表
-    issues: Vec<String>,
+    issues: Vec<Issue>,
+
+    /// 超出保留上限被丢弃的问题数
+    dropped_issues: usize,
 
     /// 代码行数
     lines: usize,
+
+    /// 文件的语言
+    language: LanguageType,
+
+    /// 文件内容哈希
+    content_hash: u64,
+
+    /// 函数参数数量分布
+    parameter_distribution: ParameterDistribution,
+
+    /// 代码块指纹（用于跨文件重复检测）
+    shingles: Vec<Shingle>,
+
+    /// 死代码检测符号（未启用 `dead_code` 时为 None）
+    symbols: Option<FileSymbols>,
+}
+
+/// 每个重复代码块问题中最多列出的其他位置数
+const MAX_LISTED_CLONE_PARTNERS: usize = 3;
+
+/// 把跨文件重复代码块转换为问题
+///
+/// # Arguments
+/// * `block` - 重复代码块
+///
+/// # Returns
+/// * `Issue` - 问题
+fn clone_issue(block: &CloneBlock) -> Issue {
+    let mut locations = block
+        .partners
+        .iter()
+        .take(MAX_LISTED_CLONE_PARTNERS)
+        .map(|(path, start, end)| format!("{}:{}-{}", path, start, end))
+        .collect::<Vec<_>>()
+        .join("、");
+    if block.partners.len() > MAX_LISTED_CLONE_PARTNERS {
+        locations.push_str(&format!(" 等 {} 处", block.partners.len()));
+    }
+
+    Issue::new(
+        IssueKind::CrossFileDuplicate,
+        format!(
+            "与 {} 重复的代码块（{} 行）",
+            locations,
+            block.end_line - block.start_line + 1
+        ),
+    )
+    .with_lines(block.start_line, block.end_line)
+    .with_metric(DUPLICATION_METRIC_NAME)
+}
+
+/// 把疑似无用函数转换为问题
+///
+/// # Arguments
+/// * `candidate` - 疑似无用函数
+///
+/// # Returns
+/// * `Issue` - 问题
+fn dead_code_issue(candidate: &DeadCodeCandidate) -> Issue {
+    Issue::new(
+        IssueKind::PossiblyDeadCode,
+        format!(
+            "函数 {} 在已分析的文件中没有被引用，可能是死代码",
+            candidate.name
+        ),
+    )
+    .with_lines(candidate.start_line, candidate.end_line)
+}
+
+/// 只保留最严重的若干个问题
+///
+/// 同等严重程度的问题保持原有顺序。
+///
+/// # Arguments
+/// * `issues` - 问题列表
+/// * `limit` - 最多保留的数量（0 表示不限制）
+///
+/// # Returns
+/// * `usize` - 被丢弃的问题数
+fn retain_most_severe(issues: &mut Vec<Issue>, limit: usize) -> usize {
+    if limit == 0 || issues.len() <= limit {
+        return 0;
+    }
+
+    issues.sort_by_key(|issue| std::cmp::Reverse(issue.kind.severity()));
+    let dropped = issues.len() - limit;
+    issues.truncate(limit);
+    dropped
+}
+
+/// 按路径排序跳过记录，保证输出稳定
+///
+/// # Arguments
+/// * `skipped` - 跳过记录
+///
+/// # Returns
+/// * `Vec<SkippedFile>` - 排序后的跳过记录
+fn sorted_skipped(mut skipped: Vec<SkippedFile>) -> Vec<SkippedFile> {
+    skipped.sort_by(|a, b| a.path.cmp(&b.path));
+    skipped
+}
+
+/// 写回解析缓存并返回本次运行的命中统计
+///
+/// 缓存写入失败只给出警告，不影响分析结果
+///
+/// # Arguments
+/// * `cache` - 解析缓存
+///
+/// # Returns
+/// * `Option<CacheStats>` - 命中统计，未启用缓存时为 None
+fn finish_cache(cache: Option<ParseCache>) -> Option<CacheStats> {
+    let cache = cache?;
+    if let Err(e) = cache.save() {
+        log::warn!("无法写入解析缓存: {}", e);
+    }
+    Some(cache.stats())
+}
+
+/// 计算运行标识
+///
+/// 对排序后的（文件路径, 内容哈希）列表和分析配置求稳定哈希，
+/// 相同输入与配置总是得到相同标识，任何文件或配置变化都会改变标识。
+///
+/// # Arguments
+/// * `fingerprints` - （文件路径, 内容哈希）列表，会被原地排序
+/// * `config` - 分析配置
+///
+/// # Returns
+/// * `String` - 16 位十六进制运行标识
+fn compute_run_id(fingerprints: &mut [(String, u64)], config: &AnalysisConfig) -> String {
+    fingerprints.sort();
+
+    let mut hasher = StableHasher::new();
+    for (path, content_hash) in fingerprints.iter() {
+        hasher.write_str(path);
+        hasher.write_u64(*content_hash);
+    }
+    hasher.write_str(&serde_json::to_string(config).unwrap_or_default());
+
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::LanguageThresholds;
+
+    /// 构造带多个权重和阈值覆盖的配置，插入顺序由 `keys` 决定
+    fn config_with_weights(keys: &[&str]) -> AnalysisConfig {
+        let mut config = AnalysisConfig::default();
+        for (i, key) in keys.iter().enumerate() {
+            config.weights.insert(key.to_string(), i as f64 / 10.0);
+            config
+                .thresholds
+                .insert(key.to_string(), LanguageThresholds::default());
+            config
+                .predicate_prefixes
+                .insert(key.to_string(), vec!["is".to_string()]);
+        }
+        config
+    }
+
+    #[test]
+    fn run_id_is_independent_of_map_insertion_order() {
+        let forward = config_with_weights(&["complexity", "naming", "comment", "error"]);
+        let mut backward = AnalysisConfig::default();
+        for key in ["error", "comment", "naming", "complexity"] {
+            backward
+                .weights
+                .insert(key.to_string(), forward.weights[key]);
+            backward
+                .thresholds
+                .insert(key.to_string(), LanguageThresholds::default());
+            backward
+                .predicate_prefixes
+                .insert(key.to_string(), vec!["is".to_string()]);
+        }
+
+        let files = [("a.rs".to_string(), 1), ("b.rs".to_string(), 2)];
+        assert_eq!(
+            compute_run_id(&mut files.clone(), &forward),
+            compute_run_id(&mut files.clone(), &backward)
+        );
+    }
+
+    #[test]
+    fn run_id_ignores_file_order_but_not_content() {
+        let config = config_with_weights(&["complexity", "naming"]);
+        let mut sorted = [("a.rs".to_string(), 1), ("b.rs".to_string(), 2)];
+        let mut shuffled = [("b.rs".to_string(), 2), ("a.rs".to_string(), 1)];
+        let mut changed = [("a.rs".to_string(), 1), ("b.rs".to_string(), 3)];
+
+        let id = compute_run_id(&mut sorted, &config);
+        assert_eq!(id.len(), 16);
+        assert_eq!(id, compute_run_id(&mut shuffled, &config));
+        assert_ne!(id, compute_run_id(&mut changed, &config));
+
+        let mut other_config = config.clone();
+        other_config.weights.insert("naming".to_string(), 0.9);
+        assert_ne!(id, compute_run_id(&mut sorted, &other_config));
+    }
+
+    #[test]
+    fn run_id_is_stable_across_runs_and_changes_with_source() {
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let config = config_with_weights(&["complexity", "naming", "comment"]);
+        let source = "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n";
+
+        let first = analyzer
+            .analyze_string(source, LanguageType::Rust, &config)
+            .unwrap();
+        let second = analyzer
+            .analyze_string(source, LanguageType::Rust, &config)
+            .unwrap();
+        let changed = analyzer
+            .analyze_string(
+                &source.replace("a + b", "b + a"),
+                LanguageType::Rust,
+                &config,
+            )
+            .unwrap();
+
+        assert_eq!(first.run_id, second.run_id);
+        assert_ne!(first.run_id, changed.run_id);
+    }
+
+    #[test]
+    fn retain_most_severe_caps_issues_and_counts_the_rest() {
+        let mut issues: Vec<Issue> = (1..=10)
+            .map(|line| {
+                let kind = match line % 3 {
+                    0 => IssueKind::DeepNesting,
+                    1 => IssueKind::MediumNesting,
+                    _ => IssueKind::LongLine,
+                };
+                Issue::new(kind, "问题").with_line(line)
+            })
+            .collect();
+
+        let mut unlimited = issues.clone();
+        assert_eq!(retain_most_severe(&mut unlimited, 0), 0);
+        assert_eq!(unlimited.len(), 10);
+
+        assert_eq!(retain_most_severe(&mut issues, 4), 6);
+        let kept: Vec<(IssueKind, Option<usize>)> = issues
+            .iter()
+            .map(|issue| (issue.kind, issue.line))
+            .collect();
+        assert_eq!(
+            kept,
+            [
+                (IssueKind::DeepNesting, Some(3)),
+                (IssueKind::DeepNesting, Some(6)),
+                (IssueKind::DeepNesting, Some(9)),
+                (IssueKind::MediumNesting, Some(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn pathological_file_keeps_a_bounded_number_of_issues() {
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let source: String = (0..2000)
+            .map(|i| format!("def f{}(a, b, c, d, e, f, g, h):\n    return a\n\n", i))
+            .collect();
+
+        let capped_config = AnalysisConfig {
+            max_retained_issues: 50,
+            ..Default::default()
+        };
+        let uncapped_config = AnalysisConfig {
+            max_retained_issues: 0,
+            ..Default::default()
+        };
+        let capped = analyzer
+            .analyze_string(&source, LanguageType::Python, &capped_config)
+            .unwrap();
+        let uncapped = analyzer
+            .analyze_string(&source, LanguageType::Python, &uncapped_config)
+            .unwrap();
+
+        let capped_file = &capped.files_analyzed[0];
+        let uncapped_file = &uncapped.files_analyzed[0];
+        assert!(uncapped_file.issues.len() >= 4000);
+        assert_eq!(uncapped_file.dropped_issues, 0);
+
+        let mut per_metric: HashMap<&str, usize> = HashMap::new();
+        for issue in &capped_file.issues {
+            *per_metric
+                .entry(issue.metric.as_deref().unwrap_or_default())
+                .or_default() += 1;
+        }
+        assert!(
+            per_metric.values().all(|&count| count <= 50),
+            "{:?}",
+            per_metric
+        );
+        assert!(capped_file.dropped_issues > 0);
+        assert_eq!(
+            capped_file.issues.len() + capped_file.dropped_issues,
+            uncapped_file.issues.len()
+        );
+    }
+
+    #[test]
+    fn files_outside_the_size_range_are_skipped_and_reported() {
+        let project = crate::common::testing::TempDir::new("size-range");
+        let line = "def f():\n    return 1\n";
+        let base = line.repeat(4);
+        project.write("under.py", &base);
+        project.write("over.py", &format!("{}#", base));
+        project.write("empty.py", "");
+
+        let config = AnalysisConfig {
+            max_file_size: base.len(),
+            ..Default::default()
+        };
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let result = analyzer
+            .analyze_with_config(project.path(), &config)
+            .unwrap();
+
+        let analyzed: Vec<&str> = result
+            .files_analyzed
+            .iter()
+            .map(|file| file.file_path.rsplit(['/', '\\']).next().unwrap())
+            .collect();
+        assert_eq!(analyzed, ["under.py"]);
+
+        let skipped: Vec<(&str, SkipReason)> = result
+            .skipped_files
+            .iter()
+            .filter(|skipped| matches!(skipped.reason, SkipReason::TooLarge | SkipReason::TooSmall))
+            .map(|skipped| {
+                (
+                    skipped.path.rsplit(['/', '\\']).next().unwrap(),
+                    skipped.reason,
+                )
+            })
+            .collect();
+        assert_eq!(
+            skipped,
+            [
+                ("empty.py", SkipReason::TooSmall),
+                ("over.py", SkipReason::TooLarge)
+            ]
+        );
+    }
+
+    #[test]
+    fn average_metrics_are_weighted_by_file_lines() {
+        let metric = |score: f64| MetricResult {
+            score,
+            weight: 0.3,
+            description: "复杂度".to_string(),
+            issues: vec![],
+        };
+        let mut results = vec![(metric(0.9), 2000)];
+        results.extend((0..20).map(|_| (metric(0.0), 5)));
+        let all_metrics = HashMap::from([("complexity".to_string(), results)]);
+
+        let aggregated = CodeAnalyzer::new().calculate_average_metrics(all_metrics);
+        let complexity = &aggregated["complexity"];
+
+        // 2000 行的糟糕文件压过 20 个 5 行的干净文件：0.9 * 2000 / 2100
+        assert!((complexity.score - 0.9 * 2000.0 / 2100.0).abs() < 1e-9);
+        assert!(complexity.score > 0.8);
+        assert_eq!(complexity.weight, 0.3);
+    }
+
+    #[test]
+    fn one_huge_terrible_file_dominates_many_small_clean_ones() {
+        let branchy = |n: usize| {
+            let mut source = String::from("def f(x):\n");
+            for i in 0..n {
+                source.push_str(&format!("    if x == {}:\n        return {}\n", i, i));
+            }
+            source.push_str("    return -1\n");
+            source
+        };
+        let analyze = |project: &crate::common::testing::TempDir| {
+            let mut analyzer = CodeAnalyzer::new();
+            analyzer.set_silent(true);
+            analyzer
+                .analyze_with_config(project.path(), &AnalysisConfig::default())
+                .unwrap()
+        };
+
+        let clean_only = crate::common::testing::TempDir::new("weighted-clean");
+        let mixed = crate::common::testing::TempDir::new("weighted-mixed");
+        for i in 0..20 {
+            let helper = format!("def helper_{i}(x):\n    return x + {i}\n");
+            clean_only.write(&format!("helper_{i}.py"), &helper);
+            mixed.write(&format!("helper_{i}.py"), &helper);
+        }
+        mixed.write("monster.py", &branchy(300));
+
+        let clean_result = analyze(&clean_only);
+        let mixed_result = analyze(&mixed);
+        let monster = mixed_result
+            .files_analyzed
+            .iter()
+            .find(|file| file.file_path.ends_with("monster.py"))
+            .unwrap();
+
+        // 行数加权后项目复杂度接近大文件本身，而不是被 20 个小文件平均掉
+        let project_complexity = mixed_result.metrics["循环复杂度"].score;
+        let monster_complexity = monster.metrics["循环复杂度"].score;
+        assert!(
+            (project_complexity - monster_complexity).abs() < 0.1,
+            "project {project_complexity} vs monster {monster_complexity}"
+        );
+        assert!(mixed_result.code_quality_score > clean_result.code_quality_score);
+    }
+
+    #[test]
+    fn single_file_paths_respect_size_and_extension_filters() {
+        let project = crate::common::testing::TempDir::new("single-file-filters");
+        let source = "def f():\n    return 1\n";
+        let file = project.write("big.py", source);
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let paths = [file];
+
+        let oversized = AnalysisConfig {
+            max_file_size: source.len() - 1,
+            ..Default::default()
+        };
+        let result = analyzer
+            .analyze_paths_with_config(&paths, &oversized)
+            .unwrap();
+        assert!(result.files_analyzed.is_empty());
+        assert_eq!(result.skipped_files.len(), 1);
+        assert_eq!(result.skipped_files[0].reason, SkipReason::TooLarge);
+
+        let other_extension = AnalysisConfig {
+            extensions: vec!["rs".to_string()],
+            ..Default::default()
+        };
+        let result = analyzer
+            .analyze_paths_with_config(&paths, &other_extension)
+            .unwrap();
+        assert!(result.files_analyzed.is_empty());
+        assert_eq!(result.skipped_files[0].reason, SkipReason::ExtensionFilter);
+
+        let result = analyzer
+            .analyze_paths_with_config(&paths, &AnalysisConfig::default())
+            .unwrap();
+        assert_eq!(result.files_analyzed.len(), 1);
+    }
+
+    #[test]
+    fn each_registered_metric_reports_under_its_own_id() {
+        let project = crate::common::testing::TempDir::new("metric-ids");
+        let mut source = String::from("def update():\n    global a, b, c, d, e\n");
+        for i in 0..120 {
+            source.push_str(&format!("    a = a + {}\n", i));
+        }
+        project.write("state.py", &source);
+
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let metrics = analyzer
+            .metric_factory
+            .create_all_metrics(&AnalysisConfig::default());
+        let ids: HashSet<&str> = metrics.iter().map(|metric| metric.id()).collect();
+        let names: HashSet<&str> = metrics.iter().map(|metric| metric.name()).collect();
+        assert_eq!(ids.len(), metrics.len(), "metric ids must be unique");
+        assert_eq!(names.len(), metrics.len(), "metric names must be unique");
+
+        let mut focus = HashMap::new();
+        for metric in &metrics {
+            let config = AnalysisConfig {
+                metrics: vec![metric.id().to_string()],
+                ..Default::default()
+            };
+            let result = analyzer
+                .analyze_with_config(project.path(), &config)
+                .unwrap();
+            let reported: Vec<&String> = result.metrics.keys().collect();
+            assert_eq!(reported, [metric.name()], "{}", metric.id());
+
+            let kinds: Vec<IssueKind> = result.files_analyzed[0]
+                .issues
+                .iter()
+                .map(|issue| issue.kind)
+                .collect();
+            focus.insert(metric.id().to_string(), kinds);
+        }
+
+        // 函数长度和状态管理各自只报告自己关注的问题
+        let length = &focus["function_length"];
+        assert!(
+            length.contains(&IssueKind::FunctionVeryLong)
+                || length.contains(&IssueKind::FunctionLong)
+        );
+        assert!(!length.contains(&IssueKind::GlobalState));
+        assert_eq!(focus["state_management"], [IssueKind::GlobalState]);
+    }
+
+    #[test]
+    fn changed_lines_only_reports_issues_of_the_touched_function() {
+        let branchy = |name: &str, result: &str| {
+            let body: String = (0..15)
+                .map(|i| format!("    if x == {}:\n        return {}\n", i, result))
+                .collect();
+            format!("def {}(x):\n{}    return None\n", name, body)
+        };
+        let repo = crate::common::testing::TempDir::new("changed-lines-only");
+        repo.git(&["init", "-q"], None);
+        let original = format!("{}\n\n{}", branchy("alpha", "1"), branchy("beta", "2"));
+        let file = repo.write("app.py", &original);
+        repo.git(&["add", "."], None);
+        repo.git(&["commit", "-q", "-m", "initial"], None);
+
+        // 只改动 beta 函数体中的一行
+        let edited = original.replacen("return 2", "return 3", 1);
+        std::fs::write(&file, &edited).unwrap();
+        let beta_start = edited
+            .lines()
+            .position(|l| l.starts_with("def beta"))
+            .unwrap()
+            + 1;
+
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let analyze = |changed_lines_only: bool| {
+            let config = AnalysisConfig {
+                since: Some("HEAD".to_string()),
+                changed_lines_only,
+                ..Default::default()
+            };
+            analyzer
+                .analyze_with_config(repo.path(), &config)
+                .unwrap()
+                .files_analyzed
+                .remove(0)
+                .issues
+        };
+
+        let all_issues = analyze(false);
+        assert!(all_issues.iter().any(|issue| issue.line == Some(1)));
+
+        let changed_issues = analyze(true);
+        assert!(!changed_issues.is_empty());
+        assert!(changed_issues
+            .iter()
+            .all(|issue| issue.line.is_some_and(|line| line >= beta_start)));
+        assert!(changed_issues
+            .iter()
+            .any(|issue| issue.message.contains("beta")));
+        assert!(!changed_issues
+            .iter()
+            .any(|issue| issue.message.contains("alpha")));
+    }
+
+    #[test]
+    fn closures_are_counted_or_excluded_per_setting() {
+        let cases = [
+            (
+                "handlers.js",
+                "function setup(items) {\n\
+                 \x20 items.forEach((item) => {\n\
+                 \x20   render(item);\n\
+                 \x20 });\n\
+                 \x20 const ids = items.map(item => item.id);\n\
+                 \x20 button.on('click', function () {\n\
+                 \x20   save(ids);\n\
+                 \x20 });\n\
+                 }\n",
+                vec![
+                    "<anon>@handlers.js:2",
+                    "<anon>@handlers.js:5",
+                    "<anon>@handlers.js:6",
+                ],
+            ),
+            (
+                "lib.rs",
+                "fn total(items: &[u32]) -> u32 {\n\
+                 \x20   let doubled: Vec<u32> = items.iter().map(|x| x * 2).collect();\n\
+                 \x20   doubled.iter().fold(0, |acc, x| acc + x)\n\
+                 }\n",
+                vec!["<anon>@lib.rs:2", "<anon>@lib.rs:3"],
+            ),
+        ];
+        let analyzer = CodeAnalyzer::new();
+
+        for (file_name, source, anonymous) in cases {
+            let path = Path::new(file_name);
+            let names = |include_anonymous: bool| -> Vec<String> {
+                let config = AnalysisConfig {
+                    include_anonymous,
+                    ..Default::default()
+                };
+                let parse_result = create_parser_for_file(path).parse(path, source).unwrap();
+                analyzer
+                    .apply_anonymous_policy(path, parse_result, &config)
+                    .get_functions()
+                    .iter()
+                    .map(|f| f.name.clone())
+                    .collect()
+            };
+
+            let included = names(true);
+            assert_eq!(included.len(), anonymous.len() + 1, "{:?}", included);
+            assert!(anonymous
+                .iter()
+                .all(|name| included.contains(&name.to_string())));
+
+            let excluded = names(false);
+            assert_eq!(excluded.len(), 1, "{:?}", excluded);
+            assert!(!excluded[0].starts_with(crate::parser::ANONYMOUS_PREFIX));
+        }
+    }
+
+    #[test]
+    fn parameter_distribution_buckets_match_the_fixture() {
+        let project = crate::common::testing::TempDir::new("param-histogram");
+        project.write(
+            "a.py",
+            "def none():\n    pass\n\n\
+             def one(a):\n    pass\n\n\
+             def two(a, b):\n    pass\n\n\
+             def three(a, b, c):\n    pass\n",
+        );
+        project.write(
+            "b.py",
+            "def four(a, b, c, d):\n    pass\n\n\
+             def six(a, b, c, d, e, f):\n    pass\n\n\
+             def seven(a, b, c, d, e, f, g):\n    pass\n",
+        );
+
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let result = analyzer
+            .analyze_with_config(project.path(), &AnalysisConfig::default())
+            .unwrap();
+
+        // 0 | 1 | 2-3 | 4-5 | 6+
+        assert_eq!(result.parameter_distribution.buckets, [1, 1, 2, 1, 2]);
+        assert_eq!(result.parameter_distribution.total(), 7);
+    }
+
+    #[test]
+    fn files_from_manifest_analyzes_only_the_listed_files() {
+        let project = crate::common::testing::TempDir::new("files-from");
+        let source = "def f():\n    return 1\n";
+        let first = project.write("src/first.py", source);
+        let second = project.write("src/second.py", source);
+        project.write("src/unlisted.py", source);
+        let manifest = project.write(
+            "changed.json",
+            &serde_json::to_string(&[&first, &second]).unwrap(),
+        );
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+
+        let config = AnalysisConfig {
+            files_from: Some(manifest),
+            ..Default::default()
+        };
+        let result = analyzer
+            .analyze_with_config(project.path(), &config)
+            .unwrap();
+
+        let mut analyzed = result.analyzed_files.clone();
+        analyzed.sort();
+        assert_eq!(
+            analyzed,
+            [first.to_string_lossy(), second.to_string_lossy()]
+        );
+        assert_eq!(result.files_analyzed.len(), 2);
+        assert!(result.skipped_files.is_empty());
+    }
+
+    #[test]
+    fn function_bodies_are_captured_only_when_enabled() {
+        let cases = [
+            (
+                "util.py",
+                "import os\n\ndef home():\n    return os.environ['HOME']\n",
+                "def home():\n    return os.environ['HOME']",
+            ),
+            (
+                "util.rs",
+                "use std::env;\n\nfn home() -> String {\n    env::var(\"HOME\").unwrap()\n}\n",
+                "fn home() -> String {\n    env::var(\"HOME\").unwrap()\n}",
+            ),
+            (
+                "util.go",
+                "package util\n\nfunc Home() string {\n\treturn os.Getenv(\"HOME\")\n}\n",
+                "func Home() string {\n\treturn os.Getenv(\"HOME\")\n}",
+            ),
+            (
+                "util.js",
+                "const os = require('os');\n\nfunction home() {\n  return os.homedir();\n}\n",
+                "function home() {\n  return os.homedir();\n}",
+            ),
+        ];
+        let analyzer = CodeAnalyzer::new();
+
+        for (file_name, source, body) in cases {
+            let path = Path::new(file_name);
+            let functions = |capture_function_bodies: bool| -> Vec<Function> {
+                let config = AnalysisConfig {
+                    capture_function_bodies,
+                    ..Default::default()
+                };
+                let parse_result = create_parser_for_file(path).parse(path, source).unwrap();
+                analyzer
+                    .apply_anonymous_policy(path, parse_result, &config)
+                    .get_functions()
+                    .to_vec()
+            };
+
+            let captured = functions(true);
+            assert_eq!(captured.len(), 1, "{}", file_name);
+            assert_eq!(captured[0].body.as_deref(), Some(body), "{}", file_name);
+
+            let skipped = functions(false);
+            assert_eq!(skipped.len(), 1, "{}", file_name);
+            assert_eq!(skipped[0].body, None, "{}", file_name);
+        }
+    }
 }
@@ -0,0 +1,398 @@
+//! # 基线对比
+//!
+//! 按 git 分支保存分析结果作为基线，并计算本次结果与基线之间的差异
+
+use crate::analyzer::{AnalysisResult, FileAnalysisResult};
+use crate::common::git;
+use crate::error::{AppError, AppResult};
+use crate::metrics::Issue;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 基线目录（相对于仓库根目录）
+const BASELINE_DIR: &str = ".fuckshitcode/baselines";
+
+/// 自动基线位置
+#[derive(Debug, Clone)]
+pub struct AutoBaseline {
+    /// 基线目录
+    dir: PathBuf,
+
+    /// 当前分支名
+    branch: String,
+
+    /// 当前分支没有基线时回退的基础分支
+    base_branch: Option<String>,
+}
+
+impl AutoBaseline {
+    /// 根据分析路径定位当前分支的基线，基础分支默认为仓库的默认分支
+    ///
+    /// # Arguments
+    /// * `path` - 分析路径
+    ///
+    /// # Returns
+    /// * `Option<Self>` - 不在 git 仓库或处于游离 HEAD 时为 None
+    pub fn locate(path: &Path) -> Option<Self> {
+        let root = git::repo_root(path)?;
+        let branch = git::current_branch(path)?;
+        let base_branch = git::default_branch(path);
+
+        Some(Self {
+            base_branch,
+            ..Self::new(root.join(BASELINE_DIR), branch)
+        })
+    }
+
+    /// 使用指定目录和分支创建基线位置
+    ///
+    /// # Arguments
+    /// * `dir` - 基线目录
+    /// * `branch` - 分支名
+    ///
+    /// # Returns
+    /// * `Self` - 基线位置
+    pub fn new(dir: PathBuf, branch: impl Into<String>) -> Self {
+        AutoBaseline {
+            dir,
+            branch: branch.into(),
+            base_branch: None,
+        }
+    }
+
+    /// 设置当前分支没有基线时回退的基础分支
+    ///
+    /// # Arguments
+    /// * `base_branch` - 基础分支名
+    ///
+    /// # Returns
+    /// * `Self` - 基线位置
+    pub fn with_base_branch(mut self, base_branch: impl Into<String>) -> Self {
+        self.base_branch = Some(base_branch.into());
+        self
+    }
+
+    /// 获取当前分支名
+    ///
+    /// # Returns
+    /// * `&str` - 分支名
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    /// 获取指定分支的基线文件路径
+    ///
+    /// 分支名中的 `/` 会形成子目录，例如 `feature/x` 对应 `feature/x.json`
+    ///
+    /// # Arguments
+    /// * `branch` - 分支名
+    ///
+    /// # Returns
+    /// * `PathBuf` - 基线文件路径
+    pub fn path_for(&self, branch: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", branch))
+    }
+
+    /// 读取当前分支的基线，不存在时回退到基础分支的基线
+    ///
+    /// # Returns
+    /// * `AppResult<Option<AnalysisResult>>` - 基线结果，均不存在时为 None
+    pub fn load(&self) -> AppResult<Option<AnalysisResult>> {
+        let candidates = std::iter::once(self.branch.as_str()).chain(self.base_branch.as_deref());
+
+        for branch in candidates {
+            let path = self.path_for(branch);
+            if path.is_file() {
+                return read_baseline(&path).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// 将分析结果保存为当前分支的基线
+    ///
+    /// # Arguments
+    /// * `result` - 分析结果
+    ///
+    /// # Returns
+    /// * `AppResult<()>` - 保存结果
+    pub fn save(&self, result: &AnalysisResult) -> AppResult<()> {
+        let path = self.path_for(&self.branch);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string_pretty(result)
+            .map_err(|e| AppError::Other(format!("序列化基线失败: {}", e)))?;
+        fs::write(path, json)?;
+
+        Ok(())
+    }
+}
+
+/// 读取基线文件
+///
+/// # Arguments
+/// * `path` - 基线文件路径
+///
+/// # Returns
+/// * `AppResult<AnalysisResult>` - 基线结果
+fn read_baseline(path: &Path) -> AppResult<AnalysisResult> {
+    let content = fs::read_to_string(path)?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| AppError::ParseError(format!("基线文件 {} 格式无效: {}", path.display(), e)))
+}
+
+/// 单项指标变化
+#[derive(Debug, Clone)]
+pub struct MetricDelta {
+    /// 基线得分（0-1）
+    pub baseline: f64,
+
+    /// 本次得分（0-1）
+    pub current: f64,
+}
+
+impl MetricDelta {
+    /// 得分变化量，正数表示变差
+    ///
+    /// # Returns
+    /// * `f64` - 变化量
+    pub fn delta(&self) -> f64 {
+        self.current - self.baseline
+    }
+}
+
+/// 单个文件的得分变化
+#[derive(Debug, Clone)]
+pub struct FileDelta {
+    /// 基线得分（0-1，基线中没有该文件时为 None）
+    pub baseline: Option<f64>,
+
+    /// 本次得分（0-1，本次没有该文件时为 None）
+    pub current: Option<f64>,
+}
+
+impl FileDelta {
+    /// 得分变化量，正数表示变差
+    ///
+    /// # Returns
+    /// * `Option<f64>` - 变化量，文件只存在于一侧时为 None
+    pub fn delta(&self) -> Option<f64> {
+        Some(self.current? - self.baseline?)
+    }
+}
+
+/// 与基线的对比结果
+#[derive(Debug, Clone)]
+pub struct BaselineDiff {
+    /// 总体质量得分变化
+    pub overall: MetricDelta,
+
+    /// 各项指标变化（仅包含两次都存在的指标）
+    pub metrics: BTreeMap<String, MetricDelta>,
+
+    /// 文件得分变化（按文件路径匹配，仅包含得分变化或只存在于一侧的文件）
+    pub files: BTreeMap<String, FileDelta>,
+
+    /// 本次新出现的问题（文件路径, 问题）
+    pub new_issues: Vec<(String, Issue)>,
+
+    /// 基线中存在、本次已消失的问题（文件路径, 问题）
+    pub resolved_issues: Vec<(String, Issue)>,
+}
+
+impl BaselineDiff {
+    /// 计算本次结果与基线之间的差异
+    ///
+    /// # Arguments
+    /// * `baseline` - 基线结果
+    /// * `current` - 本次结果
+    ///
+    /// # Returns
+    /// * `Self` - 对比结果
+    pub fn compute(baseline: &AnalysisResult, current: &AnalysisResult) -> Self {
+        let metrics = current
+            .metrics
+            .iter()
+            .filter_map(|(name, result)| {
+                baseline.metrics.get(name).map(|base| {
+                    (
+                        name.clone(),
+                        MetricDelta {
+                            baseline: base.score,
+                            current: result.score,
+                        },
+                    )
+                })
+            })
+            .collect();
+
+        BaselineDiff {
+            overall: MetricDelta {
+                baseline: baseline.code_quality_score,
+                current: current.code_quality_score,
+            },
+            metrics,
+            files: file_deltas(baseline, current),
+            new_issues: issue_changes(&current.files_analyzed, &baseline.files_analyzed),
+            resolved_issues: issue_changes(&baseline.files_analyzed, &current.files_analyzed),
+        }
+    }
+}
+
+/// 按文件路径匹配两次结果，计算文件得分变化
+///
+/// # Arguments
+/// * `baseline` - 基线结果
+/// * `current` - 本次结果
+///
+/// # Returns
+/// * `BTreeMap<String, FileDelta>` - 得分变化或只存在于一侧的文件
+fn file_deltas(baseline: &AnalysisResult, current: &AnalysisResult) -> BTreeMap<String, FileDelta> {
+    let mut files: BTreeMap<String, FileDelta> = BTreeMap::new();
+
+    for file in &baseline.files_analyzed {
+        files
+            .entry(file.file_path.clone())
+            .or_insert(FileDelta {
+                baseline: None,
+                current: None,
+            })
+            .baseline = Some(file.file_score);
+    }
+    for file in &current.files_analyzed {
+        files
+            .entry(file.file_path.clone())
+            .or_insert(FileDelta {
+                baseline: None,
+                current: None,
+            })
+            .current = Some(file.file_score);
+    }
+
+    files.retain(|_, delta| delta.delta().is_none_or(|d| d.abs() > f64::EPSILON));
+    files
+}
+
+/// 找出 `files` 中在 `others` 里没有对应项的问题
+///
+/// 按文件路径、问题类型和描述匹配，同一文件中相同的问题按出现次数逐一抵消；
+/// 描述中带行号的问题在代码移动后会同时计为新增和已解决。
+///
+/// # Arguments
+/// * `files` - 要检查的文件结果
+/// * `others` - 用于对照的文件结果
+///
+/// # Returns
+/// * `Vec<(String, Issue)>` - (文件路径, 问题) 列表
+fn issue_changes(
+    files: &[FileAnalysisResult],
+    others: &[FileAnalysisResult],
+) -> Vec<(String, Issue)> {
+    let mut remaining: HashMap<(&str, &'static str, &str), usize> = HashMap::new();
+    for file in others {
+        for issue in &file.issues {
+            *remaining
+                .entry((
+                    file.file_path.as_str(),
+                    issue.kind.key(),
+                    issue.message.as_str(),
+                ))
+                .or_insert(0) += 1;
+        }
+    }
+
+    let mut changes = Vec::new();
+    for file in files {
+        for issue in &file.issues {
+            let key = (
+                file.file_path.as_str(),
+                issue.kind.key(),
+                issue.message.as_str(),
+            );
+            match remaining.get_mut(&key) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => changes.push((file.file_path.clone(), issue.clone())),
+            }
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::CodeAnalyzer;
+    use crate::common::testing::TempDir;
+    use crate::common::LanguageType;
+    use crate::config::AnalysisConfig;
+
+    /// 构造指定总体得分的分析结果
+    fn result_with_score(score: f64) -> AnalysisResult {
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let mut result = analyzer
+            .analyze_string(
+                "fn main() {}\n",
+                LanguageType::Rust,
+                &AnalysisConfig::default(),
+            )
+            .unwrap();
+        result.code_quality_score = score;
+        result
+    }
+
+    /// 读取自动基线的总体得分
+    fn loaded_score(repo: &TempDir) -> Option<f64> {
+        AutoBaseline::locate(repo.path())
+            .unwrap()
+            .load()
+            .unwrap()
+            .map(|result| result.code_quality_score)
+    }
+
+    #[test]
+    fn auto_baseline_is_kept_per_branch_and_seeded_from_base() {
+        let repo = TempDir::new("baseline-branches");
+        repo.git(&["init", "-q", "-b", "main"], None);
+        repo.write("main.rs", "fn main() {}\n");
+        repo.git(&["add", "."], None);
+        repo.git(&["commit", "-q", "-m", "init"], None);
+
+        let main = AutoBaseline::locate(repo.path()).unwrap();
+        assert_eq!(main.branch(), "main");
+        assert_eq!(loaded_score(&repo), None);
+        main.save(&result_with_score(0.2)).unwrap();
+
+        // 新分支还没有基线时使用基础分支的基线
+        repo.git(&["checkout", "-q", "-b", "feature/x"], None);
+        let feature = AutoBaseline::locate(repo.path()).unwrap();
+        assert_eq!(feature.branch(), "feature/x");
+        assert_eq!(loaded_score(&repo), Some(0.2));
+
+        feature.save(&result_with_score(0.6)).unwrap();
+        assert!(feature.path_for("feature/x").is_file());
+        assert_eq!(loaded_score(&repo), Some(0.6));
+
+        repo.git(&["checkout", "-q", "main"], None);
+        assert_eq!(loaded_score(&repo), Some(0.2));
+    }
+
+    #[test]
+    fn explicit_base_branch_is_used_as_fallback() {
+        let dir = TempDir::new("baseline-explicit");
+        let release = AutoBaseline::new(dir.path().to_path_buf(), "release");
+        release.save(&result_with_score(0.4)).unwrap();
+
+        let topic = AutoBaseline::new(dir.path().to_path_buf(), "topic");
+        assert!(topic.load().unwrap().is_none());
+
+        let seeded = topic.with_base_branch("release").load().unwrap().unwrap();
+        assert_eq!(seeded.code_quality_score, 0.4);
+    }
+}
@@ -0,0 +1,101 @@
+//! # 解析性能测量
+//!
+//! 记录重复解析目标文件的耗时，用于跟踪解析器的性能回归
+
+use std::time::Duration;
+
+/// 解析性能测量结果
+#[derive(Debug, Clone)]
+pub struct ParseBenchmark {
+    /// 每轮解析的文件数
+    pub files: usize,
+
+    /// 每轮解析的代码行数
+    pub lines: usize,
+
+    /// 解析轮数
+    pub iterations: usize,
+
+    /// 所有轮次的解析总耗时（不含文件读取）
+    pub elapsed: Duration,
+}
+
+impl ParseBenchmark {
+    /// 每秒解析的代码行数
+    ///
+    /// # Returns
+    /// * `f64` - 行/秒
+    pub fn lines_per_second(&self) -> f64 {
+        self.throughput(self.lines)
+    }
+
+    /// 每秒解析的文件数
+    ///
+    /// # Returns
+    /// * `f64` - 文件/秒
+    pub fn files_per_second(&self) -> f64 {
+        self.throughput(self.files)
+    }
+
+    /// 计算吞吐量
+    ///
+    /// # Arguments
+    /// * `per_iteration` - 每轮处理的数量
+    ///
+    /// # Returns
+    /// * `f64` - 每秒处理的数量（耗时为 0 时为 0）
+    fn throughput(&self, per_iteration: usize) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            (per_iteration * self.iterations) as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::CodeAnalyzer;
+    use crate::common::testing::TempDir;
+    use crate::config::AnalysisConfig;
+
+    #[test]
+    fn throughput_counts_every_iteration() {
+        let bench = ParseBenchmark {
+            files: 4,
+            lines: 1000,
+            iterations: 3,
+            elapsed: Duration::from_millis(500),
+        };
+        assert_eq!(bench.lines_per_second(), 6000.0);
+        assert_eq!(bench.files_per_second(), 24.0);
+
+        let instant = ParseBenchmark {
+            elapsed: Duration::ZERO,
+            ..bench
+        };
+        assert_eq!(instant.lines_per_second(), 0.0);
+    }
+
+    #[test]
+    fn bench_parse_measures_the_fixture() {
+        let project = TempDir::new("bench-parse");
+        project.write("a.py", "def a(x):\n    return x\n");
+        project.write("b.js", "function b(x) {\n  return x;\n}\n");
+
+        let bench = CodeAnalyzer::new()
+            .bench_parse(
+                &[project.path().to_path_buf()],
+                &AnalysisConfig::default(),
+                4,
+            )
+            .unwrap();
+
+        assert_eq!((bench.files, bench.lines, bench.iterations), (2, 5, 4));
+        assert!(bench.elapsed > Duration::ZERO);
+        assert!(bench.lines_per_second() > 0.0);
+        assert!(bench.files_per_second() > 0.0);
+    }
+}
@@ -0,0 +1,364 @@
+//! # 解析缓存
+//!
+//! 按文件路径和内容保存解析器的输出，内容未变的文件在下次分析时直接复用，
+//! 并统计命中、未命中次数和估算节省的解析时间。
+//!
+//! 缓存的是解析器的原始输出（匿名函数和函数体在读取后按配置处理），与分析配置无关；
+//! 缓存文件记录工具版本和构建提交，任一变化时整体失效。每次运行只写回本次用到的条目，
+//! 已删除或已修改的文件对应的旧条目会被自动清理。
+
+use crate::common::{LanguageType, StableHasher};
+use crate::error::{AppError, AppResult};
+use crate::parser::{BaseParseResult, Function, ParseResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 缓存文件名（位于缓存目录下）
+const CACHE_FILE: &str = "parse-cache.json";
+
+/// 默认缓存目录（相对于当前目录）
+pub const DEFAULT_CACHE_DIR: &str = ".fuckshitcode/cache";
+
+/// 缓存格式版本（工具版本和构建提交），解析器变化后旧缓存不再可用
+const CACHE_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "+", env!("FSC_GIT_COMMIT"));
+
+/// 单个文件的缓存条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// 函数列表
+    functions: Vec<Function>,
+
+    /// 注释行数
+    comment_lines: usize,
+
+    /// 总行数
+    total_lines: usize,
+
+    /// 语言类型
+    language: LanguageType,
+
+    /// 生成该条目时的解析耗时（纳秒），用于估算命中时节省的时间
+    parse_nanos: u64,
+}
+
+/// 缓存文件内容
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// 写入缓存的工具版本和构建提交
+    version: String,
+
+    /// 缓存键到条目的映射
+    entries: HashMap<u64, CacheEntry>,
+}
+
+/// 缓存命中统计
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheStats {
+    /// 命中次数
+    pub hits: usize,
+
+    /// 未命中次数
+    pub misses: usize,
+
+    /// 命中条目当初的解析耗时之和（估算节省的时间）
+    pub time_saved: Duration,
+}
+
+impl CacheStats {
+    /// 命中率
+    ///
+    /// # Returns
+    /// * `f64` - 0-1 之间的命中率，没有查询时为 0
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total > 0 {
+            self.hits as f64 / total as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 解析缓存
+///
+/// 计数器和本次运行的条目都是线程安全的，可以在并行分析的各工作线程间共享
+#[derive(Debug)]
+pub struct ParseCache {
+    /// 缓存文件路径
+    path: PathBuf,
+
+    /// 上次运行写入的条目
+    previous: HashMap<u64, CacheEntry>,
+
+    /// 本次运行用到的条目（命中的旧条目和新解析的条目）
+    current: Mutex<HashMap<u64, CacheEntry>>,
+
+    /// 命中次数
+    hits: AtomicUsize,
+
+    /// 未命中次数
+    misses: AtomicUsize,
+
+    /// 命中条目的解析耗时之和（纳秒）
+    saved_nanos: AtomicU64,
+}
+
+impl ParseCache {
+    /// 打开缓存目录中的解析缓存
+    ///
+    /// 缓存文件不存在、无法读取或由其他版本写入时从空缓存开始
+    ///
+    /// # Arguments
+    /// * `dir` - 缓存目录
+    ///
+    /// # Returns
+    /// * `Self` - 解析缓存
+    pub fn open(dir: &Path) -> Self {
+        let path = dir.join(CACHE_FILE);
+        let previous = fs::read_to_string(&path)
+            .ok()
+            .and_then(
+                |content| match serde_json::from_str::<CacheFile>(&content) {
+                    Ok(file) => Some(file),
+                    Err(e) => {
+                        log::warn!("解析缓存 {} 已损坏，将重新生成: {}", path.display(), e);
+                        None
+                    }
+                },
+            )
+            .filter(|file| file.version == CACHE_VERSION)
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        ParseCache {
+            path,
+            previous,
+            current: Mutex::new(HashMap::new()),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            saved_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// 查找文件的解析结果
+    ///
+    /// # Arguments
+    /// * `path` - 文件路径
+    /// * `content` - 文件内容
+    ///
+    /// # Returns
+    /// * `Option<BaseParseResult>` - 缓存的解析结果，未命中时为 None
+    pub fn get(&self, path: &Path, content: &str) -> Option<BaseParseResult> {
+        let key = cache_key(path, content);
+        let Some(entry) = self.previous.get(&key) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        self.saved_nanos
+            .fetch_add(entry.parse_nanos, Ordering::Relaxed);
+        self.current.lock().unwrap().insert(key, entry.clone());
+
+        Some(BaseParseResult {
+            functions: entry.functions.clone(),
+            comment_lines: entry.comment_lines,
+            total_lines: entry.total_lines,
+            source: content.to_string(),
+            language: entry.language,
+        })
+    }
+
+    /// 保存文件的解析结果
+    ///
+    /// # Arguments
+    /// * `path` - 文件路径
+    /// * `content` - 文件内容
+    /// * `parse_result` - 解析器的输出
+    /// * `elapsed` - 解析耗时
+    pub fn insert(
+        &self,
+        path: &Path,
+        content: &str,
+        parse_result: &dyn ParseResult,
+        elapsed: Duration,
+    ) {
+        let entry = CacheEntry {
+            functions: parse_result.get_functions().to_vec(),
+            comment_lines: parse_result.get_comment_lines(),
+            total_lines: parse_result.get_total_lines(),
+            language: parse_result.get_language(),
+            parse_nanos: u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX),
+        };
+        self.current
+            .lock()
+            .unwrap()
+            .insert(cache_key(path, content), entry);
+    }
+
+    /// 获取本次运行的命中统计
+    ///
+    /// # Returns
+    /// * `CacheStats` - 命中统计
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            time_saved: Duration::from_nanos(self.saved_nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// 把本次运行用到的条目写回缓存文件
+    ///
+    /// # Returns
+    /// * `AppResult<()>` - 写入结果
+    pub fn save(&self) -> AppResult<()> {
+        let file = CacheFile {
+            version: CACHE_VERSION.to_string(),
+            entries: std::mem::take(&mut *self.current.lock().unwrap()),
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string(&file)
+            .map_err(|e| AppError::Other(format!("序列化解析缓存失败: {}", e)))?;
+        fs::write(&self.path, json)?;
+
+        Ok(())
+    }
+}
+
+/// 计算缓存键
+///
+/// # Arguments
+/// * `path` - 文件路径（决定所用的解析器）
+/// * `content` - 文件内容
+///
+/// # Returns
+/// * `u64` - 缓存键
+fn cache_key(path: &Path, content: &str) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_str(&path.display().to_string());
+    hasher.write_str(content);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::CodeAnalyzer;
+    use crate::common::testing::TempDir;
+    use crate::config::AnalysisConfig;
+    use crate::parser::create_parser_for_file;
+
+    const SOURCE: &str = "def add(a, b):\n    return a + b\n\n\ndef sub(a, b):\n    return a - b\n";
+
+    /// 解析源码并写入缓存
+    fn parse_into(cache: &ParseCache, path: &Path, content: &str) {
+        let result = create_parser_for_file(path).parse(path, content).unwrap();
+        cache.insert(path, content, &*result, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn cached_entries_are_reused_by_the_next_run() {
+        let dir = TempDir::new("cache-reuse");
+        let path = Path::new("calc.py");
+
+        let first = ParseCache::open(dir.path());
+        assert!(first.get(path, SOURCE).is_none());
+        parse_into(&first, path, SOURCE);
+        first.save().unwrap();
+        assert_eq!(first.stats().misses, 1);
+
+        let second = ParseCache::open(dir.path());
+        let cached = second.get(path, SOURCE).unwrap();
+        assert_eq!(cached.functions.len(), 2);
+        assert_eq!(cached.functions[1].name, "sub");
+        assert_eq!(cached.source, SOURCE);
+
+        let stats = second.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 0));
+        assert_eq!(stats.hit_rate(), 1.0);
+        assert_eq!(stats.time_saved, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn changed_content_misses_and_unused_entries_are_dropped() {
+        let dir = TempDir::new("cache-invalidate");
+        let path = Path::new("calc.py");
+
+        let first = ParseCache::open(dir.path());
+        parse_into(&first, path, SOURCE);
+        parse_into(&first, Path::new("other.py"), SOURCE);
+        first.save().unwrap();
+
+        let second = ParseCache::open(dir.path());
+        let changed = SOURCE.replace("a - b", "b - a");
+        assert!(second.get(path, &changed).is_none());
+        parse_into(&second, path, &changed);
+        second.save().unwrap();
+
+        let third = ParseCache::open(dir.path());
+        assert!(third.get(path, &changed).is_some());
+        assert!(third.get(path, SOURCE).is_none());
+        assert!(third.get(Path::new("other.py"), SOURCE).is_none());
+    }
+
+    #[test]
+    fn cache_from_another_version_is_ignored() {
+        let dir = TempDir::new("cache-version");
+        let path = Path::new("calc.py");
+
+        let first = ParseCache::open(dir.path());
+        parse_into(&first, path, SOURCE);
+        first.save().unwrap();
+
+        let file = dir.path().join(CACHE_FILE);
+        let content = fs::read_to_string(&file).unwrap();
+        fs::write(&file, content.replace(CACHE_VERSION, "0.0.0+old")).unwrap();
+
+        assert!(ParseCache::open(dir.path()).get(path, SOURCE).is_none());
+    }
+
+    #[test]
+    fn second_analysis_run_reports_a_high_hit_rate() {
+        let project = TempDir::new("cache-project");
+        project.write("src/calc.py", SOURCE);
+        project.write("src/util.js", "function twice(x) {\n  return x * 2;\n}\n");
+        project.write("src/lib.rs", "pub fn one() -> i32 {\n    1\n}\n");
+        let cache_dir = TempDir::new("cache-dir");
+
+        let config = AnalysisConfig {
+            cache_dir: Some(cache_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+
+        let first = analyzer
+            .analyze_with_config(&project.path().join("src"), &config)
+            .unwrap();
+        let first_stats = first.cache_stats.unwrap();
+        assert_eq!((first_stats.hits, first_stats.misses), (0, 3));
+
+        let second = analyzer
+            .analyze_with_config(&project.path().join("src"), &config)
+            .unwrap();
+        let second_stats = second.cache_stats.unwrap();
+        assert_eq!((second_stats.hits, second_stats.misses), (3, 0));
+        assert!(second_stats.hit_rate() >= 0.99);
+        assert!((first.code_quality_score - second.code_quality_score).abs() < 1e-9);
+        assert_eq!(first.run_id, second.run_id);
+
+        let uncached = analyzer
+            .analyze_with_config(&project.path().join("src"), &AnalysisConfig::default())
+            .unwrap();
+        assert!(uncached.cache_stats.is_none());
+    }
+}
@@ -0,0 +1,115 @@
+//! # 变更范围
+//!
+//! 记录相对于某个 git 版本修改过的文件和行，用于只分析变更的文件、只报告变更行上的问题
+
+use crate::analyzer::AnalysisResult;
+use crate::common::git;
+use crate::error::{AppError, AppResult};
+use crate::metrics::Issue;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 修改过的文件及行范围
+#[derive(Debug, Clone, Default)]
+pub struct ChangedLines {
+    /// 规范化后的文件路径到修改行范围的映射
+    files: HashMap<PathBuf, Vec<(usize, usize)>>,
+}
+
+impl ChangedLines {
+    /// 读取相对于指定版本的修改
+    ///
+    /// # Arguments
+    /// * `path` - 分析路径
+    /// * `since` - 比较的版本
+    ///
+    /// # Returns
+    /// * `AppResult<Self>` - 修改范围，不在 git 仓库中时返回配置错误
+    pub fn load(path: &Path, since: &str) -> AppResult<Self> {
+        let changes = git::changed_lines(path, since).ok_or_else(|| {
+            AppError::ConfigError(format!(
+                "无法获取相对于 {} 的修改：{} 不在 git 仓库中",
+                since,
+                path.display()
+            ))
+        })?;
+
+        Ok(Self::from_ranges(changes))
+    }
+
+    /// 使用已知的修改范围创建
+    ///
+    /// # Arguments
+    /// * `ranges` - 文件路径到修改行范围的映射
+    ///
+    /// # Returns
+    /// * `Self` - 修改范围
+    pub fn from_ranges(ranges: HashMap<PathBuf, Vec<(usize, usize)>>) -> Self {
+        ChangedLines {
+            files: ranges
+                .into_iter()
+                .map(|(file, ranges)| (normalize(&file), ranges))
+                .collect(),
+        }
+    }
+
+    /// 合并另一份修改范围（如另一个仓库中的修改）
+    ///
+    /// # Arguments
+    /// * `other` - 修改范围
+    pub fn extend(&mut self, other: ChangedLines) {
+        for (file, ranges) in other.files {
+            self.files.entry(file).or_default().extend(ranges);
+        }
+    }
+
+    /// 判断文件是否被修改
+    ///
+    /// # Arguments
+    /// * `file` - 文件路径
+    ///
+    /// # Returns
+    /// * `bool` - 是否被修改
+    pub fn contains_file(&self, file: &Path) -> bool {
+        self.files.contains_key(&normalize(file))
+    }
+
+    /// 判断问题是否位于修改过的行上
+    ///
+    /// # Arguments
+    /// * `file` - 文件路径
+    /// * `issue` - 问题
+    ///
+    /// # Returns
+    /// * `bool` - 是否位于修改行上，没有行号的文件级问题视为不在修改行上
+    pub fn touches(&self, file: &Path, issue: &Issue) -> bool {
+        self.files.get(&normalize(file)).is_some_and(|ranges| {
+            ranges
+                .iter()
+                .any(|&(start, end)| issue.overlaps(start, end))
+        })
+    }
+
+    /// 只保留位于修改行上的问题，文件得分保持不变
+    ///
+    /// # Arguments
+    /// * `result` - 分析结果
+    pub fn retain_changed_issues(&self, result: &mut AnalysisResult) {
+        for file in &mut result.files_analyzed {
+            let path = PathBuf::from(&file.file_path);
+            file.issues.retain(|issue| self.touches(&path, issue));
+        }
+    }
+}
+
+/// 规范化路径，使不同写法的同一文件可以比较
+///
+/// # Arguments
+/// * `path` - 路径
+///
+/// # Returns
+/// * `PathBuf` - 规范化后的路径，文件不存在时保持原样
+fn normalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,220 @@
+//! # 跨文件重复代码检测
+//!
+//! 对每个文件的规范化代码行计算连续 k 行窗口的滚动哈希（shingle），
+//! 汇总所有文件后找出在不同文件中出现的相同窗口，并向后延伸成完整的重复代码块。
+
+use crate::common::{stable_hash, LanguageType};
+use crate::parser::strip_source;
+use std::collections::HashMap;
+
+/// 默认构成重复代码块的最少连续代码行数
+pub const DEFAULT_CLONE_MIN_LINES: usize = 6;
+
+/// 滚动哈希的基数
+const ROLLING_BASE: u64 = 1_000_003;
+
+/// 同一窗口出现次数超过该值时视为样板代码，不参与检测
+const MAX_WINDOW_OCCURRENCES: usize = 20;
+
+/// 导入语句的前缀（导入列表在文件之间天然相似，不计入重复）
+const IMPORT_PREFIXES: &[&str] = &[
+    "use ",
+    "import ",
+    "from ",
+    "#include",
+    "using ",
+    "package ",
+    "require",
+    "extern crate",
+];
+
+/// 连续 k 行规范化代码的指纹
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shingle {
+    /// 窗口内容的滚动哈希
+    pub hash: u64,
+
+    /// 窗口第一行的行号（从 1 开始）
+    pub start_line: usize,
+
+    /// 窗口最后一行的行号
+    pub end_line: usize,
+}
+
+/// 与其他文件重复的代码块
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloneBlock {
+    /// 起始行号
+    pub start_line: usize,
+
+    /// 结束行号
+    pub end_line: usize,
+
+    /// 其他文件中的相同代码块（路径、起始行、结束行）
+    pub partners: Vec<(String, usize, usize)>,
+}
+
+/// 计算文件的代码块指纹
+///
+/// 注释和字符串内容先被剥离，空白统一折叠；空行、只有符号的行（如 `}`）
+/// 和导入语句不计入窗口，因此窗口的行号范围可能大于 `window` 行。
+///
+/// # Arguments
+/// * `content` - 文件内容
+/// * `language` - 语言类型
+/// * `window` - 窗口包含的代码行数（0 表示不检测）
+///
+/// # Returns
+/// * `Vec<Shingle>` - 按位置排列的指纹
+pub fn compute_shingles(content: &str, language: LanguageType, window: usize) -> Vec<Shingle> {
+    if window == 0 {
+        return Vec::new();
+    }
+
+    let stripped = strip_source(content, language);
+    let lines: Vec<(usize, u64)> = stripped
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let normalized = line.split_whitespace().collect::<Vec<_>>().join(" ");
+            is_significant(&normalized).then(|| (index + 1, stable_hash(normalized.as_bytes())))
+        })
+        .collect();
+
+    if lines.len() < window {
+        return Vec::new();
+    }
+
+    // 最早进入窗口的行的权重 ROLLING_BASE^(window-1)
+    let leading_weight = (1..window).fold(1u64, |acc, _| acc.wrapping_mul(ROLLING_BASE));
+    let mut hash = lines[..window].iter().fold(0u64, |acc, &(_, line_hash)| {
+        acc.wrapping_mul(ROLLING_BASE).wrapping_add(line_hash)
+    });
+
+    let mut shingles = Vec::with_capacity(lines.len() - window + 1);
+    shingles.push(Shingle {
+        hash,
+        start_line: lines[0].0,
+        end_line: lines[window - 1].0,
+    });
+
+    for start in 1..=lines.len() - window {
+        let outgoing = lines[start - 1].1;
+        let (end_line, incoming) = lines[start + window - 1];
+        hash = hash
+            .wrapping_sub(outgoing.wrapping_mul(leading_weight))
+            .wrapping_mul(ROLLING_BASE)
+            .wrapping_add(incoming);
+        shingles.push(Shingle {
+            hash,
+            start_line: lines[start].0,
+            end_line,
+        });
+    }
+
+    shingles
+}
+
+/// 判断规范化后的行是否参与重复检测
+///
+/// # Arguments
+/// * `line` - 规范化后的代码行
+///
+/// # Returns
+/// * `bool` - 是否参与
+fn is_significant(line: &str) -> bool {
+    line.chars().any(char::is_alphanumeric)
+        && !IMPORT_PREFIXES
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+}
+
+/// 查找各文件中与其他文件重复的代码块
+///
+/// 相同的窗口会向后延伸到两份代码第一次不同为止；同一文件内的重复不在这里报告。
+///
+/// # Arguments
+/// * `files` - （文件路径, 指纹）列表
+///
+/// # Returns
+/// * `Vec<Vec<CloneBlock>>` - 与 `files` 一一对应的重复代码块
+pub fn find_cross_file_clones(files: &[(String, Vec<Shingle>)]) -> Vec<Vec<CloneBlock>> {
+    let mut index: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+    for (file, (_, shingles)) in files.iter().enumerate() {
+        for (position, shingle) in shingles.iter().enumerate() {
+            index
+                .entry(shingle.hash)
+                .or_default()
+                .push((file, position));
+        }
+    }
+
+    files
+        .iter()
+        .enumerate()
+        .map(|(file, (_, shingles))| {
+            let mut blocks = Vec::new();
+            let mut position = 0;
+
+            while position < shingles.len() {
+                let occurrences = index
+                    .get(&shingles[position].hash)
+                    .filter(|occurrences| occurrences.len() <= MAX_WINDOW_OCCURRENCES);
+                let mut longest = 1;
+                let mut partners = Vec::new();
+
+                for &(other, other_position) in occurrences.into_iter().flatten() {
+                    if other == file {
+                        continue;
+                    }
+
+                    let (other_path, other_shingles) = &files[other];
+                    let length =
+                        matching_length(&shingles[position..], &other_shingles[other_position..]);
+                    longest = longest.max(length);
+                    partners.push((
+                        other_path.clone(),
+                        other_shingles[other_position].start_line,
+                        other_shingles[other_position + length - 1].end_line,
+                    ));
+                }
+
+                if partners.is_empty() {
+                    position += 1;
+                    continue;
+                }
+
+                // 并行分析的文件顺序不固定，排序保证输出稳定
+                partners.sort();
+                let end_line = shingles[position + longest - 1].end_line;
+                blocks.push(CloneBlock {
+                    start_line: shingles[position].start_line,
+                    end_line,
+                    partners,
+                });
+
+                // 跳过与当前代码块重叠的窗口，避免重复计数
+                while position < shingles.len() && shingles[position].start_line <= end_line {
+                    position += 1;
+                }
+            }
+
+            blocks
+        })
+        .collect()
+}
+
+/// 计算两段指纹从开头起连续相同的窗口数
+///
+/// # Arguments
+/// * `a` - 指纹
+/// * `b` - 指纹
+///
+/// # Returns
+/// * `usize` - 相同的窗口数
+fn matching_length(a: &[Shingle], b: &[Shingle]) -> usize {
+    a.iter()
+        .zip(b)
+        .take_while(|(x, y)| x.hash == y.hash)
+        .count()
+}
@@ -0,0 +1,156 @@
+//! # 疑似无用函数检测
+//!
+//! 汇总所有文件后，统计每个函数名在各文件原始内容中出现的次数（扣除定义本身），
+//! 从未在其他地方出现过的非导出函数被报告为可能的死代码。
+//!
+//! 这是基于文本的启发式检测：通过反射、动态分发（trait/接口实现、虚函数）、
+//! 拼接出的名称或框架约定（生命周期钩子、路由注解等）调用的函数会被误报；
+//! 未参与本次分析的文件（被排除、超出大小限制或不在 `--since` 范围内）中的调用也无法识别。
+//! 反过来，名称只要在任何位置出现（包括注释和字符串、同名的其他符号、递归调用），
+//! 函数就被视为已引用，因此不会报告。
+
+use crate::parser::Function;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// 标识符（含 JS/PHP 中允许的 `$`）
+static IDENTIFIER: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z_$][\w$]*").unwrap());
+
+/// 由语言、运行时或常见框架隐式调用的函数名，不参与检测
+const IMPLICIT_ENTRY_POINTS: &[&str] = &[
+    "main",
+    "init",
+    "new",
+    "default",
+    "constructor",
+    "fmt",
+    "drop",
+    "clone",
+    "eq",
+    "hash",
+    "cmp",
+    "partial_cmp",
+    "from",
+    "deref",
+    "deref_mut",
+    "next",
+    "setUp",
+    "tearDown",
+    "toString",
+    "equals",
+    "hashCode",
+    "compareTo",
+    "Dispose",
+    "ToString",
+    "Equals",
+    "GetHashCode",
+];
+
+/// 可能成为死代码的函数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadCodeCandidate {
+    /// 函数名
+    pub name: String,
+
+    /// 起始行号
+    pub start_line: usize,
+
+    /// 结束行号
+    pub end_line: usize,
+}
+
+/// 单个文件中与死代码检测相关的符号信息
+#[derive(Debug, Clone, Default)]
+pub struct FileSymbols {
+    /// 本文件中可能成为死代码的函数
+    pub candidates: Vec<DeadCodeCandidate>,
+
+    /// 标识符出现次数（已扣除本文件中函数定义处的名称）
+    pub references: HashMap<String, usize>,
+}
+
+/// 收集文件的死代码检测符号
+///
+/// # Arguments
+/// * `functions` - 文件中的函数
+/// * `content` - 文件原始内容
+///
+/// # Returns
+/// * `FileSymbols` - 符号信息
+pub fn collect_symbols(functions: &[Function], content: &str) -> FileSymbols {
+    let mut references: HashMap<String, usize> = HashMap::new();
+    for token in IDENTIFIER.find_iter(content) {
+        *references.entry(token.as_str().to_string()).or_default() += 1;
+    }
+
+    // 每个定义行本身包含一次函数名，不算作引用
+    for func in functions.iter().filter(|func| !func.is_anonymous()) {
+        if let Some(count) = references.get_mut(&func.name) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    let candidates = functions
+        .iter()
+        .filter(|func| is_candidate(func))
+        .map(|func| DeadCodeCandidate {
+            name: func.name.clone(),
+            start_line: func.start_line,
+            end_line: func.end_line,
+        })
+        .collect();
+
+    FileSymbols {
+        candidates,
+        references,
+    }
+}
+
+/// 判断函数是否参与死代码检测
+///
+/// 导出函数、匿名函数、测试函数、Python 的 `__dunder__` 方法和隐式入口函数不参与
+///
+/// # Arguments
+/// * `func` - 函数信息
+///
+/// # Returns
+/// * `bool` - 是否参与
+fn is_candidate(func: &Function) -> bool {
+    let name = func.name.as_str();
+    if func.exported || func.is_anonymous() || IMPLICIT_ENTRY_POINTS.contains(&name) {
+        return false;
+    }
+
+    let is_test = name.starts_with("test") || name.starts_with("Test");
+    let is_dunder = name.starts_with("__") && name.ends_with("__");
+    let is_identifier = IDENTIFIER.find(name).is_some_and(|m| m.as_str() == name);
+    is_identifier && !is_test && !is_dunder
+}
+
+/// 查找在所有文件中都没有被引用的函数
+///
+/// # Arguments
+/// * `files` - 各文件的符号信息
+///
+/// # Returns
+/// * `Vec<Vec<&DeadCodeCandidate>>` - 与 `files` 一一对应的疑似无用函数
+pub fn find_unreferenced(files: &[FileSymbols]) -> Vec<Vec<&DeadCodeCandidate>> {
+    let mut totals: HashMap<&str, usize> = HashMap::new();
+    for symbols in files {
+        for (name, count) in &symbols.references {
+            *totals.entry(name.as_str()).or_default() += count;
+        }
+    }
+
+    files
+        .iter()
+        .map(|symbols| {
+            symbols
+                .candidates
+                .iter()
+                .filter(|candidate| totals.get(candidate.name.as_str()).copied().unwrap_or(0) == 0)
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,154 @@
+//! # 结果合并
+//!
+//! 将多次（例如按服务拆分的流水线）分析得到的结果合并为一份
+
+use crate::analyzer::{AnalysisResult, FileAnalysisResult, LanguageScore, ParameterDistribution};
+use crate::common::StableHasher;
+use crate::error::{AppError, AppResult};
+use crate::metrics::MetricResult;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// 从 JSON 文件读取分析结果
+///
+/// 兼容 `--json` 的输出：若文件以 `quality` 方向导出，会先还原为内部的屎气分数。
+///
+/// # Arguments
+/// * `path` - JSON 文件路径
+///
+/// # Returns
+/// * `AppResult<AnalysisResult>` - 分析结果
+pub fn load_result(path: &Path) -> AppResult<AnalysisResult> {
+    if !path.is_file() {
+        return Err(AppError::FileNotFound(path.to_path_buf()));
+    }
+
+    let content = fs::read_to_string(path)?;
+    let invalid = |e: serde_json::Error| {
+        AppError::ParseError(format!("结果文件 {} 格式无效: {}", path.display(), e))
+    };
+
+    let value: serde_json::Value = serde_json::from_str(&content).map_err(invalid)?;
+    let is_quality = value.get("polarity").and_then(|p| p.as_str()) == Some("quality");
+    let mut result: AnalysisResult = serde_json::from_value(value).map_err(invalid)?;
+
+    if is_quality {
+        result.code_quality_score = 1.0 - result.code_quality_score;
+        for metric in result.metrics.values_mut() {
+            metric.score = 1.0 - metric.score;
+        }
+        for file in &mut result.files_analyzed {
+            file.file_score = 1.0 - file.file_score;
+            for metric in file.metrics.values_mut() {
+                metric.score = 1.0 - metric.score;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// 合并多个分析结果
+///
+/// 文件数、行数直接累加，文件列表拼接；总体评分与各项指标按代码行数加权平均。
+///
+/// # Arguments
+/// * `results` - 分析结果列表
+///
+/// # Returns
+/// * `AnalysisResult` - 合并后的结果
+pub fn merge_results(results: Vec<AnalysisResult>) -> AnalysisResult {
+    let total_lines: usize = results.iter().map(|r| r.total_lines).sum();
+    let total_files = results.iter().map(|r| r.total_files).sum();
+    let weight_of = |r: &AnalysisResult| line_weight(r.total_lines, total_lines, results.len());
+
+    let code_quality_score = results
+        .iter()
+        .map(|r| r.code_quality_score * weight_of(r))
+        .sum();
+
+    let mut metrics: HashMap<String, MetricResult> = HashMap::new();
+    let mut metric_weights: HashMap<String, f64> = HashMap::new();
+    for result in &results {
+        let weight = weight_of(result);
+        for (name, metric) in &result.metrics {
+            let merged = metrics.entry(name.clone()).or_insert_with(|| MetricResult {
+                score: 0.0,
+                weight: metric.weight,
+                description: metric.description.clone(),
+                issues: vec![],
+            });
+            merged.score += metric.score * weight;
+            *metric_weights.entry(name.clone()).or_default() += weight;
+        }
+    }
+
+    // 某些指标只出现在部分结果中时，按实际参与的权重归一化
+    for (name, metric) in metrics.iter_mut() {
+        let weight = metric_weights[name];
+        if weight > 0.0 {
+            metric.score /= weight;
+        }
+    }
+
+    let mut run_ids: Vec<&str> = results.iter().map(|r| r.run_id.as_str()).collect();
+    run_ids.sort_unstable();
+    let mut hasher = StableHasher::new();
+    for run_id in run_ids {
+        hasher.write_str(run_id);
+    }
+
+    let mut parameter_distribution = ParameterDistribution::default();
+    for result in &results {
+        parameter_distribution.merge(&result.parameter_distribution);
+    }
+
+    let mut analyzed_files: Vec<String> = results
+        .iter()
+        .flat_map(|r| r.analyzed_files.iter().cloned())
+        .collect();
+    analyzed_files.sort();
+    let skipped_files = results
+        .iter()
+        .flat_map(|r| r.skipped_files.iter().cloned())
+        .collect();
+
+    let is_empty = results.iter().all(|r| r.is_empty);
+    let files_analyzed: Vec<FileAnalysisResult> =
+        results.into_iter().flat_map(|r| r.files_analyzed).collect();
+
+    AnalysisResult {
+        code_quality_score,
+        metrics,
+        is_empty,
+        languages: LanguageScore::from_files(&files_analyzed),
+        files_analyzed,
+        total_files,
+        total_lines,
+        run_id: format!("{:016x}", hasher.finish()),
+        parameter_distribution,
+        analyzed_files,
+        skipped_files,
+        cache_stats: None,
+    }
+}
+
+/// 计算单个结果的行数权重
+///
+/// 所有结果都没有代码行时退化为等权平均。
+///
+/// # Arguments
+/// * `lines` - 该结果的代码行数
+/// * `total_lines` - 总代码行数
+/// * `count` - 结果数量
+///
+/// # Returns
+/// * `f64` - 权重（所有结果权重之和为 1）
+fn line_weight(lines: usize, total_lines: usize, count: usize) -> f64 {
+    if total_lines > 0 {
+        lines as f64 / total_lines as f64
+    } else {
+        1.0 / count.max(1) as f64
+    }
+}
@@ -3,7 +3,21 @@
 //! 负责协调整个代码分析流程
 
 mod analyzer;
+mod baseline;
+mod bench;
+mod cache;
+mod changes;
+mod clones;
+mod dead_code;
+mod merge;
 mod result;
+mod todo;
 
 pub use analyzer::CodeAnalyzer;
-pub use result::{AnalysisResult, FileAnalysisResult};
+pub use baseline::{AutoBaseline, BaselineDiff, FileDelta, MetricDelta};
+pub use bench::ParseBenchmark;
+pub use cache::{CacheStats, ParseCache, DEFAULT_CACHE_DIR};
+pub use changes::ChangedLines;
+pub use clones::DEFAULT_CLONE_MIN_LINES;
+pub use merge::{load_result, merge_results};
+pub use result::{AnalysisResult, FileAnalysisResult, LanguageScore, ParameterDistribution};
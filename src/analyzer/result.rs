@@ -2,11 +2,17 @@
 //!
 //! 定义分析结果的数据结构
 
-use crate::metrics::MetricResult;
+use crate::common::{LanguageType, SkippedFile};
+use crate::metrics::{Issue, MetricResult};
+use crate::parser::Function;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+
+use super::cache::CacheStats;
 
 /// 分析结果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisResult {
     /// 代码质量得分（0-1）
     pub code_quality_score: f64,
@@ -25,10 +31,85 @@ pub struct AnalysisResult {
 
     /// 是否为空项目
     pub is_empty: bool,
+
+    /// 运行标识（由分析文件、文件内容和配置计算，输入相同则标识相同）
+    #[serde(default)]
+    pub run_id: String,
+
+    /// 函数参数数量分布
+    #[serde(default)]
+    pub parameter_distribution: ParameterDistribution,
+
+    /// 所有参与分析的文件路径（已排序）
+    #[serde(default)]
+    pub analyzed_files: Vec<String>,
+
+    /// 被跳过的文件及原因
+    #[serde(default)]
+    pub skipped_files: Vec<SkippedFile>,
+
+    /// 按语言汇总的得分（最差的语言在前）
+    #[serde(default)]
+    pub languages: Vec<LanguageScore>,
+
+    /// 本次运行的解析缓存命中统计（未启用缓存时为 None，不写入结果文件）
+    #[serde(skip)]
+    pub cache_stats: Option<CacheStats>,
+}
+
+/// 使用通用解析器的文件占比超过该值时提示结果不可靠
+pub const GENERIC_PARSER_WARNING_RATIO: f64 = 0.2;
+
+impl AnalysisResult {
+    /// 把文件路径改写为相对于根目录的路径（不在根目录下的路径保持不变）
+    ///
+    /// 用于按相对路径匹配两棵目录树中的同一文件。
+    ///
+    /// # Arguments
+    /// * `root` - 根目录
+    pub fn relativize_paths(&mut self, root: &Path) {
+        let relative = |path: &str| {
+            Path::new(path)
+                .strip_prefix(root)
+                .map(|rel| rel.display().to_string())
+                .unwrap_or_else(|_| path.to_string())
+        };
+
+        for file in &mut self.files_analyzed {
+            file.file_path = relative(&file.file_path);
+        }
+        for path in &mut self.analyzed_files {
+            *path = relative(path);
+        }
+        for skipped in &mut self.skipped_files {
+            skipped.path = relative(&skipped.path);
+        }
+    }
+
+    /// 使用通用解析器分析的文件数
+    ///
+    /// # Returns
+    /// * `usize` - 文件数
+    pub fn generic_parser_files(&self) -> usize {
+        self.files_analyzed
+            .iter()
+            .filter(|file| file.generic_parser)
+            .count()
+    }
+
+    /// 结果是否只是近似值（通用解析器分析的文件占比过高）
+    ///
+    /// # Returns
+    /// * `bool` - 是否需要提示
+    pub fn is_approximate(&self) -> bool {
+        let total = self.files_analyzed.len();
+        total > 0
+            && self.generic_parser_files() as f64 / total as f64 > GENERIC_PARSER_WARNING_RATIO
+    }
 }
 
 /// 文件分析结果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileAnalysisResult {
     /// 文件路径
     pub file_path: String,
@@ -36,6 +117,154 @@ pub struct FileAnalysisResult {
     /// 文件得分
     pub file_score: f64,
 
+    /// 代码行数
+    #[serde(default)]
+    pub lines: usize,
+
+    /// 各项指标在该文件上的得分（问题已移到 `issues`）
+    #[serde(default)]
+    pub metrics: HashMap<String, MetricResult>,
+
     /// 发现的问题
-    pub issues: Vec<String>,
+    pub issues: Vec<Issue>,
+
+    /// 超出保留上限被丢弃的问题数
+    #[serde(default)]
+    pub dropped_issues: usize,
+
+    /// 是否由通用解析器分析（结果只是近似值）
+    #[serde(default)]
+    pub generic_parser: bool,
+
+    /// 文件的语言
+    #[serde(default)]
+    pub language: LanguageType,
+}
+
+/// 单个语言的汇总得分
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanguageScore {
+    /// 语言
+    pub language: LanguageType,
+
+    /// 得分（0-1，该语言各文件得分按行数加权的平均值）
+    pub score: f64,
+
+    /// 文件数
+    pub files: usize,
+
+    /// 代码行数
+    pub lines: usize,
+}
+
+impl LanguageScore {
+    /// 按语言汇总文件得分
+    ///
+    /// 各文件得分按行数加权，没有代码行的语言退化为等权平均。
+    ///
+    /// # Arguments
+    /// * `files` - 文件分析结果
+    ///
+    /// # Returns
+    /// * `Vec<Self>` - 各语言的得分（最差的在前，得分相同时按语言名称排序）
+    pub fn from_files(files: &[FileAnalysisResult]) -> Vec<Self> {
+        let mut groups: HashMap<LanguageType, Vec<&FileAnalysisResult>> = HashMap::new();
+        for file in files {
+            groups.entry(file.language).or_default().push(file);
+        }
+
+        let mut scores: Vec<Self> = groups
+            .into_iter()
+            .map(|(language, files)| {
+                let lines: usize = files.iter().map(|file| file.lines).sum();
+                let score = if lines > 0 {
+                    files
+                        .iter()
+                        .map(|file| file.file_score * file.lines as f64)
+                        .sum::<f64>()
+                        / lines as f64
+                } else {
+                    files.iter().map(|file| file.file_score).sum::<f64>() / files.len() as f64
+                };
+
+                LanguageScore {
+                    language,
+                    score,
+                    files: files.len(),
+                    lines,
+                }
+            })
+            .collect();
+
+        scores.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.language.display_name().cmp(b.language.display_name()))
+        });
+        scores
+    }
+}
+
+/// 函数参数数量分布
+///
+/// 按 0、1、2-3、4-5、6+ 五个区间统计函数个数，用于发现系统性的接口设计问题
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterDistribution {
+    /// 各区间的函数个数
+    pub buckets: [usize; 5],
+}
+
+impl ParameterDistribution {
+    /// 区间标签
+    pub const LABELS: [&'static str; 5] = ["0", "1", "2-3", "4-5", "6+"];
+
+    /// 统计函数列表的参数数量分布
+    ///
+    /// # Arguments
+    /// * `functions` - 函数列表
+    ///
+    /// # Returns
+    /// * `Self` - 参数数量分布
+    pub fn from_functions(functions: &[Function]) -> Self {
+        let mut distribution = Self::default();
+        for func in functions {
+            distribution.buckets[Self::bucket_of(func.parameters)] += 1;
+        }
+        distribution
+    }
+
+    /// 获取参数数量所属区间
+    ///
+    /// # Arguments
+    /// * `parameters` - 参数数量
+    ///
+    /// # Returns
+    /// * `usize` - 区间下标
+    pub fn bucket_of(parameters: usize) -> usize {
+        match parameters {
+            0 => 0,
+            1 => 1,
+            2..=3 => 2,
+            4..=5 => 3,
+            _ => 4,
+        }
+    }
+
+    /// 合并另一个分布
+    ///
+    /// # Arguments
+    /// * `other` - 另一个分布
+    pub fn merge(&mut self, other: &ParameterDistribution) {
+        for (bucket, count) in self.buckets.iter_mut().zip(other.buckets) {
+            *bucket += count;
+        }
+    }
+
+    /// 函数总数
+    ///
+    /// # Returns
+    /// * `usize` - 所有区间的函数个数之和
+    pub fn total(&self) -> usize {
+        self.buckets.iter().sum()
+    }
 }
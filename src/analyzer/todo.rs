@@ -0,0 +1,125 @@
+//! # 陈旧 TODO 检测
+//!
+//! 找出源码中的 TODO/FIXME 标记，并借助 git blame 标记长期未处理的技术债
+
+use crate::common::git;
+use crate::metrics::{Issue, IssueKind};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 技术债标记正则
+static TODO_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(TODO|FIXME)\b").unwrap());
+
+/// 一天的秒数
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// 查找包含 TODO/FIXME 标记的行
+///
+/// # Arguments
+/// * `content` - 文件内容
+///
+/// # Returns
+/// * `Vec<(usize, String)>` - (行号（从 1 开始）, 标记)
+pub fn find_todo_markers(content: &str) -> Vec<(usize, String)> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            TODO_REGEX
+                .find(line)
+                .map(|m| (index + 1, m.as_str().to_string()))
+        })
+        .collect()
+}
+
+/// 检测文件中超过指定天数的 TODO/FIXME 标记
+///
+/// 不在 git 仓库中或文件未被跟踪时返回空列表。
+///
+/// # Arguments
+/// * `path` - 文件路径
+/// * `content` - 文件内容
+/// * `max_age_days` - 允许的最长存在天数
+///
+/// # Returns
+/// * `Vec<Issue>` - 问题列表
+pub fn stale_todo_issues(path: &Path, content: &str, max_age_days: u64) -> Vec<Issue> {
+    let markers = find_todo_markers(content);
+    if markers.is_empty() {
+        return Vec::new();
+    }
+
+    let lines: Vec<usize> = markers.iter().map(|(line, _)| *line).collect();
+    let Some(times) = git::blame_line_times(path, &lines) else {
+        return Vec::new();
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    markers
+        .into_iter()
+        .filter_map(|(line, marker)| {
+            let age_days = (now - *times.get(&line)?) / SECONDS_PER_DAY;
+            (age_days > max_age_days as i64).then(|| {
+                Issue::new(
+                    IssueKind::StaleTodo,
+                    format!(
+                        "第 {} 行的 {} 已存在 {} 天，属于陈旧技术债",
+                        line, marker, age_days
+                    ),
+                )
+                .with_line(line)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::testing::TempDir;
+    use std::fs;
+
+    #[test]
+    fn finds_markers_as_whole_words() {
+        let content = "// TODO: a\nlet todo_list = 1;\n# FIXME later\n// TODOS\n";
+        assert_eq!(
+            find_todo_markers(content),
+            [(1, "TODO".to_string()), (3, "FIXME".to_string())]
+        );
+    }
+
+    #[test]
+    fn old_todo_is_stale_and_fresh_one_is_not() {
+        let repo = TempDir::new("stale-todo");
+        repo.git(&["init", "-q"], None);
+        let file = repo.write("lib.py", "# TODO: old debt\ndef a():\n    pass\n");
+        repo.git(&["add", "."], None);
+        repo.git(&["commit", "-q", "-m", "old"], Some("2020-01-01T00:00:00Z"));
+
+        let content = "# TODO: old debt\ndef a():\n    pass\n# FIXME: fresh\n";
+        fs::write(&file, content).unwrap();
+        repo.git(&["commit", "-q", "-am", "new"], None);
+
+        let issues = stale_todo_issues(&file, content, 30);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, IssueKind::StaleTodo);
+        assert_eq!(issues[0].line, Some(1));
+
+        let uncommitted = format!("{}# TODO: not committed\n", content);
+        fs::write(&file, &uncommitted).unwrap();
+        assert_eq!(stale_todo_issues(&file, &uncommitted, 30).len(), 1);
+    }
+
+    #[test]
+    fn files_outside_git_are_not_flagged() {
+        let dir = TempDir::new("stale-todo-nogit");
+        let file = dir.write("lib.py", "# TODO: anything\n");
+        assert!(stale_todo_issues(&file, "# TODO: anything\n", 0).is_empty());
+    }
+}
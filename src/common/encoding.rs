@@ -0,0 +1,101 @@
+//! # 文件编码处理
+//!
+//! 去除 UTF-8 BOM，并在启用 `encoding` 特性时把常见的非 UTF-8 编码转换为 UTF-8
+
+use std::io;
+
+/// UTF-8 字节序标记
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// 将文件字节解码为 UTF-8 文本
+///
+/// # Arguments
+/// * `bytes` - 文件内容
+///
+/// # Returns
+/// * `io::Result<(String, &'static str)>` - (解码后的文本, 检测到的编码名称)
+pub fn decode_source(bytes: &[u8]) -> io::Result<(String, &'static str)> {
+    let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok((text.to_string(), "UTF-8")),
+        Err(e) => decode_legacy(bytes, e),
+    }
+}
+
+/// 检测并解码非 UTF-8 编码
+///
+/// # Arguments
+/// * `bytes` - 文件内容
+/// * `_error` - UTF-8 解码错误
+///
+/// # Returns
+/// * `io::Result<(String, &'static str)>` - (解码后的文本, 检测到的编码名称)
+#[cfg(feature = "encoding")]
+fn decode_legacy(bytes: &[u8], _error: std::str::Utf8Error) -> io::Result<(String, &'static str)> {
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(bytes, true);
+    let encoding = detector.guess(None, true);
+
+    let (text, _, _) = encoding.decode(bytes);
+    Ok((text.into_owned(), encoding.name()))
+}
+
+/// 未启用 `encoding` 特性时，非 UTF-8 文件直接报错
+///
+/// # Arguments
+/// * `_bytes` - 文件内容
+/// * `error` - UTF-8 解码错误
+///
+/// # Returns
+/// * `io::Result<(String, &'static str)>` - 始终返回错误
+#[cfg(not(feature = "encoding"))]
+fn decode_legacy(_bytes: &[u8], error: std::str::Utf8Error) -> io::Result<(String, &'static str)> {
+    Err(io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::create_parser_for_file;
+    use std::path::Path;
+
+    #[test]
+    fn bom_prefixed_file_parses_like_plain_utf8() {
+        let source = "def greet(name):\n    return 'hi ' + name\n";
+        let bytes = [UTF8_BOM, source.as_bytes()].concat();
+
+        let (text, encoding) = decode_source(&bytes).unwrap();
+        assert_eq!(text, source);
+        assert_eq!(encoding, "UTF-8");
+
+        let path = Path::new("greet.py");
+        let parse_result = create_parser_for_file(path).parse(path, &text).unwrap();
+        let functions = parse_result.get_functions();
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "greet");
+        assert_eq!(functions[0].start_line, 1);
+    }
+
+    #[cfg(not(feature = "encoding"))]
+    #[test]
+    fn non_utf8_file_is_rejected_without_the_feature() {
+        let error = decode_source(b"x = '\x82\xb1\x82\xf1'\n").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn shift_jis_file_is_decoded() {
+        // "# 設定ファイルを読み込んで、ユーザーの名前を表示する関数です。" 等三行的 Shift-JIS 编码
+        let bytes = b"# \x90\xdd\x92\xe8\x83t\x83@\x83C\x83\x8b\x82\xf0\x93\xc7\x82\xdd\x8d\x9e\x82\xf1\x82\xc5\x81A\x83\x86\x81[\x83U\x81[\x82\xcc\x96\xbc\x91O\x82\xf0\x95\x5c\x8e\xa6\x82\xb7\x82\xe9\x8a\xd6\x90\x94\x82\xc5\x82\xb7\x81B\ndef greet(name):\n    print(\"\x82\xb1\x82\xf1\x82\xc9\x82\xbf\x82\xcd\x81A\" + name + \"\x82\xb3\x82\xf1\")\n";
+
+        let (text, encoding) = decode_source(bytes).unwrap();
+        assert_eq!(encoding, "Shift_JIS");
+        assert_eq!(
+            text,
+            "# 設定ファイルを読み込んで、ユーザーの名前を表示する関数です。\n\
+             def greet(name):\n    print(\"こんにちは、\" + name + \"さん\")\n"
+        );
+    }
+}
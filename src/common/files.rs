@@ -2,13 +2,109 @@
 //!
 //! 提供文件搜索、过滤等功能
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use walkdir::{DirEntry, WalkDir};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
-use super::LanguageDetector;
+use ignore::{WalkBuilder, WalkState};
+
+use super::{LanguageDetector, LanguageType};
+
+/// 根目录下读取的忽略文件（gitignore 风格，每行一个模式）
+pub const IGNORE_FILES: &[&str] = &[".fscignore", ".gitignore"];
+
+/// 文件被跳过的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// 隐藏目录
+    Hidden,
+
+    /// 匹配排除模式
+    Excluded,
+
+    /// 不匹配任何包含模式
+    NotIncluded,
+
+    /// 不支持的文件类型
+    Unsupported,
+
+    /// 不在扩展名过滤列表中
+    ExtensionFilter,
+
+    /// 相对于 --since 版本未修改
+    Unchanged,
+
+    /// 文件不存在
+    Missing,
+
+    /// 文件大于 max_file_size
+    TooLarge,
+
+    /// 文件小于 min_file_size
+    TooSmall,
+
+    /// 读取或解析失败
+    Failed,
+}
+
+/// 被跳过的文件（或目录）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedFile {
+    /// 路径
+    pub path: String,
+
+    /// 跳过原因
+    pub reason: SkipReason,
+
+    /// 补充说明（如错误信息）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl SkippedFile {
+    /// 创建跳过记录
+    ///
+    /// # Arguments
+    /// * `path` - 路径
+    /// * `reason` - 跳过原因
+    ///
+    /// # Returns
+    /// * `Self` - 跳过记录
+    pub fn new(path: &Path, reason: SkipReason) -> Self {
+        SkippedFile {
+            path: path.display().to_string(),
+            reason,
+            detail: None,
+        }
+    }
+
+    /// 附加补充说明
+    ///
+    /// # Arguments
+    /// * `detail` - 补充说明
+    ///
+    /// # Returns
+    /// * `Self` - 跳过记录
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// 源文件搜索结果
+#[derive(Debug, Default)]
+pub struct SourceFiles {
+    /// 找到的源文件
+    pub files: Vec<PathBuf>,
+
+    /// 被跳过的文件和目录
+    pub skipped: Vec<SkippedFile>,
+}
 
 /// 文件查找器
 ///
@@ -23,8 +119,17 @@ pub struct FileFinder {
     /// 排除模式集
     exclude_patterns: GlobSet,
 
+    /// 忽略文件中的模式集
+    ignore_patterns: GlobSet,
+
     /// 语言检测器
     detector: LanguageDetector,
+
+    /// 只保留这些扩展名的文件（小写、不含点，为空时不限制）
+    extensions: HashSet<String>,
+
+    /// 是否用 ignore crate 遍历目录（遵循嵌套的 .gitignore）
+    respect_gitignore: bool,
 }
 
 impl FileFinder {
@@ -49,10 +154,76 @@ impl FileFinder {
             root_dir: root_dir.to_path_buf(),
             include_patterns: include_set,
             exclude_patterns: exclude_set,
+            ignore_patterns: GlobSet::empty(),
             detector: LanguageDetector::new(),
+            extensions: HashSet::new(),
+            respect_gitignore: false,
         })
     }
 
+    /// 设置扩展名过滤
+    ///
+    /// # Arguments
+    /// * `extensions` - 扩展名列表（不区分大小写，可带开头的点），为空时不限制
+    ///
+    /// # Returns
+    /// * `Self` - 查找器实例
+    pub fn with_extensions(mut self, extensions: &[String]) -> Self {
+        self.extensions = extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.').to_lowercase())
+            .collect();
+        self
+    }
+
+    /// 读取根目录下的忽略文件，把其中的模式追加为排除规则
+    ///
+    /// 支持注释、空行、`/` 开头的根目录锚定和 `/` 结尾的目录模式；
+    /// 不支持 `!` 取反模式，遇到时忽略该行并输出警告。
+    ///
+    /// # Returns
+    /// * `Result<Self>` - 查找器实例
+    pub fn with_ignore_files(mut self) -> Result<Self> {
+        let mut patterns = Vec::new();
+
+        for name in IGNORE_FILES {
+            let path = self.root_dir.join(name);
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+
+            for line in content.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                if line.starts_with('!') {
+                    log::warn!("{} 中的取反模式 {} 不受支持，已忽略", path.display(), line);
+                    continue;
+                }
+                patterns.extend(ignore_line_to_globs(line));
+            }
+        }
+
+        self.ignore_patterns = Self::build_glob_set(&patterns)
+            .with_context(|| format!("忽略文件中的模式无效（{}）", IGNORE_FILES.join("、")))?;
+        Ok(self)
+    }
+
+    /// 改用 ignore crate 遍历目录
+    ///
+    /// 除了本查找器的排除规则外，还会遵循各级目录中的 .gitignore 和 .fscignore、
+    /// .git/info/exclude 以及全局 gitignore。
+    ///
+    /// # Arguments
+    /// * `enabled` - 是否启用
+    ///
+    /// # Returns
+    /// * `Self` - 查找器实例
+    pub fn with_gitignore(mut self, enabled: bool) -> Self {
+        self.respect_gitignore = enabled;
+        self
+    }
+
     /// 构建glob模式集
     ///
     /// # Arguments
@@ -73,60 +244,103 @@ impl FileFinder {
 
     /// 查找源文件
     ///
+    /// 目录遍历在多个线程上并行进行，返回的文件按路径排序。
+    ///
     /// # Arguments
-    /// * `progress_callback` - 进度回调
+    /// * `progress_callback` - 进度回调（可能在遍历线程中调用）
     ///
     /// # Returns
-    /// * `Vec<PathBuf>` - 找到的文件列表
-    pub fn find_source_files<F>(&self, progress_callback: F) -> Vec<PathBuf>
+    /// * `SourceFiles` - 找到的文件和被跳过的文件
+    pub fn find_source_files<F>(&self, progress_callback: F) -> SourceFiles
     where
-        F: Fn(usize),
+        F: Fn(usize) + Sync,
     {
-        let mut files = Vec::new();
-        let mut visited_dirs = HashSet::new();
-        let mut file_count = 0;
-
-        // 创建目录遍历器
-        let walker = WalkDir::new(&self.root_dir)
-            .follow_links(false)
-            .into_iter()
-            .filter_entry(|e| self.should_visit_dir(e, &mut visited_dirs));
-
-        // 遍历文件
-        for entry in walker.flatten() {
-            if self.is_valid_source_file(&entry) {
-                files.push(entry.path().to_path_buf());
-                file_count += 1;
-                progress_callback(file_count);
-            }
-        }
+        let files = Mutex::new(Vec::new());
+        let skipped = Mutex::new(Vec::new());
+        let file_count = AtomicUsize::new(0);
+
+        self.walk_builder().build_parallel().run(|| {
+            Box::new(|entry| {
+                let Ok(entry) = entry else {
+                    return WalkState::Continue;
+                };
+                let path = entry.path();
+
+                // 根目录本身不做隐藏和排除判断
+                if entry.depth() > 0 {
+                    if let Err(reason) = self.check_entry(path) {
+                        lock(&skipped).push(SkippedFile::new(path, reason));
+                        return WalkState::Skip;
+                    }
+                }
+
+                if !entry.file_type().is_some_and(|t| t.is_file()) {
+                    return WalkState::Continue;
+                }
+
+                match self.check_source_file(path) {
+                    Ok(()) => {
+                        lock(&files).push(entry.into_path());
+                        progress_callback(file_count.fetch_add(1, Ordering::Relaxed) + 1);
+                    }
+                    Err(reason) => lock(&skipped).push(SkippedFile::new(path, reason)),
+                }
+                WalkState::Continue
+            })
+        });
 
-        files
+        let mut files = files.into_inner().unwrap_or_else(PoisonError::into_inner);
+        files.sort();
+
+        SourceFiles {
+            files,
+            skipped: skipped.into_inner().unwrap_or_else(PoisonError::into_inner),
+        }
     }
 
-    /// 判断是否应该访问目录
+    /// 创建目录遍历器
     ///
-    /// # Arguments
-    /// * `entry` - 目录项
-    /// * `visited` - 已访问集合
+    /// 默认只做基本遍历，隐藏和排除判断由查找器自己完成；
+    /// 启用 gitignore 时额外遵循各级 .gitignore、.fscignore、.git/info/exclude 和全局 gitignore。
     ///
     /// # Returns
-    /// * `bool` - 是否访问
-    fn should_visit_dir(&self, entry: &DirEntry, visited: &mut HashSet<PathBuf>) -> bool {
-        let path = entry.path();
+    /// * `WalkBuilder` - 遍历器构建器
+    fn walk_builder(&self) -> WalkBuilder {
+        let mut builder = WalkBuilder::new(&self.root_dir);
+        builder.follow_links(false).standard_filters(false);
 
-        // 避免重复访问
-        if !visited.insert(path.to_path_buf()) {
-            return false;
+        if self.respect_gitignore {
+            builder
+                .git_ignore(true)
+                .git_exclude(true)
+                .git_global(true)
+                .parents(true)
+                .require_git(false)
+                .add_custom_ignore_filename(IGNORE_FILES[0]);
         }
 
+        builder
+    }
+
+    /// 检查目录项是否应该访问
+    ///
+    /// # Arguments
+    /// * `path` - 路径
+    ///
+    /// # Returns
+    /// * `Result<(), SkipReason>` - 应该访问时为 Ok，否则返回跳过原因
+    fn check_entry(&self, path: &Path) -> Result<(), SkipReason> {
         // 跳过隐藏目录
         if self.is_hidden_dir(path) {
-            return false;
+            return Err(SkipReason::Hidden);
         }
 
         // 检查排除模式
-        !self.is_excluded(path)
+        if self.is_excluded(path) {
+            return Err(SkipReason::Excluded);
+        }
+
+        Ok(())
     }
 
     /// 判断是否为隐藏目录
@@ -152,60 +366,153 @@ impl FileFinder {
     /// * `bool` - 是否排除
     fn is_excluded(&self, path: &Path) -> bool {
         if let Ok(rel_path) = path.strip_prefix(&self.root_dir) {
-            return self.exclude_patterns.is_match(rel_path);
+            return self.exclude_patterns.is_match(rel_path)
+                || self.ignore_patterns.is_match(rel_path);
         }
         false
     }
 
-    /// 判断是否为有效的源文件
+    /// 检查文件是否为有效的源文件
+    ///
+    /// # Arguments
+    /// * `path` - 文件路径
+    ///
+    /// # Returns
+    /// * `Result<(), SkipReason>` - 有效时为 Ok，否则返回跳过原因
+    fn check_source_file(&self, path: &Path) -> Result<(), SkipReason> {
+        self.check_language(path)?;
+
+        // 检查包含和排除模式
+        self.check_include_file(path)
+    }
+
+    /// 检查文件语言是否可以分析
+    ///
+    /// # Arguments
+    /// * `path` - 文件路径
+    ///
+    /// # Returns
+    /// * `Result<(), SkipReason>` - 可以分析时为 Ok，否则返回跳过原因
+    fn check_language(&self, path: &Path) -> Result<(), SkipReason> {
+        // 必须是支持的文件类型（内置语言或已注册自定义解析器）
+        if !self.detector.is_supported_file(path) && !crate::parser::has_registered_parser(path) {
+            return Err(SkipReason::Unsupported);
+        }
+
+        // 指定了扩展名时只保留匹配的文件
+        if !self.matches_extension(path) {
+            return Err(SkipReason::ExtensionFilter);
+        }
+
+        Ok(())
+    }
+
+    /// 过滤给定的文件列表（不遍历目录，只做存在性和语言检查）
     ///
     /// # Arguments
-    /// * `entry` - 目录项
+    /// * `paths` - 文件列表
     ///
     /// # Returns
-    /// * `bool` - 是否有效
-    fn is_valid_source_file(&self, entry: &DirEntry) -> bool {
-        // 必须是文件
-        if !entry.file_type().is_file() {
-            return false;
+    /// * `SourceFiles` - 可分析的文件和被跳过的文件
+    pub fn filter_files(&self, paths: Vec<PathBuf>) -> SourceFiles {
+        let mut search = SourceFiles::default();
+
+        for path in paths {
+            let checked = if path.is_file() {
+                self.check_language(&path)
+            } else {
+                Err(SkipReason::Missing)
+            };
+
+            match checked {
+                Ok(()) => search.files.push(path),
+                Err(reason) => search.skipped.push(SkippedFile::new(&path, reason)),
+            }
         }
 
-        let path = entry.path();
+        search
+    }
 
-        // 必须是支持的文件类型
-        if !self.detector.is_supported_file(path) {
-            return false;
+    /// 判断文件扩展名是否在过滤列表中
+    ///
+    /// # Arguments
+    /// * `path` - 文件路径
+    ///
+    /// # Returns
+    /// * `bool` - 未设置过滤或扩展名匹配时为 true
+    fn matches_extension(&self, path: &Path) -> bool {
+        if self.extensions.is_empty() {
+            return true;
         }
 
-        // 检查是否应该包含
-        self.should_include_file(path)
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| self.extensions.contains(&ext.to_lowercase()))
+            .unwrap_or(false)
     }
 
-    /// 判断是否应该包含文件
+    /// 检查是否应该包含文件
     ///
     /// # Arguments
     /// * `path` - 文件路径
     ///
     /// # Returns
-    /// * `bool` - 是否包含
-    fn should_include_file(&self, path: &Path) -> bool {
-        if let Ok(rel_path) = path.strip_prefix(&self.root_dir) {
-            // 检查排除模式
-            if self.exclude_patterns.is_match(rel_path) {
-                return false;
-            }
+    /// * `Result<(), SkipReason>` - 包含时为 Ok，否则返回跳过原因
+    fn check_include_file(&self, path: &Path) -> Result<(), SkipReason> {
+        let Ok(rel_path) = path.strip_prefix(&self.root_dir) else {
+            return Err(SkipReason::NotIncluded);
+        };
 
-            // 如果没有包含模式，默认包含
-            if self.include_patterns.is_empty() {
-                return true;
-            }
+        // 检查排除模式
+        if self.exclude_patterns.is_match(rel_path) || self.ignore_patterns.is_match(rel_path) {
+            return Err(SkipReason::Excluded);
+        }
 
-            // 检查包含模式
-            return self.include_patterns.is_match(rel_path);
+        // 如果没有包含模式，默认包含；否则检查包含模式
+        if self.include_patterns.is_empty() || self.include_patterns.is_match(rel_path) {
+            Ok(())
+        } else {
+            Err(SkipReason::NotIncluded)
         }
+    }
+}
 
-        false
+/// 获取互斥锁（遍历线程 panic 导致锁中毒时仍然取出数据）
+///
+/// # Arguments
+/// * `mutex` - 互斥锁
+///
+/// # Returns
+/// * `MutexGuard<T>` - 锁守卫
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 把忽略文件中的一行转换为 glob 模式
+///
+/// 含 `/` 的模式相对根目录锚定，否则匹配任意层级；同时匹配路径本身和其下的所有内容。
+///
+/// # Arguments
+/// * `line` - 忽略文件中的一行（已去除首尾空白，非注释、非取反）
+///
+/// # Returns
+/// * `Vec<String>` - glob 模式
+fn ignore_line_to_globs(line: &str) -> Vec<String> {
+    let pattern = line.trim_end_matches('/');
+    let anchored = pattern.contains('/');
+    let pattern = pattern.trim_start_matches('/');
+
+    if pattern.is_empty() {
+        return Vec::new();
     }
+
+    let base = if anchored {
+        pattern.to_string()
+    } else {
+        format!("**/{}", pattern)
+    };
+
+    vec![base.clone(), format!("{}/**", base)]
 }
 
 /// 查找源文件（便捷函数）
@@ -214,19 +521,208 @@ impl FileFinder {
 /// * `root_dir` - 根目录
 /// * `include_patterns` - 包含模式
 /// * `exclude_patterns` - 排除模式
-/// * `progress_callback` - 进度回调
+/// * `extensions` - 扩展名过滤（为空时不限制）
+/// * `use_ignore_files` - 是否读取根目录下的忽略文件
+/// * `respect_gitignore` - 是否用 ignore crate 遍历目录（遵循嵌套的 .gitignore）
+/// * `progress_callback` - 进度回调（可能在遍历线程中调用）
 ///
 /// # Returns
-/// * `Result<Vec<PathBuf>>` - 文件列表
+/// * `Result<SourceFiles>` - 找到的文件和被跳过的文件
 pub fn find_source_files<F>(
     root_dir: &Path,
     include_patterns: &[String],
     exclude_patterns: &[String],
+    extensions: &[String],
+    use_ignore_files: bool,
+    respect_gitignore: bool,
     progress_callback: F,
-) -> Result<Vec<PathBuf>>
+) -> Result<SourceFiles>
 where
-    F: Fn(usize),
+    F: Fn(usize) + Sync,
 {
-    let finder = FileFinder::new(root_dir, include_patterns, exclude_patterns)?;
+    let mut finder = FileFinder::new(root_dir, include_patterns, exclude_patterns)?
+        .with_extensions(extensions)
+        .with_gitignore(respect_gitignore);
+    if use_ignore_files {
+        finder = finder.with_ignore_files()?;
+    }
     Ok(finder.find_source_files(progress_callback))
 }
+
+/// 检查文件大小是否在允许范围内
+///
+/// 无法读取元数据的文件不在这里跳过，留给分析阶段报告读取失败
+///
+/// # Arguments
+/// * `path` - 文件路径
+/// * `min_size` - 最小文件大小（字节）
+/// * `max_size` - 最大文件大小（字节）
+///
+/// # Returns
+/// * `Result<(), SkippedFile>` - 在范围内时为 Ok，否则返回带文件大小说明的跳过记录
+pub fn check_file_size(path: &Path, min_size: usize, max_size: usize) -> Result<(), SkippedFile> {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return Ok(());
+    };
+    let size = metadata.len();
+
+    let reason = if size > max_size as u64 {
+        SkipReason::TooLarge
+    } else if size < min_size as u64 {
+        SkipReason::TooSmall
+    } else {
+        return Ok(());
+    };
+
+    Err(SkippedFile::new(path, reason).with_detail(format!("{} 字节", size)))
+}
+
+/// 检查显式指定的文件是否符合扩展名过滤
+///
+/// # Arguments
+/// * `path` - 文件路径
+/// * `extensions` - 扩展名列表（不区分大小写，可带开头的点），为空时不限制
+///
+/// # Returns
+/// * `Result<(), SkippedFile>` - 符合过滤时为 Ok，否则返回跳过记录
+pub fn check_file_extension(path: &Path, extensions: &[String]) -> Result<(), SkippedFile> {
+    let matches = extensions.is_empty()
+        || path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                extensions
+                    .iter()
+                    .any(|allowed| allowed.trim_start_matches('.').eq_ignore_ascii_case(ext))
+            });
+
+    if matches {
+        Ok(())
+    } else {
+        Err(SkippedFile::new(path, SkipReason::ExtensionFilter))
+    }
+}
+
+/// 读取文件清单并过滤出可分析的文件
+///
+/// 清单可以是 JSON 字符串数组，也可以是每行一个路径的文本（如 `find`、`fd`、
+/// `git ls-files` 的输出，空行忽略）；路径为 `-` 时从标准输入读取。
+/// 相对路径相对于当前工作目录解析，不存在或不支持的文件记为跳过并输出警告。
+///
+/// # Arguments
+/// * `manifest` - 清单文件路径
+/// * `extensions` - 扩展名过滤（为空时不限制）
+///
+/// # Returns
+/// * `Result<SourceFiles>` - 可分析的文件和被跳过的文件
+pub fn read_file_manifest(manifest: &Path, extensions: &[String]) -> Result<SourceFiles> {
+    let content = if manifest == Path::new("-") {
+        std::io::read_to_string(std::io::stdin()).context("无法从标准输入读取文件清单")?
+    } else {
+        std::fs::read_to_string(manifest)
+            .with_context(|| format!("无法读取文件清单 {}", manifest.display()))?
+    };
+
+    let paths: Vec<PathBuf> = if content.trim_start().starts_with('[') {
+        serde_json::from_str(&content)
+            .with_context(|| format!("文件清单 {} 应为路径字符串数组", manifest.display()))?
+    } else {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect()
+    };
+
+    let finder = FileFinder::new(Path::new("."), &[], &[])?.with_extensions(extensions);
+    let search = finder.filter_files(paths);
+    for skipped in &search.skipped {
+        match skipped.reason {
+            SkipReason::Missing => log::warn!("文件清单中的 {} 不存在，已跳过", skipped.path),
+            _ => log::warn!("文件清单中的 {} 不是支持的源文件，已跳过", skipped.path),
+        }
+    }
+
+    Ok(search)
+}
+
+/// 找出无法分析的扩展名（既不是内置语言，也没有注册自定义解析器）
+///
+/// # Arguments
+/// * `extensions` - 扩展名列表
+///
+/// # Returns
+/// * `Vec<String>` - 不支持的扩展名
+pub fn unsupported_extensions(extensions: &[String]) -> Vec<String> {
+    extensions
+        .iter()
+        .map(|ext| ext.trim_start_matches('.').to_lowercase())
+        .filter(|ext| {
+            LanguageType::from_extension(ext) == LanguageType::Unsupported
+                && !crate::parser::has_registered_parser(Path::new(&format!("file.{}", ext)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::testing::TempDir;
+
+    #[test]
+    fn file_size_limits_are_inclusive() {
+        let dir = TempDir::new("file-size");
+        let at_max = dir.write("at_max.py", &"x".repeat(100));
+        let over_max = dir.write("over_max.py", &"x".repeat(101));
+        let at_min = dir.write("at_min.py", "xx");
+        let under_min = dir.write("under_min.py", "x");
+
+        assert!(check_file_size(&at_max, 2, 100).is_ok());
+        assert!(check_file_size(&at_min, 2, 100).is_ok());
+
+        let too_large = check_file_size(&over_max, 2, 100).unwrap_err();
+        assert_eq!(too_large.reason, SkipReason::TooLarge);
+        assert_eq!(too_large.detail.as_deref(), Some("101 字节"));
+
+        let too_small = check_file_size(&under_min, 2, 100).unwrap_err();
+        assert_eq!(too_small.reason, SkipReason::TooSmall);
+
+        // 无法读取元数据的文件留给分析阶段处理
+        assert!(check_file_size(&dir.path().join("missing.py"), 2, 100).is_ok());
+    }
+
+    #[test]
+    fn excludes_win_over_includes() {
+        let dir = TempDir::new("include-exclude");
+        dir.write("src/main.rs", "fn main() {}\n");
+        dir.write("src/util.py", "x = 1\n");
+        dir.write("vendor/lib.rs", "pub fn lib() {}\n");
+
+        let finder = FileFinder::new(
+            dir.path(),
+            &["**/*.rs".to_string()],
+            &["**/vendor/**".to_string()],
+        )
+        .unwrap();
+        let found = finder.find_source_files(|_| {});
+
+        let files: Vec<PathBuf> = found
+            .files
+            .iter()
+            .map(|file| file.strip_prefix(dir.path()).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(files, [Path::new("src").join("main.rs")]);
+
+        let reason_for = |name: &str| {
+            found
+                .skipped
+                .iter()
+                .find(|skipped| skipped.path.ends_with(name))
+                .map(|skipped| skipped.reason)
+        };
+        // vendor/lib.rs 同时匹配包含和排除模式，排除优先
+        assert_eq!(reason_for("lib.rs"), Some(SkipReason::Excluded));
+        assert_eq!(reason_for("util.py"), Some(SkipReason::NotIncluded));
+    }
+}
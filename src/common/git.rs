@@ -0,0 +1,369 @@
+//! # Git 工具
+//!
+//! 通过调用 git 命令行获取仓库信息，不在仓库中时优雅降级
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 在指定目录下执行 git 命令并返回去除首尾空白的标准输出
+///
+/// # Arguments
+/// * `dir` - 执行目录
+/// * `args` - git 参数
+///
+/// # Returns
+/// * `Option<String>` - 命令成功时的输出，失败或 git 不可用时为 None
+fn run_git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// 将路径转换为可以执行 git 命令的目录
+///
+/// # Arguments
+/// * `path` - 文件或目录路径
+///
+/// # Returns
+/// * `PathBuf` - 目录路径
+fn working_dir(path: &Path) -> PathBuf {
+    if path.is_file() {
+        path.parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."))
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// 获取 git 仓库根目录
+///
+/// # Arguments
+/// * `path` - 仓库内任意路径
+///
+/// # Returns
+/// * `Option<PathBuf>` - 仓库根目录，不在仓库中时为 None
+pub fn repo_root(path: &Path) -> Option<PathBuf> {
+    run_git(&working_dir(path), &["rev-parse", "--show-toplevel"]).map(PathBuf::from)
+}
+
+/// 获取当前分支名
+///
+/// # Arguments
+/// * `path` - 仓库内任意路径
+///
+/// # Returns
+/// * `Option<String>` - 当前分支名，处于游离 HEAD 或不在仓库中时为 None
+pub fn current_branch(path: &Path) -> Option<String> {
+    run_git(&working_dir(path), &["rev-parse", "--abbrev-ref", "HEAD"])
+        .filter(|branch| branch != "HEAD")
+}
+
+/// 没有远程默认分支信息时依次尝试的本地分支
+const FALLBACK_DEFAULT_BRANCHES: [&str; 2] = ["main", "master"];
+
+/// 获取仓库的默认分支
+///
+/// 依次尝试远程 `origin` 的 HEAD 指向、`init.defaultBranch` 配置（需本地存在该分支），
+/// 最后回退到本地存在的 `main` 或 `master`。
+///
+/// # Arguments
+/// * `path` - 仓库内任意路径
+///
+/// # Returns
+/// * `Option<String>` - 默认分支名，无法确定或不在仓库中时为 None
+pub fn default_branch(path: &Path) -> Option<String> {
+    let dir = working_dir(path);
+
+    if let Some(head) = run_git(
+        &dir,
+        &[
+            "symbolic-ref",
+            "--quiet",
+            "--short",
+            "refs/remotes/origin/HEAD",
+        ],
+    ) {
+        if let Some(branch) = head.strip_prefix("origin/") {
+            return Some(branch.to_string());
+        }
+    }
+
+    let configured = run_git(&dir, &["config", "--get", "init.defaultBranch"]);
+    configured
+        .into_iter()
+        .chain(FALLBACK_DEFAULT_BRANCHES.iter().map(|b| b.to_string()))
+        .find(|branch| local_branch_exists(&dir, branch))
+}
+
+/// 判断本地分支是否存在
+///
+/// # Arguments
+/// * `dir` - 仓库内目录
+/// * `branch` - 分支名
+///
+/// # Returns
+/// * `bool` - 是否存在
+fn local_branch_exists(dir: &Path, branch: &str) -> bool {
+    let reference = format!("refs/heads/{}", branch);
+    run_git(dir, &["rev-parse", "--verify", "--quiet", &reference]).is_some()
+}
+
+/// 批量获取文件中指定行最后一次修改的时间
+///
+/// 一次 `git blame` 调用查询所有行，避免逐行启动进程。
+/// 尚未提交的行视为刚刚修改。
+///
+/// # Arguments
+/// * `file` - 文件路径
+/// * `lines` - 行号列表（从 1 开始）
+///
+/// # Returns
+/// * `Option<HashMap<usize, i64>>` - 行号到修改时间（Unix 秒）的映射，不在仓库中或文件未被跟踪时为 None
+pub fn blame_line_times(file: &Path, lines: &[usize]) -> Option<HashMap<usize, i64>> {
+    if lines.is_empty() {
+        return Some(HashMap::new());
+    }
+
+    let file_name = file.file_name()?.to_str()?;
+    let mut args = vec!["blame".to_string(), "--line-porcelain".to_string()];
+    for line in lines {
+        args.push("-L".to_string());
+        args.push(format!("{},{}", line, line));
+    }
+    args.push("--".to_string());
+    args.push(file_name.to_string());
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = run_git(&working_dir(file), &args)?;
+
+    Some(parse_blame_porcelain(&output))
+}
+
+/// 解析 `git blame --line-porcelain` 的输出
+///
+/// # Arguments
+/// * `output` - blame 输出
+///
+/// # Returns
+/// * `HashMap<usize, i64>` - 行号到作者时间（Unix 秒）的映射
+pub fn parse_blame_porcelain(output: &str) -> HashMap<usize, i64> {
+    let mut times = HashMap::new();
+    let mut current_line: Option<usize> = None;
+
+    for line in output.lines() {
+        // 源码行以制表符开头，表示一条记录结束
+        if line.starts_with('\t') {
+            current_line = None;
+            continue;
+        }
+
+        if let Some(time) = line.strip_prefix("author-time ") {
+            if let (Some(number), Ok(time)) = (current_line, time.trim().parse()) {
+                times.insert(number, time);
+            }
+            continue;
+        }
+
+        // 记录头："<sha> <原行号> <最终行号> [<行数>]"
+        let mut parts = line.split_whitespace();
+        let is_header = parts
+            .next()
+            .is_some_and(|sha| sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit()));
+        if is_header {
+            current_line = parts.nth(1).and_then(|n| n.parse().ok());
+        }
+    }
+
+    times
+}
+
+/// 获取相对于指定版本修改过的文件及行范围
+///
+/// 给出单个版本时比较工作区与该版本之间的差异，给出 `main...HEAD` 这样的范围时比较两个版本；
+/// 未跟踪的文件不包含在内，已删除的文件不会出现在分析结果中。
+///
+/// # Arguments
+/// * `path` - 仓库内任意路径
+/// * `since` - 比较的版本（分支、标签、提交或版本范围）
+///
+/// # Returns
+/// * `Option<HashMap<PathBuf, Vec<(usize, usize)>>>` - 文件绝对路径到修改行范围的映射，不在仓库中时为 None
+pub fn changed_lines(path: &Path, since: &str) -> Option<HashMap<PathBuf, Vec<(usize, usize)>>> {
+    let root = repo_root(path)?;
+    let diff = run_git(
+        &root,
+        &[
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            since,
+            "--",
+        ],
+    )
+    .unwrap_or_default();
+
+    Some(
+        parse_diff_hunks(&diff)
+            .into_iter()
+            .map(|(file, ranges)| (root.join(file), ranges))
+            .collect(),
+    )
+}
+
+/// 解析 `git diff --unified=0` 的输出
+///
+/// 只删除而没有新增行的位置记录为删除点所在的行，便于判断该处的函数是否被改动。
+///
+/// # Arguments
+/// * `diff` - diff 输出
+///
+/// # Returns
+/// * `HashMap<String, Vec<(usize, usize)>>` - 文件相对路径到新文件中修改行范围（含两端）的映射
+pub fn parse_diff_hunks(diff: &str) -> HashMap<String, Vec<(usize, usize)>> {
+    let mut hunks: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+    let mut current_file: Option<String> = None;
+
+    for line in diff.lines() {
+        if let Some(file) = line.strip_prefix("+++ ") {
+            // 删除的文件以 /dev/null 作为新文件名
+            current_file = file.strip_prefix("b/").map(str::to_string);
+            continue;
+        }
+
+        let Some(header) = line.strip_prefix("@@ ") else {
+            continue;
+        };
+        let (Some(file), Some(new_range)) = (
+            current_file.as_ref(),
+            header.split_whitespace().find(|part| part.starts_with('+')),
+        ) else {
+            continue;
+        };
+
+        let mut parts = new_range[1..].splitn(2, ',');
+        let start: usize = parts.next().and_then(|n| n.parse().ok()).unwrap_or(0);
+        let count: usize = parts.next().and_then(|n| n.parse().ok()).unwrap_or(1);
+
+        let range = if count == 0 {
+            (start.max(1), start.max(1))
+        } else {
+            (start, start + count - 1)
+        };
+        hunks.entry(file.clone()).or_default().push(range);
+    }
+
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::testing::TempDir;
+
+    /// 创建在指定分支上有一次提交的仓库
+    fn repo_on(label: &str, branch: &str) -> TempDir {
+        let repo = TempDir::new(label);
+        repo.git(&["init", "-q", "-b", branch], None);
+        repo.write("README.md", "hello\n");
+        repo.git(&["add", "."], None);
+        repo.git(&["commit", "-q", "-m", "init"], None);
+        repo
+    }
+
+    #[test]
+    fn default_branch_follows_the_remote_head() {
+        let upstream = repo_on("default-branch-upstream", "trunk");
+        let clone = TempDir::new("default-branch-clone");
+        clone.git(
+            &["clone", "-q", upstream.path().to_str().unwrap(), "."],
+            None,
+        );
+        clone.git(&["checkout", "-q", "-b", "feature/x"], None);
+
+        assert_eq!(default_branch(clone.path()).as_deref(), Some("trunk"));
+        assert_eq!(current_branch(clone.path()).as_deref(), Some("feature/x"));
+    }
+
+    #[test]
+    fn default_branch_without_remote_uses_config_then_fallbacks() {
+        let repo = repo_on("default-branch-local", "trunk");
+        repo.git(&["checkout", "-q", "-b", "feature/x"], None);
+        repo.git(&["config", "init.defaultBranch", "trunk"], None);
+        assert_eq!(default_branch(repo.path()).as_deref(), Some("trunk"));
+
+        let fallback = repo_on("default-branch-fallback", "main");
+        fallback.git(&["checkout", "-q", "-b", "develop"], None);
+        assert_eq!(default_branch(fallback.path()).as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn blame_porcelain_maps_final_lines_to_author_times() {
+        let output = "\
+1111111111111111111111111111111111111111 3 5 1
+author test
+author-time 1577836800
+author-tz +0000
+summary old
+filename src/lib.rs
+\t// TODO: old
+2222222222222222222222222222222222222222 9 12
+author test
+author-time 1700000000
+summary new
+filename src/lib.rs
+\t// author-time 42
+0000000000000000000000000000000000000000 13 13 1
+author Not Committed Yet
+author-time 1800000000
+filename src/lib.rs
+\t// FIXME: local
+";
+        let times = parse_blame_porcelain(output);
+        assert_eq!(times.len(), 3);
+        assert_eq!(times[&5], 1577836800);
+        assert_eq!(times[&12], 1700000000);
+        assert_eq!(times[&13], 1800000000);
+    }
+
+    #[test]
+    fn diff_hunks_record_new_ranges_and_pure_deletions() {
+        let diff = "\
+diff --git a/src/a.rs b/src/a.rs
+--- a/src/a.rs
++++ b/src/a.rs
+@@ -3 +3 @@ fn a() {
+@@ -10,0 +11,4 @@
+@@ -20,3 +23,0 @@
+diff --git a/old.rs b/old.rs
+--- a/old.rs
++++ /dev/null
+@@ -1,5 +0,0 @@
+diff --git a/new.rs b/new.rs
+--- /dev/null
++++ b/new.rs
+@@ -0,0 +1,2 @@
+";
+        let hunks = parse_diff_hunks(diff);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks["src/a.rs"], [(3, 3), (11, 14), (23, 23)]);
+        assert_eq!(hunks["new.rs"], [(1, 2)]);
+    }
+}
@@ -0,0 +1,85 @@
+//! # 稳定哈希
+//!
+//! 提供跨进程、跨平台结果一致的 FNV-1a 64 位哈希，用于生成可复现的标识
+
+/// FNV-1a 偏移基数
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 质数
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 稳定哈希器
+///
+/// 与 `std::collections::hash_map::DefaultHasher` 不同，结果不依赖随机种子或平台字节序
+#[derive(Debug, Clone)]
+pub struct StableHasher {
+    /// 当前哈希状态
+    state: u64,
+}
+
+impl StableHasher {
+    /// 创建新的哈希器
+    ///
+    /// # Returns
+    /// * `Self` - 哈希器实例
+    pub fn new() -> Self {
+        StableHasher {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+
+    /// 写入字节
+    ///
+    /// # Arguments
+    /// * `bytes` - 字节数据
+    pub fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state ^= u64::from(byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// 写入字符串（带长度前缀，避免相邻字段拼接产生歧义）
+    ///
+    /// # Arguments
+    /// * `text` - 字符串
+    pub fn write_str(&mut self, text: &str) {
+        self.write_u64(text.len() as u64);
+        self.write(text.as_bytes());
+    }
+
+    /// 写入整数（固定小端序）
+    ///
+    /// # Arguments
+    /// * `value` - 整数
+    pub fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// 获取哈希值
+    ///
+    /// # Returns
+    /// * `u64` - 哈希值
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 计算字节数据的稳定哈希
+///
+/// # Arguments
+/// * `bytes` - 字节数据
+///
+/// # Returns
+/// * `u64` - 哈希值
+pub fn stable_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
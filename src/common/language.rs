@@ -2,10 +2,12 @@
 //!
 //! 提供编程语言检测功能
 
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 /// 编程语言类型枚举
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum LanguageType {
     /// Rust语言
     Rust,
@@ -37,6 +39,18 @@ pub enum LanguageType {
     /// PHP
     PHP,
 
+    /// Zig
+    Zig,
+
+    /// Elixir
+    Elixir,
+
+    /// Shell 脚本（sh/bash/zsh）
+    Shell,
+
+    /// Swift
+    Swift,
+
     /// HTML
     HTML,
 
@@ -44,6 +58,7 @@ pub enum LanguageType {
     CSS,
 
     /// 不支持的语言
+    #[default]
     Unsupported,
 }
 
@@ -67,12 +82,84 @@ impl LanguageType {
             "c" | "h" => LanguageType::C,
             "cs" | "razor" => LanguageType::CSharp,
             "php" | "php3" | "php4" | "php5" | "php7" | "php8" | "phtml" => LanguageType::PHP,
+            "zig" => LanguageType::Zig,
+            "ex" | "exs" => LanguageType::Elixir,
+            "sh" | "bash" | "zsh" => LanguageType::Shell,
+            "swift" => LanguageType::Swift,
             "html" | "htm" | "xhtml" => LanguageType::HTML,
             "css" | "scss" | "sass" | "less" => LanguageType::CSS,
             _ => LanguageType::Unsupported,
         }
     }
 
+    /// 根据语言名称或扩展名判断语言类型
+    ///
+    /// 先按显示名称（不区分大小写）匹配，如 `rust`、`c++`，再按扩展名匹配，如 `rs`、`cpp`
+    ///
+    /// # Arguments
+    /// * `name` - 语言名称或扩展名
+    ///
+    /// # Returns
+    /// * `Self` - 语言类型
+    pub fn from_name(name: &str) -> Self {
+        Self::all_supported()
+            .iter()
+            .copied()
+            .find(|language| language.display_name().eq_ignore_ascii_case(name))
+            .unwrap_or_else(|| Self::from_extension(name))
+    }
+
+    /// 获取语言的典型扩展名
+    ///
+    /// # Returns
+    /// * `Option<&'static str>` - 扩展名，不支持的语言为 None
+    pub fn primary_extension(&self) -> Option<&'static str> {
+        match self {
+            LanguageType::Rust => Some("rs"),
+            LanguageType::Go => Some("go"),
+            LanguageType::JavaScript => Some("js"),
+            LanguageType::TypeScript => Some("ts"),
+            LanguageType::Python => Some("py"),
+            LanguageType::Java => Some("java"),
+            LanguageType::CPlusPlus => Some("cpp"),
+            LanguageType::C => Some("c"),
+            LanguageType::CSharp => Some("cs"),
+            LanguageType::PHP => Some("php"),
+            LanguageType::Zig => Some("zig"),
+            LanguageType::Elixir => Some("ex"),
+            LanguageType::Shell => Some("sh"),
+            LanguageType::Swift => Some("swift"),
+            LanguageType::HTML => Some("html"),
+            LanguageType::CSS => Some("css"),
+            LanguageType::Unsupported => None,
+        }
+    }
+
+    /// 获取所有支持的语言
+    ///
+    /// # Returns
+    /// * `&[LanguageType]` - 支持的语言列表（不含 Unsupported）
+    pub fn all_supported() -> &'static [LanguageType] {
+        &[
+            LanguageType::Rust,
+            LanguageType::Go,
+            LanguageType::JavaScript,
+            LanguageType::TypeScript,
+            LanguageType::Python,
+            LanguageType::Java,
+            LanguageType::CPlusPlus,
+            LanguageType::C,
+            LanguageType::CSharp,
+            LanguageType::PHP,
+            LanguageType::Zig,
+            LanguageType::Elixir,
+            LanguageType::Shell,
+            LanguageType::Swift,
+            LanguageType::HTML,
+            LanguageType::CSS,
+        ]
+    }
+
     /// 获取语言的显示名称
     ///
     /// # Returns
@@ -89,6 +176,10 @@ impl LanguageType {
             LanguageType::C => "C",
             LanguageType::CSharp => "C#",
             LanguageType::PHP => "PHP",
+            LanguageType::Zig => "Zig",
+            LanguageType::Elixir => "Elixir",
+            LanguageType::Shell => "Shell",
+            LanguageType::Swift => "Swift",
             LanguageType::HTML => "HTML",
             LanguageType::CSS => "CSS",
             LanguageType::Unsupported => "Unknown",
@@ -144,7 +235,8 @@ impl LanguageDetector {
         vec![
             "rs", "go", "js", "mjs", "cjs", "ts", "tsx", "jsx", "py", "pyw", "java", "cpp", "cc",
             "cxx", "hpp", "h++", "c", "h", "cs", "razor", "php", "php3", "php4", "php5", "php7",
-            "php8", "phtml", "html", "htm", "xhtml", "css", "scss", "sass", "less",
+            "php8", "phtml", "zig", "ex", "exs", "html", "htm", "xhtml", "css", "scss", "sass",
+            "less", "sh", "bash", "zsh", "swift",
         ]
     }
 }
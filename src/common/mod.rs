@@ -2,8 +2,16 @@
 //!
 //! 提供文件操作、语言检测等通用功能
 
+pub mod encoding;
 pub mod files;
+pub mod git;
+pub mod hash;
 pub mod language;
 
+#[cfg(test)]
+pub(crate) mod testing;
+
+pub use encoding::*;
 pub use files::*;
+pub use hash::*;
 pub use language::*;
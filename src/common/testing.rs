@@ -0,0 +1,104 @@
+//! # 测试辅助
+//!
+//! 为需要读写文件的单元测试提供自动清理的临时目录
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 同一进程内临时目录的序号
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// 临时目录，离开作用域时删除
+pub struct TempDir {
+    /// 目录路径
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// 在系统临时目录下创建新的空目录
+    ///
+    /// # Arguments
+    /// * `label` - 目录名前缀，便于定位测试
+    ///
+    /// # Returns
+    /// * `Self` - 临时目录
+    pub fn new(label: &str) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "fsc-{}-{}-{}",
+            label,
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir { path }
+    }
+
+    /// 获取目录路径
+    ///
+    /// # Returns
+    /// * `&Path` - 目录路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 写入文件（自动创建上级目录）
+    ///
+    /// # Arguments
+    /// * `relative` - 相对于临时目录的路径
+    /// * `content` - 文件内容
+    ///
+    /// # Returns
+    /// * `PathBuf` - 文件的完整路径
+    pub fn write(&self, relative: &str, content: &str) -> PathBuf {
+        let path = self.path.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    /// 在目录中执行 git 命令（使用固定的提交者信息，不读取用户配置）
+    ///
+    /// # Arguments
+    /// * `args` - git 参数
+    /// * `date` - 作者和提交时间（如 `2020-01-01T00:00:00Z`），None 时使用当前时间
+    ///
+    /// # Returns
+    /// * `String` - 标准输出
+    pub fn git(&self, args: &[&str], date: Option<&str>) -> String {
+        let mut command = Command::new("git");
+        command
+            .args(args)
+            .current_dir(&self.path)
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("GIT_AUTHOR_NAME", "test")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "test")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com");
+        if let Some(date) = date {
+            command
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date);
+        }
+
+        let output = command.output().unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} 失败: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -2,10 +2,103 @@
 //!
 //! 提供应用程序的各种配置结构和默认值
 
+use crate::analyzer::DEFAULT_CLONE_MIN_LINES;
+use crate::error::{AppError, AppResult};
+use crate::metrics::{
+    IssueKind, LanguageThresholds, Severity, DEFAULT_DUPLICATION_MIN_LINES,
+    DEFAULT_MAX_BOOLEAN_OPERATORS, DEFAULT_MAX_IMPORTS,
+};
+use crate::report::{Polarity, SortKey, DEFAULT_LEVEL_CUTS, LEVEL_CUT_COUNT};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 默认每个文件的每项指标最多保留的问题数
+pub const DEFAULT_MAX_RETAINED_ISSUES: usize = 200;
+
+/// 项目配置文件名
+pub const PROJECT_CONFIG_FILE: &str = ".fuckshitcode.toml";
+
+/// 内置的默认排除模式（依赖、构建产物、缓存和测试文件）
+const DEFAULT_EXCLUDE_PATTERNS: &[&str] = &[
+    // 前端项目通用排除
+    "**/node_modules/**",
+    "**/dist/**",
+    "**/build/**",
+    "**/.next/**",
+    "**/public/assets/**",
+    "**/out/**",
+    "**/.cache/**",
+    "**/.nuxt/**",
+    "**/.output/**",
+    "**/coverage/**",
+    "**/.vscode/**",
+    "**/.idea/**",
+    "**/.git/**",
+    "**/bower_components/**",
+    "**/*.min.js",
+    "**/*.bundle.js",
+    "**/*.chunk.js",
+    "**/static/js/*.js",
+    "**/static/css/*.css",
+    // 后端项目通用排除
+    "**/vendor/**",
+    "**/bin/**",
+    "**/obj/**",
+    "**/target/**",
+    "**/__pycache__/**",
+    "**/*.pyc",
+    "**/venv/**",
+    "**/.env/**",
+    "**/migrations/**",
+    "**/generated/**",
+    "**/logs/**",
+    "**/tmp/**",
+    "**/temp/**",
+    "**/test-results/**",
+    "**/testdata/**",
+    // Rust特定排除
+    "**/Cargo.lock",
+    // Tauri项目排除
+    "**/src-tauri/target/**",
+    // PHP特定排除
+    "**/composer.lock",
+    "**/.phpunit.cache/**",
+    "**/storage/logs/**",
+    "**/storage/cache/**",
+    "**/bootstrap/cache/**",
+    // 前端构建文件
+    "**/*.min.css",
+    "**/*.bundle.css",
+    "**/*.chunk.css",
+    // 测试文件排除
+    "**/*_test.go",
+    "**/test_*.py",
+    "**/*_test.py",
+    "**/tests/**/*.py",
+    "**/*.spec.js",
+    "**/*.test.js",
+    "**/__tests__/**",
+    "**/*.spec.ts",
+    "**/*.test.ts",
+    "**/src/test/**/*.java",
+    "**/*Test.java",
+    "**/*_test.c",
+    "**/*_test.cpp",
+    "**/tests/**",
+    "**/*_test.rs",
+    "**/test_*.rs",
+    "**/*Test.php",
+    "**/*_test.php",
+    "**/tests/**/*.php",
+];
 
 /// 分析配置
+///
+/// 反序列化时缺省的字段使用默认值，项目配置文件只需写出要修改的项
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AnalysisConfig {
     /// 包含的文件模式
     pub include_patterns: Vec<String>,
@@ -13,14 +106,172 @@ pub struct AnalysisConfig {
     /// 排除的文件模式
     pub exclude_patterns: Vec<String>,
 
+    /// 只分析这些扩展名的文件（为空时分析所有支持的文件）
+    #[serde(default)]
+    pub extensions: Vec<String>,
+
+    /// 从 JSON 文件清单读取待分析文件，不再遍历目录
+    #[serde(default)]
+    pub files_from: Option<PathBuf>,
+
+    /// 是否读取根目录下的 .fscignore 和 .gitignore，把其中的模式追加为排除规则
+    #[serde(default = "default_use_ignore_files")]
+    pub use_ignore_files: bool,
+
+    /// 是否用 ignore crate 遍历目录，遵循嵌套的 .gitignore、.git/info/exclude 和全局 gitignore
+    pub respect_gitignore: bool,
+
     /// 是否启用并行分析
     pub parallel: bool,
 
-    /// 最大文件大小（字节）
+    /// 最大文件大小（字节），更大的文件跳过不分析
     pub max_file_size: usize,
 
-    /// 最小文件大小（字节）
+    /// 最小文件大小（字节），更小的文件（如空文件）跳过不分析
     pub min_file_size: usize,
+
+    /// 超过该天数的 TODO/FIXME 标记视为陈旧（需要 git，未设置时不检测）
+    #[serde(default)]
+    pub stale_todo_days: Option<u64>,
+
+    /// 按语言自定义判断函数名前缀（键为语言名，不区分大小写），未配置的语言使用默认前缀
+    #[serde(default)]
+    pub predicate_prefixes: BTreeMap<String, Vec<String>>,
+
+    /// 自定义指标权重（键为指标名称、标识或别名，负数按 0 处理），未配置的指标使用内置权重
+    #[serde(default)]
+    pub weights: BTreeMap<String, f64>,
+
+    /// 只运行这些指标（别名、标识或名称，如 complexity），为空时运行全部指标
+    #[serde(default)]
+    pub metrics: Vec<String>,
+
+    /// 按语言覆盖函数长度、复杂度和参数数量的阈值（键为语言名，不区分大小写；`default` 对所有语言生效），未配置的项使用内置默认值
+    #[serde(default)]
+    pub thresholds: BTreeMap<String, LanguageThresholds>,
+
+    /// 只分析相对于该 git 版本（或版本范围）修改过的文件
+    #[serde(default)]
+    pub since: Option<String>,
+
+    /// 只报告位于修改行上的问题（需要设置 since）
+    #[serde(default)]
+    pub changed_lines_only: bool,
+
+    /// 是否把匿名函数（箭头函数、闭包、lambda 等）计入度量
+    #[serde(default = "default_include_anonymous")]
+    pub include_anonymous: bool,
+
+    /// 参与重复度分析的最小函数行数，更短的函数不参与比较
+    #[serde(default = "default_duplication_min_lines")]
+    pub duplication_min_lines: usize,
+
+    /// 单行条件表达式允许的最多逻辑运算符数，超过即报告问题
+    #[serde(default = "default_max_boolean_operators")]
+    pub max_boolean_operators: usize,
+
+    /// 每个文件允许的最多导入语句数，超过即报告耦合度过高
+    #[serde(default = "default_max_imports")]
+    pub max_imports: usize,
+
+    /// 跨文件重复代码块的最少连续代码行数（0 表示不检测跨文件重复）
+    #[serde(default = "default_clone_min_lines")]
+    pub clone_min_lines: usize,
+
+    /// 每个文件的每项指标最多保留的问题数（按严重程度保留，0 表示不限制）
+    #[serde(default = "default_max_retained_issues")]
+    pub max_retained_issues: usize,
+
+    /// 把文件开头不超过该行数的许可证注释排除在注释率之外（None 表示不排除）
+    #[serde(default)]
+    pub license_header_max_lines: Option<usize>,
+
+    /// 是否在解析结果中保存每个函数的函数体源码，供需要分析函数体的指标使用（默认关闭以节省内存）
+    #[serde(default)]
+    pub capture_function_bodies: bool,
+
+    /// 无法识别编码的文件是否按 UTF-8 有损解码后继续分析（无效字节替换为 U+FFFD），默认跳过这类文件
+    #[serde(default)]
+    pub lossy_utf8: bool,
+
+    /// 是否在汇总后报告没有被任何已分析文件引用的非导出函数（基于文本的启发式检测，默认关闭）
+    #[serde(default)]
+    pub dead_code: bool,
+
+    /// 解析缓存目录，设置后复用内容未变的文件的解析结果（None 表示不使用缓存）
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// 获取内置的默认排除模式
+///
+/// 可以用 `--no-default-excludes` 关闭，只使用配置文件和命令行指定的排除模式
+///
+/// # Returns
+/// * `Vec<String>` - 去重后的默认排除模式列表（保持原有顺序）
+pub fn default_exclude_patterns() -> Vec<String> {
+    let mut seen = HashSet::new();
+    DEFAULT_EXCLUDE_PATTERNS
+        .iter()
+        .filter(|pattern| seen.insert(**pattern))
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+/// 默认计入匿名函数
+///
+/// # Returns
+/// * `bool` - true
+fn default_include_anonymous() -> bool {
+    true
+}
+
+/// 默认读取忽略文件
+///
+/// # Returns
+/// * `bool` - true
+fn default_use_ignore_files() -> bool {
+    true
+}
+
+/// 默认参与重复度分析的最小函数行数
+///
+/// # Returns
+/// * `usize` - 最小行数
+fn default_duplication_min_lines() -> usize {
+    DEFAULT_DUPLICATION_MIN_LINES
+}
+
+/// 默认单行条件表达式允许的最多逻辑运算符数
+///
+/// # Returns
+/// * `usize` - 最多运算符数
+fn default_max_boolean_operators() -> usize {
+    DEFAULT_MAX_BOOLEAN_OPERATORS
+}
+
+/// 默认每个文件允许的最多导入语句数
+///
+/// # Returns
+/// * `usize` - 最多导入语句数
+fn default_max_imports() -> usize {
+    DEFAULT_MAX_IMPORTS
+}
+
+/// 默认跨文件重复代码块的最少连续代码行数
+///
+/// # Returns
+/// * `usize` - 最少行数
+fn default_clone_min_lines() -> usize {
+    DEFAULT_CLONE_MIN_LINES
+}
+
+/// 默认每个文件的每项指标最多保留的问题数
+///
+/// # Returns
+/// * `usize` - 最多保留的问题数
+fn default_max_retained_issues() -> usize {
+    DEFAULT_MAX_RETAINED_ISSUES
 }
 
 impl Default for AnalysisConfig {
@@ -29,13 +280,124 @@ impl Default for AnalysisConfig {
         AnalysisConfig {
             include_patterns: vec![],
             exclude_patterns: vec![],
+            extensions: vec![],
+            files_from: None,
+            use_ignore_files: true,
+            respect_gitignore: false,
             parallel: true,
             max_file_size: 10 * 1024 * 1024, // 10MB
             min_file_size: 1,
+            stale_todo_days: None,
+            predicate_prefixes: BTreeMap::new(),
+            thresholds: BTreeMap::new(),
+            weights: BTreeMap::new(),
+            metrics: Vec::new(),
+            since: None,
+            changed_lines_only: false,
+            include_anonymous: true,
+            duplication_min_lines: DEFAULT_DUPLICATION_MIN_LINES,
+            max_boolean_operators: DEFAULT_MAX_BOOLEAN_OPERATORS,
+            max_imports: DEFAULT_MAX_IMPORTS,
+            clone_min_lines: DEFAULT_CLONE_MIN_LINES,
+            max_retained_issues: DEFAULT_MAX_RETAINED_ISSUES,
+            license_header_max_lines: None,
+            capture_function_bodies: false,
+            lossy_utf8: false,
+            dead_code: false,
+            cache_dir: None,
         }
     }
 }
 
+/// 项目配置文件（`.fuckshitcode.toml`）
+///
+/// 分析配置的各项直接写在顶层，另外可以设置报告的显示数量和输出语言；
+/// 排除模式在内置默认值基础上追加，其余各项被命令行参数覆盖
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProjectConfig {
+    /// 分析配置
+    #[serde(flatten)]
+    pub analysis: AnalysisConfig,
+
+    /// 显示的文件数量
+    #[serde(default)]
+    pub top_files: Option<usize>,
+
+    /// 每个文件显示的问题数量
+    #[serde(default)]
+    pub max_issues: Option<usize>,
+
+    /// 输出语言（zh-CN、en-US）
+    #[serde(default)]
+    pub lang: Option<String>,
+}
+
+impl ProjectConfig {
+    /// 读取项目配置文件
+    ///
+    /// # Arguments
+    /// * `path` - 配置文件路径
+    ///
+    /// # Returns
+    /// * `AppResult<Self>` - 项目配置，文件无法读取或格式错误时返回配置错误
+    pub fn load(path: &Path) -> AppResult<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            AppError::ConfigError(format!("无法读取配置文件 {}: {}", path.display(), e))
+        })?;
+
+        let table: toml::Table = toml::from_str(&content)
+            .map_err(|e| AppError::ConfigError(format!("{}: {}", path.display(), e)))?;
+        if let Some(key) = table.keys().find(|key| !Self::is_known_key(key)) {
+            return Err(AppError::ConfigError(format!(
+                "{}: 未知的配置项 `{}`",
+                path.display(),
+                key
+            )));
+        }
+
+        toml::from_str(&content)
+            .map_err(|e| AppError::ConfigError(format!("{}: {}", path.display(), e)))
+    }
+
+    /// 判断配置项是否存在
+    ///
+    /// # Arguments
+    /// * `key` - 配置项名称
+    ///
+    /// # Returns
+    /// * `bool` - 是否为分析配置或报告设置中的配置项
+    fn is_known_key(key: &str) -> bool {
+        if matches!(key, "top_files" | "max_issues" | "lang") {
+            return true;
+        }
+
+        serde_json::to_value(AnalysisConfig::default())
+            .ok()
+            .and_then(|value| value.as_object().map(|fields| fields.contains_key(key)))
+            .unwrap_or(false)
+    }
+
+    /// 从分析路径开始逐级向上查找项目配置文件
+    ///
+    /// # Arguments
+    /// * `start` - 分析路径（文件或目录）
+    ///
+    /// # Returns
+    /// * `Option<PathBuf>` - 找到的配置文件路径
+    pub fn discover(start: &Path) -> Option<PathBuf> {
+        let start = start.canonicalize().ok()?;
+        let dir = if start.is_file() {
+            start.parent()?
+        } else {
+            start.as_path()
+        };
+
+        dir.ancestors()
+            .map(|ancestor| ancestor.join(PROJECT_CONFIG_FILE))
+            .find(|candidate| candidate.is_file())
+    }
+}
+
 /// 输出配置
 #[derive(Debug, Clone)]
 pub struct OutputConfig {
@@ -51,8 +413,71 @@ pub struct OutputConfig {
     /// 是否只显示摘要
     pub summary_only: bool,
 
+    /// 是否只输出总体得分
+    pub quiet: bool,
+
     /// 是否输出Markdown格式
     pub markdown_output: bool,
+
+    /// 是否输出JSON格式
+    pub json_output: bool,
+
+    /// 是否输出HTML格式
+    pub html_output: bool,
+
+    /// 是否输出CSV格式
+    pub csv_output: bool,
+
+    /// JSON、CSV 或 HTML 报告的输出文件（未设置时输出到标准输出）
+    pub output: Option<PathBuf>,
+
+    /// JSON 是否输出为紧凑的单行
+    pub json_compact: bool,
+
+    /// 分数展示方向
+    pub polarity: Polarity,
+
+    /// 只显示得分高于该值的文件（0-100）
+    pub min_file_score: Option<f64>,
+
+    /// 问题文件的排序方式
+    pub sort_by: SortKey,
+
+    /// 是否反转排序
+    pub reverse: bool,
+
+    /// 报告中显示的问题的最低严重程度
+    pub min_severity: Severity,
+
+    /// 用于对比的基线结果文件（--json 的输出）
+    pub baseline: Option<PathBuf>,
+
+    /// 是否按git分支自动读取并更新基线
+    pub baseline_auto: bool,
+
+    /// 基础分支（未设置时自动检测仓库的默认分支）
+    pub base_branch: Option<String>,
+
+    /// 是否报告总体得分在参考分布中的百分位
+    pub percentile: bool,
+
+    /// 自定义参考分布文件（未设置时使用内置分布）
+    pub reference: Option<PathBuf>,
+
+    /// 得分显示的小数位数
+    pub precision: usize,
+
+    /// 质量等级分界点（屎气指数，0-100，升序）
+    pub level_cuts: [f64; LEVEL_CUT_COUNT],
+
+    /// 出现这些类型的问题时以非零状态退出
+    pub deny: Vec<IssueKind>,
+
+    /// 屎气指数（0-100）高于该值时以非零状态退出
+    pub fail_under: Option<f64>,
+
+    /// 单项指标的屎气指数（0-100）高于对应阈值时以非零状态退出（指标名称, 阈值）
+    pub max_metric: Vec<(String, f64)>,
 }
 
 impl Default for OutputConfig {
@@ -63,7 +488,54 @@ impl Default for OutputConfig {
             top_files: 5,
             max_issues: 5,
             summary_only: false,
+            quiet: false,
             markdown_output: false,
+            json_output: false,
+            html_output: false,
+            csv_output: false,
+            output: None,
+            json_compact: false,
+            polarity: Polarity::Smell,
+            min_file_score: None,
+            sort_by: SortKey::Score,
+            reverse: false,
+            min_severity: Severity::Low,
+            baseline: None,
+            baseline_auto: false,
+            base_branch: None,
+            percentile: false,
+            reference: None,
+            precision: 2,
+            level_cuts: DEFAULT_LEVEL_CUTS,
+            deny: Vec::new(),
+            fail_under: None,
+            max_metric: Vec::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_exclude_patterns_have_no_duplicates() {
+        let patterns = default_exclude_patterns();
+        let unique: HashSet<&String> = patterns.iter().collect();
+        assert_eq!(unique.len(), patterns.len());
+
+        for pattern in ["**/vendor/**", "**/target/**", "**/*.min.js"] {
+            assert_eq!(patterns.iter().filter(|p| *p == pattern).count(), 1);
+        }
+    }
+
+    #[test]
+    fn default_exclude_patterns_keep_their_order() {
+        let patterns = default_exclude_patterns();
+        assert_eq!(patterns[0], "**/node_modules/**");
+
+        let position = |pattern: &str| patterns.iter().position(|p| p == pattern).unwrap();
+        assert!(position("**/dist/**") < position("**/vendor/**"));
+        assert!(position("**/vendor/**") < position("**/target/**"));
+    }
+}
@@ -13,8 +13,16 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "metric.cyclomatic_complexity".to_string(),
         "Cyclomatic Complexity".to_string(),
     );
+    m.insert(
+        "metric.cognitive_complexity".to_string(),
+        "Cognitive Complexity".to_string(),
+    );
     m.insert(
         "metric.function_length".to_string(),
+        "Function Length".to_string(),
+    );
+    m.insert(
+        "metric.state_management".to_string(),
         "State Management".to_string(),
     );
     m.insert(
@@ -37,6 +45,7 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "metric.structure_analysis".to_string(),
         "Code Structure".to_string(),
     );
+    m.insert("metric.line_length".to_string(), "Line Length".to_string());
 
     // Analyzer progress
     m.insert(
@@ -145,6 +154,40 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "cmd.analysis_failed".to_string(),
         "Analysis failed: %s".to_string(),
     );
+    m.insert(
+        "cmd.fail_under".to_string(),
+        "Smell score %s is above the --fail-under threshold %s".to_string(),
+    );
+    m.insert(
+        "cmd.max_metric".to_string(),
+        "Metric %s smell score %s is above the --max-metric threshold %s".to_string(),
+    );
+    m.insert(
+        "cmd.denied_issues".to_string(),
+        "Found %s denied issue(s):".to_string(),
+    );
+
+    // 解析性能测量
+    m.insert(
+        "bench.summary".to_string(),
+        "Parsed %s files (%s lines) %s times in %s s".to_string(),
+    );
+    m.insert(
+        "bench.throughput".to_string(),
+        "Throughput: %s lines/s, %s files/s".to_string(),
+    );
+
+    // 解析缓存
+    m.insert(
+        "cache.stats".to_string(),
+        "Parse cache: %s hits, %s misses (%s% hit rate), about %s s saved".to_string(),
+    );
+
+    // 构建信息
+    m.insert("info.commit".to_string(), "Commit:".to_string());
+    m.insert("info.languages".to_string(), "Languages:".to_string());
+    m.insert("info.features".to_string(), "Features:".to_string());
+    m.insert("info.none".to_string(), "none".to_string());
 
     // Report
     m.insert(
@@ -189,6 +232,12 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "report.quality_level".to_string(),
         "Quality Level".to_string(),
     );
+    m.insert("report.grade".to_string(), "Grade".to_string());
+    m.insert("grade.a".to_string(), "Excellent".to_string());
+    m.insert("grade.b".to_string(), "Good".to_string());
+    m.insert("grade.c".to_string(), "Fair".to_string());
+    m.insert("grade.d".to_string(), "Poor".to_string());
+    m.insert("grade.f".to_string(), "Failing".to_string());
     m.insert(
         "report.analyzed_files".to_string(),
         "Analyzed Files".to_string(),
@@ -202,6 +251,13 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
     m.insert("report.score".to_string(), "Score".to_string());
     m.insert("report.weight".to_string(), "Weight".to_string());
     m.insert("report.status".to_string(), "Status".to_string());
+    m.insert("report.issue_count".to_string(), "Issues".to_string());
+    m.insert(
+        "report.languages".to_string(),
+        "Scores by Language".to_string(),
+    );
+    m.insert("report.language".to_string(), "Language".to_string());
+    m.insert("report.file_count".to_string(), "Files".to_string());
     m.insert(
         "report.problem_files".to_string(),
         "Problem Files".to_string(),
@@ -220,6 +276,38 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "report.improvement_suggestions".to_string(),
         "Improvement Suggestions".to_string(),
     );
+    m.insert(
+        "report.baseline_comparison".to_string(),
+        "Baseline Comparison".to_string(),
+    );
+    m.insert("report.baseline".to_string(), "Baseline".to_string());
+    m.insert("report.current".to_string(), "Current".to_string());
+    m.insert("report.change".to_string(), "Change".to_string());
+    m.insert(
+        "report.file_changes".to_string(),
+        "File changes".to_string(),
+    );
+    m.insert("report.file".to_string(), "File".to_string());
+    m.insert("report.file_added".to_string(), "added".to_string());
+    m.insert("report.file_removed".to_string(), "removed".to_string());
+    m.insert(
+        "report.issue_changes".to_string(),
+        "Issues: {0} new, {1} resolved".to_string(),
+    );
+    m.insert("report.run_id".to_string(), "Run ID".to_string());
+    m.insert(
+        "report.percentile_label".to_string(),
+        "Reference percentile".to_string(),
+    );
+    m.insert(
+        "report.percentile".to_string(),
+        "Smellier than {0}% of reference projects".to_string(),
+    );
+    m.insert(
+        "report.generic_parser_warning".to_string(),
+        "{0}/{1} files could only be analyzed by the generic parser, scores are approximate"
+            .to_string(),
+    );
 
     // Metric score suffix
     m.insert("metric.score.suffix".to_string(), " pts".to_string());
@@ -238,16 +326,44 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "Functions like labyrinths, maintenance like a dungeon raid".to_string(),
     );
 
+    // Cognitive complexity comments
+    m.insert(
+        "metric.cognitive.good".to_string(),
+        "Reads top to bottom without a map, nice".to_string(),
+    );
+    m.insert(
+        "metric.cognitive.medium".to_string(),
+        "Ifs inside ifs, readers lose track of which level they're on".to_string(),
+    );
+    m.insert(
+        "metric.cognitive.bad".to_string(),
+        "Nesting so deep you need a flowchart before reading it".to_string(),
+    );
+
     // Function length comments
     m.insert(
         "metric.length.good".to_string(),
-        "Clear state management, reasonable variable scope, predictable state".to_string(),
+        "Short, sharp functions you can read at a glance".to_string(),
     );
     m.insert(
         "metric.length.medium".to_string(),
+        "Some functions drag on; your scroll wheel is getting a workout".to_string(),
+    );
+    m.insert(
+        "metric.length.bad".to_string(),
+        "Functions longer than a CVS receipt, and just as readable".to_string(),
+    );
+
+    // State management comments
+    m.insert(
+        "metric.state.good".to_string(),
+        "Clear state management, reasonable variable scope, predictable state".to_string(),
+    );
+    m.insert(
+        "metric.state.medium".to_string(),
         "Average state management, some global state or unclear state changes".to_string(),
     );
-    m.insert("metric.length.bad".to_string(), "Chaotic state management, excessive use of global variables, difficult to track state changes".to_string());
+    m.insert("metric.state.bad".to_string(), "Chaotic state management, excessive use of global variables, difficult to track state changes".to_string());
 
     // Comment ratio comments
     m.insert(
@@ -319,6 +435,20 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "Nested like Russian dolls, dizzying to read".to_string(),
     );
 
+    // Line length comments
+    m.insert(
+        "metric.line_length.good".to_string(),
+        "Lines fit on screen, no neck exercises required".to_string(),
+    );
+    m.insert(
+        "metric.line_length.medium".to_string(),
+        "A few long lines; the horizontal scrollbar is clocking in".to_string(),
+    );
+    m.insert(
+        "metric.line_length.bad".to_string(),
+        "Lines stretch to the horizon, even an ultrawide monitor gives up".to_string(),
+    );
+
     // Quality advice
     m.insert(
         "advice.good".to_string(),
@@ -425,7 +555,8 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
     );
 
     // Metric descriptions
-    m.insert("metric.function_length.description".to_string(), "Detects how you manage state variables. Good state management means you won't lose your mind maintaining this code.".to_string());
+    m.insert("metric.function_length.description".to_string(), "Measures function length and parameter count. Long functions are hard to read, test, and reuse.".to_string());
+    m.insert("metric.state_management.description".to_string(), "Detects how you manage state variables. Good state management means you won't lose your mind maintaining this code.".to_string());
     m.insert("metric.comment_ratio.description".to_string(), "Checks if your code has enough comments. Good comments mean you won't curse your past self.".to_string());
     m.insert("metric.error_handling.description".to_string(), "Sniffs out your error handling. Good error handling means your code won't explode at runtime.".to_string());
     m.insert(
@@ -439,6 +570,16 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "Detects nesting depth and reference complexity. The less Russian doll, the less headache."
             .to_string(),
     );
+    m.insert(
+        "metric.line_length.description".to_string(),
+        "Detects lines longer than 120 characters. Long lines force horizontal scrolling and are hard to review."
+            .to_string(),
+    );
+    m.insert(
+        "metric.cognitive_complexity.description".to_string(),
+        "Weighs branches and boolean operator sequences by nesting level. The deeper the nesting, the harder the logic is to follow."
+            .to_string(),
+    );
     m.insert("metric.cyclomatic_complexity.description".to_string(), "Measures how twisted your control flow is. The higher the complexity, the more likely you'll regret touching this code.".to_string());
 
     // Quality level descriptions
@@ -606,11 +747,169 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "issue.exported_func_no_comment".to_string(),
         "Exported function %s lacks documentation comment".to_string(),
     );
+    m.insert(
+        "issue.complex_func_no_comment".to_string(),
+        "Function %s is complex (complexity %s, %s lines) but lacks documentation comment"
+            .to_string(),
+    );
     m.insert(
         "issue.exported_type_no_comment".to_string(),
         "Exported type %s lacks documentation comment".to_string(),
     );
 
+    // Fix suggestions
+    m.insert(
+        "fix.high_complexity".to_string(),
+        "Extract the nested conditionals into helper functions, or use early returns to cut branches".to_string(),
+    );
+    m.insert(
+        "fix.medium_complexity".to_string(),
+        "Replace deep if/else chains with guard clauses and merge duplicated conditions"
+            .to_string(),
+    );
+    m.insert(
+        "fix.high_cognitive_complexity".to_string(),
+        "Flatten the nesting with guard clauses and early returns, and extract inner branches into functions"
+            .to_string(),
+    );
+    m.insert(
+        "fix.complex_predicate".to_string(),
+        "Split the condition into small, well-named predicates and combine them with && / ||"
+            .to_string(),
+    );
+    m.insert(
+        "fix.function_very_long".to_string(),
+        "Split the function by responsibility into several small functions that each do one thing"
+            .to_string(),
+    );
+    m.insert(
+        "fix.function_long".to_string(),
+        "Extract self-contained steps into well-named helper functions".to_string(),
+    );
+    m.insert(
+        "fix.function_medium".to_string(),
+        "Look for blocks that can be extracted or reused".to_string(),
+    );
+    m.insert(
+        "fix.too_many_parameters".to_string(),
+        "Group related parameters into a struct or options object".to_string(),
+    );
+    m.insert(
+        "fix.many_parameters".to_string(),
+        "Consider wrapping parameters that travel together into a single type".to_string(),
+    );
+    m.insert(
+        "fix.comment_very_low".to_string(),
+        "Document public interfaces and key logic, explaining intent rather than restating code"
+            .to_string(),
+    );
+    m.insert(
+        "fix.comment_low".to_string(),
+        "Add comments to complex branches and non-obvious implementations".to_string(),
+    );
+    m.insert(
+        "fix.exported_func_no_comment".to_string(),
+        "Document exported functions: describe their purpose, parameters and return value"
+            .to_string(),
+    );
+    m.insert(
+        "fix.complex_func_no_comment".to_string(),
+        "Document complex functions: describe their purpose, parameters and return value"
+            .to_string(),
+    );
+    m.insert(
+        "fix.duplicate_functions".to_string(),
+        "Merge the duplicated functions into one and express differences through parameters"
+            .to_string(),
+    );
+    m.insert(
+        "fix.similar_functions".to_string(),
+        "Extract the shared part of similar functions and pass the differences as parameters or closures".to_string(),
+    );
+    m.insert(
+        "fix.repeated_naming_pattern".to_string(),
+        "Check whether functions sharing a prefix can be merged into one generic function"
+            .to_string(),
+    );
+    m.insert(
+        "fix.repeated_signatures".to_string(),
+        "Consider unifying functions with identical signatures behind a trait/interface or generics".to_string(),
+    );
+    m.insert(
+        "fix.cross_file_duplicate".to_string(),
+        "Extract the duplicated block into a shared module or function and call it from each place"
+            .to_string(),
+    );
+    m.insert(
+        "fix.missing_error_handling".to_string(),
+        "Check calls that can fail and handle or propagate their errors explicitly".to_string(),
+    );
+    m.insert(
+        "fix.weak_error_handling".to_string(),
+        "Don't ignore or swallow errors; at least log them or return them to the caller"
+            .to_string(),
+    );
+    m.insert(
+        "fix.bad_naming".to_string(),
+        "Use complete names that express purpose and follow the language's naming conventions"
+            .to_string(),
+    );
+    m.insert(
+        "fix.overloaded_function".to_string(),
+        "Make sure the overloads share the same meaning; use distinct names when their parameters differ widely"
+            .to_string(),
+    );
+    m.insert(
+        "fix.shadowed_function".to_string(),
+        "Remove or rename the overridden definition and keep a single implementation".to_string(),
+    );
+    m.insert(
+        "fix.global_state".to_string(),
+        "Wrap the globals in a struct and pass it explicitly or inject it where needed".to_string(),
+    );
+    m.insert(
+        "fix.deep_nesting".to_string(),
+        "Reduce nesting with early returns, smaller functions, or by extracting loop bodies"
+            .to_string(),
+    );
+    m.insert(
+        "fix.medium_nesting".to_string(),
+        "Replace the outer condition with a guard clause to remove one nesting level".to_string(),
+    );
+    m.insert(
+        "fix.inconsistent_brace_style".to_string(),
+        "Use a formatter (e.g. rustfmt, prettier) to apply one brace style".to_string(),
+    );
+    m.insert(
+        "fix.complex_condition".to_string(),
+        "Extract parts of the condition into well-named boolean variables or predicate functions"
+            .to_string(),
+    );
+    m.insert(
+        "fix.callback_hell".to_string(),
+        "Flatten callbacks and promise chains with async/await, or extract inner callbacks into named functions"
+            .to_string(),
+    );
+    m.insert(
+        "fix.too_many_imports".to_string(),
+        "Split the file by responsibility so each file depends only on the modules it really needs"
+            .to_string(),
+    );
+    m.insert(
+        "fix.long_line".to_string(),
+        "Break the line at operators or arguments, or extract long expressions into named variables"
+            .to_string(),
+    );
+    m.insert(
+        "fix.stale_todo".to_string(),
+        "Resolve this TODO soon, or move it to a tracked issue and remove the marker".to_string(),
+    );
+    m.insert(
+        "fix.possibly_dead_code".to_string(),
+        "Check for calls via reflection or dynamic dispatch; if there are none, delete the function"
+            .to_string(),
+    );
+
     // Verbose report
     m.insert(
         "verbose.basic_statistics".to_string(),
@@ -628,6 +927,11 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "verbose.total_issues".to_string(),
         "Total issues:".to_string(),
     );
+    m.insert("verbose.run_id".to_string(), "Run ID:".to_string());
+    m.insert(
+        "verbose.parameter_distribution".to_string(),
+        "Parameter count distribution:".to_string(),
+    );
     m.insert(
         "verbose.metric_details".to_string(),
         "🔍 Metric details (the juicy bits):".to_string(),
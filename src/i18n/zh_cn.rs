@@ -13,7 +13,15 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "metric.cyclomatic_complexity".to_string(),
         "循环复杂度".to_string(),
     );
-    m.insert("metric.function_length".to_string(), "状态管理".to_string());
+    m.insert(
+        "metric.cognitive_complexity".to_string(),
+        "认知复杂度".to_string(),
+    );
+    m.insert("metric.function_length".to_string(), "函数长度".to_string());
+    m.insert(
+        "metric.state_management".to_string(),
+        "状态管理".to_string(),
+    );
     m.insert("metric.comment_ratio".to_string(), "注释覆盖率".to_string());
     m.insert("metric.error_handling".to_string(), "错误处理".to_string());
     m.insert(
@@ -28,6 +36,7 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "metric.structure_analysis".to_string(),
         "代码结构".to_string(),
     );
+    m.insert("metric.line_length".to_string(), "行长度".to_string());
 
     // 分析器进度
     m.insert(
@@ -109,6 +118,40 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "cmd.analysis_failed".to_string(),
         "分析失败：%s".to_string(),
     );
+    m.insert(
+        "cmd.fail_under".to_string(),
+        "屎气指数 %s 高于 --fail-under 阈值 %s".to_string(),
+    );
+    m.insert(
+        "cmd.max_metric".to_string(),
+        "指标 %s 的屎气指数 %s 高于 --max-metric 阈值 %s".to_string(),
+    );
+    m.insert(
+        "cmd.denied_issues".to_string(),
+        "发现 %s 个被禁止的问题:".to_string(),
+    );
+
+    // 解析性能测量
+    m.insert(
+        "bench.summary".to_string(),
+        "解析 %s 个文件（%s 行）%s 轮，耗时 %s 秒".to_string(),
+    );
+    m.insert(
+        "bench.throughput".to_string(),
+        "吞吐量: %s 行/秒，%s 文件/秒".to_string(),
+    );
+
+    // 解析缓存
+    m.insert(
+        "cache.stats".to_string(),
+        "解析缓存: 命中 %s 次，未命中 %s 次（命中率 %s%），约节省 %s 秒".to_string(),
+    );
+
+    // 构建信息
+    m.insert("info.commit".to_string(), "构建提交:".to_string());
+    m.insert("info.languages".to_string(), "支持语言数:".to_string());
+    m.insert("info.features".to_string(), "启用特性:".to_string());
+    m.insert("info.none".to_string(), "无".to_string());
 
     // 报告
     m.insert("report.title".to_string(), "屎山代码分析报告".to_string());
@@ -141,6 +184,12 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
     );
     m.insert("report.quality_score".to_string(), "质量评分".to_string());
     m.insert("report.quality_level".to_string(), "质量等级".to_string());
+    m.insert("report.grade".to_string(), "评级".to_string());
+    m.insert("grade.a".to_string(), "优秀".to_string());
+    m.insert("grade.b".to_string(), "良好".to_string());
+    m.insert("grade.c".to_string(), "一般".to_string());
+    m.insert("grade.d".to_string(), "较差".to_string());
+    m.insert("grade.f".to_string(), "糟糕".to_string());
     m.insert(
         "report.analyzed_files".to_string(),
         "分析文件数".to_string(),
@@ -151,6 +200,10 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
     m.insert("report.score".to_string(), "得分".to_string());
     m.insert("report.weight".to_string(), "权重".to_string());
     m.insert("report.status".to_string(), "状态".to_string());
+    m.insert("report.issue_count".to_string(), "问题数".to_string());
+    m.insert("report.languages".to_string(), "按语言统计".to_string());
+    m.insert("report.language".to_string(), "语言".to_string());
+    m.insert("report.file_count".to_string(), "文件数".to_string());
     m.insert("report.problem_files".to_string(), "问题文件".to_string());
     m.insert(
         "report.issue_categories".to_string(),
@@ -163,6 +216,34 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "report.improvement_suggestions".to_string(),
         "改进建议".to_string(),
     );
+    m.insert(
+        "report.baseline_comparison".to_string(),
+        "与基线对比".to_string(),
+    );
+    m.insert("report.baseline".to_string(), "基线".to_string());
+    m.insert("report.current".to_string(), "本次".to_string());
+    m.insert("report.change".to_string(), "变化".to_string());
+    m.insert("report.file_changes".to_string(), "文件变化".to_string());
+    m.insert("report.file".to_string(), "文件".to_string());
+    m.insert("report.file_added".to_string(), "新增".to_string());
+    m.insert("report.file_removed".to_string(), "已删除".to_string());
+    m.insert(
+        "report.issue_changes".to_string(),
+        "问题变化：新增 {0} 个，已解决 {1} 个".to_string(),
+    );
+    m.insert("report.run_id".to_string(), "运行标识".to_string());
+    m.insert(
+        "report.percentile_label".to_string(),
+        "参考百分位".to_string(),
+    );
+    m.insert(
+        "report.percentile".to_string(),
+        "比参考语料中 {0}% 的项目更屎".to_string(),
+    );
+    m.insert(
+        "report.generic_parser_warning".to_string(),
+        "有 {0}/{1} 个文件只能用通用解析器分析，评分仅供参考".to_string(),
+    );
 
     // 指标评分后缀
     m.insert("metric.score.suffix".to_string(), "分".to_string());
@@ -181,17 +262,45 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "函数像迷宫，维护像打副本".to_string(),
     );
 
+    // 认知复杂度评价
+    m.insert(
+        "metric.cognitive.good".to_string(),
+        "平铺直叙，读起来不费脑".to_string(),
+    );
+    m.insert(
+        "metric.cognitive.medium".to_string(),
+        "if 套 if，读着读着就忘了自己在第几层".to_string(),
+    );
+    m.insert(
+        "metric.cognitive.bad".to_string(),
+        "嵌套深不见底，读一遍需要先画流程图".to_string(),
+    );
+
     // 函数长度评价
     m.insert(
         "metric.length.good".to_string(),
-        "状态管理清晰，变量作用域合理，状态可预测".to_string(),
+        "函数短小精悍，一眼就能看完".to_string(),
     );
     m.insert(
         "metric.length.medium".to_string(),
-        "状态管理一般，存在部分全局状态或状态变化不明确的情况".to_string(),
+        "有些函数偏长，翻页翻到手酸".to_string(),
     );
     m.insert(
         "metric.length.bad".to_string(),
+        "函数长得像裹脚布，又臭又长".to_string(),
+    );
+
+    // 状态管理评价
+    m.insert(
+        "metric.state.good".to_string(),
+        "状态管理清晰，变量作用域合理，状态可预测".to_string(),
+    );
+    m.insert(
+        "metric.state.medium".to_string(),
+        "状态管理一般，存在部分全局状态或状态变化不明确的情况".to_string(),
+    );
+    m.insert(
+        "metric.state.bad".to_string(),
         "状态管理混乱，大量使用全局变量，状态变化难以追踪".to_string(),
     );
 
@@ -265,6 +374,20 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "层层嵌套，套娃结构，看完眼花".to_string(),
     );
 
+    // 行长度评价
+    m.insert(
+        "metric.line_length.good".to_string(),
+        "行宽克制，不用左右甩头".to_string(),
+    );
+    m.insert(
+        "metric.line_length.medium".to_string(),
+        "偶有长行，横向滚动条开始上班".to_string(),
+    );
+    m.insert(
+        "metric.line_length.bad".to_string(),
+        "一行写到天涯海角，带鱼屏都装不下".to_string(),
+    );
+
     // 质量建议
     m.insert(
         "advice.good".to_string(),
@@ -365,6 +488,10 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
     // 指标描述
     m.insert(
         "metric.function_length.description".to_string(),
+        "检测函数的长度和参数数量，过长的函数难以理解、测试和复用".to_string(),
+    );
+    m.insert(
+        "metric.state_management.description".to_string(),
         "检测代码中状态变量的管理，良好的状态管理能提高代码可维护性和可预测性".to_string(),
     );
     m.insert(
@@ -387,6 +514,14 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "metric.structure_analysis.description".to_string(),
         "检测代码的嵌套深度和引用复杂度，评估结构清晰度".to_string(),
     );
+    m.insert(
+        "metric.line_length.description".to_string(),
+        "检测超过 120 个字符的代码行，过长的行需要横向滚动，难以阅读和审查".to_string(),
+    );
+    m.insert(
+        "metric.cognitive_complexity.description".to_string(),
+        "按嵌套层级加权统计分支和布尔运算符序列，嵌套越深的逻辑越难读懂".to_string(),
+    );
     m.insert(
         "metric.cyclomatic_complexity.description".to_string(),
         "测量函数的控制流复杂度，复杂度越高，代码越难理解和测试".to_string(),
@@ -558,11 +693,149 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
         "issue.exported_func_no_comment".to_string(),
         "导出函数 %s 缺少文档注释".to_string(),
     );
+    m.insert(
+        "issue.complex_func_no_comment".to_string(),
+        "函数 %s 较复杂（复杂度 %s，%s 行）但缺少文档注释".to_string(),
+    );
     m.insert(
         "issue.exported_type_no_comment".to_string(),
         "导出类型 %s 缺少文档注释".to_string(),
     );
 
+    // 修复建议
+    m.insert(
+        "fix.high_complexity".to_string(),
+        "把嵌套的条件分支提取为独立的辅助函数，或用提前返回减少分支".to_string(),
+    );
+    m.insert(
+        "fix.medium_complexity".to_string(),
+        "用提前返回（卫语句）替代深层 if/else，合并重复的判断条件".to_string(),
+    );
+    m.insert(
+        "fix.high_cognitive_complexity".to_string(),
+        "用卫语句提前返回来拍平嵌套，把内层分支提取为独立函数".to_string(),
+    );
+    m.insert(
+        "fix.complex_predicate".to_string(),
+        "把判断条件拆成几个命名清晰的小判断函数，再用 && / || 组合".to_string(),
+    );
+    m.insert(
+        "fix.function_very_long".to_string(),
+        "按职责把函数拆分为若干小函数，每个函数只做一件事".to_string(),
+    );
+    m.insert(
+        "fix.function_long".to_string(),
+        "把独立的步骤提取为命名清晰的辅助函数".to_string(),
+    );
+    m.insert(
+        "fix.function_medium".to_string(),
+        "检查是否有可以提取或复用的代码块".to_string(),
+    );
+    m.insert(
+        "fix.too_many_parameters".to_string(),
+        "把相关参数合并为结构体或配置对象".to_string(),
+    );
+    m.insert(
+        "fix.many_parameters".to_string(),
+        "考虑把经常一起传递的参数封装为一个类型".to_string(),
+    );
+    m.insert(
+        "fix.comment_very_low".to_string(),
+        "为公开接口和关键逻辑补充文档注释，说明意图而不是复述代码".to_string(),
+    );
+    m.insert(
+        "fix.comment_low".to_string(),
+        "为复杂的分支和不直观的实现补充注释".to_string(),
+    );
+    m.insert(
+        "fix.exported_func_no_comment".to_string(),
+        "为导出函数补充文档注释，说明用途、参数和返回值".to_string(),
+    );
+    m.insert(
+        "fix.complex_func_no_comment".to_string(),
+        "为复杂函数补充文档注释，说明用途、参数和返回值".to_string(),
+    );
+    m.insert(
+        "fix.duplicate_functions".to_string(),
+        "把重复的函数合并为一个，并通过参数区分差异".to_string(),
+    );
+    m.insert(
+        "fix.similar_functions".to_string(),
+        "提取相似函数的公共部分，差异部分通过参数或闭包传入".to_string(),
+    );
+    m.insert(
+        "fix.repeated_naming_pattern".to_string(),
+        "检查同一前缀的函数是否可以合并为一个通用函数".to_string(),
+    );
+    m.insert(
+        "fix.repeated_signatures".to_string(),
+        "考虑用 trait/接口或泛型统一签名相同的函数".to_string(),
+    );
+    m.insert(
+        "fix.cross_file_duplicate".to_string(),
+        "把重复的代码块提取到公共模块或函数中，各处改为调用".to_string(),
+    );
+    m.insert(
+        "fix.missing_error_handling".to_string(),
+        "检查可能失败的调用，显式处理或向上传递错误".to_string(),
+    );
+    m.insert(
+        "fix.weak_error_handling".to_string(),
+        "不要忽略或吞掉错误，至少记录日志或返回给调用方".to_string(),
+    );
+    m.insert(
+        "fix.bad_naming".to_string(),
+        "使用能表达用途的完整名称，并遵循语言的命名约定".to_string(),
+    );
+    m.insert(
+        "fix.overloaded_function".to_string(),
+        "确认各重载的语义一致，参数差异较大时改用不同的函数名".to_string(),
+    );
+    m.insert(
+        "fix.shadowed_function".to_string(),
+        "删除或重命名被覆盖的定义，只保留一个实现".to_string(),
+    );
+    m.insert(
+        "fix.global_state".to_string(),
+        "把全局变量封装到结构体中，通过参数传递或依赖注入使用".to_string(),
+    );
+    m.insert(
+        "fix.deep_nesting".to_string(),
+        "使用提前返回、拆分函数或提取循环体来降低嵌套层级".to_string(),
+    );
+    m.insert(
+        "fix.medium_nesting".to_string(),
+        "用卫语句替代外层条件判断，减少一层嵌套".to_string(),
+    );
+    m.insert(
+        "fix.inconsistent_brace_style".to_string(),
+        "使用格式化工具（如 rustfmt、prettier）统一大括号风格".to_string(),
+    );
+    m.insert(
+        "fix.complex_condition".to_string(),
+        "把部分条件提取为有意义名字的布尔变量或判断函数".to_string(),
+    );
+    m.insert(
+        "fix.callback_hell".to_string(),
+        "改用 async/await 展平回调和 Promise 链，或把内层回调提取为具名函数".to_string(),
+    );
+    m.insert(
+        "fix.too_many_imports".to_string(),
+        "按职责拆分文件，让每个文件只依赖它真正需要的模块".to_string(),
+    );
+    m.insert(
+        "fix.long_line".to_string(),
+        "在运算符或参数处换行，或把长表达式提取为具名变量".to_string(),
+    );
+    m.insert(
+        "fix.stale_todo".to_string(),
+        "尽快处理该 TODO，或转为正式的问题单后删除标记".to_string(),
+    );
+    m.insert(
+        "fix.possibly_dead_code".to_string(),
+        "确认没有通过反射或动态分发调用后删除该函数".to_string(),
+    );
+
     // 详细报告
     m.insert(
         "verbose.basic_statistics".to_string(),
@@ -571,6 +844,11 @@ pub static MESSAGES: Lazy<HashMap<String, String>> = Lazy::new(|| {
     m.insert("verbose.total_files".to_string(), "总文件数:".to_string());
     m.insert("verbose.total_lines".to_string(), "总代码行:".to_string());
     m.insert("verbose.total_issues".to_string(), "总问题数:".to_string());
+    m.insert("verbose.run_id".to_string(), "运行标识:".to_string());
+    m.insert(
+        "verbose.parameter_distribution".to_string(),
+        "函数参数数量分布:".to_string(),
+    );
     m.insert(
         "verbose.metric_details".to_string(),
         "🔍 指标详细信息:".to_string(),
@@ -0,0 +1,91 @@
+#![allow(
+    clippy::needless_range_loop,
+    clippy::field_reassign_with_default,
+    clippy::ptr_arg,
+    clippy::redundant_closure,
+    clippy::or_fun_call,
+    clippy::manual_clamp,
+    clippy::upper_case_acronyms,
+    clippy::module_inception,
+    clippy::needless_borrow,
+    clippy::unnecessary_filter_map,
+    clippy::map_identity,
+    clippy::unnecessary_unwrap,
+    dead_code,
+    unused_imports
+)]
+//! # 屎山代码检测器 - 库入口
+//!
+//! 将分析器以库的形式对外提供，方便其他 Rust 工具直接嵌入代码质量分析。
+//!
+//! ## 稳定接口
+//! - [`CodeAnalyzer`]：分析入口，[`CodeAnalyzer::analyze_string`] 可直接分析内存中的源码
+//! - [`AnalysisConfig`]：分析配置
+//! - [`AnalysisResult`] / [`FileAnalysisResult`]：分析结果，可用 [`merge_results`] 合并多次结果
+//! - [`Parser`] / [`ParseResult`]：解析器接口
+//! - [`register_parser`]：为新的扩展名注册自定义解析器
+//! - [`Metric`] / [`MetricResult`]：度量指标接口，可通过 [`CodeAnalyzer::register_metric`] 注册自定义指标
+//! - [`LanguageType`]：语言类型
+//!
+//! 其余导出项（报告、基线、翻译等）主要服务于命令行工具，可能随版本调整。
+//! 各模块的内部实现不对外公开。
+//!
+//! ## 示例
+//!
+//! ```
+//! use fuck_shit_code::{AnalysisConfig, CodeAnalyzer};
+//!
+//! let dir = std::env::temp_dir().join("fuck_shit_code_doctest");
+//! std::fs::create_dir_all(&dir).unwrap();
+//! std::fs::write(dir.join("main.rs"), "fn main() {\n    println!(\"hi\");\n}\n").unwrap();
+//!
+//! let mut analyzer = CodeAnalyzer::new();
+//! analyzer.set_silent(true);
+//!
+//! let result = analyzer
+//!     .analyze_with_config(&dir, &AnalysisConfig::default())
+//!     .unwrap();
+//!
+//! assert_eq!(result.total_files, 1);
+//! assert!((0.0..=1.0).contains(&result.code_quality_score));
+//! ```
+
+mod analyzer;
+mod common;
+mod config;
+mod error;
+mod i18n;
+mod metrics;
+mod parser;
+mod report;
+
+// 稳定接口
+pub use analyzer::{
+    load_result, merge_results, AnalysisResult, CodeAnalyzer, FileAnalysisResult, LanguageScore,
+    ParameterDistribution, DEFAULT_CLONE_MIN_LINES,
+};
+pub use common::{LanguageType, SkipReason, SkippedFile};
+pub use config::{default_exclude_patterns, AnalysisConfig, DEFAULT_MAX_RETAINED_ISSUES};
+pub use error::{AppError, AppResult};
+pub use metrics::{
+    Issue, IssueKind, LanguageThresholds, Metric, MetricResult, Severity,
+    DEFAULT_DUPLICATION_MIN_LINES, DEFAULT_MAX_BOOLEAN_OPERATORS, DEFAULT_MAX_IMPORTS,
+    DEFAULT_THRESHOLDS_KEY,
+};
+pub use parser::{
+    register_parser, BaseParseResult, Function, Identifier, IdentifierKind, ParseResult, Parser,
+    ParserConstructor,
+};
+
+// 命令行工具使用的辅助接口
+pub use analyzer::{
+    AutoBaseline, BaselineDiff, CacheStats, FileDelta, MetricDelta, ParseBenchmark,
+    DEFAULT_CACHE_DIR,
+};
+pub use config::{OutputConfig, ProjectConfig, PROJECT_CONFIG_FILE};
+pub use i18n::{Language, Translator};
+pub use parser::create_parser_for_language;
+pub use report::{
+    grade, quality_level, Grade, Polarity, ReferenceDistribution, Report, ReportOptions, SortKey,
+    DEFAULT_LEVEL_CUTS, LEVEL_CUT_COUNT,
+};
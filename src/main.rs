@@ -1,19 +1,3 @@
-#![allow(
-    clippy::needless_range_loop,
-    clippy::field_reassign_with_default,
-    clippy::ptr_arg,
-    clippy::redundant_closure,
-    clippy::or_fun_call,
-    clippy::manual_clamp,
-    clippy::upper_case_acronyms,
-    clippy::module_inception,
-    clippy::needless_borrow,
-    clippy::unnecessary_filter_map,
-    clippy::map_identity,
-    clippy::unnecessary_unwrap,
-    dead_code,
-    unused_imports
-)]
 //! # 屎山代码检测器 - 主入口
 //!
 //! 这是一个专门用于分析代码质量的工具，能够客观评估代码的各项指标，
@@ -27,112 +11,331 @@
 //! - 分析代码结构
 //! - 评估错误处理质量
 
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process;
 
-mod analyzer;
-mod common;
-mod config;
-mod error;
-mod i18n;
-mod metrics;
-mod parser;
-mod report;
+use fuck_shit_code::{
+    default_exclude_patterns, load_result, merge_results, AnalysisConfig, AnalysisResult,
+    AutoBaseline, BaselineDiff, CacheStats, CodeAnalyzer, Issue, IssueKind, Language, LanguageType,
+    OutputConfig, Polarity, ProjectConfig, ReferenceDistribution, Report, ReportOptions, Severity,
+    SortKey, Translator, DEFAULT_CACHE_DIR, DEFAULT_LEVEL_CUTS, DEFAULT_THRESHOLDS_KEY,
+    LEVEL_CUT_COUNT,
+};
 
-use crate::analyzer::CodeAnalyzer;
-use crate::config::{AnalysisConfig, OutputConfig};
-use crate::i18n::{Language, Translator};
-use crate::report::{Report, ReportOptions};
+/// 表示从标准输入读取源码的路径参数
+const STDIN_PATH: &str = "-";
 
 /// 命令行参数解析结构
 #[derive(Parser)]
 #[command(name = "fuck-shit-code")]
-#[command(version = "1.0.0")]
+#[command(version)]
 #[command(about = "💻 屎山代码检测器 - 客观评估您的代码质量", long_about = None)]
 struct Cli {
-    /// 要分析的路径
-    path: Option<PathBuf>,
+    /// 分析参数
+    #[command(flatten)]
+    args: AnalyzeArgs,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+/// 分析参数（主命令与 analyze 子命令共用）
+#[derive(Args, Clone)]
+struct AnalyzeArgs {
+    /// 要分析的路径，可以指定多个（`-` 表示从标准输入读取单个文件的源码）
+    paths: Vec<PathBuf>,
+
+    /// 标准输入源码的语言，支持语言名或扩展名（如 rust、rs、ts），未指定时使用通用解析器
+    #[arg(long, value_name = "LANG", value_parser = parse_stdin_lang)]
+    stdin_lang: Option<LanguageType>,
 
     /// 指定输出语言（支持：zh-CN, en-US，默认：zh-CN）
-    #[arg(short = 'l', long, default_value = "zh-CN")]
-    lang: String,
+    #[arg(short = 'l', long)]
+    lang: Option<String>,
+
+    /// 使用指定的项目配置文件（默认从分析路径向上查找 .fuckshitcode.toml）
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
 
     /// 显示详细分析报告
     #[arg(short = 'v', long)]
     verbose: bool,
 
-    /// 显示问题最多的文件数量
-    #[arg(short = 't', long, default_value = "5")]
-    top: usize,
+    /// 显示问题最多的文件数量（默认 5，0 表示全部）
+    #[arg(short = 't', long)]
+    top: Option<usize>,
 
-    /// 每个文件显示多少条问题
-    #[arg(short = 'i', long, default_value = "5")]
-    issues: usize,
+    /// 每个文件显示多少条问题（默认 5，0 表示全部）
+    #[arg(short = 'i', long)]
+    issues: Option<usize>,
 
     /// 只看结论，过程略过
     #[arg(short = 's', long)]
     summary: bool,
 
+    /// 只输出总体得分（如 `score: 73.21`），与 --json 同用时只输出数字，便于脚本使用
+    #[arg(short = 'q', long, conflicts_with_all = ["markdown", "html"])]
+    quiet: bool,
+
+    /// 得分显示的小数位数（JSON 输出保留完整精度）
+    #[arg(long, value_name = "N", default_value_t = 2)]
+    precision: usize,
+
+    /// 只运行指定的指标，逗号分隔（如 complexity,duplication），总体得分只按这些指标的权重计算
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    metrics: Vec<String>,
+
+    /// 列出可以用 --metrics 选择的指标，然后退出
+    #[arg(long)]
+    list_metrics: bool,
+
+    /// 自定义质量等级分界点：10 个升序的屎气指数（0-100），逗号分隔
+    #[arg(long, value_name = "CUTS", value_parser = parse_level_cuts)]
+    level_cuts: Option<[f64; LEVEL_CUT_COUNT]>,
+
     /// 输出Markdown格式的精简报告
     #[arg(short = 'm', long)]
     markdown: bool,
 
+    /// 输出JSON格式的完整分析结果
+    #[arg(long, group = "file_format")]
+    json: bool,
+
+    /// 输出单文件HTML报告（样式内联，可离线打开）
+    #[arg(long, group = "file_format", conflicts_with_all = ["markdown", "json"])]
+    html: bool,
+
+    /// 输出CSV格式的文件得分表（每个文件一行，含各项指标得分）
+    #[arg(long, group = "file_format", conflicts_with_all = ["markdown", "json", "html"])]
+    csv: bool,
+
+    /// 把 --json、--csv 或 --html 报告写入文件而不是标准输出
+    #[arg(short = 'o', long, value_name = "FILE", requires = "file_format")]
+    output: Option<PathBuf>,
+
+    /// JSON 缩进排版输出（默认在终端中缩进，输出到管道或文件时紧凑）
+    #[arg(long, conflicts_with = "json_compact")]
+    json_pretty: bool,
+
+    /// JSON 紧凑输出为单行
+    #[arg(long)]
+    json_compact: bool,
+
+    /// 分数方向：smell 越高越差（默认），quality 越高越好
+    #[arg(long, value_enum, default_value_t = Polarity::Smell)]
+    polarity: Polarity,
+
     /// 排除的文件/目录模式
     #[arg(short = 'e', long)]
     exclude: Vec<String>,
 
+    /// 只分析匹配的文件/目录模式（可重复，如 -I "**/*.rs"），匹配排除模式的文件仍然被排除
+    #[arg(short = 'I', long)]
+    include: Vec<String>,
+
+    /// 不使用内置的默认排除模式（node_modules、target、tests 等），只使用配置文件和命令行指定的排除模式
+    #[arg(long)]
+    no_default_excludes: bool,
+
+    /// 只分析指定扩展名的文件，逗号分隔（如 py,rs）
+    #[arg(long, value_name = "EXTS", value_delimiter = ',')]
+    ext: Vec<String>,
+
+    /// 只分析清单中列出的文件，不再遍历目录（每行一个路径或 JSON 字符串数组，`-` 表示标准输入）
+    #[arg(long, value_name = "MANIFEST")]
+    files_from: Option<PathBuf>,
+
+    /// 不读取根目录下的 .fscignore 和 .gitignore
+    #[arg(long, conflicts_with = "respect_gitignore")]
+    no_ignore: bool,
+
+    /// 遵循各级目录中的 .gitignore、.git/info/exclude 和全局 gitignore（排除模式仍然生效）
+    #[arg(long)]
+    respect_gitignore: bool,
+
+    /// 跳过大于该字节数的文件（默认 10485760，即 10MB）
+    #[arg(long, value_name = "BYTES")]
+    max_file_size: Option<usize>,
+
+    /// 跳过小于该字节数的文件（默认 1，即跳过空文件）
+    #[arg(long, value_name = "BYTES")]
+    min_file_size: Option<usize>,
+
     /// 跳过所有 index.js/index.ts 文件
     #[arg(short = 'x', long)]
     skipindex: bool,
 
-    #[command(subcommand)]
-    command: Option<Commands>,
+    /// 只列出屎气指数高于该值的文件（0-100，不影响总体评分）
+    #[arg(long, value_name = "SCORE", value_parser = parse_score_threshold)]
+    min_file_score: Option<f64>,
+
+    /// 问题文件的排序方式：score（默认）、issues、lines 或 metric:<指标>（如 metric:complexity）
+    #[arg(long, value_name = "KEY", value_parser = parse_sort_key)]
+    sort_by: Option<SortKey>,
+
+    /// 反转问题文件的排序，用于找出最干净的文件
+    #[arg(long)]
+    reverse: bool,
+
+    /// 报告中只显示不低于该严重程度的问题（info、warning、error，JSON 输出保留全部问题）
+    #[arg(long, value_enum, value_name = "LEVEL", default_value_t = Severity::Low)]
+    min_severity: Severity,
+
+    /// 标记存在超过指定天数的 TODO/FIXME（通过 git blame 获取时间）
+    #[arg(long, value_name = "DAYS")]
+    stale_todo_days: Option<u64>,
+
+    /// 报告总体得分在参考项目得分分布中的百分位
+    #[arg(long)]
+    percentile: bool,
+
+    /// 自定义参考分布文件（屎气指数 JSON 数组，0-100），需配合 --percentile 使用
+    #[arg(long, value_name = "FILE", requires = "percentile")]
+    reference: Option<PathBuf>,
+
+    /// 与之前保存的 JSON 报告对比，输出得分变化以及新增和已解决的问题
+    #[arg(long, value_name = "FILE", conflicts_with = "baseline_auto")]
+    baseline: Option<PathBuf>,
+
+    /// 按当前 git 分支自动读取并更新基线（.fuckshitcode/baselines/<分支>.json），只能用于单个分析路径
+    #[arg(long)]
+    baseline_auto: bool,
+
+    /// 屎气指数（0-100，越高越差）高于该值时以状态码 1 退出，用于 CI 门禁
+    #[arg(long, value_name = "SCORE", value_parser = parse_score_threshold)]
+    fail_under: Option<f64>,
+
+    /// 某项指标的屎气指数（0-100）高于阈值时以状态码 1 退出，格式为 指标=分数（可重复，如 complexity=60）
+    #[arg(long, value_name = "METRIC=SCORE", value_parser = parse_metric_threshold)]
+    max_metric: Vec<(String, f64)>,
+
+    /// 出现指定类型的问题时以非零状态退出（与得分无关），逗号分隔（如 high_complexity）
+    #[arg(long, value_name = "KINDS", value_delimiter = ',')]
+    deny: Vec<IssueKind>,
+
+    /// 当前分支没有自动基线时回退的基础分支（默认自动检测仓库的默认分支）
+    #[arg(long, value_name = "BRANCH", requires = "baseline_auto")]
+    base_branch: Option<String>,
+
+    /// 只分析相对于指定 git 版本（分支、标签、提交或 main...HEAD 这样的范围）修改过的文件（不在 git 仓库中时分析全部文件）
+    #[arg(long, value_name = "REF", visible_alias = "changed")]
+    since: Option<String>,
+
+    /// 只报告位于修改行上的问题（文件评分仍基于整个文件），需配合 --since 使用
+    #[arg(long, requires = "since")]
+    changed_lines_only: bool,
+
+    /// 不把匿名函数（箭头函数、闭包、lambda）计入度量
+    #[arg(long)]
+    exclude_anonymous: bool,
+
+    /// 无法识别编码的文件按 UTF-8 有损解码后继续分析，而不是跳过
+    #[arg(long)]
+    lossy_utf8: bool,
+
+    /// 报告在所有分析文件中都没有被引用的非导出函数（启发式检测，反射和动态调用会被误报）
+    #[arg(long)]
+    dead_code: bool,
+
+    /// 启用解析缓存，复用内容未变的文件的解析结果（缓存目录默认为 .fuckshitcode/cache）
+    #[arg(long)]
+    cache: bool,
+
+    /// 解析缓存目录（指定后即启用缓存）
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// 参与重复度分析的最小函数行数（默认 8），更短的函数不参与比较
+    #[arg(long, value_name = "LINES")]
+    dup_min_lines: Option<usize>,
+
+    /// 单行条件表达式允许的最多逻辑运算符数（&&、||，默认 5），超过即报告问题
+    #[arg(long, value_name = "NUM")]
+    max_bool_ops: Option<usize>,
+
+    /// 每个文件允许的最多导入语句数（import/require/use/#include 等，默认 20），超过即报告耦合度过高
+    #[arg(long, value_name = "NUM")]
+    max_imports: Option<usize>,
+
+    /// 循环复杂度超过该值时报告“较高”（默认 10），对所有语言生效
+    #[arg(long, value_name = "NUM")]
+    complexity_warn: Option<usize>,
+
+    /// 循环复杂度超过该值时报告“过高”（默认 15），对所有语言生效
+    #[arg(long, value_name = "NUM")]
+    complexity_error: Option<usize>,
+
+    /// 函数行数超过该值时报告（默认 40），对所有语言生效
+    #[arg(long, value_name = "LINES")]
+    max_function_lines: Option<usize>,
+
+    /// 函数参数数量超过该值时报告（默认 6），对所有语言生效
+    #[arg(long, value_name = "NUM")]
+    max_params: Option<usize>,
+
+    /// 跨文件重复代码块的最少连续代码行数（默认 6，0 表示不检测跨文件重复）
+    #[arg(long, value_name = "LINES")]
+    clone_min_lines: Option<usize>,
+
+    /// 每个文件的每项指标最多保留的问题数（保留最严重的，默认 200，0 表示不限制）
+    #[arg(long, value_name = "NUM")]
+    max_retained_issues: Option<usize>,
+
+    /// 把文件开头不超过 LINES 行的许可证注释排除在注释率之外
+    #[arg(long, value_name = "LINES")]
+    license_header_lines: Option<usize>,
+
+    /// 以 JSON 输出合并默认值和命令行参数后实际生效的分析配置，然后退出
+    #[arg(long)]
+    print_config: bool,
+
+    /// 只重复解析目标 K 次并报告解析吞吐量，不计算指标（用于性能测量）
+    #[arg(long, value_name = "K", hide = true)]
+    bench_parse: Option<usize>,
 }
 
 /// 子命令定义
 #[derive(Subcommand)]
 enum Commands {
     /// 分析代码质量并输出评分
-    Analyze {
-        /// 要分析的路径
-        path: Option<PathBuf>,
-
-        /// 指定输出语言
-        #[arg(short = 'l', long, default_value = "zh-CN")]
-        lang: String,
+    Analyze(Box<AnalyzeArgs>),
 
-        /// 显示详细分析报告
-        #[arg(short = 'v', long)]
-        verbose: bool,
+    /// 显示版本、构建提交和支持的语言等构建信息
+    Info,
 
-        /// 显示问题最多的文件数量
-        #[arg(short = 't', long, default_value = "5")]
-        top: usize,
-
-        /// 每个文件显示多少条问题
-        #[arg(short = 'i', long, default_value = "5")]
-        issues: usize,
-
-        /// 只看结论，过程略过
-        #[arg(short = 's', long)]
-        summary: bool,
-
-        /// 输出Markdown格式的精简报告
-        #[arg(short = 'm', long)]
-        markdown: bool,
+    /// 合并多个 --json 输出的分析结果并生成报告
+    Merge {
+        /// 要合并的结果文件
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+    },
 
-        /// 排除的文件/目录模式
-        #[arg(short = 'e', long)]
-        exclude: Vec<String>,
+    /// 分析两个目录（如两个分支的检出）并按相对路径对比文件和总体得分
+    Compare {
+        /// 作为基准的目录
+        before: PathBuf,
 
-        /// 跳过所有 index.js/index.ts 文件
-        #[arg(short = 'x', long)]
-        skipindex: bool,
+        /// 要对比的目录
+        after: PathBuf,
     },
 }
 
+impl Cli {
+    /// 获取生效的分析参数
+    ///
+    /// # Returns
+    /// * `&AnalyzeArgs` - 子命令参数（如有），否则为主命令参数
+    fn analyze_args(&self) -> &AnalyzeArgs {
+        match &self.command {
+            Some(Commands::Analyze(args)) => args,
+            _ => &self.args,
+        }
+    }
+}
+
 /// 程序主入口
 ///
 /// 解析命令行参数，初始化配置，并执行代码分析
@@ -163,25 +366,108 @@ fn init_logger() {
 /// # Returns
 /// * `Result<()>` - 执行结果
 fn run(cli: Cli) -> Result<()> {
-    // 解析语言设置
-    let language = parse_language(&cli.lang);
+    let args = cli.analyze_args();
+
+    // 读取项目配置文件
+    let project = load_project_config(args)?;
+
+    // 解析语言设置（命令行优先于配置文件）
+    let lang = args.lang.as_deref().or(project.lang.as_deref());
+    let language = parse_language(lang.unwrap_or("zh-CN"));
     let translator = Translator::new(language);
 
-    // 获取分析路径
-    let path = get_analysis_path(&cli, &translator)?;
+    match &cli.command {
+        // 构建信息命令
+        Some(Commands::Info) => {
+            print_build_info(&translator);
+            return Ok(());
+        }
+        // 合并结果命令
+        Some(Commands::Merge { inputs }) => {
+            return execute_merge(inputs, build_output_config(args, &project), translator);
+        }
+        // 目录对比命令
+        Some(Commands::Compare { before, after }) => {
+            return execute_compare(
+                before,
+                after,
+                build_analysis_config(args, &project)?,
+                build_output_config(args, &project),
+                translator,
+            );
+        }
+        _ => {}
+    }
 
     // 构建分析配置
-    let config = build_analysis_config(&cli)?;
+    let config = build_analysis_config(args, &project)?;
+
+    // 列出可选择的指标
+    if args.list_metrics {
+        print_metric_list();
+        return Ok(());
+    }
+
+    // 输出生效的配置
+    if args.print_config {
+        println!("{}", effective_config(&config)?);
+        return Ok(());
+    }
+
+    // 获取分析路径
+    let paths = get_analysis_paths(&cli, &translator)?;
+
+    // 解析性能测量
+    if let Some(iterations) = args.bench_parse {
+        return execute_bench_parse(&paths, &config, iterations, &translator);
+    }
 
     // 构建输出配置
-    let output_config = build_output_config(&cli);
+    let output_config = build_output_config(args, &project);
 
     // 执行分析
-    execute_analysis(path, config, output_config, translator)?;
+    execute_analysis(paths, args.stdin_lang, config, output_config, translator)?;
 
     Ok(())
 }
 
+/// 打印构建信息
+///
+/// # Arguments
+/// * `translator` - 翻译器
+fn print_build_info(translator: &Translator) {
+    let features = match env!("FSC_FEATURES") {
+        "" => translator.translate("info.none"),
+        features => features.to_string(),
+    };
+
+    println!("fuck-shit-code {}", env!("CARGO_PKG_VERSION"));
+    println!(
+        "  {:<12} {}",
+        translator.translate("info.commit"),
+        env!("FSC_GIT_COMMIT")
+    );
+    println!(
+        "  {:<12} {}",
+        translator.translate("info.languages"),
+        LanguageType::all_supported().len()
+    );
+    println!(
+        "  {:<12} {}",
+        translator.translate("info.features"),
+        features
+    );
+}
+
+/// 打印可以用 --metrics 选择的指标
+fn print_metric_list() {
+    let analyzer = CodeAnalyzer::new();
+
+    for (key, name, description) in analyzer.available_metrics() {
+        println!("  {:<14} {}  {}", key, name, description);
+    }
+}
+
 /// 解析语言设置
 ///
 /// # Arguments
@@ -196,6 +482,118 @@ fn parse_language(lang_str: &str) -> Language {
     }
 }
 
+/// 解析标准输入源码的语言
+///
+/// # Arguments
+/// * `value` - 命令行参数值
+///
+/// # Returns
+/// * `Result<LanguageType, String>` - 语言类型
+fn parse_stdin_lang(value: &str) -> std::result::Result<LanguageType, String> {
+    match LanguageType::from_name(value) {
+        LanguageType::Unsupported => Err(format!("不支持的语言: {}", value)),
+        language => Ok(language),
+    }
+}
+
+/// 解析 0-100 之间的分数阈值
+///
+/// # Arguments
+/// * `value` - 命令行参数值
+///
+/// # Returns
+/// * `Result<f64, String>` - 分数阈值
+fn parse_score_threshold(value: &str) -> std::result::Result<f64, String> {
+    let score: f64 = value
+        .parse()
+        .map_err(|_| format!("无效的分数: {}", value))?;
+
+    if (0.0..=100.0).contains(&score) {
+        Ok(score)
+    } else {
+        Err(format!("分数必须在 0-100 之间: {}", value))
+    }
+}
+
+/// 解析问题文件的排序方式，`metric:<指标>` 中的指标可以是 --list-metrics 列出的名称或指标名称
+///
+/// # Arguments
+/// * `value` - 命令行参数值
+///
+/// # Returns
+/// * `Result<SortKey, String>` - 排序方式（指标统一为指标名称）
+fn parse_sort_key(value: &str) -> std::result::Result<SortKey, String> {
+    match value.parse::<SortKey>()? {
+        SortKey::Metric(metric) => resolve_metric_name(&metric).map(SortKey::Metric),
+        key => Ok(key),
+    }
+}
+
+/// 解析单项指标的分数阈值
+///
+/// # Arguments
+/// * `value` - 命令行参数值，格式为 `指标=分数`，指标可以是 --list-metrics 列出的名称或指标名称
+///
+/// # Returns
+/// * `Result<(String, f64), String>` - (指标名称, 阈值)
+fn parse_metric_threshold(value: &str) -> std::result::Result<(String, f64), String> {
+    let (metric, score) = value
+        .split_once('=')
+        .ok_or_else(|| format!("格式应为 指标=分数: {}", value))?;
+
+    Ok((
+        resolve_metric_name(metric.trim())?,
+        parse_score_threshold(score.trim())?,
+    ))
+}
+
+/// 把 --list-metrics 列出的名称或指标名称统一为指标名称
+///
+/// # Arguments
+/// * `metric` - 用户输入的指标
+///
+/// # Returns
+/// * `Result<String, String>` - 指标名称
+fn resolve_metric_name(metric: &str) -> std::result::Result<String, String> {
+    CodeAnalyzer::new()
+        .available_metrics()
+        .into_iter()
+        .find(|(key, name, _)| key.eq_ignore_ascii_case(metric) || name == metric)
+        .map(|(_, name, _)| name)
+        .ok_or_else(|| {
+            format!(
+                "未知的指标: {}（可用 --list-metrics 查看可选的指标）",
+                metric
+            )
+        })
+}
+
+/// 解析质量等级分界点
+///
+/// # Arguments
+/// * `value` - 命令行参数值，逗号分隔的升序分数
+///
+/// # Returns
+/// * `Result<[f64; LEVEL_CUT_COUNT], String>` - 等级分界点
+fn parse_level_cuts(value: &str) -> std::result::Result<[f64; LEVEL_CUT_COUNT], String> {
+    let cuts = value
+        .split(',')
+        .map(|cut| parse_score_threshold(cut.trim()))
+        .collect::<std::result::Result<Vec<f64>, String>>()?;
+
+    if cuts.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(format!("等级分界点必须严格递增: {}", value));
+    }
+
+    cuts.try_into().map_err(|cuts: Vec<f64>| {
+        format!(
+            "需要 {} 个等级分界点，实际为 {} 个",
+            LEVEL_CUT_COUNT,
+            cuts.len()
+        )
+    })
+}
+
 /// 获取要分析的路径
 ///
 /// # Arguments
@@ -203,24 +601,29 @@ fn parse_language(lang_str: &str) -> Language {
 /// * `translator` - 翻译器
 ///
 /// # Returns
-/// * `Result<PathBuf>` - 分析路径
-fn get_analysis_path(cli: &Cli, translator: &Translator) -> Result<PathBuf> {
+/// * `Result<Vec<PathBuf>>` - 分析路径（`-` 和 `--baseline-auto` 只能用于单个路径）
+fn get_analysis_paths(cli: &Cli, translator: &Translator) -> Result<Vec<PathBuf>> {
     // 从命令或参数中获取路径
-    let path = match &cli.command {
-        Some(Commands::Analyze { path, .. }) => path.clone(),
-        None => cli.path.clone(),
-    };
+    let paths = cli.analyze_args().paths.clone();
 
-    // 如果没有提供路径，显示帮助信息
-    match path {
-        Some(p) => Ok(p),
-        None => {
-            if cli.command.is_none() && cli.path.is_none() {
-                show_help_and_exit(translator);
-            }
-            Ok(PathBuf::from("."))
+    if paths.is_empty() {
+        // 如果没有提供路径，显示帮助信息
+        if cli.command.is_none() {
+            show_help_and_exit(translator);
         }
+        return Ok(vec![PathBuf::from(".")]);
+    }
+
+    if paths.len() > 1 && paths.iter().any(|path| path == Path::new(STDIN_PATH)) {
+        anyhow::bail!("从标准输入读取源码（-）时不能再指定其他路径");
+    }
+
+    // 自动基线按所在仓库和分支存取，多个路径的合并结果无法对应到单一基线
+    if paths.len() > 1 && cli.analyze_args().baseline_auto {
+        anyhow::bail!("--baseline-auto 只能用于单个分析路径");
     }
+
+    Ok(paths)
 }
 
 /// 显示帮助信息并退出
@@ -233,51 +636,90 @@ fn show_help_and_exit(translator: &Translator) -> ! {
     process::exit(0);
 }
 
+/// 读取项目配置文件
+///
+/// 指定了 `--config` 时读取该文件，否则从分析路径（未指定时为当前目录）向上查找
+/// `.fuckshitcode.toml`，找不到时使用默认配置。
+///
+/// # Arguments
+/// * `args` - 分析参数
+///
+/// # Returns
+/// * `Result<ProjectConfig>` - 项目配置
+fn load_project_config(args: &AnalyzeArgs) -> Result<ProjectConfig> {
+    let path = match &args.config {
+        Some(path) => Some(path.clone()),
+        None => ProjectConfig::discover(
+            args.paths
+                .first()
+                .map(PathBuf::as_path)
+                .filter(|path| *path != Path::new(STDIN_PATH))
+                .unwrap_or(Path::new(".")),
+        ),
+    };
+
+    match path {
+        Some(path) => Ok(ProjectConfig::load(&path)?),
+        None => Ok(ProjectConfig::default()),
+    }
+}
+
 /// 构建分析配置
 ///
+/// 以项目配置文件为基础，再应用命令行参数
+///
 /// # Arguments
-/// * `cli` - 命令行参数
+/// * `args` - 分析参数
+/// * `project` - 项目配置
 ///
 /// # Returns
 /// * `Result<AnalysisConfig>` - 分析配置
-fn build_analysis_config(cli: &Cli) -> Result<AnalysisConfig> {
-    let mut config = AnalysisConfig::default();
+fn build_analysis_config(args: &AnalyzeArgs, project: &ProjectConfig) -> Result<AnalysisConfig> {
+    let mut config = project.analysis.clone();
 
-    // 添加排除模式
-    config.exclude_patterns = get_exclude_patterns(cli);
+    // 添加包含和排除模式
+    config.include_patterns.extend(args.include.iter().cloned());
+    config.exclude_patterns = get_exclude_patterns(args, &project.analysis.exclude_patterns);
 
     // 应用其他配置
-    apply_cli_options(&mut config, cli);
+    apply_cli_options(&mut config, args);
 
     Ok(config)
 }
 
+/// 生成生效配置的 JSON 文本（`--print-config` 的输出）
+///
+/// 配置中的表均为有序映射，相同配置总是输出相同的文本
+///
+/// # Arguments
+/// * `config` - 分析配置
+///
+/// # Returns
+/// * `Result<String>` - 格式化的 JSON
+fn effective_config(config: &AnalysisConfig) -> Result<String> {
+    Ok(serde_json::to_string_pretty(config)?)
+}
+
 /// 获取排除模式列表
 ///
 /// # Arguments
-/// * `cli` - 命令行参数
+/// * `args` - 分析参数
+/// * `configured` - 项目配置文件中的排除模式
 ///
 /// # Returns
-/// * `Vec<String>` - 排除模式列表
-fn get_exclude_patterns(cli: &Cli) -> Vec<String> {
-    let mut patterns = get_default_excludes();
+/// * `Vec<String>` - 排除模式列表（内置默认值、配置文件、命令行依次追加）
+fn get_exclude_patterns(args: &AnalyzeArgs, configured: &[String]) -> Vec<String> {
+    let mut patterns = if args.no_default_excludes {
+        Vec::new()
+    } else {
+        default_exclude_patterns()
+    };
 
-    // 添加用户指定的排除模式
-    match &cli.command {
-        Some(Commands::Analyze {
-            exclude, skipindex, ..
-        }) => {
-            patterns.extend(exclude.clone());
-            if *skipindex {
-                add_index_excludes(&mut patterns);
-            }
-        }
-        None => {
-            patterns.extend(cli.exclude.clone());
-            if cli.skipindex {
-                add_index_excludes(&mut patterns);
-            }
-        }
+    // 添加配置文件和用户指定的排除模式
+    patterns.extend(configured.iter().cloned());
+    patterns.extend(args.exclude.clone());
+    if args.skipindex {
+        add_index_excludes(&mut patterns);
     }
 
     patterns
@@ -296,52 +738,161 @@ fn add_index_excludes(patterns: &mut Vec<String>) {
     ]);
 }
 
-/// 应用命令行选项到配置
+/// 应用命令行选项到配置（只覆盖命令行中给出的项）
+///
+/// # Arguments
+/// * `config` - 分析配置
+/// * `args` - 分析参数
+fn apply_cli_options(config: &mut AnalysisConfig, args: &AnalyzeArgs) {
+    if !args.ext.is_empty() {
+        config.extensions = args.ext.clone();
+    }
+    if !args.metrics.is_empty() {
+        config.metrics = args.metrics.clone();
+    }
+    if args.files_from.is_some() {
+        config.files_from = args.files_from.clone();
+    }
+    if args.no_ignore {
+        config.use_ignore_files = false;
+    }
+    if args.respect_gitignore {
+        config.respect_gitignore = true;
+    }
+    if args.stale_todo_days.is_some() {
+        config.stale_todo_days = args.stale_todo_days;
+    }
+    if args.since.is_some() {
+        config.since = args.since.clone();
+    }
+    if args.changed_lines_only {
+        config.changed_lines_only = true;
+    }
+    if args.exclude_anonymous {
+        config.include_anonymous = false;
+    }
+    if args.lossy_utf8 {
+        config.lossy_utf8 = true;
+    }
+    if args.dead_code {
+        config.dead_code = true;
+    }
+    if let Some(dir) = &args.cache_dir {
+        config.cache_dir = Some(dir.clone());
+    } else if args.cache && config.cache_dir.is_none() {
+        config.cache_dir = Some(PathBuf::from(DEFAULT_CACHE_DIR));
+    }
+    if let Some(size) = args.max_file_size {
+        config.max_file_size = size;
+    }
+    if let Some(size) = args.min_file_size {
+        config.min_file_size = size;
+    }
+    if let Some(lines) = args.dup_min_lines {
+        config.duplication_min_lines = lines;
+    }
+    if let Some(max) = args.max_bool_ops {
+        config.max_boolean_operators = max;
+    }
+    if let Some(max) = args.max_imports {
+        config.max_imports = max;
+    }
+    if let Some(lines) = args.clone_min_lines {
+        config.clone_min_lines = lines;
+    }
+    apply_threshold_options(config, args);
+    if let Some(max) = args.max_retained_issues {
+        config.max_retained_issues = max;
+    }
+    if args.license_header_lines.is_some() {
+        config.license_header_max_lines = args.license_header_lines;
+    }
+}
+
+/// 把命令行指定的阈值写入对所有语言生效的 `default` 阈值（各语言的配置仍然优先）
 ///
 /// # Arguments
-/// * `_config` - 分析配置
-/// * `_cli` - 命令行参数
-fn apply_cli_options(_config: &mut AnalysisConfig, _cli: &Cli) {
-    // 预留用于将来的配置扩展
+/// * `config` - 分析配置
+/// * `args` - 分析参数
+fn apply_threshold_options(config: &mut AnalysisConfig, args: &AnalyzeArgs) {
+    let overrides = [
+        args.complexity_warn,
+        args.complexity_error,
+        args.max_function_lines,
+        args.max_params,
+    ];
+    if overrides.iter().all(Option::is_none) {
+        return;
+    }
+
+    let defaults = config
+        .thresholds
+        .entry(DEFAULT_THRESHOLDS_KEY.to_string())
+        .or_default();
+    if args.complexity_warn.is_some() {
+        defaults.complexity_warn = args.complexity_warn;
+    }
+    if args.complexity_error.is_some() {
+        defaults.complexity_error = args.complexity_error;
+    }
+    if args.max_function_lines.is_some() {
+        defaults.function_lines_medium = args.max_function_lines;
+    }
+    if args.max_params.is_some() {
+        defaults.parameters_medium = args.max_params;
+    }
 }
 
 /// 构建输出配置
 ///
 /// # Arguments
-/// * `cli` - 命令行参数
+/// * `args` - 分析参数
+/// * `project` - 项目配置
 ///
 /// # Returns
 /// * `OutputConfig` - 输出配置
-fn build_output_config(cli: &Cli) -> OutputConfig {
-    match &cli.command {
-        Some(Commands::Analyze {
-            verbose,
-            top,
-            issues,
-            summary,
-            markdown,
-            ..
-        }) => OutputConfig {
-            verbose: *verbose,
-            top_files: *top,
-            max_issues: *issues,
-            summary_only: *summary,
-            markdown_output: *markdown,
-        },
-        None => OutputConfig {
-            verbose: cli.verbose,
-            top_files: cli.top,
-            max_issues: cli.issues,
-            summary_only: cli.summary,
-            markdown_output: cli.markdown,
-        },
+fn build_output_config(args: &AnalyzeArgs, project: &ProjectConfig) -> OutputConfig {
+    let defaults = OutputConfig::default();
+
+    OutputConfig {
+        verbose: args.verbose,
+        top_files: args.top.or(project.top_files).unwrap_or(defaults.top_files),
+        max_issues: args
+            .issues
+            .or(project.max_issues)
+            .unwrap_or(defaults.max_issues),
+        summary_only: args.summary,
+        quiet: args.quiet,
+        markdown_output: args.markdown,
+        json_output: args.json,
+        html_output: args.html,
+        csv_output: args.csv,
+        output: args.output.clone(),
+        json_compact: args.json_compact
+            || (!args.json_pretty && (args.output.is_some() || !std::io::stdout().is_terminal())),
+        polarity: args.polarity,
+        min_file_score: args.min_file_score,
+        sort_by: args.sort_by.clone().unwrap_or_default(),
+        reverse: args.reverse,
+        min_severity: args.min_severity,
+        baseline: args.baseline.clone(),
+        baseline_auto: args.baseline_auto,
+        base_branch: args.base_branch.clone(),
+        percentile: args.percentile,
+        reference: args.reference.clone(),
+        precision: args.precision,
+        level_cuts: args.level_cuts.unwrap_or(DEFAULT_LEVEL_CUTS),
+        deny: args.deny.clone(),
+        fail_under: args.fail_under,
+        max_metric: args.max_metric.clone(),
     }
 }
 
 /// 执行代码分析
 ///
 /// # Arguments
-/// * `path` - 分析路径
+/// * `paths` - 分析路径，`-` 表示从标准输入读取
+/// * `stdin_lang` - 标准输入源码的语言
 /// * `config` - 分析配置
 /// * `output_config` - 输出配置
 /// * `translator` - 翻译器
@@ -349,40 +900,370 @@ fn build_output_config(cli: &Cli) -> OutputConfig {
 /// # Returns
 /// * `Result<()>` - 执行结果
 fn execute_analysis(
-    path: PathBuf,
+    paths: Vec<PathBuf>,
+    stdin_lang: Option<LanguageType>,
     config: AnalysisConfig,
     output_config: OutputConfig,
     translator: Translator,
 ) -> Result<()> {
+    // 机器可读格式只输出报告本身
+    let silent = output_config.quiet
+        || output_config.markdown_output
+        || output_config.json_output
+        || output_config.html_output
+        || output_config.csv_output;
+
     // 显示开始信息
-    if !output_config.markdown_output {
-        print_analysis_start(&path, &config, &translator);
+    if !silent {
+        print_analysis_start(&paths, &config, &translator);
     }
 
     // 创建分析器
     let mut analyzer = CodeAnalyzer::new();
     analyzer.set_language(translator.get_language());
-    analyzer.set_silent(output_config.markdown_output);
+    analyzer.set_silent(silent);
 
     // 执行分析
-    let result = analyzer.analyze_with_config(&path, &config)?;
+    let result = if paths == [Path::new(STDIN_PATH)] {
+        let content = std::io::read_to_string(std::io::stdin())?;
+        analyzer.analyze_source(&stdin_source_path(stdin_lang), &content, &config)?
+    } else {
+        analyzer.analyze_paths_with_config(&paths, &config)?
+    };
+
+    // 指定基线文件时直接对比；自动基线则读取当前分支（或基础分支）的基线并保存本次结果
+    let baseline_diff = if let Some(baseline) = &output_config.baseline {
+        Some(BaselineDiff::compute(&load_result(baseline)?, &result))
+    } else if output_config.baseline_auto && !result.is_empty {
+        compare_with_auto_baseline(&paths[0], &result, output_config.base_branch.as_deref())
+    } else {
+        None
+    };
+
+    let denied = denied_issues(&result, &output_config.deny);
+    let smell_score = result.code_quality_score * 100.0;
+    let over_threshold = output_config
+        .fail_under
+        .filter(|&threshold| !result.is_empty && smell_score > threshold);
+    let metric_violations = metric_violations(&result, &output_config.max_metric);
+    let cache_stats = result.cache_stats.filter(|_| !output_config.quiet);
 
     // 生成报告
-    generate_report(result, output_config, translator)?;
+    generate_report(result, baseline_diff, output_config, translator.clone())?;
+
+    if let Some(stats) = cache_stats {
+        print_cache_stats(&stats, &translator);
+    }
+    if let Some(threshold) = over_threshold {
+        eprintln!(
+            "❌ {}",
+            translator.translate_with_args(
+                "cmd.fail_under",
+                vec![format!("{:.2}", smell_score), format!("{:.2}", threshold)],
+            )
+        );
+    }
+    for (name, score, threshold) in &metric_violations {
+        eprintln!(
+            "❌ {}",
+            translator.translate_with_args(
+                "cmd.max_metric",
+                vec![
+                    name.clone(),
+                    format!("{:.2}", score),
+                    format!("{:.2}", threshold)
+                ],
+            )
+        );
+    }
+    if !denied.is_empty() {
+        print_denied_issues(&denied, &translator);
+    }
+    if over_threshold.is_some() || !metric_violations.is_empty() || !denied.is_empty() {
+        process::exit(1);
+    }
 
     Ok(())
 }
 
-/// 打印分析开始信息
+/// 打印解析缓存的命中统计（输出到标准错误）
 ///
 /// # Arguments
-/// * `path` - 分析路径
+/// * `stats` - 命中统计
+/// * `translator` - 翻译器
+fn print_cache_stats(stats: &CacheStats, translator: &Translator) {
+    eprintln!(
+        "🗃️  {}",
+        translator.translate_with_args(
+            "cache.stats",
+            vec![
+                stats.hits.to_string(),
+                stats.misses.to_string(),
+                format!("{:.0}", stats.hit_rate() * 100.0),
+                format!("{:.3}", stats.time_saved.as_secs_f64()),
+            ],
+        )
+    );
+}
+
+/// 构造标准输入源码在报告中的路径，扩展名决定使用的解析器
+///
+/// # Arguments
+/// * `language` - 源码语言
+///
+/// # Returns
+/// * `PathBuf` - 形如 `<stdin>.rs` 的路径
+fn stdin_source_path(language: Option<LanguageType>) -> PathBuf {
+    match language.and_then(|language| language.primary_extension()) {
+        Some(ext) => PathBuf::from(format!("<stdin>.{}", ext)),
+        None => PathBuf::from("<stdin>"),
+    }
+}
+
+/// 重复解析目标并打印解析吞吐量
+///
+/// # Arguments
+/// * `paths` - 分析路径
 /// * `config` - 分析配置
+/// * `iterations` - 解析轮数
 /// * `translator` - 翻译器
-fn print_analysis_start(path: &PathBuf, config: &AnalysisConfig, translator: &Translator) {
+///
+/// # Returns
+/// * `Result<()>` - 执行结果
+fn execute_bench_parse(
+    paths: &[PathBuf],
+    config: &AnalysisConfig,
+    iterations: usize,
+    translator: &Translator,
+) -> Result<()> {
+    let mut analyzer = CodeAnalyzer::new();
+    analyzer.set_language(translator.get_language());
+    analyzer.set_silent(true);
+
+    let bench = analyzer.bench_parse(paths, config, iterations)?;
+
+    println!(
+        "⏱️  {}",
+        translator.translate_with_args(
+            "bench.summary",
+            vec![
+                bench.files.to_string(),
+                bench.lines.to_string(),
+                bench.iterations.to_string(),
+                format!("{:.3}", bench.elapsed.as_secs_f64()),
+            ],
+        )
+    );
     println!(
+        "  {}",
+        translator.translate_with_args(
+            "bench.throughput",
+            vec![
+                format!("{:.0}", bench.lines_per_second()),
+                format!("{:.1}", bench.files_per_second()),
+            ],
+        )
+    );
+
+    Ok(())
+}
+
+/// 找出屎气指数高于各自阈值的指标
+///
+/// 本次分析中没有出现的指标（未选择或空项目）不参与检查
+///
+/// # Arguments
+/// * `result` - 分析结果
+/// * `thresholds` - (指标名称, 阈值) 列表
+///
+/// # Returns
+/// * `Vec<(String, f64, f64)>` - (指标名称, 屎气指数, 阈值) 列表
+fn metric_violations(
+    result: &AnalysisResult,
+    thresholds: &[(String, f64)],
+) -> Vec<(String, f64, f64)> {
+    thresholds
+        .iter()
+        .filter_map(|(name, threshold)| {
+            let score = result.metrics.get(name)?.score * 100.0;
+            (score > *threshold).then(|| (name.clone(), score, *threshold))
+        })
+        .collect()
+}
+
+/// 收集被禁止类型的问题
+///
+/// # Arguments
+/// * `result` - 分析结果
+/// * `deny` - 被禁止的问题类型
+///
+/// # Returns
+/// * `Vec<(String, Issue)>` - (文件路径, 问题) 列表
+fn denied_issues(result: &AnalysisResult, deny: &[IssueKind]) -> Vec<(String, Issue)> {
+    result
+        .files_analyzed
+        .iter()
+        .flat_map(|file| {
+            file.issues
+                .iter()
+                .filter(|issue| deny.contains(&issue.kind))
+                .map(|issue| (file.file_path.clone(), issue.clone()))
+        })
+        .collect()
+}
+
+/// 打印被禁止的问题（输出到 stderr，不影响机器可读格式的报告）
+///
+/// # Arguments
+/// * `denied` - (文件路径, 问题) 列表
+/// * `translator` - 翻译器
+fn print_denied_issues(denied: &[(String, Issue)], translator: &Translator) {
+    eprintln!(
+        "❌ {}",
+        translator.translate_with_args("cmd.denied_issues", vec![denied.len().to_string()])
+    );
+
+    for (path, issue) in denied {
+        match issue.line {
+            Some(line) => eprintln!("  {}:{} [{}] {}", path, line, issue.kind.key(), issue),
+            None => eprintln!("  {} [{}] {}", path, issue.kind.key(), issue),
+        }
+    }
+}
+
+/// 合并多个分析结果并生成报告
+///
+/// # Arguments
+/// * `inputs` - 结果文件列表
+/// * `output_config` - 输出配置
+/// * `translator` - 翻译器
+///
+/// # Returns
+/// * `Result<()>` - 执行结果
+fn execute_merge(
+    inputs: &[PathBuf],
+    output_config: OutputConfig,
+    translator: Translator,
+) -> Result<()> {
+    let results = inputs
+        .iter()
+        .map(|input| load_result(input))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    generate_report(merge_results(results), None, output_config, translator)
+}
+
+/// 分析两个目录并对比结果
+///
+/// 两侧的文件路径都改写为相对路径后再匹配，只存在于一侧的文件标注为新增或删除。
+///
+/// # Arguments
+/// * `before` - 作为基准的目录
+/// * `after` - 要对比的目录
+/// * `config` - 分析配置
+/// * `output_config` - 输出配置
+/// * `translator` - 翻译器
+///
+/// # Returns
+/// * `Result<()>` - 执行结果
+fn execute_compare(
+    before: &Path,
+    after: &Path,
+    config: AnalysisConfig,
+    output_config: OutputConfig,
+    translator: Translator,
+) -> Result<()> {
+    let (current, diff) = compare_directories(before, after, &config, translator.get_language())?;
+
+    generate_report(current, Some(diff), output_config, translator)
+}
+
+/// 分析两个目录，返回后者的结果及其相对前者的差异
+///
+/// # Arguments
+/// * `before` - 作为基准的目录
+/// * `after` - 要对比的目录
+/// * `config` - 分析配置
+/// * `language` - 输出语言
+///
+/// # Returns
+/// * `Result<(AnalysisResult, BaselineDiff)>` - (`after` 的分析结果, 对比结果)
+fn compare_directories(
+    before: &Path,
+    after: &Path,
+    config: &AnalysisConfig,
+    language: Language,
+) -> Result<(AnalysisResult, BaselineDiff)> {
+    let mut analyzer = CodeAnalyzer::new();
+    analyzer.set_language(language);
+    analyzer.set_silent(true);
+
+    let analyze = |root: &Path| -> Result<AnalysisResult> {
+        let mut result = analyzer.analyze_with_config(root, config)?;
+        result.relativize_paths(root);
+        Ok(result)
+    };
+
+    let baseline = analyze(before)?;
+    let current = analyze(after)?;
+    let diff = BaselineDiff::compute(&baseline, &current);
+
+    Ok((current, diff))
+}
+
+/// 与当前分支的自动基线进行对比，并用本次结果更新基线
+///
+/// # Arguments
+/// * `path` - 分析路径
+/// * `result` - 本次分析结果
+/// * `base_branch` - 指定的基础分支（未指定时使用仓库的默认分支）
+///
+/// # Returns
+/// * `Option<BaselineDiff>` - 对比结果（没有可用基线时为 None）
+fn compare_with_auto_baseline(
+    path: &Path,
+    result: &AnalysisResult,
+    base_branch: Option<&str>,
+) -> Option<BaselineDiff> {
+    let Some(mut location) = AutoBaseline::locate(path) else {
+        log::warn!("当前路径不在 git 仓库中，已跳过自动基线");
+        return None;
+    };
+    if let Some(base_branch) = base_branch {
+        location = location.with_base_branch(base_branch);
+    }
+
+    let diff = match location.load() {
+        Ok(Some(baseline)) => Some(BaselineDiff::compute(&baseline, result)),
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!("读取基线失败: {}", e);
+            None
+        }
+    };
+
+    if let Err(e) = location.save(result) {
+        log::warn!("保存基线失败: {}", e);
+    }
+
+    diff
+}
+
+/// 打印分析开始信息
+///
+/// # Arguments
+/// * `paths` - 分析路径
+/// * `config` - 分析配置
+/// * `translator` - 翻译器
+fn print_analysis_start(paths: &[PathBuf], config: &AnalysisConfig, translator: &Translator) {
+    let paths = paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    eprintln!(
         "🔍 {}",
-        translator.translate_with_args("cmd.start_analyzing", vec![path.display().to_string()])
+        translator.translate_with_args("cmd.start_analyzing", vec![paths])
     );
 
     // 显示排除模式
@@ -397,39 +1278,49 @@ fn print_analysis_start(path: &PathBuf, config: &AnalysisConfig, translator: &Tr
 /// * `patterns` - 排除模式列表
 /// * `translator` - 翻译器
 fn print_exclude_patterns(patterns: &[String], translator: &Translator) {
-    println!("📂 {}", translator.translate("cmd.exclude_patterns"));
+    eprintln!("📂 {}", translator.translate("cmd.exclude_patterns"));
 
     // 显示前5个模式
     let display_count = patterns.len().min(5);
     for pattern in &patterns[..display_count] {
-        println!("  - {}", pattern);
+        eprintln!("  - {}", pattern);
     }
 
     // 如果有更多模式，显示数量
     if patterns.len() > 5 {
-        println!("  ... 还有 {} 个排除模式", patterns.len() - 5);
+        eprintln!("  ... 还有 {} 个排除模式", patterns.len() - 5);
     }
 
-    println!();
+    eprintln!();
 }
 
 /// 生成分析报告
 ///
 /// # Arguments
 /// * `result` - 分析结果
+/// * `baseline_diff` - 基线对比结果
 /// * `output_config` - 输出配置
 /// * `translator` - 翻译器
 ///
 /// # Returns
 /// * `Result<()>` - 执行结果
 fn generate_report(
-    result: analyzer::AnalysisResult,
+    result: AnalysisResult,
+    baseline_diff: Option<BaselineDiff>,
     output_config: OutputConfig,
     translator: Translator,
 ) -> Result<()> {
     // 创建报告生成器
     let mut report = Report::new(result);
     report.set_translator(translator);
+    report.set_baseline_diff(baseline_diff);
+
+    // 加载参考分布
+    let percentile = match (output_config.percentile, &output_config.reference) {
+        (false, _) => None,
+        (true, Some(path)) => Some(ReferenceDistribution::load(path)?),
+        (true, None) => Some(ReferenceDistribution::default()),
+    };
 
     // 转换配置
     let options = ReportOptions {
@@ -437,93 +1328,242 @@ fn generate_report(
         top_files: output_config.top_files,
         max_issues: output_config.max_issues,
         summary_only: output_config.summary_only,
+        quiet: output_config.quiet,
         markdown_output: output_config.markdown_output,
+        json_output: output_config.json_output,
+        html_output: output_config.html_output,
+        csv_output: output_config.csv_output,
+        json_compact: output_config.json_compact,
+        polarity: output_config.polarity,
+        min_file_score: output_config.min_file_score,
+        sort_by: output_config.sort_by,
+        reverse: output_config.reverse,
+        min_severity: output_config.min_severity,
+        percentile,
+        precision: output_config.precision,
+        level_cuts: output_config.level_cuts,
     };
 
-    // 生成报告
-    report.generate_console_report(&options);
+    // 生成报告（指定 -o 时写入文件）
+    match &output_config.output {
+        Some(path) => {
+            let content = report
+                .render_file_report(&options)
+                .with_context(|| format!("无法生成写入 {} 的报告", path.display()))?;
+            std::fs::write(path, content)
+                .with_context(|| format!("无法写入报告文件 {}", path.display()))?;
+        }
+        None => report.generate_console_report(&options),
+    }
 
     Ok(())
 }
 
-/// 获取默认排除模式列表
-///
-/// # Returns
-/// * `Vec<String>` - 默认排除模式列表
-fn get_default_excludes() -> Vec<String> {
-    vec![
-        // 前端项目通用排除
-        "**/node_modules/**".to_string(),
-        "**/dist/**".to_string(),
-        "**/build/**".to_string(),
-        "**/.next/**".to_string(),
-        "**/public/assets/**".to_string(),
-        "**/out/**".to_string(),
-        "**/.cache/**".to_string(),
-        "**/.nuxt/**".to_string(),
-        "**/.output/**".to_string(),
-        "**/coverage/**".to_string(),
-        "**/.vscode/**".to_string(),
-        "**/.idea/**".to_string(),
-        "**/.git/**".to_string(),
-        "**/bower_components/**".to_string(),
-        "**/*.min.js".to_string(),
-        "**/*.bundle.js".to_string(),
-        "**/*.chunk.js".to_string(),
-        "**/static/js/*.js".to_string(),
-        "**/static/css/*.css".to_string(),
-        // 后端项目通用排除
-        "**/vendor/**".to_string(),
-        "**/bin/**".to_string(),
-        "**/obj/**".to_string(),
-        "**/target/**".to_string(),
-        "**/__pycache__/**".to_string(),
-        "**/*.pyc".to_string(),
-        "**/venv/**".to_string(),
-        "**/.env/**".to_string(),
-        "**/migrations/**".to_string(),
-        "**/generated/**".to_string(),
-        "**/logs/**".to_string(),
-        "**/tmp/**".to_string(),
-        "**/temp/**".to_string(),
-        "**/test-results/**".to_string(),
-        "**/testdata/**".to_string(),
-        // Rust特定排除
-        "**/target/**".to_string(),
-        "**/Cargo.lock".to_string(),
-        // Tauri项目排除
-        "**/src-tauri/target/**".to_string(),
-        // PHP特定排除
-        "**/vendor/**".to_string(),
-        "**/composer.lock".to_string(),
-        "**/.phpunit.cache/**".to_string(),
-        "**/storage/logs/**".to_string(),
-        "**/storage/cache/**".to_string(),
-        "**/bootstrap/cache/**".to_string(),
-        // 前端构建文件
-        "**/*.min.css".to_string(),
-        "**/*.min.js".to_string(),
-        "**/*.bundle.css".to_string(),
-        "**/*.chunk.css".to_string(),
-        // 测试文件排除
-        "**/*_test.go".to_string(),
-        "**/test_*.py".to_string(),
-        "**/*_test.py".to_string(),
-        "**/tests/**/*.py".to_string(),
-        "**/*.spec.js".to_string(),
-        "**/*.test.js".to_string(),
-        "**/__tests__/**".to_string(),
-        "**/*.spec.ts".to_string(),
-        "**/*.test.ts".to_string(),
-        "**/src/test/**/*.java".to_string(),
-        "**/*Test.java".to_string(),
-        "**/*_test.c".to_string(),
-        "**/*_test.cpp".to_string(),
-        "**/tests/**".to_string(),
-        "**/*_test.rs".to_string(),
-        "**/test_*.rs".to_string(),
-        "**/*Test.php".to_string(),
-        "**/*_test.php".to_string(),
-        "**/tests/**/*.php".to_string(),
-    ]
+// 与库的单元测试共用临时目录辅助（库的 cfg(test) 模块对可执行文件不可见）
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "common/testing.rs"]
+mod testing;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    /// 解析命令行参数
+    fn parse_args(args: &[&str]) -> AnalyzeArgs {
+        let cli =
+            Cli::try_parse_from(std::iter::once("fuck-shit-code").chain(args.iter().copied()))
+                .unwrap();
+        cli.analyze_args().clone()
+    }
+
+    /// 解析项目配置文件内容
+    fn project_config(content: &str) -> ProjectConfig {
+        toml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn print_config_lists_config_file_and_cli_excludes_in_precedence_order() {
+        let project = project_config("exclude_patterns = [\"**/from_config/**\"]\n");
+        let args = parse_args(&["--exclude", "**/from_cli/**", "."]);
+        let config = build_analysis_config(&args, &project).unwrap();
+        let printed = effective_config(&config).unwrap();
+
+        let from_config = printed.find("**/from_config/**").unwrap();
+        let from_cli = printed.find("**/from_cli/**").unwrap();
+        assert!(from_config < from_cli);
+        assert!(printed.contains("**/node_modules/**"));
+    }
+
+    #[test]
+    fn print_config_output_is_deterministic() {
+        let project = project_config(
+            "[weights]\nnaming = 0.5\nerror = 0.4\ncomplexity = 0.3\ncomment = 0.2\n\n\
+             [thresholds.python]\nfunction_lines_high = 50\n\n\
+             [thresholds.java]\nfunction_lines_high = 150\n",
+        );
+        let args = parse_args(&["."]);
+        let printed = effective_config(&build_analysis_config(&args, &project).unwrap()).unwrap();
+
+        for _ in 0..5 {
+            let again = effective_config(&build_analysis_config(&args, &project).unwrap()).unwrap();
+            assert_eq!(printed, again);
+        }
+
+        let positions: Vec<usize> = ["\"comment\"", "\"complexity\"", "\"error\"", "\"naming\""]
+            .iter()
+            .map(|key| printed.find(key).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(printed.find("\"java\"").unwrap() < printed.find("\"python\"").unwrap());
+    }
+
+    #[test]
+    fn compare_reports_regressed_added_and_removed_files() {
+        let clean = "def total(items):\n    \"\"\"Sum the items.\"\"\"\n    return sum(items)\n";
+        let messy = "def total(a, b, c, d, e, f, g, h):\n    x = 0\n    if a:\n        if b:\n            if c:\n                if d:\n                    if e:\n                        x = f\n    return x\n";
+        let before = TempDir::new("compare-before");
+        before.write("same.py", clean);
+        before.write("worse.py", clean);
+        before.write("removed.py", clean);
+        let after = TempDir::new("compare-after");
+        after.write("same.py", clean);
+        after.write("worse.py", messy);
+        after.write("added.py", clean);
+
+        let (current, diff) = compare_directories(
+            before.path(),
+            after.path(),
+            &AnalysisConfig::default(),
+            Language::ZhCN,
+        )
+        .unwrap();
+
+        assert!(current
+            .files_analyzed
+            .iter()
+            .any(|f| f.file_path == "worse.py"));
+        assert!(diff.files["worse.py"].delta().unwrap() > 0.0);
+        assert!(diff.overall.delta() > 0.0);
+        assert!(!diff.files.contains_key("same.py"));
+        assert_eq!(diff.files["added.py"].baseline, None);
+        assert_eq!(diff.files["removed.py"].current, None);
+        assert!(diff.new_issues.iter().any(|(path, _)| path == "worse.py"));
+        assert!(diff.new_issues.iter().all(|(path, _)| path != "same.py"));
+    }
+
+    #[test]
+    fn deny_accepts_comma_separated_issue_kinds() {
+        let args = parse_args(&["--deny", "high_complexity,deep_nesting", "."]);
+        assert_eq!(
+            args.deny,
+            [IssueKind::HighComplexity, IssueKind::DeepNesting]
+        );
+
+        let unknown =
+            Cli::try_parse_from(["fuck-shit-code", "--deny", "not_a_kind", "."]).map(|_| ());
+        assert!(unknown.is_err());
+    }
+
+    #[test]
+    fn denied_issues_lists_only_denied_kinds() {
+        let body: String = (0..20)
+            .map(|i| format!("    if value == {}:\n        return {}\n", i, i))
+            .collect();
+        let source = format!("def classify(value):\n{}    return -1\n", body);
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let result = analyzer
+            .analyze_string(&source, LanguageType::Python, &AnalysisConfig::default())
+            .unwrap();
+
+        let denied = denied_issues(&result, &[IssueKind::HighComplexity]);
+        assert!(!denied.is_empty());
+        assert!(denied
+            .iter()
+            .all(|(_, issue)| issue.kind == IssueKind::HighComplexity && issue.line == Some(1)));
+
+        assert!(denied_issues(&result, &[IssueKind::StaleTodo]).is_empty());
+        assert!(denied_issues(&result, &[]).is_empty());
+    }
+
+    #[test]
+    fn no_default_excludes_keeps_only_explicit_patterns() {
+        let configured = vec!["**/from_config/**".to_string()];
+
+        let defaults =
+            get_exclude_patterns(&parse_args(&["--exclude", "**/cli/**", "."]), &configured);
+        assert!(defaults.iter().any(|p| p == "**/node_modules/**"));
+
+        let explicit = get_exclude_patterns(
+            &parse_args(&["--no-default-excludes", "--exclude", "**/cli/**", "."]),
+            &configured,
+        );
+        assert_eq!(explicit, ["**/from_config/**", "**/cli/**"]);
+    }
+
+    #[test]
+    fn include_flags_are_repeatable_and_defaults_still_exclude() {
+        let args = parse_args(&["-I", "**/*.rs", "--include", "**/*.py", "."]);
+        let config = build_analysis_config(&args, &ProjectConfig::default()).unwrap();
+
+        assert_eq!(config.include_patterns, ["**/*.rs", "**/*.py"]);
+        assert!(config
+            .exclude_patterns
+            .iter()
+            .any(|pattern| pattern == "**/target/**"));
+    }
+
+    #[test]
+    fn max_metric_accepts_keys_and_names_and_rejects_bad_values() {
+        let args = parse_args(&[
+            "--max-metric",
+            "complexity=60",
+            "--max-metric",
+            "代码重复度 = 40",
+            ".",
+        ]);
+        assert_eq!(
+            args.max_metric,
+            [
+                ("循环复杂度".to_string(), 60.0),
+                ("代码重复度".to_string(), 40.0)
+            ]
+        );
+
+        assert!(parse_metric_threshold("complexity").is_err());
+        assert!(parse_metric_threshold("complexity=abc").is_err());
+        assert!(parse_metric_threshold("complexity=150").is_err());
+        assert!(parse_metric_threshold("no_such_metric=10").is_err());
+    }
+
+    #[test]
+    fn metric_violations_only_lists_metrics_over_their_threshold() {
+        let root = TempDir::new("max-metric");
+        root.write("a.py", "def add(a, b):\n    return a + b\n");
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let mut result = analyzer
+            .analyze_with_config(root.path(), &AnalysisConfig::default())
+            .unwrap();
+
+        result.metrics.get_mut("循环复杂度").unwrap().score = 0.7;
+        result.metrics.get_mut("代码重复度").unwrap().score = 0.3;
+        let thresholds = [
+            ("循环复杂度".to_string(), 60.0),
+            ("代码重复度".to_string(), 40.0),
+            ("未分析的指标".to_string(), 0.0),
+        ];
+
+        let violations = metric_violations(&result, &thresholds);
+        assert_eq!(violations.len(), 1);
+        let (name, score, threshold) = &violations[0];
+        assert_eq!(name, "循环复杂度");
+        assert!((score - 70.0).abs() < 1e-9);
+        assert_eq!(*threshold, 60.0);
+
+        // 恰好等于阈值不算超出
+        assert!(metric_violations(&result, &[("循环复杂度".to_string(), 70.0)]).is_empty());
+    }
 }
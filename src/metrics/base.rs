@@ -2,10 +2,13 @@
 //!
 //! 定义度量指标的基础接口和数据结构
 
+use super::Issue;
 use crate::parser::ParseResult;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// 度量结果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricResult {
     /// 得分（0-1，越高越差）
     pub score: f64,
@@ -17,7 +20,7 @@ pub struct MetricResult {
     pub description: String,
 
     /// 发现的问题
-    pub issues: Vec<String>,
+    pub issues: Vec<Issue>,
 }
 
 impl MetricResult {
@@ -31,9 +34,9 @@ impl MetricResult {
     ///
     /// # Returns
     /// * `Self` - 度量结果实例
-    pub fn new(score: f64, weight: f64, description: String, issues: Vec<String>) -> Self {
+    pub fn new(score: f64, weight: f64, description: String, issues: Vec<Issue>) -> Self {
         MetricResult {
-            score: score.min(1.0).max(0.0), // 确保在0-1范围内
+            score: sanitize_score(score),
             weight,
             description,
             issues,
@@ -49,6 +52,21 @@ impl MetricResult {
     }
 }
 
+/// 将得分限制在 0-1 范围内，NaN 视为 0
+///
+/// # Arguments
+/// * `score` - 原始得分
+///
+/// # Returns
+/// * `f64` - 有效得分
+pub fn sanitize_score(score: f64) -> f64 {
+    if score.is_nan() {
+        0.0
+    } else {
+        score.clamp(0.0, 1.0)
+    }
+}
+
 /// 度量指标trait
 pub trait Metric {
     /// 获取指标名称
@@ -57,6 +75,16 @@ pub trait Metric {
     /// * `&str` - 名称
     fn name(&self) -> &str;
 
+    /// 获取指标标识
+    ///
+    /// 标识稳定且与语言无关，默认与名称相同
+    ///
+    /// # Returns
+    /// * `&str` - 标识
+    fn id(&self) -> &str {
+        self.name()
+    }
+
     /// 获取指标描述
     ///
     /// # Returns
@@ -78,3 +106,40 @@ pub trait Metric {
     /// * `MetricResult` - 度量结果
     fn analyze(&self, parse_result: &dyn ParseResult) -> MetricResult;
 }
+
+/// 共享度量指标同样可以作为度量指标使用
+impl<M: Metric + ?Sized> Metric for Arc<M> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn id(&self) -> &str {
+        (**self).id()
+    }
+
+    fn description(&self) -> &str {
+        (**self).description()
+    }
+
+    fn weight(&self) -> f64 {
+        (**self).weight()
+    }
+
+    fn analyze(&self, parse_result: &dyn ParseResult) -> MetricResult {
+        (**self).analyze(parse_result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_score_never_returns_nan_or_out_of_range() {
+        assert_eq!(sanitize_score(f64::NAN), 0.0);
+        assert_eq!(sanitize_score(-0.5), 0.0);
+        assert_eq!(sanitize_score(1.5), 1.0);
+        assert_eq!(sanitize_score(f64::INFINITY), 1.0);
+        assert_eq!(sanitize_score(0.42), 0.42);
+    }
+}
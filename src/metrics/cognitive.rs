@@ -0,0 +1,126 @@
+//! # 认知复杂度度量
+//!
+//! 与循环复杂度不同，认知复杂度会对嵌套在其他结构中的分支额外加分，
+//! 更接近阅读代码时的实际负担
+
+use crate::i18n::Translator;
+use crate::metrics::{Issue, IssueKind, Metric, MetricResult};
+use crate::parser::{measure_cognitive_complexity, Function, ParseResult};
+
+/// 函数的认知复杂度超过该值时报告问题（SonarSource 的默认阈值）
+const MAX_COGNITIVE_COMPLEXITY: usize = 15;
+
+/// 平均认知复杂度不超过该值时得分为 0
+const CLEAN_AVG_COGNITIVE: f64 = 2.0;
+
+/// 平均认知复杂度达到该值时得分为满分
+const FULL_SCORE_AVG_COGNITIVE: f64 = 20.0;
+
+/// 认知复杂度度量器
+pub struct CognitiveComplexityMetric {
+    /// 翻译器
+    translator: Translator,
+}
+
+impl CognitiveComplexityMetric {
+    /// 创建新的认知复杂度度量器
+    ///
+    /// # Arguments
+    /// * `translator` - 翻译器
+    ///
+    /// # Returns
+    /// * `Self` - 度量器实例
+    pub fn new(translator: Translator) -> Self {
+        CognitiveComplexityMetric { translator }
+    }
+
+    /// 检查函数的认知复杂度
+    ///
+    /// # Arguments
+    /// * `func` - 函数信息
+    /// * `complexity` - 认知复杂度
+    ///
+    /// # Returns
+    /// * `Option<Issue>` - 发现的问题
+    fn check_function(&self, func: &Function, complexity: usize) -> Option<Issue> {
+        (complexity > MAX_COGNITIVE_COMPLEXITY).then(|| {
+            Issue::new(
+                IssueKind::HighCognitiveComplexity,
+                format!(
+                    "函数 {} 的认知复杂度过高 ({}，上限 {})，层层嵌套的分支难以读懂",
+                    func.name, complexity, MAX_COGNITIVE_COMPLEXITY
+                ),
+            )
+            .with_lines(func.start_line, func.end_line)
+        })
+    }
+
+    /// 计算认知复杂度得分
+    ///
+    /// 平均认知复杂度在 2 以内得 0 分，之后线性增长，到 20 时为满分
+    ///
+    /// # Arguments
+    /// * `avg_complexity` - 平均认知复杂度
+    ///
+    /// # Returns
+    /// * `f64` - 得分（0-1）
+    fn calculate_score(&self, avg_complexity: f64) -> f64 {
+        ((avg_complexity - CLEAN_AVG_COGNITIVE) / (FULL_SCORE_AVG_COGNITIVE - CLEAN_AVG_COGNITIVE))
+            .clamp(0.0, 1.0)
+    }
+}
+
+impl Metric for CognitiveComplexityMetric {
+    /// 获取指标名称
+    fn name(&self) -> &str {
+        "认知复杂度"
+    }
+
+    /// 获取指标标识
+    fn id(&self) -> &str {
+        "cognitive_complexity"
+    }
+
+    /// 获取指标描述
+    fn description(&self) -> &str {
+        "按嵌套层级加权统计分支和布尔运算符序列，嵌套越深的逻辑越难读懂"
+    }
+
+    /// 获取权重
+    fn weight(&self) -> f64 {
+        0.2
+    }
+
+    /// 分析认知复杂度
+    ///
+    /// # Arguments
+    /// * `parse_result` - 解析结果
+    ///
+    /// # Returns
+    /// * `MetricResult` - 度量结果
+    fn analyze(&self, parse_result: &dyn ParseResult) -> MetricResult {
+        let functions = parse_result.get_functions();
+        let lines: Vec<&str> = parse_result.get_source().lines().collect();
+        let language = parse_result.get_language();
+
+        let mut total = 0;
+        let mut issues = Vec::new();
+        for func in functions {
+            let start = func.start_line.saturating_sub(1).min(lines.len());
+            let end = func.end_line.clamp(start, lines.len());
+            let complexity = measure_cognitive_complexity(&lines[start..end], language);
+
+            total += complexity;
+            issues.extend(self.check_function(func, complexity));
+        }
+
+        let avg_complexity = if functions.is_empty() {
+            0.0
+        } else {
+            total as f64 / functions.len() as f64
+        };
+        let score = self.calculate_score(avg_complexity);
+
+        MetricResult::new(score, self.weight(), self.description().to_string(), issues)
+    }
+}
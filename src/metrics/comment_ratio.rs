@@ -1,14 +1,46 @@
+use crate::common::LanguageType;
 use crate::i18n::Translator;
-use crate::metrics::{Metric, MetricResult};
-use crate::parser::ParseResult;
+use crate::metrics::{Issue, IssueKind, Metric, MetricResult};
+use crate::parser::{Function, ParseResult};
+
+/// 复杂度超过该值的函数需要文档注释
+const DOC_REQUIRED_COMPLEXITY: usize = 10;
+
+/// 行数超过该值的函数需要文档注释
+const DOC_REQUIRED_LINES: usize = 50;
+
+/// 每个缺少文档注释的导出函数或复杂函数增加的分数
+const UNDOCUMENTED_FUNCTION_PENALTY: f64 = 0.05;
+
+/// 缺少文档注释的函数最多增加的分数
+const MAX_UNDOCUMENTED_PENALTY: f64 = 0.2;
+
+/// 出现这些关键词的文件头注释视为许可证声明
+const LICENSE_KEYWORDS: &[&str] = &["license", "licence", "copyright", "spdx-", "版权", "许可"];
 
 pub struct CommentRatioMetric {
     translator: Translator,
+    license_header_max_lines: Option<usize>,
 }
 
 impl CommentRatioMetric {
     pub fn new(translator: Translator) -> Self {
-        CommentRatioMetric { translator }
+        CommentRatioMetric {
+            translator,
+            license_header_max_lines: None,
+        }
+    }
+
+    /// 设置是否把文件开头的许可证注释排除在注释率之外
+    ///
+    /// # Arguments
+    /// * `max_lines` - 最多排除的许可证头行数，None 表示不排除
+    ///
+    /// # Returns
+    /// * `Self` - 度量器实例
+    pub fn with_license_header_max_lines(mut self, max_lines: Option<usize>) -> Self {
+        self.license_header_max_lines = max_lines;
+        self
     }
 }
 
@@ -17,6 +49,10 @@ impl Metric for CommentRatioMetric {
         "注释覆盖率"
     }
 
+    fn id(&self) -> &str {
+        "comment_ratio"
+    }
+
     fn description(&self) -> &str {
         "检测代码的注释覆盖率，良好的注释能提高代码可读性和可维护性"
     }
@@ -26,9 +62,21 @@ impl Metric for CommentRatioMetric {
     }
 
     fn analyze(&self, parse_result: &dyn ParseResult) -> MetricResult {
-        // 使用 dyn
-        let total_lines = parse_result.get_total_lines();
-        let comment_lines = parse_result.get_comment_lines();
+        // 许可证头不是对代码的说明，不计入注释率
+        let header_lines = self
+            .license_header_max_lines
+            .map(|max_lines| {
+                license_header_lines(
+                    parse_result.get_source(),
+                    parse_result.get_language(),
+                    max_lines,
+                )
+            })
+            .unwrap_or(0);
+        let total_lines = parse_result.get_total_lines().saturating_sub(header_lines);
+        let comment_lines = parse_result
+            .get_comment_lines()
+            .saturating_sub(header_lines);
 
         let comment_ratio = if total_lines > 0 {
             comment_lines as f64 / total_lines as f64
@@ -39,18 +87,46 @@ impl Metric for CommentRatioMetric {
         let mut issues = Vec::new();
 
         if comment_ratio < 0.05 {
-            issues.push(format!(
-                "代码注释率极低 ({:.2}%)，几乎没有注释",
-                comment_ratio * 100.0
+            issues.push(Issue::new(
+                IssueKind::CommentVeryLow,
+                format!(
+                    "代码注释率极低 ({:.2}%)，几乎没有注释",
+                    comment_ratio * 100.0
+                ),
             ));
         } else if comment_ratio < 0.1 {
-            issues.push(format!(
-                "代码注释率较低 ({:.2}%)，建议增加注释",
-                comment_ratio * 100.0
+            issues.push(Issue::new(
+                IssueKind::CommentLow,
+                format!(
+                    "代码注释率较低 ({:.2}%)，建议增加注释",
+                    comment_ratio * 100.0
+                ),
             ));
         }
 
-        let score = self.calculate_score(comment_ratio);
+        let undocumented = find_undocumented_functions(parse_result);
+        for func in &undocumented {
+            let (kind, args) = if func.exported {
+                (IssueKind::ExportedFuncNoComment, vec![func.name.clone()])
+            } else {
+                (
+                    IssueKind::ComplexFuncNoComment,
+                    vec![
+                        func.name.clone(),
+                        func.complexity.to_string(),
+                        func.line_count().to_string(),
+                    ],
+                )
+            };
+            let message = self
+                .translator
+                .translate_with_args(&format!("issue.{}", kind.key()), args);
+            issues.push(Issue::new(kind, message).with_lines(func.start_line, func.end_line));
+        }
+
+        let penalty = (undocumented.len() as f64 * UNDOCUMENTED_FUNCTION_PENALTY)
+            .min(MAX_UNDOCUMENTED_PENALTY);
+        let score = (self.calculate_score(comment_ratio) + penalty).min(1.0);
 
         MetricResult {
             score,
@@ -77,3 +153,236 @@ impl CommentRatioMetric {
         }
     }
 }
+
+/// 查找没有文档注释的导出函数，以及复杂度或长度超过阈值却没有文档注释的具名函数
+///
+/// # Arguments
+/// * `parse_result` - 解析结果
+///
+/// # Returns
+/// * `Vec<&Function>` - 缺少文档注释的函数
+fn find_undocumented_functions(parse_result: &dyn ParseResult) -> Vec<&Function> {
+    // HTML/CSS 的"函数"是代码块，没有文档注释的概念
+    if matches!(
+        parse_result.get_language(),
+        LanguageType::HTML | LanguageType::CSS
+    ) {
+        return Vec::new();
+    }
+
+    parse_result
+        .get_functions()
+        .iter()
+        .filter(|f| !f.is_anonymous() && !f.documented)
+        .filter(|f| {
+            f.exported
+                || f.complexity > DOC_REQUIRED_COMPLEXITY
+                || f.line_count() > DOC_REQUIRED_LINES
+        })
+        .collect()
+}
+
+/// 统计文件开头许可证注释块的行数
+///
+/// 注释块从文件开头（跳过 shebang 和空行）连续到第一行非注释内容为止，
+/// 只有包含许可证关键词时才视为许可证头。
+///
+/// # Arguments
+/// * `source` - 源代码
+/// * `language` - 语言类型
+/// * `max_lines` - 最多计入的行数
+///
+/// # Returns
+/// * `usize` - 许可证头行数，没有许可证头时为 0
+fn license_header_lines(source: &str, language: LanguageType, max_lines: usize) -> usize {
+    let hash_comments = matches!(language, LanguageType::Python | LanguageType::PHP);
+    let mut block_end: Option<&str> = None;
+    let mut header = String::new();
+    let mut lines = 0;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+
+        if let Some(end) = block_end {
+            if trimmed.contains(end) {
+                block_end = None;
+            }
+        } else if trimmed.is_empty() || trimmed.starts_with("#!") {
+            if lines > 0 {
+                break;
+            }
+            continue;
+        } else if let Some((start, end)) = block_comment_delimiters(trimmed, language) {
+            if !trimmed[start.len()..].contains(end) {
+                block_end = Some(end);
+            }
+        } else if !(trimmed.starts_with("//") || (hash_comments && trimmed.starts_with('#'))) {
+            break;
+        }
+
+        lines += 1;
+        header.push_str(&trimmed.to_lowercase());
+        header.push('\n');
+    }
+
+    if LICENSE_KEYWORDS
+        .iter()
+        .any(|keyword| header.contains(keyword))
+    {
+        lines.min(max_lines)
+    } else {
+        0
+    }
+}
+
+/// 获取以块注释开头的行对应的起止符
+///
+/// # Arguments
+/// * `trimmed` - 去除首尾空白的行
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `Option<(&str, &str)>` - 块注释起止符
+fn block_comment_delimiters(
+    trimmed: &str,
+    language: LanguageType,
+) -> Option<(&'static str, &'static str)> {
+    let delimiters: &[(&str, &str)] = match language {
+        LanguageType::Python => &[("\"\"\"", "\"\"\""), ("'''", "'''")],
+        LanguageType::HTML => &[("<!--", "-->")],
+        _ => &[("/*", "*/")],
+    };
+
+    delimiters
+        .iter()
+        .copied()
+        .find(|(start, _)| trimmed.starts_with(start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Language;
+    use crate::parser::create_parser_for_file;
+    use std::path::Path;
+
+    fn analyze(file_name: &str, source: &str, language: Language) -> MetricResult {
+        let path = Path::new(file_name);
+        let parse_result = create_parser_for_file(path).parse(path, source).unwrap();
+        CommentRatioMetric::new(Translator::new(language)).analyze(&*parse_result)
+    }
+
+    /// 生成复杂度为 `branches + 1` 的 Python 函数
+    fn branchy(name: &str, branches: usize) -> String {
+        let body: String = (0..branches)
+            .map(|i| format!("    if x == {}:\n        return {}\n", i, i))
+            .collect();
+        format!("def {}(x):\n{}    return -1\n", name, body)
+    }
+
+    #[test]
+    fn complex_function_under_a_plain_comment_is_undocumented() {
+        let source = format!("# TODO: fix\n{}", branchy("_is_valid", 10));
+        let result = analyze("check.py", &source, Language::ZhCN);
+
+        let issue = result
+            .issues
+            .iter()
+            .find(|issue| issue.kind == IssueKind::ComplexFuncNoComment)
+            .expect("complex undocumented function should be flagged");
+        assert_eq!(issue.line, Some(2));
+        assert_eq!(
+            issue.message,
+            "函数 _is_valid 较复杂（复杂度 11，22 行）但缺少文档注释"
+        );
+        assert!(!result
+            .issues
+            .iter()
+            .any(|issue| issue.kind == IssueKind::ExportedFuncNoComment));
+
+        let source = format!("# TODO: fix\n{}", branchy("is_valid", 10));
+        let result = analyze("check.py", &source, Language::ZhCN);
+        let kinds: Vec<IssueKind> = result.issues.iter().map(|issue| issue.kind).collect();
+        assert!(kinds.contains(&IssueKind::ExportedFuncNoComment));
+    }
+
+    #[test]
+    fn exported_functions_use_the_translated_message() {
+        let source = "// check the value\npub fn is_valid(x: i32) -> bool {\n    x > 0\n}\n";
+
+        let result = analyze("lib.rs", source, Language::EnUS);
+        let issue = result
+            .issues
+            .iter()
+            .find(|issue| issue.kind == IssueKind::ExportedFuncNoComment)
+            .expect("exported function with a plain comment should be flagged");
+        assert_eq!(
+            issue.message,
+            "Exported function is_valid lacks documentation comment"
+        );
+
+        let documented = "/// Checks the value.\npub fn is_valid(x: i32) -> bool {\n    x > 0\n}\n";
+        let result = analyze("lib.rs", documented, Language::EnUS);
+        assert!(!result
+            .issues
+            .iter()
+            .any(|issue| issue.kind == IssueKind::ExportedFuncNoComment));
+    }
+
+    /// 30 行许可证头加 30 行没有注释的代码
+    fn licensed_source() -> String {
+        let mut header = String::from("/*\n * Copyright (c) 2024 Example Corp.\n *\n * Licensed under the Apache License, Version 2.0\n");
+        for i in 4..29 {
+            header.push_str(&format!(" * license term {}\n", i));
+        }
+        header.push_str(" */\n");
+        let body: String = (0..10)
+            .map(|i| format!("function f{}(x) {{\n  return x + {};\n}}\n", i, i))
+            .collect();
+        format!("{}{}", header, body)
+    }
+
+    #[test]
+    fn thirty_line_license_header_does_not_inflate_the_comment_ratio() {
+        let source = licensed_source();
+        assert_eq!(
+            license_header_lines(&source, LanguageType::JavaScript, 50),
+            30
+        );
+        assert_eq!(
+            license_header_lines(&source, LanguageType::JavaScript, 10),
+            10
+        );
+
+        let path = Path::new("lib.js");
+        let parse_result = create_parser_for_file(path).parse(path, &source).unwrap();
+        let metric = |max_lines| {
+            CommentRatioMetric::new(Translator::new(Language::ZhCN))
+                .with_license_header_max_lines(max_lines)
+                .analyze(&*parse_result)
+        };
+        let has_very_low = |result: &MetricResult| {
+            result
+                .issues
+                .iter()
+                .any(|issue| issue.kind == IssueKind::CommentVeryLow)
+        };
+
+        // 计入许可证头时注释率约 50%，掩盖了代码本身没有注释
+        let counted = metric(None);
+        assert!(!has_very_low(&counted));
+
+        let excluded = metric(Some(50));
+        assert!(has_very_low(&excluded));
+        assert!(excluded.score > counted.score);
+    }
+
+    #[test]
+    fn leading_comment_without_license_keywords_is_not_a_header() {
+        let source = "// Helpers for formatting prices\nfunction f(x) {\n  return x;\n}\n";
+        assert_eq!(
+            license_header_lines(source, LanguageType::JavaScript, 50),
+            0
+        );
+    }
+}
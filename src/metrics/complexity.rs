@@ -2,14 +2,32 @@
 //!
 //! 计算代码的循环复杂度
 
+use crate::common::LanguageType;
 use crate::i18n::Translator;
-use crate::metrics::{Metric, MetricResult};
-use crate::parser::ParseResult;
+use crate::metrics::function_length::resolve_thresholds;
+use crate::metrics::{Issue, IssueKind, LanguageThresholds, Metric, MetricResult};
+use crate::parser::{Function, ParseResult};
+use std::collections::BTreeMap;
+
+/// 判断函数的复杂度超过该值时报告问题
+const PREDICATE_COMPLEXITY_THRESHOLD: usize = 5;
+
+/// 平均复杂度不超过该值时得分为 0（只有一两个分支的函数算简单）
+const CLEAN_AVG_COMPLEXITY: f64 = 1.5;
+
+/// 平均复杂度达到该值时得分为满分
+const FULL_SCORE_AVG_COMPLEXITY: f64 = 15.0;
 
 /// 循环复杂度度量器
 pub struct CyclomaticComplexityMetric {
     /// 翻译器
     translator: Translator,
+
+    /// 按语言自定义的判断函数名前缀（键为小写语言名）
+    predicate_prefixes: BTreeMap<String, Vec<String>>,
+
+    /// 按语言覆盖的阈值（键为小写语言名或 `default`）
+    language_thresholds: BTreeMap<String, LanguageThresholds>,
 }
 
 impl CyclomaticComplexityMetric {
@@ -21,7 +39,44 @@ impl CyclomaticComplexityMetric {
     /// # Returns
     /// * `Self` - 度量器实例
     pub fn new(translator: Translator) -> Self {
-        CyclomaticComplexityMetric { translator }
+        CyclomaticComplexityMetric {
+            translator,
+            predicate_prefixes: BTreeMap::new(),
+            language_thresholds: BTreeMap::new(),
+        }
+    }
+
+    /// 设置按语言覆盖的阈值（使用其中的 `complexity_warn` 和 `complexity_error`）
+    ///
+    /// # Arguments
+    /// * `thresholds` - 语言名（或 `default`）到阈值覆盖的映射，语言名不区分大小写
+    ///
+    /// # Returns
+    /// * `Self` - 度量器实例
+    pub fn with_language_thresholds(
+        mut self,
+        thresholds: BTreeMap<String, LanguageThresholds>,
+    ) -> Self {
+        self.language_thresholds = thresholds
+            .into_iter()
+            .map(|(language, thresholds)| (language.to_lowercase(), thresholds))
+            .collect();
+        self
+    }
+
+    /// 设置按语言自定义的判断函数名前缀
+    ///
+    /// # Arguments
+    /// * `prefixes` - 语言名到前缀列表的映射，语言名不区分大小写，例如 `{"python": ["is_", "has_"]}`
+    ///
+    /// # Returns
+    /// * `Self` - 度量器实例
+    pub fn with_predicate_prefixes(mut self, prefixes: BTreeMap<String, Vec<String>>) -> Self {
+        self.predicate_prefixes = prefixes
+            .into_iter()
+            .map(|(language, prefixes)| (language.to_lowercase(), prefixes))
+            .collect();
+        self
     }
 
     /// 计算平均复杂度
@@ -30,8 +85,8 @@ impl CyclomaticComplexityMetric {
     /// * `parse_result` - 解析结果
     ///
     /// # Returns
-    /// * `(f64, Vec<String>)` - (平均复杂度, 问题列表)
-    fn calculate_average_complexity(&self, parse_result: &dyn ParseResult) -> (f64, Vec<String>) {
+    /// * `(f64, Vec<Issue>)` - (平均复杂度, 问题列表)
+    fn calculate_average_complexity(&self, parse_result: &dyn ParseResult) -> (f64, Vec<Issue>) {
         let functions = parse_result.get_functions();
 
         if functions.is_empty() {
@@ -40,13 +95,23 @@ impl CyclomaticComplexityMetric {
 
         let mut issues = Vec::new();
         let mut total_complexity = 0;
+        let language = parse_result.get_language();
+        let thresholds = resolve_thresholds(&self.language_thresholds, language);
 
         // 分析每个函数
         for func in functions {
             total_complexity += func.complexity;
 
             // 检查复杂度问题
-            if let Some(issue) = self.check_function_complexity(func) {
+            if let Some(issue) = self.check_function_complexity(
+                func,
+                thresholds.complexity_warn,
+                thresholds.complexity_error,
+            ) {
+                issues.push(issue);
+            }
+
+            if let Some(issue) = self.check_predicate_complexity(func, language) {
                 issues.push(issue);
             }
         }
@@ -59,39 +124,92 @@ impl CyclomaticComplexityMetric {
     ///
     /// # Arguments
     /// * `func` - 函数信息
+    /// * `warn` - 超过该值视为较高
+    /// * `error` - 超过该值视为过高
     ///
     /// # Returns
-    /// * `Option<String>` - 问题描述
-    fn check_function_complexity(&self, func: &crate::parser::Function) -> Option<String> {
-        if func.complexity > 15 {
-            Some(format!(
-                "函数 {} 的循环复杂度过高 ({}), 考虑重构",
-                func.name, func.complexity
-            ))
-        } else if func.complexity > 10 {
-            Some(format!(
-                "函数 {} 的循环复杂度较高 ({}), 建议简化",
-                func.name, func.complexity
-            ))
+    /// * `Option<Issue>` - 发现的问题
+    fn check_function_complexity(
+        &self,
+        func: &crate::parser::Function,
+        warn: usize,
+        error: usize,
+    ) -> Option<Issue> {
+        if func.complexity > error {
+            Some(
+                Issue::new(
+                    IssueKind::HighComplexity,
+                    format!(
+                        "函数 {} 的循环复杂度过高 ({}), 考虑重构",
+                        func.name, func.complexity
+                    ),
+                )
+                .with_lines(func.start_line, func.end_line),
+            )
+        } else if func.complexity > warn {
+            Some(
+                Issue::new(
+                    IssueKind::MediumComplexity,
+                    format!(
+                        "函数 {} 的循环复杂度较高 ({}), 建议简化",
+                        func.name, func.complexity
+                    ),
+                )
+                .with_lines(func.start_line, func.end_line),
+            )
         } else {
             None
         }
     }
 
+    /// 检查判断函数（如 `is_`、`has_` 开头）的复杂度
+    ///
+    /// # Arguments
+    /// * `func` - 函数信息
+    /// * `language` - 语言类型
+    ///
+    /// # Returns
+    /// * `Option<Issue>` - 发现的问题
+    fn check_predicate_complexity(&self, func: &Function, language: LanguageType) -> Option<Issue> {
+        if func.complexity <= PREDICATE_COMPLEXITY_THRESHOLD {
+            return None;
+        }
+
+        let is_predicate = match self
+            .predicate_prefixes
+            .get(&language.display_name().to_lowercase())
+        {
+            Some(prefixes) => prefixes.iter().any(|p| has_predicate_prefix(&func.name, p)),
+            None => default_predicate_prefixes(language)
+                .iter()
+                .any(|p| has_predicate_prefix(&func.name, p)),
+        };
+
+        is_predicate.then(|| {
+            Issue::new(
+                IssueKind::ComplexPredicate,
+                format!(
+                    "判断函数 {} 的循环复杂度为 {}，建议拆分或简化判断条件",
+                    func.name, func.complexity
+                ),
+            )
+            .with_lines(func.start_line, func.end_line)
+        })
+    }
+
     /// 计算复杂度得分
     ///
+    /// 平均复杂度在 1.5 以内得 0 分，之后线性增长，到 15 时为满分
+    ///
     /// # Arguments
     /// * `avg_complexity` - 平均复杂度
     ///
     /// # Returns
     /// * `f64` - 得分（0-1）
     fn calculate_score(&self, avg_complexity: f64) -> f64 {
-        // 基础分0.4，每点复杂度增加0.1分
-        let base_score = 0.4;
-        let increase_per_level = 0.1;
-
-        let score = base_score + (avg_complexity * increase_per_level);
-        score.min(1.0)
+        ((avg_complexity - CLEAN_AVG_COMPLEXITY)
+            / (FULL_SCORE_AVG_COMPLEXITY - CLEAN_AVG_COMPLEXITY))
+            .clamp(0.0, 1.0)
     }
 }
 
@@ -101,6 +219,11 @@ impl Metric for CyclomaticComplexityMetric {
         "循环复杂度"
     }
 
+    /// 获取指标标识
+    fn id(&self) -> &str {
+        "cyclomatic_complexity"
+    }
+
     /// 获取指标描述
     fn description(&self) -> &str {
         "测量函数的控制流复杂度，复杂度越高，代码越难理解和测试"
@@ -125,3 +248,166 @@ impl Metric for CyclomaticComplexityMetric {
         MetricResult::new(score, self.weight(), self.description().to_string(), issues)
     }
 }
+
+/// 获取语言默认的判断函数名前缀
+///
+/// # Arguments
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `&[&str]` - 前缀列表
+fn default_predicate_prefixes(language: LanguageType) -> &'static [&'static str] {
+    match language {
+        LanguageType::Rust | LanguageType::Python | LanguageType::C | LanguageType::CPlusPlus => {
+            &["is_", "has_", "should_", "can_"]
+        }
+        LanguageType::CSharp => &["Is", "Has", "Should", "Can"],
+        LanguageType::Go | LanguageType::Zig => {
+            &["is", "has", "should", "can", "Is", "Has", "Should", "Can"]
+        }
+        _ => &[
+            "is", "has", "should", "can", "is_", "has_", "should_", "can_",
+        ],
+    }
+}
+
+/// 判断函数名是否以判断前缀开头
+///
+/// 以字母结尾的前缀（驼峰风格）要求后面紧跟大写字母，避免把 `island` 识别为 `is` 开头
+///
+/// # Arguments
+/// * `name` - 函数名
+/// * `prefix` - 前缀
+///
+/// # Returns
+/// * `bool` - 是否匹配
+fn has_predicate_prefix(name: &str, prefix: &str) -> bool {
+    let Some(rest) = name.strip_prefix(prefix) else {
+        return false;
+    };
+
+    if prefix.ends_with(|c: char| c.is_alphanumeric()) {
+        rest.starts_with(|c: char| c.is_uppercase())
+    } else {
+        !rest.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Language;
+    use crate::parser::BaseParseResult;
+
+    fn metric() -> CyclomaticComplexityMetric {
+        CyclomaticComplexityMetric::new(Translator::new(Language::ZhCN))
+    }
+
+    /// 构造复杂度分别为 `complexities` 的函数
+    fn functions(complexities: &[usize]) -> BaseParseResult {
+        let functions = complexities
+            .iter()
+            .enumerate()
+            .map(|(i, &complexity)| {
+                Function::new(
+                    format!("handler_{}", i),
+                    i * 10 + 1,
+                    i * 10 + 9,
+                    complexity,
+                    1,
+                )
+            })
+            .collect();
+
+        BaseParseResult {
+            functions,
+            comment_lines: 0,
+            total_lines: complexities.len() * 10,
+            source: String::new(),
+            language: LanguageType::Rust,
+        }
+    }
+
+    #[test]
+    fn score_boundaries_follow_the_documented_range() {
+        let metric = metric();
+        assert_eq!(metric.calculate_score(1.0), 0.0);
+        assert_eq!(metric.calculate_score(CLEAN_AVG_COMPLEXITY), 0.0);
+        assert_eq!(metric.calculate_score(FULL_SCORE_AVG_COMPLEXITY), 1.0);
+        assert_eq!(metric.calculate_score(40.0), 1.0);
+
+        let midpoint = (CLEAN_AVG_COMPLEXITY + FULL_SCORE_AVG_COMPLEXITY) / 2.0;
+        assert!((metric.calculate_score(midpoint) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn flat_functions_score_zero() {
+        let result = metric().analyze(&functions(&[1, 1, 2, 1, 1, 1]));
+        assert_eq!(result.score, 0.0);
+        assert!(result.issues.is_empty());
+    }
+
+    #[test]
+    fn deeply_branchy_functions_score_full() {
+        let result = metric().analyze(&functions(&[16, 22, 30]));
+        assert_eq!(result.score, 1.0);
+        assert_eq!(result.issues.len(), 3);
+    }
+
+    /// 解析包含复杂度为 `branches + 1` 的 Python 函数 `name` 的源码
+    fn python_function(name: &str, branches: usize) -> Box<dyn ParseResult> {
+        let body: String = (0..branches)
+            .map(|i| format!("    if value == {}:\n        return False\n", i))
+            .collect();
+        let source = format!("def {}(value):\n{}    return True\n", name, body);
+        let path = std::path::Path::new("check.py");
+        crate::parser::create_parser_for_file(path)
+            .parse(path, &source)
+            .unwrap()
+    }
+
+    fn has_predicate_issue(result: &MetricResult) -> bool {
+        result
+            .issues
+            .iter()
+            .any(|issue| issue.kind == IssueKind::ComplexPredicate)
+    }
+
+    #[test]
+    fn complex_is_valid_fires_the_predicate_issue() {
+        let parse_result = python_function("is_valid", 11);
+        assert_eq!(parse_result.get_functions()[0].complexity, 12);
+
+        let result = metric().analyze(&*parse_result);
+        let issue = result
+            .issues
+            .iter()
+            .find(|issue| issue.kind == IssueKind::ComplexPredicate)
+            .expect("complex predicate should be reported");
+        assert!(issue.message.contains("is_valid"));
+        assert_eq!(issue.line, Some(1));
+
+        // 非判断函数名、或复杂度不超过阈值时不报告
+        assert!(!has_predicate_issue(
+            &metric().analyze(&*python_function("validate", 11))
+        ));
+        assert!(!has_predicate_issue(
+            &metric().analyze(&*python_function("is_valid", 4))
+        ));
+    }
+
+    #[test]
+    fn predicate_prefixes_are_configurable_per_language() {
+        let metric = metric().with_predicate_prefixes(BTreeMap::from([(
+            "Python".to_string(),
+            vec!["check_".to_string()],
+        )]));
+
+        assert!(has_predicate_issue(
+            &metric.analyze(&*python_function("check_input", 11))
+        ));
+        assert!(!has_predicate_issue(
+            &metric.analyze(&*python_function("is_valid", 11))
+        ));
+    }
+}
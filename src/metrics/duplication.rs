@@ -1,21 +1,65 @@
+use crate::common::StableHasher;
 use crate::i18n::Translator;
-use crate::metrics::{Metric, MetricResult};
-use crate::parser::{Function, ParseResult};
-use std::collections::HashMap;
+use crate::metrics::{Issue, IssueKind, Metric, MetricResult};
+use crate::parser::{strip_source, Function, ParseResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+/// 默认参与重复度分析的最小函数行数
+pub const DEFAULT_DUPLICATION_MIN_LINES: usize = 8;
+
+/// 函数体的 token：标识符、数字或单个符号
+static TOKEN_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z_$][\w$]*|\d[\w.]*|\S").unwrap());
+
+/// 比较函数体时使用的连续 token 数
+const TOKEN_GRAM: usize = 3;
+
+/// 相似度不低于该值的函数视为复制粘贴
+const DUPLICATE_SIMILARITY: f64 = 0.9;
+
+/// 相似度不低于该值的函数视为结构相似
+const SIMILAR_SIMILARITY: f64 = 0.7;
+
+/// 代码重复度指标的名称（跨文件重复检测把结果合并到该指标）
+pub const DUPLICATION_METRIC_NAME: &str = "代码重复度";
 
 pub struct CodeDuplicationMetric {
     translator: Translator,
+    min_lines: usize,
 }
 
 impl CodeDuplicationMetric {
     pub fn new(translator: Translator) -> Self {
-        CodeDuplicationMetric { translator }
+        CodeDuplicationMetric {
+            translator,
+            min_lines: DEFAULT_DUPLICATION_MIN_LINES,
+        }
+    }
+
+    /// 设置参与重复度分析的最小函数行数
+    ///
+    /// 行数更少的函数（getter、setter 等样板代码）不参与比较，避免误报
+    ///
+    /// # Arguments
+    /// * `min_lines` - 最小函数行数
+    ///
+    /// # Returns
+    /// * `Self` - 度量器实例
+    pub fn with_min_lines(mut self, min_lines: usize) -> Self {
+        self.min_lines = min_lines;
+        self
     }
 }
 
 impl Metric for CodeDuplicationMetric {
     fn name(&self) -> &str {
-        "代码重复度"
+        DUPLICATION_METRIC_NAME
+    }
+
+    fn id(&self) -> &str {
+        "code_duplication"
     }
 
     fn description(&self) -> &str {
@@ -27,7 +71,13 @@ impl Metric for CodeDuplicationMetric {
     }
 
     fn analyze(&self, parse_result: &dyn ParseResult) -> MetricResult {
-        let functions = parse_result.get_functions();
+        let functions: Vec<Function> = parse_result
+            .get_functions()
+            .iter()
+            .filter(|f| f.end_line - f.start_line + 1 >= self.min_lines)
+            .cloned()
+            .collect();
+        let functions = &functions[..];
         let mut issues = Vec::new();
 
         if functions.len() < 2 {
@@ -39,8 +89,13 @@ impl Metric for CodeDuplicationMetric {
             };
         }
 
-        // 多维度分析函数相似性
-        let mut similarity_groups = self.group_similar_functions(functions);
+        // 比较规范化后的函数体，找出复制粘贴的函数
+        let code = strip_source(parse_result.get_source(), parse_result.get_language());
+        let code_lines: Vec<&str> = code.lines().collect();
+        let grams: Vec<HashSet<u64>> = functions
+            .iter()
+            .map(|func| token_grams(func, &code_lines))
+            .collect();
         let mut duplication_score = 0.0;
         let mut total_duplicated_lines = 0;
         let total_lines: usize = functions
@@ -48,48 +103,54 @@ impl Metric for CodeDuplicationMetric {
             .map(|f| f.end_line - f.start_line + 1)
             .sum();
 
-        // 分析每个相似组
-        for (pattern, group) in &mut similarity_groups {
-            if group.len() > 1 {
-                // 排序以便生成一致的输出
-                group.sort_by(|a, b| a.name.cmp(&b.name));
-
-                let similarity_score = self.calculate_similarity_score(pattern);
-
-                if similarity_score > 0.7 {
-                    // 高度相似
-                    let duplicated_lines: usize = group
-                        .iter()
-                        .skip(1) // 第一个不算重复
-                        .map(|f| f.end_line - f.start_line + 1)
-                        .sum();
-
-                    total_duplicated_lines += duplicated_lines;
-
-                    issues.push(format!(
-                        "高度相似的函数（相似度 {:.0}%）: {}",
-                        similarity_score * 100.0,
-                        group
-                            .iter()
-                            .map(|f| &f.name[..])
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    ));
-
-                    duplication_score += similarity_score * group.len() as f64;
-                } else if similarity_score > 0.5 {
-                    // 中度相似
-                    issues.push(format!(
-                        "相似的函数结构: {}",
-                        group
-                            .iter()
-                            .map(|f| &f.name[..])
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    ));
-
-                    duplication_score += similarity_score * 0.5 * group.len() as f64;
-                }
+        for (members, similarity) in similar_groups(functions, &grams) {
+            let mut group: Vec<&Function> = members.iter().map(|&i| &functions[i]).collect();
+            // 排序以便生成一致的输出
+            group.sort_by(|a, b| a.name.cmp(&b.name));
+            let names = group
+                .iter()
+                .map(|f| &f.name[..])
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            if similarity >= DUPLICATE_SIMILARITY {
+                // 高度相似
+                let duplicated_lines: usize = group
+                    .iter()
+                    .skip(1) // 第一个不算重复
+                    .map(|f| f.end_line - f.start_line + 1)
+                    .sum();
+
+                total_duplicated_lines += duplicated_lines;
+
+                issues.push(
+                    Issue::new(
+                        IssueKind::DuplicateFunctions,
+                        format!(
+                            "高度相似的函数（相似度 {:.0}%）: {}",
+                            similarity * 100.0,
+                            names
+                        ),
+                    )
+                    .with_line(first_line(&group)),
+                );
+
+                duplication_score += similarity * group.len() as f64;
+            } else {
+                // 中度相似
+                issues.push(
+                    Issue::new(
+                        IssueKind::SimilarFunctions,
+                        format!(
+                            "相似的函数结构（相似度 {:.0}%）: {}",
+                            similarity * 100.0,
+                            names
+                        ),
+                    )
+                    .with_line(first_line(&group)),
+                );
+
+                duplication_score += similarity * 0.5 * group.len() as f64;
             }
         }
 
@@ -97,11 +158,17 @@ impl Metric for CodeDuplicationMetric {
         let naming_duplicates = self.detect_naming_pattern_duplication(functions);
         for (base_name, duplicates) in naming_duplicates {
             if duplicates.len() > 2 {
-                issues.push(format!(
-                    "发现重复的命名模式 '{}*': {} 个类似函数，建议使用更有意义的命名或合并逻辑",
-                    base_name,
-                    duplicates.len()
-                ));
+                issues.push(
+                    Issue::new(
+                        IssueKind::RepeatedNamingPattern,
+                        format!(
+                        "发现重复的命名模式 '{}*': {} 个类似函数，建议使用更有意义的命名或合并逻辑",
+                        base_name,
+                        duplicates.len()
+                    ),
+                    )
+                    .with_line(first_line(&duplicates)),
+                );
                 duplication_score += 0.3 * duplicates.len() as f64;
             }
         }
@@ -109,9 +176,12 @@ impl Metric for CodeDuplicationMetric {
         // 检测参数列表重复
         let param_duplicates = self.detect_parameter_duplication(functions);
         if param_duplicates > 3 {
-            issues.push(format!(
-                "发现 {} 个函数有相同的参数数量和复杂度，可能存在逻辑重复",
-                param_duplicates
+            issues.push(Issue::new(
+                IssueKind::RepeatedSignatures,
+                format!(
+                    "发现 {} 个函数有相同的参数数量和复杂度，可能存在逻辑重复",
+                    param_duplicates
+                ),
             ));
             duplication_score += 0.2 * param_duplicates as f64;
         }
@@ -137,152 +207,13 @@ impl Metric for CodeDuplicationMetric {
 }
 
 impl CodeDuplicationMetric {
-    fn group_similar_functions<'a>(
-        &self,
-        functions: &'a [Function],
-    ) -> HashMap<String, Vec<&'a Function>> {
-        let mut groups: HashMap<String, Vec<&'a Function>> = HashMap::new();
-
-        for func in functions {
-            let pattern = self.extract_function_pattern(func);
-            groups.entry(pattern).or_default().push(func);
-        }
-
-        groups
-    }
-
-    fn extract_function_pattern(&self, func: &Function) -> String {
-        // 创建函数的特征模式
-        let lines = func.end_line - func.start_line + 1;
-
-        // 根据多个维度创建模式
-        let size_category = match lines {
-            0..=10 => "tiny",
-            11..=30 => "small",
-            31..=60 => "medium",
-            61..=100 => "large",
-            _ => "huge",
-        };
-
-        let complexity_category = match func.complexity {
-            0..=3 => "trivial",
-            4..=7 => "simple",
-            8..=12 => "moderate",
-            13..=20 => "complex",
-            _ => "very_complex",
-        };
-
-        let param_category = match func.parameters {
-            0 => "no_params",
-            1 => "single_param",
-            2..=3 => "few_params",
-            4..=5 => "several_params",
-            _ => "many_params",
-        };
-
-        // 检查函数名称模式
-        let name_pattern = self.extract_name_pattern(&func.name);
-
-        format!(
-            "{}:{}:{}:{}:{}",
-            size_category,
-            complexity_category,
-            param_category,
-            lines, // 精确行数用于更细粒度的比较
-            name_pattern
-        )
-    }
-
-    fn extract_name_pattern(&self, name: &str) -> String {
-        // 提取函数名的模式（去掉数字后缀等）
-        let mut pattern = String::new();
-        let mut has_uppercase = false;
-        let mut has_underscore = false;
-
-        for ch in name.chars() {
-            if ch.is_uppercase() {
-                has_uppercase = true;
-            } else if ch == '_' {
-                has_underscore = true;
-            }
-        }
-
-        // 检查常见前缀
-        let prefixes = [
-            "get", "set", "handle", "process", "check", "validate", "init", "create", "update",
-            "delete",
-        ];
-        for prefix in &prefixes {
-            if name.starts_with(prefix) {
-                pattern.push_str(prefix);
-                pattern.push('_');
-                break;
-            }
-        }
-
-        // 添加命名风格
-        if has_uppercase {
-            pattern.push_str("camel");
-        } else if has_underscore {
-            pattern.push_str("snake");
-        } else {
-            pattern.push_str("flat");
-        }
-
-        pattern
-    }
-
-    fn calculate_similarity_score(&self, pattern: &str) -> f64 {
-        let parts: Vec<&str> = pattern.split(':').collect();
-
-        if parts.len() < 4 {
-            return 0.0;
-        }
-
-        let mut score: f64 = 0.0; // 明确指定类型为 f64
-
-        // 相同大小类别
-        if parts[0] == "tiny" || parts[0] == "small" {
-            score += 0.2;
-        } else if parts[0] == "medium" {
-            score += 0.3;
-        } else {
-            score += 0.4;
-        }
-
-        // 相同复杂度类别
-        match parts[1] {
-            "trivial" => score += 0.1,
-            "simple" => score += 0.2,
-            "moderate" => score += 0.3,
-            "complex" | "very_complex" => score += 0.4,
-            _ => {}
-        }
-
-        // 相同参数类别
-        if parts[2] != "no_params" {
-            score += 0.2;
-        }
-
-        // 如果行数完全相同，增加相似度
-        if let Ok(lines) = parts[3].parse::<usize>() {
-            if lines > 20 {
-                score += 0.2; // 长函数行数相同更可能是复制
-            } else if lines > 10 {
-                score += 0.1;
-            }
-        }
-
-        score.min(1.0)
-    }
-
     fn detect_naming_pattern_duplication<'a>(
         &self,
         functions: &'a [Function],
     ) -> HashMap<String, Vec<&'a Function>> {
         let mut pattern_groups: HashMap<String, Vec<&'a Function>> = HashMap::new();
 
-        for func in functions {
+        for func in functions.iter().filter(|f| !f.is_anonymous()) {
             // 去掉末尾的数字和常见后缀
             let base_name = self.get_base_name(&func.name);
 
@@ -335,3 +266,271 @@ impl CodeDuplicationMetric {
             .sum()
     }
 }
+
+/// 获取一组函数中最靠前的起始行，作为问题的定位行
+///
+/// # Arguments
+/// * `group` - 函数组（非空）
+///
+/// # Returns
+/// * `usize` - 最小的起始行号
+fn first_line(group: &[&Function]) -> usize {
+    group.iter().map(|f| f.start_line).min().unwrap_or(1)
+}
+
+/// 计算函数体的 token k-gram 集合
+///
+/// 函数体取自剥离注释和字符串内容后的源码，空白不参与比较；
+/// 函数自身的名称替换为占位符，使改名复制的函数仍能匹配。
+///
+/// # Arguments
+/// * `func` - 函数
+/// * `code_lines` - 剥离后的文件代码行
+///
+/// # Returns
+/// * `HashSet<u64>` - k-gram 的哈希集合
+fn token_grams(func: &Function, code_lines: &[&str]) -> HashSet<u64> {
+    let tokens: Vec<&str> = func
+        .source_lines(code_lines)
+        .iter()
+        .flat_map(|line| TOKEN_REGEX.find_iter(line))
+        .map(|token| {
+            if token.as_str() == func.name {
+                "$fn"
+            } else {
+                token.as_str()
+            }
+        })
+        .collect();
+
+    tokens
+        .windows(TOKEN_GRAM.min(tokens.len()).max(1))
+        .map(|gram| {
+            let mut hasher = StableHasher::new();
+            for token in gram {
+                hasher.write_str(token);
+            }
+            hasher.finish()
+        })
+        .collect()
+}
+
+/// 计算两个集合的 Jaccard 相似度
+///
+/// # Arguments
+/// * `a` - 集合
+/// * `b` - 集合
+///
+/// # Returns
+/// * `f64` - 交集大小 / 并集大小（两个集合都为空时为 0）
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let intersection = small.iter().filter(|gram| large.contains(gram)).count();
+    let union = a.len() + b.len() - intersection;
+
+    if union == 0 {
+        0.0
+    } else {
+        intersection as f64 / union as f64
+    }
+}
+
+/// 把两两相似的函数合并为相似组
+///
+/// 相似度不低于 `SIMILAR_SIMILARITY` 的函数对连成一组（传递闭包），
+/// 组的相似度取组内相似函数对的平均值。
+///
+/// 行范围重叠的函数（如闭包与外层函数）不互相比较。
+///
+/// # Arguments
+/// * `functions` - 函数列表
+/// * `grams` - 各函数的 k-gram 集合
+///
+/// # Returns
+/// * `Vec<(Vec<usize>, f64)>` - (组内函数的下标, 相似度)，按第一个函数的下标排序
+fn similar_groups(functions: &[Function], grams: &[HashSet<u64>]) -> Vec<(Vec<usize>, f64)> {
+    let mut parent: Vec<usize> = (0..grams.len()).collect();
+    let mut pairs = Vec::new();
+
+    for i in 0..grams.len() {
+        for j in i + 1..grams.len() {
+            // 集合大小相差过大时相似度不可能达到阈值
+            let (small, large) = (
+                grams[i].len().min(grams[j].len()),
+                grams[i].len().max(grams[j].len()),
+            );
+            if large == 0 || (small as f64) < large as f64 * SIMILAR_SIMILARITY {
+                continue;
+            }
+
+            let (a, b) = (&functions[i], &functions[j]);
+            if a.start_line <= b.end_line && b.start_line <= a.end_line {
+                continue;
+            }
+
+            let similarity = jaccard(&grams[i], &grams[j]);
+            if similarity >= SIMILAR_SIMILARITY {
+                let (a, b) = (find_root(&mut parent, i), find_root(&mut parent, j));
+                parent[b] = a;
+                pairs.push((i, similarity));
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..grams.len() {
+        let root = find_root(&mut parent, i);
+        groups.entry(root).or_default().push(i);
+    }
+
+    let mut result: Vec<(Vec<usize>, f64)> = groups
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .map(|members| {
+            let root = find_root(&mut parent, members[0]);
+            let similarities: Vec<f64> = pairs
+                .iter()
+                .filter(|(i, _)| find_root(&mut parent, *i) == root)
+                .map(|(_, similarity)| *similarity)
+                .collect();
+            let average = similarities.iter().sum::<f64>() / similarities.len() as f64;
+            (members, average)
+        })
+        .collect();
+    result.sort_by_key(|(members, _)| members[0]);
+    result
+}
+
+/// 查找并查集中元素所在组的根（顺带压缩路径）
+///
+/// # Arguments
+/// * `parent` - 并查集
+/// * `i` - 元素下标
+///
+/// # Returns
+/// * `usize` - 根的下标
+fn find_root(parent: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    parent[i] = root;
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Language;
+    use crate::parser::create_parser_for_file;
+    use std::path::Path;
+
+    fn analyze(source: &str) -> MetricResult {
+        let path = Path::new("sample.py");
+        let parse_result = create_parser_for_file(path).parse(path, source).unwrap();
+        CodeDuplicationMetric::new(Translator::new(Language::ZhCN)).analyze(&*parse_result)
+    }
+
+    fn has_kind(result: &MetricResult, kind: IssueKind) -> bool {
+        result.issues.iter().any(|issue| issue.kind == kind)
+    }
+
+    #[test]
+    fn copy_pasted_functions_are_reported_as_duplicates() {
+        let source = r#"def export_users(rows, out):
+    # 导出用户
+    total = 0
+    for row in rows:
+        if row.active:
+            out.write(row.name + "\n")
+            total += 1
+    out.flush()
+    return total
+
+
+def export_orders(rows, out):
+    total = 0
+    for row in rows:
+        if row.active:
+            out.write(row.name + ";\n")
+            total += 1
+
+    out.flush()
+    return total
+"#;
+        let result = analyze(source);
+        let issue = result
+            .issues
+            .iter()
+            .find(|issue| issue.kind == IssueKind::DuplicateFunctions)
+            .expect("copy-pasted functions should be duplicates");
+
+        assert!(issue.message.contains("export_orders, export_users"));
+        assert_eq!(issue.line, Some(1));
+        assert!(result.score > 0.0);
+    }
+
+    #[test]
+    fn same_shaped_but_different_functions_are_not_similar() {
+        // 两个函数行数、参数个数和分支数都相同，但函数体完全不同
+        let source = r#"def parse_header(text, strict):
+    parts = text.split(":")
+    if len(parts) != 2:
+        raise ValueError(text)
+    key = parts[0].strip().lower()
+    value = parts[1].strip()
+    if strict and not value:
+        return None
+    return key, value
+
+
+def retry_request(client, attempts):
+    delay = 0.5
+    while attempts > 0:
+        response = client.send()
+        if response.ok:
+            return response
+        attempts -= 1
+        delay *= 2
+    return None
+"#;
+        let result = analyze(source);
+
+        assert!(!has_kind(&result, IssueKind::DuplicateFunctions));
+        assert!(!has_kind(&result, IssueKind::SimilarFunctions));
+        assert_eq!(result.score, 0.0);
+    }
+
+    #[test]
+    fn raising_min_lines_drops_tiny_duplicate_getters() {
+        let source = r#"def get_name(user):
+    value = user.profile
+    return value.strip()
+
+
+def get_email(user):
+    value = user.profile
+    return value.strip()
+
+
+def get_phone(user):
+    value = user.profile
+    return value.strip()
+"#;
+        let path = Path::new("sample.py");
+        let parse_result = create_parser_for_file(path).parse(path, source).unwrap();
+        let metric = |min_lines| {
+            CodeDuplicationMetric::new(Translator::new(Language::ZhCN))
+                .with_min_lines(min_lines)
+                .analyze(&*parse_result)
+        };
+
+        let lenient = metric(1);
+        assert!(has_kind(&lenient, IssueKind::DuplicateFunctions));
+        assert!(lenient.score > 0.0);
+
+        let strict = metric(DEFAULT_DUPLICATION_MIN_LINES);
+        assert!(strict.issues.is_empty());
+        assert_eq!(strict.score, 0.0);
+    }
+}
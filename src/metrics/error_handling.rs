@@ -1,7 +1,28 @@
 use crate::common::LanguageType;
 use crate::i18n::Translator;
-use crate::metrics::{Metric, MetricResult};
-use crate::parser::{Function, ParseResult};
+use crate::metrics::{Issue, IssueKind, Metric, MetricResult};
+use crate::parser::{strip_source, Function, ParseResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Rust `?` 错误传播运算符（排除 `?Sized` 之类的 trait 约束）
+static RUST_TRY_OPERATOR: Lazy<Regex> = Lazy::new(|| Regex::new(r"[\w)\]>]\s*\?").unwrap());
+
+/// 返回 `Result` 的 Rust 函数签名（含 `io::Result`、`AppResult` 等别名）
+static RUST_RESULT_RETURN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"->\s*(?:[\w:]+::)?\w*Result\b").unwrap());
+
+/// 显式处理 `Result` 的写法
+static RUST_RESULT_HANDLING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(?:Ok|Err)\s*\(|\.(?:map_err|or_else|unwrap_or|unwrap_or_else|unwrap_or_default|ok)\s*\(",
+    )
+    .unwrap()
+});
+
+/// 出错时直接 panic 的写法
+static RUST_PANIC: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\.(?:unwrap|expect)\s*\(|\bpanic!").unwrap());
 
 pub struct ErrorHandlingMetric {
     translator: Translator,
@@ -18,6 +39,10 @@ impl Metric for ErrorHandlingMetric {
         "错误处理"
     }
 
+    fn id(&self) -> &str {
+        "error_handling"
+    }
+
     fn description(&self) -> &str {
         "检测代码中的错误处理情况，良好的错误处理能提高代码的健壮性"
     }
@@ -42,15 +67,30 @@ impl Metric for ErrorHandlingMetric {
 
         let mut total_error_handling_score = 0.0;
         let mut functions_with_errors = 0;
+        let lines: Vec<&str> = parse_result.get_source().lines().collect();
 
         for func in functions {
-            let error_score = self.analyze_function_error_handling(func, language);
+            // 剥离注释和字符串，避免注释里的 unwrap 之类被误判
+            let body = strip_source(&func.source_lines(&lines).join("\n"), language);
+            let error_score = self.analyze_function_error_handling(func, &body, language);
 
             if error_score.has_error_potential && !error_score.has_error_handling {
                 functions_with_errors += 1;
-                issues.push(format!("函数 '{}' 可能产生错误但缺少错误处理", func.name));
+                issues.push(
+                    Issue::new(
+                        IssueKind::MissingErrorHandling,
+                        format!("函数 '{}' 可能产生错误但缺少错误处理", func.name),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
             } else if error_score.has_error_potential && error_score.error_handling_quality < 0.3 {
-                issues.push(format!("函数 '{}' 的错误处理不完善", func.name));
+                issues.push(
+                    Issue::new(
+                        IssueKind::WeakErrorHandling,
+                        format!("函数 '{}' 的错误处理不完善", func.name),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
             }
 
             total_error_handling_score += error_score.error_handling_quality;
@@ -88,11 +128,12 @@ impl ErrorHandlingMetric {
     fn analyze_function_error_handling(
         &self,
         func: &Function,
+        body: &str,
         language: LanguageType,
     ) -> ErrorHandlingScore {
         // 根据不同语言检测错误处理模式
         match language {
-            LanguageType::Rust => self.analyze_rust_error_handling(func),
+            LanguageType::Rust => self.analyze_rust_error_handling(body),
             LanguageType::Go => self.analyze_go_error_handling(func),
             LanguageType::JavaScript | LanguageType::TypeScript => {
                 self.analyze_js_error_handling(func)
@@ -108,22 +149,33 @@ impl ErrorHandlingMetric {
         }
     }
 
-    fn analyze_rust_error_handling(&self, func: &Function) -> ErrorHandlingScore {
-        // Rust 错误处理检测
-        // 检查 Result、Option、unwrap、expect、? 操作符等
-        let has_result_return = func.name.contains("Result") || func.complexity > 5;
-        let has_error_handling = func.complexity > 3; // 简化判断：复杂度高说明有分支处理
-
-        let quality = if has_error_handling {
-            0.8 // Rust 强制错误处理，质量通常较高
-        } else if has_result_return {
-            0.5
-        } else {
-            0.3
+    /// Rust 错误处理检测
+    ///
+    /// 检查 `?` 传播、`Result` 返回值、对 `Ok`/`Err` 的匹配与组合子，以及 `unwrap`/`expect`/`panic!`
+    ///
+    /// # Arguments
+    /// * `body` - 剥离注释与字符串后的函数源码
+    ///
+    /// # Returns
+    /// * `ErrorHandlingScore` - 错误处理评估
+    fn analyze_rust_error_handling(&self, body: &str) -> ErrorHandlingScore {
+        let signature = body.split('{').next().unwrap_or(body);
+        let returns_result = RUST_RESULT_RETURN.is_match(signature);
+        let propagates = RUST_TRY_OPERATOR.is_match(body);
+        let handles = RUST_RESULT_HANDLING.is_match(body);
+        let panics = RUST_PANIC.is_match(body);
+
+        let has_error_handling = propagates || handles;
+        let quality = match (has_error_handling, panics) {
+            (true, false) => 0.9,
+            (true, true) => 0.5,  // 有处理但仍夹杂 unwrap/expect
+            (false, true) => 0.2, // 出错直接 panic
+            (false, false) if returns_result => 0.6,
+            (false, false) => 0.8, // 不涉及错误的纯函数
         };
 
         ErrorHandlingScore {
-            has_error_potential: has_result_return || func.complexity > 8,
+            has_error_potential: returns_result || propagates || handles || panics,
             has_error_handling,
             error_handling_quality: quality,
         }
@@ -1,24 +1,196 @@
+use crate::common::LanguageType;
 use crate::i18n::Translator;
-use crate::metrics::{Metric, MetricResult};
+use crate::metrics::{Issue, IssueKind, Metric, MetricResult};
 use crate::parser::ParseResult;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
+/// 阈值表中对所有语言生效的键（`[thresholds.default]`），各语言的配置在其基础上覆盖
+pub const DEFAULT_THRESHOLDS_KEY: &str = "default";
+
+/// 函数相关阈值（超过即报告对应级别的问题）
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Thresholds {
+    pub(crate) function_lines_medium: usize,
+    pub(crate) function_lines_high: usize,
+    pub(crate) function_lines_extreme: usize,
+    pub(crate) complexity_medium: usize,
+    pub(crate) complexity_high: usize,
+    pub(crate) complexity_warn: usize,
+    pub(crate) complexity_error: usize,
+    pub(crate) parameters_medium: usize,
+    pub(crate) parameters_high: usize,
+}
+
+/// 内置默认阈值
+const DEFAULT_THRESHOLDS: Thresholds = Thresholds {
+    function_lines_medium: 40,
+    function_lines_high: 70,
+    function_lines_extreme: 120,
+    complexity_medium: 12,
+    complexity_high: 18,
+    complexity_warn: 10,
+    complexity_error: 15,
+    parameters_medium: 6,
+    parameters_high: 8,
+};
+
+/// 某种语言（或 `default` 表示所有语言）的阈值覆盖，未设置的项使用内置默认值
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanguageThresholds {
+    /// 函数行数超过该值视为较长（默认 40）
+    pub function_lines_medium: Option<usize>,
+
+    /// 函数行数超过该值视为过长（默认 70）
+    pub function_lines_high: Option<usize>,
+
+    /// 函数行数超过该值视为极度过长（默认 120）
+    pub function_lines_extreme: Option<usize>,
+
+    /// 复杂度超过该值视为过高（默认 12）
+    pub complexity_medium: Option<usize>,
+
+    /// 复杂度超过该值视为严重过高（默认 18）
+    pub complexity_high: Option<usize>,
+
+    /// 循环复杂度指标中复杂度超过该值视为较高（默认 10）
+    pub complexity_warn: Option<usize>,
+
+    /// 循环复杂度指标中复杂度超过该值视为过高（默认 15）
+    pub complexity_error: Option<usize>,
+
+    /// 参数数量超过该值视为过多（默认 6）
+    pub parameters_medium: Option<usize>,
+
+    /// 参数数量超过该值视为极多（默认 8）
+    pub parameters_high: Option<usize>,
+}
+
+impl LanguageThresholds {
+    /// 用覆盖值替换默认阈值
+    ///
+    /// # Arguments
+    /// * `defaults` - 默认阈值
+    ///
+    /// # Returns
+    /// * `Thresholds` - 生效的阈值
+    fn apply_to(&self, defaults: Thresholds) -> Thresholds {
+        Thresholds {
+            complexity_warn: self.complexity_warn.unwrap_or(defaults.complexity_warn),
+            complexity_error: self.complexity_error.unwrap_or(defaults.complexity_error),
+            function_lines_medium: self
+                .function_lines_medium
+                .unwrap_or(defaults.function_lines_medium),
+            function_lines_high: self
+                .function_lines_high
+                .unwrap_or(defaults.function_lines_high),
+            function_lines_extreme: self
+                .function_lines_extreme
+                .unwrap_or(defaults.function_lines_extreme),
+            complexity_medium: self.complexity_medium.unwrap_or(defaults.complexity_medium),
+            complexity_high: self.complexity_high.unwrap_or(defaults.complexity_high),
+            parameters_medium: self.parameters_medium.unwrap_or(defaults.parameters_medium),
+            parameters_high: self.parameters_high.unwrap_or(defaults.parameters_high),
+        }
+    }
+}
+
+/// 获取某种语言生效的阈值
+///
+/// 依次应用内置默认值、`default` 键和该语言的覆盖；较高一级的阈值不会低于较低一级，
+/// 因此只调高较低一级（如 `--max-params`）时，超过它的函数仍会被报告。
+///
+/// # Arguments
+/// * `overrides` - 阈值覆盖（键为小写语言名或 `default`）
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `Thresholds` - 阈值
+pub(crate) fn resolve_thresholds(
+    overrides: &BTreeMap<String, LanguageThresholds>,
+    language: LanguageType,
+) -> Thresholds {
+    let mut thresholds = DEFAULT_THRESHOLDS;
+    for key in [
+        DEFAULT_THRESHOLDS_KEY.to_string(),
+        language.display_name().to_lowercase(),
+    ] {
+        if let Some(layer) = overrides.get(&key) {
+            thresholds = layer.apply_to(thresholds);
+        }
+    }
+
+    thresholds.function_lines_high = thresholds
+        .function_lines_high
+        .max(thresholds.function_lines_medium);
+    thresholds.function_lines_extreme = thresholds
+        .function_lines_extreme
+        .max(thresholds.function_lines_high);
+    thresholds.complexity_high = thresholds.complexity_high.max(thresholds.complexity_medium);
+    thresholds.complexity_error = thresholds.complexity_error.max(thresholds.complexity_warn);
+    thresholds.parameters_high = thresholds.parameters_high.max(thresholds.parameters_medium);
+    thresholds
+}
+
+/// 函数长度度量器
 pub struct FunctionLengthMetric {
+    /// 翻译器
     translator: Translator,
+
+    /// 按语言覆盖的阈值（键为小写语言名或 `default`）
+    language_thresholds: BTreeMap<String, LanguageThresholds>,
 }
 
 impl FunctionLengthMetric {
     pub fn new(translator: Translator) -> Self {
-        FunctionLengthMetric { translator }
+        FunctionLengthMetric {
+            translator,
+            language_thresholds: BTreeMap::new(),
+        }
+    }
+
+    /// 设置按语言覆盖的阈值
+    ///
+    /// # Arguments
+    /// * `thresholds` - 语言名（或 `default`）到阈值覆盖的映射，语言名不区分大小写，例如 `{"python": {"function_lines_high": 50}}`
+    ///
+    /// # Returns
+    /// * `Self` - 度量器实例
+    pub fn with_language_thresholds(
+        mut self,
+        thresholds: BTreeMap<String, LanguageThresholds>,
+    ) -> Self {
+        self.language_thresholds = thresholds
+            .into_iter()
+            .map(|(language, thresholds)| (language.to_lowercase(), thresholds))
+            .collect();
+        self
+    }
+
+    /// 获取某种语言生效的阈值
+    ///
+    /// # Arguments
+    /// * `language` - 语言类型
+    ///
+    /// # Returns
+    /// * `Thresholds` - 阈值
+    fn thresholds_for(&self, language: LanguageType) -> Thresholds {
+        resolve_thresholds(&self.language_thresholds, language)
     }
 }
 
 impl Metric for FunctionLengthMetric {
     fn name(&self) -> &str {
-        "状态管理"
+        "函数长度"
+    }
+
+    fn id(&self) -> &str {
+        "function_length"
     }
 
     fn description(&self) -> &str {
-        "检测代码中状态变量的管理，良好的状态管理能提高代码可维护性和可预测性"
+        "检测函数的长度和参数数量，过长的函数难以理解、测试和复用"
     }
 
     fn weight(&self) -> f64 {
@@ -26,68 +198,119 @@ impl Metric for FunctionLengthMetric {
     }
 
     fn analyze(&self, parse_result: &dyn ParseResult) -> MetricResult {
-        // 使用 dyn
-        let functions = parse_result.get_functions();
         let mut issues = Vec::new();
+        let score = self.analyze_functions(parse_result, &mut issues);
 
+        MetricResult {
+            score,
+            weight: self.weight(),
+            description: self.description().to_string(),
+            issues,
+        }
+    }
+}
+
+impl FunctionLengthMetric {
+    /// 检查函数长度、复杂度和参数数量
+    ///
+    /// # Arguments
+    /// * `parse_result` - 解析结果
+    /// * `issues` - 问题列表
+    ///
+    /// # Returns
+    /// * `f64` - 函数部分得分
+    fn analyze_functions(&self, parse_result: &dyn ParseResult, issues: &mut Vec<Issue>) -> f64 {
+        let functions = parse_result.get_functions();
         if functions.is_empty() {
-            return MetricResult {
-                score: 0.0,
-                weight: self.weight(),
-                description: self.description().to_string(),
-                issues,
-            };
+            return 0.0;
         }
 
         let mut long_functions = 0;
         let mut very_long_functions = 0;
         let mut extreme_long_functions = 0;
+        let thresholds = self.thresholds_for(parse_result.get_language());
 
         for func in functions {
             let line_count = func.end_line - func.start_line + 1;
 
-            if line_count > 120 {
-                issues.push(format!(
-                    "函数 '{}' 极度过长 ({} 行)，必须拆分",
-                    func.name, line_count
-                ));
+            if line_count > thresholds.function_lines_extreme {
+                issues.push(
+                    Issue::new(
+                        IssueKind::FunctionVeryLong,
+                        format!(
+                            "函数 '{}' 极度过长 ({} 行)，必须拆分",
+                            func.name, line_count
+                        ),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
                 extreme_long_functions += 1;
-            } else if line_count > 70 {
-                issues.push(format!(
-                    "函数 '{}' 过长 ({} 行)，建议拆分",
-                    func.name, line_count
-                ));
+            } else if line_count > thresholds.function_lines_high {
+                issues.push(
+                    Issue::new(
+                        IssueKind::FunctionLong,
+                        format!("函数 '{}' 过长 ({} 行)，建议拆分", func.name, line_count),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
                 very_long_functions += 1;
-            } else if line_count > 40 {
-                issues.push(format!(
-                    "函数 '{}' 较长 ({} 行)，可考虑重构",
-                    func.name, line_count
-                ));
+            } else if line_count > thresholds.function_lines_medium {
+                issues.push(
+                    Issue::new(
+                        IssueKind::FunctionMedium,
+                        format!("函数 '{}' 较长 ({} 行)，可考虑重构", func.name, line_count),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
                 long_functions += 1;
             }
 
-            if func.complexity > 18 {
-                issues.push(format!(
-                    "函数 '{}' 复杂度严重过高 ({})，必须简化",
-                    func.name, func.complexity
-                ));
-            } else if func.complexity > 12 {
-                issues.push(format!(
-                    "函数 '{}' 复杂度过高 ({})，建议简化",
-                    func.name, func.complexity
-                ));
+            if func.complexity > thresholds.complexity_high {
+                issues.push(
+                    Issue::new(
+                        IssueKind::HighComplexity,
+                        format!(
+                            "函数 '{}' 复杂度严重过高 ({})，必须简化",
+                            func.name, func.complexity
+                        ),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
+            } else if func.complexity > thresholds.complexity_medium {
+                issues.push(
+                    Issue::new(
+                        IssueKind::MediumComplexity,
+                        format!(
+                            "函数 '{}' 复杂度过高 ({})，建议简化",
+                            func.name, func.complexity
+                        ),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
             }
 
-            if func.parameters > 8 {
-                issues.push(format!(
-                    "函数 '{}' 参数极多 ({} 个)，必须使用结构体封装",
-                    func.name, func.parameters
-                ));
-            } else if func.parameters > 6 {
-                issues.push(format!(
-                    "函数 '{}' 参数过多 ({} 个)，建议使用结构体封装",
-                    func.name, func.parameters
-                ));
+            if func.parameters > thresholds.parameters_high {
+                issues.push(
+                    Issue::new(
+                        IssueKind::TooManyParameters,
+                        format!(
+                            "函数 '{}' 参数极多 ({} 个)，必须使用结构体封装",
+                            func.name, func.parameters
+                        ),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
+            } else if func.parameters > thresholds.parameters_medium {
+                issues.push(
+                    Issue::new(
+                        IssueKind::ManyParameters,
+                        format!(
+                            "函数 '{}' 参数过多 ({} 个)，建议使用结构体封装",
+                            func.name, func.parameters
+                        ),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
             }
         }
 
@@ -97,13 +320,116 @@ impl Metric for FunctionLengthMetric {
         let extreme_long_ratio = extreme_long_functions as f64 / total_functions;
 
         let score = long_ratio * 0.3 + very_long_ratio * 0.5 + extreme_long_ratio * 0.8;
-        let score = if score > 1.0 { 1.0 } else { score };
+        score.min(1.0)
+    }
+}
 
-        MetricResult {
-            score,
-            weight: self.weight(),
-            description: self.description().to_string(),
-            issues,
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Language;
+    use crate::parser::create_parser_for_file;
+    use std::path::Path;
+
+    /// 生成函数体为 `body_lines` 行的 Python 函数
+    fn python_function(body_lines: usize) -> String {
+        let body: String = (0..body_lines - 1)
+            .map(|i| format!("    x{} = {}\n", i, i))
+            .collect();
+        format!("def process(items):\n{}    return items\n", body)
+    }
+
+    /// 生成函数体为 `body_lines` 行的 Java 方法
+    fn java_method(body_lines: usize) -> String {
+        let body: String = (0..body_lines)
+            .map(|i| format!("        int x{} = {};\n", i, i))
+            .collect();
+        format!(
+            "public class Worker {{\n    public void process() {{\n{}    }}\n}}\n",
+            body
+        )
+    }
+
+    /// 用按语言覆盖的阈值分析源码，返回函数长度相关的问题类型
+    fn length_issues(file_name: &str, source: &str) -> Vec<IssueKind> {
+        let thresholds = BTreeMap::from([
+            (
+                "Python".to_string(),
+                LanguageThresholds {
+                    function_lines_medium: Some(30),
+                    function_lines_high: Some(50),
+                    ..Default::default()
+                },
+            ),
+            (
+                "java".to_string(),
+                LanguageThresholds {
+                    function_lines_medium: Some(80),
+                    function_lines_high: Some(120),
+                    function_lines_extreme: Some(200),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let metric = FunctionLengthMetric::new(Translator::new(Language::ZhCN))
+            .with_language_thresholds(thresholds);
+
+        let path = Path::new(file_name);
+        let parse_result = create_parser_for_file(path).parse(path, source).unwrap();
+        assert_eq!(parse_result.get_functions().len(), 1);
+        metric
+            .analyze(&*parse_result)
+            .issues
+            .into_iter()
+            .map(|issue| issue.kind)
+            .filter(|kind| {
+                matches!(
+                    kind,
+                    IssueKind::FunctionMedium
+                        | IssueKind::FunctionLong
+                        | IssueKind::FunctionVeryLong
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_length_is_flagged_in_python_but_not_java() {
+        assert_eq!(
+            length_issues("worker.py", &python_function(59)),
+            [IssueKind::FunctionLong]
+        );
+        assert!(length_issues("Worker.java", &java_method(58)).is_empty());
+    }
+
+    #[test]
+    fn language_overrides_layer_on_top_of_default_key() {
+        let overrides = BTreeMap::from([
+            (
+                DEFAULT_THRESHOLDS_KEY.to_string(),
+                LanguageThresholds {
+                    function_lines_medium: Some(20),
+                    parameters_medium: Some(10),
+                    ..Default::default()
+                },
+            ),
+            (
+                "python".to_string(),
+                LanguageThresholds {
+                    function_lines_medium: Some(15),
+                    ..Default::default()
+                },
+            ),
+        ]);
+
+        let python = resolve_thresholds(&overrides, LanguageType::Python);
+        assert_eq!(python.function_lines_medium, 15);
+        assert_eq!(python.function_lines_high, 70);
+        // 较高一级不低于较低一级
+        assert_eq!(python.parameters_high, 10);
+
+        let go = resolve_thresholds(&overrides, LanguageType::Go);
+        assert_eq!(go.function_lines_medium, 20);
+        assert_eq!(go.complexity_warn, 10);
     }
 }
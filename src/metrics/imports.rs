@@ -0,0 +1,174 @@
+//! # 导入语句统计
+//!
+//! 按各语言的写法统计文件中的导入/依赖语句数量，供代码结构指标评估耦合度
+
+use crate::common::LanguageType;
+use crate::parser::strip_source;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// 默认每个文件允许的最多导入语句数
+pub const DEFAULT_MAX_IMPORTS: usize = 20;
+
+/// Rust: `use`（含 `pub use`）和 `extern crate`
+static RUST_IMPORT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:(?:pub(?:\([^)]*\))?\s+)?use\s|extern\s+crate\s)").unwrap());
+
+/// Python: `import x` 和 `from x import y`
+static PYTHON_IMPORT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:import|from)\s+[\w.]").unwrap());
+
+/// JavaScript/TypeScript: `import ... from`、`import "x"` 和 `require(...)`
+static JS_IMPORT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*import(?:\s+[\w{*$]|\s*[{*"'])|\brequire\s*\("#).unwrap());
+
+/// Java: `import`
+static JAVA_IMPORT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*import\s").unwrap());
+
+/// C#: `using` 指令（不含 `using (...)` 语句）
+static CSHARP_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:global\s+)?using\s+(?:static\s+)?[\w.]+(?:\s*=\s*[\w.<>]+)?\s*;").unwrap()
+});
+
+/// C/C++: `#include`
+static C_IMPORT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*#\s*include\b").unwrap());
+
+/// PHP: `use` 和 `require`/`include` 系列
+static PHP_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*use\s+[\w\\]|(?:^|[^\w$>])(?:require|include)(?:_once)?\b").unwrap()
+});
+
+/// Zig: `@import(...)`
+static ZIG_IMPORT: Lazy<Regex> = Lazy::new(|| Regex::new(r"@import\s*\(").unwrap());
+
+/// Elixir: `import`、`alias`、`require`、`use`
+static ELIXIR_IMPORT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:import|alias|require|use)\s+[A-Z:]").unwrap());
+
+/// Swift: `import`（可带 `@testable` 或 `import struct` 等限定）
+static SWIFT_IMPORT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:@\w+\s+)*import\s+\w").unwrap());
+
+/// Shell: `source` 或 `.` 引入其他脚本
+static SHELL_IMPORT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(?:source|\.)\s+\S").unwrap());
+
+/// Go: 单行 `import "x"` 或导入块开头 `import (`
+static GO_IMPORT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*import\s*(?:(\()|(?:[\w.]+\s+)?")"#).unwrap());
+
+/// 统计文件中的导入语句数量
+///
+/// 在剥离注释和字符串后的源码上逐行匹配，每行最多计一次；Go 的导入块按块内的每个包计数。
+/// 不支持的语言返回 0。
+///
+/// # Arguments
+/// * `source` - 源码
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `usize` - 导入语句数量
+pub fn count_imports(source: &str, language: LanguageType) -> usize {
+    if language == LanguageType::Go {
+        return count_go_imports(&strip_source(source, language));
+    }
+
+    let pattern: &Regex = match language {
+        LanguageType::Rust => &RUST_IMPORT,
+        LanguageType::Python => &PYTHON_IMPORT,
+        LanguageType::JavaScript | LanguageType::TypeScript => &JS_IMPORT,
+        LanguageType::Java => &JAVA_IMPORT,
+        LanguageType::CSharp => &CSHARP_IMPORT,
+        LanguageType::C | LanguageType::CPlusPlus => &C_IMPORT,
+        LanguageType::PHP => &PHP_IMPORT,
+        LanguageType::Zig => &ZIG_IMPORT,
+        LanguageType::Elixir => &ELIXIR_IMPORT,
+        LanguageType::Shell => &SHELL_IMPORT,
+        LanguageType::Swift => &SWIFT_IMPORT,
+        _ => return 0,
+    };
+
+    strip_source(source, language)
+        .lines()
+        .filter(|line| pattern.is_match(line))
+        .count()
+}
+
+/// 统计 Go 文件中导入的包数量
+///
+/// # Arguments
+/// * `stripped` - 剥离注释和字符串后的源码
+///
+/// # Returns
+/// * `usize` - 导入的包数量
+fn count_go_imports(stripped: &str) -> usize {
+    let mut count = 0;
+    let mut in_block = false;
+
+    for line in stripped.lines().map(str::trim) {
+        if in_block {
+            if line.starts_with(')') {
+                in_block = false;
+            } else if !line.is_empty() {
+                count += 1;
+            }
+            continue;
+        }
+
+        if let Some(captures) = GO_IMPORT.captures(line) {
+            if captures.get(1).is_some() {
+                in_block = true;
+            } else {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_language_specific_import_forms() {
+        let cases = [
+            (
+                LanguageType::Rust,
+                "use std::fs;\npub use crate::a::B;\nextern crate core;\nfn user() {}\n",
+                3,
+            ),
+            (
+                LanguageType::Python,
+                "import os\nfrom typing import List\nimported = 1\n# import sys\n",
+                2,
+            ),
+            (
+                LanguageType::JavaScript,
+                "import React from 'react';\nimport './style.css';\nconst fs = require('fs');\n\
+                 const s = \"import x from 'y'\";\n",
+                3,
+            ),
+            (
+                LanguageType::CSharp,
+                "using System;\nusing static System.Math;\nusing (var f = Open()) {}\n",
+                2,
+            ),
+            (
+                LanguageType::C,
+                "#include <stdio.h>\n# include \"a.h\"\n",
+                2,
+            ),
+            (
+                LanguageType::Go,
+                "package main\n\nimport \"fmt\"\nimport (\n\t\"os\"\n\tio \"io/ioutil\"\n\n)\n",
+                3,
+            ),
+            (LanguageType::CSS, "@import url(a.css);\n", 0),
+        ];
+
+        for (language, source, expected) in cases {
+            assert_eq!(count_imports(source, language), expected, "{:?}", language);
+        }
+    }
+}
@@ -0,0 +1,386 @@
+//! # 问题定义
+//!
+//! 定义度量指标发现的问题及其类型
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 问题严重程度（从低到高排序）
+///
+/// 命令行中分别写作 `info`、`warning`、`error`（也接受 `low`、`medium`、`high`）
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// 轻微
+    #[default]
+    #[value(name = "info", alias = "low")]
+    Low,
+
+    /// 中等
+    #[value(name = "warning", alias = "medium")]
+    Medium,
+
+    /// 严重
+    #[value(name = "error", alias = "high")]
+    High,
+}
+
+/// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// 循环复杂度过高
+    HighComplexity,
+
+    /// 循环复杂度较高
+    MediumComplexity,
+
+    /// 判断函数过于复杂
+    ComplexPredicate,
+
+    /// 认知复杂度过高
+    HighCognitiveComplexity,
+
+    /// 函数极度过长
+    FunctionVeryLong,
+
+    /// 函数过长
+    FunctionLong,
+
+    /// 函数较长
+    FunctionMedium,
+
+    /// 参数极多
+    TooManyParameters,
+
+    /// 参数较多
+    ManyParameters,
+
+    /// 注释率极低
+    CommentVeryLow,
+
+    /// 注释率较低
+    CommentLow,
+
+    /// 导出函数缺少文档注释
+    ExportedFuncNoComment,
+
+    /// 复杂或过长的非导出函数缺少文档注释
+    ComplexFuncNoComment,
+
+    /// 高度相似的函数
+    DuplicateFunctions,
+
+    /// 结构相似的函数
+    SimilarFunctions,
+
+    /// 重复的命名模式
+    RepeatedNamingPattern,
+
+    /// 重复的函数签名
+    RepeatedSignatures,
+
+    /// 与其他文件重复的代码块
+    CrossFileDuplicate,
+
+    /// 缺少错误处理
+    MissingErrorHandling,
+
+    /// 错误处理不完善
+    WeakErrorHandling,
+
+    /// 命名不规范
+    BadNaming,
+
+    /// 同一文件中的函数重载（语言允许）
+    OverloadedFunction,
+
+    /// 同一文件中重复定义的函数（后面的定义会覆盖前面的）
+    ShadowedFunction,
+
+    /// 可变全局状态过多
+    GlobalState,
+
+    /// 嵌套过深
+    DeepNesting,
+
+    /// 嵌套较深
+    MediumNesting,
+
+    /// 大括号风格不一致
+    InconsistentBraceStyle,
+
+    /// 条件表达式过长
+    ComplexCondition,
+
+    /// 回调嵌套过深或 Promise 链过长（JS/TS）
+    CallbackHell,
+
+    /// 文件导入的模块过多
+    TooManyImports,
+
+    /// 代码行过长
+    LongLine,
+
+    /// 陈旧的 TODO/FIXME
+    StaleTodo,
+
+    /// 没有被引用的非导出函数（启发式检测）
+    PossiblyDeadCode,
+
+    /// 自定义指标产生的问题
+    Custom,
+}
+
+impl IssueKind {
+    /// 获取问题类型标识（与 i18n 键后缀一致）
+    ///
+    /// # Returns
+    /// * `&str` - 类型标识
+    pub fn key(&self) -> &'static str {
+        match self {
+            IssueKind::HighComplexity => "high_complexity",
+            IssueKind::MediumComplexity => "medium_complexity",
+            IssueKind::ComplexPredicate => "complex_predicate",
+            IssueKind::HighCognitiveComplexity => "high_cognitive_complexity",
+            IssueKind::FunctionVeryLong => "function_very_long",
+            IssueKind::FunctionLong => "function_long",
+            IssueKind::FunctionMedium => "function_medium",
+            IssueKind::TooManyParameters => "too_many_parameters",
+            IssueKind::ManyParameters => "many_parameters",
+            IssueKind::CommentVeryLow => "comment_very_low",
+            IssueKind::CommentLow => "comment_low",
+            IssueKind::ExportedFuncNoComment => "exported_func_no_comment",
+            IssueKind::ComplexFuncNoComment => "complex_func_no_comment",
+            IssueKind::DuplicateFunctions => "duplicate_functions",
+            IssueKind::SimilarFunctions => "similar_functions",
+            IssueKind::RepeatedNamingPattern => "repeated_naming_pattern",
+            IssueKind::RepeatedSignatures => "repeated_signatures",
+            IssueKind::CrossFileDuplicate => "cross_file_duplicate",
+            IssueKind::MissingErrorHandling => "missing_error_handling",
+            IssueKind::WeakErrorHandling => "weak_error_handling",
+            IssueKind::BadNaming => "bad_naming",
+            IssueKind::OverloadedFunction => "overloaded_function",
+            IssueKind::ShadowedFunction => "shadowed_function",
+            IssueKind::GlobalState => "global_state",
+            IssueKind::DeepNesting => "deep_nesting",
+            IssueKind::MediumNesting => "medium_nesting",
+            IssueKind::InconsistentBraceStyle => "inconsistent_brace_style",
+            IssueKind::ComplexCondition => "complex_condition",
+            IssueKind::CallbackHell => "callback_hell",
+            IssueKind::TooManyImports => "too_many_imports",
+            IssueKind::LongLine => "long_line",
+            IssueKind::StaleTodo => "stale_todo",
+            IssueKind::PossiblyDeadCode => "possibly_dead_code",
+            IssueKind::Custom => "custom",
+        }
+    }
+
+    /// 获取问题的严重程度
+    ///
+    /// # Returns
+    /// * `Severity` - 严重程度
+    pub fn severity(&self) -> Severity {
+        match self {
+            IssueKind::HighComplexity
+            | IssueKind::FunctionVeryLong
+            | IssueKind::TooManyParameters
+            | IssueKind::CommentVeryLow
+            | IssueKind::DuplicateFunctions
+            | IssueKind::CrossFileDuplicate
+            | IssueKind::MissingErrorHandling
+            | IssueKind::GlobalState
+            | IssueKind::ShadowedFunction
+            | IssueKind::DeepNesting => Severity::High,
+            IssueKind::MediumComplexity
+            | IssueKind::ComplexPredicate
+            | IssueKind::HighCognitiveComplexity
+            | IssueKind::FunctionLong
+            | IssueKind::ManyParameters
+            | IssueKind::CommentLow
+            | IssueKind::ExportedFuncNoComment
+            | IssueKind::ComplexFuncNoComment
+            | IssueKind::SimilarFunctions
+            | IssueKind::RepeatedNamingPattern
+            | IssueKind::RepeatedSignatures
+            | IssueKind::WeakErrorHandling
+            | IssueKind::BadNaming
+            | IssueKind::MediumNesting
+            | IssueKind::ComplexCondition
+            | IssueKind::CallbackHell
+            | IssueKind::TooManyImports => Severity::Medium,
+            IssueKind::FunctionMedium
+            | IssueKind::InconsistentBraceStyle
+            | IssueKind::OverloadedFunction
+            | IssueKind::LongLine
+            | IssueKind::StaleTodo
+            | IssueKind::PossiblyDeadCode
+            | IssueKind::Custom => Severity::Low,
+        }
+    }
+
+    /// 获取问题分类（与 `issue.category.*` i18n 键后缀一致）
+    ///
+    /// # Returns
+    /// * `&str` - 分类标识
+    pub fn category(&self) -> &'static str {
+        match self {
+            IssueKind::HighComplexity
+            | IssueKind::MediumComplexity
+            | IssueKind::ComplexPredicate
+            | IssueKind::HighCognitiveComplexity
+            | IssueKind::FunctionVeryLong
+            | IssueKind::FunctionLong
+            | IssueKind::FunctionMedium
+            | IssueKind::TooManyParameters
+            | IssueKind::ManyParameters => "complexity",
+            IssueKind::CommentVeryLow
+            | IssueKind::CommentLow
+            | IssueKind::ExportedFuncNoComment
+            | IssueKind::ComplexFuncNoComment
+            | IssueKind::StaleTodo => "comment",
+            IssueKind::BadNaming | IssueKind::OverloadedFunction | IssueKind::ShadowedFunction => {
+                "naming"
+            }
+            IssueKind::GlobalState
+            | IssueKind::DeepNesting
+            | IssueKind::MediumNesting
+            | IssueKind::InconsistentBraceStyle
+            | IssueKind::ComplexCondition
+            | IssueKind::CallbackHell
+            | IssueKind::TooManyImports
+            | IssueKind::LongLine
+            | IssueKind::PossiblyDeadCode => "structure",
+            IssueKind::DuplicateFunctions
+            | IssueKind::SimilarFunctions
+            | IssueKind::RepeatedNamingPattern
+            | IssueKind::RepeatedSignatures
+            | IssueKind::CrossFileDuplicate => "duplication",
+            IssueKind::MissingErrorHandling | IssueKind::WeakErrorHandling => "error",
+            IssueKind::Custom => "other",
+        }
+    }
+
+    /// 获取修复建议的 i18n 键
+    ///
+    /// # Returns
+    /// * `String` - 形如 `fix.<issue_kind>` 的键
+    pub fn fix_key(&self) -> String {
+        format!("fix.{}", self.key())
+    }
+}
+
+/// 度量指标发现的问题
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Issue {
+    /// 问题类型
+    pub kind: IssueKind,
+
+    /// 问题描述
+    pub message: String,
+
+    /// 起始行号（文件级问题为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+
+    /// 结束行号（单行问题与起始行相同）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+
+    /// 产生该问题的指标（与分析结果中 metrics 的键一致，非指标问题为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metric: Option<String>,
+}
+
+impl Issue {
+    /// 创建新的问题
+    ///
+    /// # Arguments
+    /// * `kind` - 问题类型
+    /// * `message` - 问题描述
+    ///
+    /// # Returns
+    /// * `Self` - 问题实例
+    pub fn new(kind: IssueKind, message: impl Into<String>) -> Self {
+        Issue {
+            kind,
+            message: message.into(),
+            line: None,
+            end_line: None,
+            metric: None,
+        }
+    }
+
+    /// 标记问题所在的单行
+    ///
+    /// # Arguments
+    /// * `line` - 行号
+    ///
+    /// # Returns
+    /// * `Self` - 问题实例
+    pub fn with_line(self, line: usize) -> Self {
+        self.with_lines(line, line)
+    }
+
+    /// 标记问题所在的行范围
+    ///
+    /// # Arguments
+    /// * `start` - 起始行号
+    /// * `end` - 结束行号
+    ///
+    /// # Returns
+    /// * `Self` - 问题实例
+    pub fn with_lines(mut self, start: usize, end: usize) -> Self {
+        self.line = Some(start);
+        self.end_line = Some(end);
+        self
+    }
+
+    /// 标记产生该问题的指标
+    ///
+    /// # Arguments
+    /// * `metric` - 指标名称
+    ///
+    /// # Returns
+    /// * `Self` - 问题实例
+    pub fn with_metric(mut self, metric: impl Into<String>) -> Self {
+        self.metric = Some(metric.into());
+        self
+    }
+
+    /// 判断问题是否与指定行范围重叠
+    ///
+    /// # Arguments
+    /// * `start` - 起始行号
+    /// * `end` - 结束行号
+    ///
+    /// # Returns
+    /// * `bool` - 是否重叠，文件级问题始终为 false
+    pub fn overlaps(&self, start: usize, end: usize) -> bool {
+        match (self.line, self.end_line) {
+            (Some(line), Some(end_line)) => line <= end && start <= end_line,
+            (Some(line), None) => start <= line && line <= end,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
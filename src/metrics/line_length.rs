@@ -0,0 +1,85 @@
+//! # 行长度度量
+//!
+//! 统计超过长度上限的代码行，过长的行需要横向滚动，难以阅读和审查
+
+use crate::i18n::Translator;
+use crate::metrics::{Issue, IssueKind, Metric, MetricResult};
+use crate::parser::ParseResult;
+
+/// 单行允许的最大字符数
+const MAX_LINE_LENGTH: usize = 120;
+
+/// 最多报告的超长行数量（按长度从大到小）
+const MAX_REPORTED_LINES: usize = 5;
+
+/// 超长行占比达到该值时得分为满分
+const FULL_SCORE_RATIO: f64 = 0.2;
+
+pub struct LineLengthMetric {
+    translator: Translator,
+}
+
+impl LineLengthMetric {
+    pub fn new(translator: Translator) -> Self {
+        LineLengthMetric { translator }
+    }
+}
+
+impl Metric for LineLengthMetric {
+    fn name(&self) -> &str {
+        "行长度"
+    }
+
+    fn id(&self) -> &str {
+        "line_length"
+    }
+
+    fn description(&self) -> &str {
+        "检测超过 120 个字符的代码行，过长的行需要横向滚动，难以阅读和审查"
+    }
+
+    fn weight(&self) -> f64 {
+        0.05
+    }
+
+    fn analyze(&self, parse_result: &dyn ParseResult) -> MetricResult {
+        let lines: Vec<&str> = parse_result.get_source().lines().collect();
+        let mut long_lines: Vec<(usize, usize)> = lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim_end().chars().count()))
+            .filter(|&(_, length)| length > MAX_LINE_LENGTH)
+            .collect();
+
+        let ratio = if lines.is_empty() {
+            0.0
+        } else {
+            long_lines.len() as f64 / lines.len() as f64
+        };
+        let score = (ratio / FULL_SCORE_RATIO).min(1.0);
+
+        // 只报告最长的几行，避免整份压缩文件刷屏
+        long_lines.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let issues = long_lines
+            .iter()
+            .take(MAX_REPORTED_LINES)
+            .map(|&(line, length)| {
+                Issue::new(
+                    IssueKind::LongLine,
+                    format!(
+                        "第 {} 行过长 ({} 个字符，上限 {})",
+                        line, length, MAX_LINE_LENGTH
+                    ),
+                )
+                .with_lines(line, line)
+            })
+            .collect();
+
+        MetricResult {
+            score,
+            weight: self.weight(),
+            description: self.description().to_string(),
+            issues,
+        }
+    }
+}
@@ -3,24 +3,55 @@
 //! 提供各种代码质量度量指标的计算
 
 mod base;
+mod cognitive;
 mod comment_ratio;
 mod complexity;
 mod duplication;
 mod error_handling;
 mod function_length;
+mod imports;
+mod issue;
+mod line_length;
 mod naming;
+mod state;
 mod structure;
 
+use crate::config::AnalysisConfig;
+use crate::error::{AppError, AppResult};
 use crate::i18n::Translator;
+use crate::parser::ParseResult;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 
-pub use base::{Metric, MetricResult};
+pub use base::{sanitize_score, Metric, MetricResult};
+pub use cognitive::CognitiveComplexityMetric;
 pub use comment_ratio::CommentRatioMetric;
 pub use complexity::CyclomaticComplexityMetric;
-pub use duplication::CodeDuplicationMetric;
+pub use duplication::{
+    CodeDuplicationMetric, DEFAULT_DUPLICATION_MIN_LINES, DUPLICATION_METRIC_NAME,
+};
 pub use error_handling::ErrorHandlingMetric;
-pub use function_length::FunctionLengthMetric;
+pub use function_length::{FunctionLengthMetric, LanguageThresholds, DEFAULT_THRESHOLDS_KEY};
+pub use imports::DEFAULT_MAX_IMPORTS;
+pub use issue::{Issue, IssueKind, Severity};
+pub use line_length::LineLengthMetric;
 pub use naming::NamingConventionMetric;
-pub use structure::StructureAnalysisMetric;
+pub use state::StateManagementMetric;
+pub use structure::{StructureAnalysisMetric, DEFAULT_MAX_BOOLEAN_OPERATORS};
+
+/// 内置指标的简短别名（别名 -> 指标标识），用于 `--metrics` 选择指标
+pub const METRIC_ALIASES: &[(&str, &str)] = &[
+    ("complexity", "cyclomatic_complexity"),
+    ("cognitive", "cognitive_complexity"),
+    ("length", "function_length"),
+    ("state", "state_management"),
+    ("comment", "comment_ratio"),
+    ("error", "error_handling"),
+    ("naming", "naming_convention"),
+    ("duplication", "code_duplication"),
+    ("structure", "structure_analysis"),
+    ("line_length", "line_length"),
+];
 
 /// 度量工厂
 ///
@@ -28,6 +59,9 @@ pub use structure::StructureAnalysisMetric;
 pub struct MetricFactory {
     /// 翻译器
     translator: Translator,
+
+    /// 用户注册的自定义指标
+    custom_metrics: Vec<Arc<dyn Metric + Send + Sync>>,
 }
 
 impl MetricFactory {
@@ -39,22 +73,203 @@ impl MetricFactory {
     /// # Returns
     /// * `Self` - 工厂实例
     pub fn new(translator: Translator) -> Self {
-        MetricFactory { translator }
+        MetricFactory {
+            translator,
+            custom_metrics: Vec::new(),
+        }
+    }
+
+    /// 切换翻译器（保留已注册的自定义指标）
+    ///
+    /// # Arguments
+    /// * `translator` - 翻译器
+    pub fn set_translator(&mut self, translator: Translator) {
+        self.translator = translator;
+    }
+
+    /// 注册自定义度量指标
+    ///
+    /// 自定义指标与内置指标一起运行，并按其权重计入文件和总体评分。
+    ///
+    /// # Arguments
+    /// * `metric` - 自定义指标
+    ///
+    /// # Returns
+    /// * `AppResult<()>` - 名称或标识与已有指标（或内置指标的别名）重复时返回配置错误
+    pub fn register_metric(&mut self, metric: Arc<dyn Metric + Send + Sync>) -> AppResult<()> {
+        let (name, id) = (metric.name(), metric.id());
+        let builtin = self.create_builtin_metrics(&AnalysisConfig::default());
+        let existing = builtin
+            .iter()
+            .map(|m| (m.name(), m.id()))
+            .chain(self.custom_metrics.iter().map(|m| (m.name(), m.id())));
+
+        for (existing_name, existing_id) in existing {
+            if existing_name == name {
+                return Err(AppError::ConfigError(format!(
+                    "自定义指标名称与已有指标重复: {}",
+                    name
+                )));
+            }
+            if existing_id == id {
+                return Err(AppError::ConfigError(format!(
+                    "自定义指标标识与已有指标重复: {}",
+                    id
+                )));
+            }
+        }
+
+        if METRIC_ALIASES.iter().any(|(alias, _)| *alias == id) {
+            return Err(AppError::ConfigError(format!(
+                "自定义指标标识与内置指标的别名重复: {}",
+                id
+            )));
+        }
+
+        self.custom_metrics.push(metric);
+        Ok(())
+    }
+
+    /// 创建所有度量指标（内置指标与自定义指标）
+    ///
+    /// 指标只依赖配置，每次分析创建一次，由各工作线程共享
+    ///
+    /// # Arguments
+    /// * `config` - 分析配置
+    ///
+    /// # Returns
+    /// * `Vec<Box<dyn Metric + Send + Sync>>` - 度量指标列表
+    pub fn create_all_metrics(
+        &self,
+        config: &AnalysisConfig,
+    ) -> Vec<Box<dyn Metric + Send + Sync>> {
+        let mut metrics = self.create_builtin_metrics(config);
+
+        for metric in &self.custom_metrics {
+            metrics.push(Box::new(Arc::clone(metric)));
+        }
+
+        if !config.metrics.is_empty() {
+            metrics.retain(|metric| {
+                config
+                    .metrics
+                    .iter()
+                    .any(|selected| is_selected(selected, &**metric))
+            });
+        }
+
+        if config.weights.is_empty() {
+            return metrics;
+        }
+
+        metrics
+            .into_iter()
+            .map(|metric| match weight_override(&config.weights, &*metric) {
+                Some(weight) => {
+                    Box::new(WeightOverride { metric, weight }) as Box<dyn Metric + Send + Sync>
+                }
+                None => metric,
+            })
+            .collect()
+    }
+
+    /// 列出可选择的指标
+    ///
+    /// # Returns
+    /// * `Vec<(String, String, String)>` - （选择用的名称、指标名称、描述），内置指标使用别名
+    pub fn available_metrics(&self) -> Vec<(String, String, String)> {
+        self.create_all_metrics(&AnalysisConfig::default())
+            .iter()
+            .map(|metric| {
+                let key = METRIC_ALIASES
+                    .iter()
+                    .find(|(_, id)| *id == metric.id())
+                    .map_or(metric.id(), |(alias, _)| alias);
+                (
+                    key.to_string(),
+                    metric.name().to_string(),
+                    metric.description().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    /// 找出指标选择中不对应任何指标的名称
+    ///
+    /// # Arguments
+    /// * `config` - 分析配置
+    ///
+    /// # Returns
+    /// * `Vec<String>` - 未知的指标名称
+    pub fn unknown_metric_names(&self, config: &AnalysisConfig) -> Vec<String> {
+        let metrics = self.create_all_metrics(&AnalysisConfig::default());
+        config
+            .metrics
+            .iter()
+            .filter(|selected| !metrics.iter().any(|m| is_selected(selected, &**m)))
+            .cloned()
+            .collect()
     }
 
-    /// 创建所有度量指标
+    /// 找出权重配置中不对应任何指标的键
+    ///
+    /// # Arguments
+    /// * `config` - 分析配置
     ///
     /// # Returns
-    /// * `Vec<Box<dyn Metric>>` - 度量指标列表
-    pub fn create_all_metrics(&self) -> Vec<Box<dyn Metric>> {
+    /// * `Vec<String>` - 未知的指标名称
+    pub fn unknown_weight_keys(&self, config: &AnalysisConfig) -> Vec<String> {
+        let metrics = self.create_all_metrics(&AnalysisConfig::default());
+        let mut unknown: Vec<String> = config
+            .weights
+            .keys()
+            .filter(|key| !metrics.iter().any(|m| is_selected(key, &**m)))
+            .cloned()
+            .collect();
+
+        unknown.sort();
+        unknown
+    }
+
+    /// 创建内置度量指标
+    ///
+    /// # Arguments
+    /// * `config` - 分析配置
+    ///
+    /// # Returns
+    /// * `Vec<Box<dyn Metric + Send + Sync>>` - 内置指标列表
+    fn create_builtin_metrics(
+        &self,
+        config: &AnalysisConfig,
+    ) -> Vec<Box<dyn Metric + Send + Sync>> {
         vec![
-            Box::new(CyclomaticComplexityMetric::new(self.translator.clone())),
-            Box::new(FunctionLengthMetric::new(self.translator.clone())),
-            Box::new(CommentRatioMetric::new(self.translator.clone())),
+            Box::new(
+                CyclomaticComplexityMetric::new(self.translator.clone())
+                    .with_predicate_prefixes(config.predicate_prefixes.clone())
+                    .with_language_thresholds(config.thresholds.clone()),
+            ),
+            Box::new(CognitiveComplexityMetric::new(self.translator.clone())),
+            Box::new(
+                FunctionLengthMetric::new(self.translator.clone())
+                    .with_language_thresholds(config.thresholds.clone()),
+            ),
+            Box::new(StateManagementMetric::new(self.translator.clone())),
+            Box::new(
+                CommentRatioMetric::new(self.translator.clone())
+                    .with_license_header_max_lines(config.license_header_max_lines),
+            ),
             Box::new(ErrorHandlingMetric::new(self.translator.clone())),
             Box::new(NamingConventionMetric::new(self.translator.clone())),
-            Box::new(CodeDuplicationMetric::new(self.translator.clone())),
-            Box::new(StructureAnalysisMetric::new(self.translator.clone())),
+            Box::new(
+                CodeDuplicationMetric::new(self.translator.clone())
+                    .with_min_lines(config.duplication_min_lines),
+            ),
+            Box::new(
+                StructureAnalysisMetric::new(self.translator.clone())
+                    .with_max_boolean_operators(config.max_boolean_operators)
+                    .with_max_imports(config.max_imports),
+            ),
+            Box::new(LineLengthMetric::new(self.translator.clone())),
         ]
     }
 
@@ -70,3 +285,230 @@ impl MetricFactory {
         ]
     }
 }
+
+/// 判断指标是否被选择（按别名、标识或名称匹配，不区分大小写）
+///
+/// # Arguments
+/// * `selected` - 用户指定的指标名称
+/// * `metric` - 度量指标
+///
+/// # Returns
+/// * `bool` - 是否选中
+fn is_selected(selected: &str, metric: &dyn Metric) -> bool {
+    let selected = selected.trim().to_lowercase();
+    let id = METRIC_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == selected)
+        .map_or(selected.as_str(), |(_, id)| id);
+
+    id == metric.id().to_lowercase() || selected == metric.name().to_lowercase()
+}
+
+/// 查找指标的自定义权重（负数按 0 处理）
+///
+/// 键可以是指标名称、标识或别名（与 `--metrics` 相同），
+/// 同一指标配置了多个键时，名称优先于标识，标识优先于别名
+///
+/// # Arguments
+/// * `weights` - 权重配置
+/// * `metric` - 度量指标
+///
+/// # Returns
+/// * `Option<f64>` - 自定义权重，未配置时为 None
+fn weight_override(weights: &BTreeMap<String, f64>, metric: &dyn Metric) -> Option<f64> {
+    weights
+        .get(metric.name())
+        .or_else(|| weights.get(metric.id()))
+        .or_else(|| {
+            weights
+                .iter()
+                .find(|(key, _)| is_selected(key, metric))
+                .map(|(_, weight)| weight)
+        })
+        .map(|&weight| {
+            if weight.is_nan() {
+                0.0
+            } else {
+                weight.max(0.0)
+            }
+        })
+}
+
+/// 使用自定义权重的度量指标
+struct WeightOverride {
+    /// 原始指标
+    metric: Box<dyn Metric + Send + Sync>,
+
+    /// 自定义权重
+    weight: f64,
+}
+
+impl Metric for WeightOverride {
+    fn name(&self) -> &str {
+        self.metric.name()
+    }
+
+    fn id(&self) -> &str {
+        self.metric.id()
+    }
+
+    fn description(&self) -> &str {
+        self.metric.description()
+    }
+
+    fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    fn analyze(&self, parse_result: &dyn ParseResult) -> MetricResult {
+        MetricResult {
+            weight: self.weight,
+            ..self.metric.analyze(parse_result)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::CodeAnalyzer;
+    use crate::common::LanguageType;
+    use crate::i18n::Language;
+
+    /// 总是给出最差得分的自定义指标
+    struct AlwaysWorst {
+        name: String,
+        id: String,
+    }
+
+    impl AlwaysWorst {
+        fn new(name: &str, id: &str) -> Self {
+            AlwaysWorst {
+                name: name.to_string(),
+                id: id.to_string(),
+            }
+        }
+    }
+
+    impl Metric for AlwaysWorst {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn description(&self) -> &str {
+            "always worst"
+        }
+
+        fn weight(&self) -> f64 {
+            1.0
+        }
+
+        fn analyze(&self, _parse_result: &dyn ParseResult) -> MetricResult {
+            MetricResult::new(1.0, self.weight(), String::new(), Vec::new())
+        }
+    }
+
+    fn custom(name: &str, id: &str) -> Arc<dyn Metric + Send + Sync> {
+        Arc::new(AlwaysWorst::new(name, id))
+    }
+
+    fn factory() -> MetricFactory {
+        MetricFactory::new(Translator::new(Language::ZhCN))
+    }
+
+    #[test]
+    fn register_metric_rejects_name_and_id_collisions() {
+        let mut factory = factory();
+        let builtin_name = factory.create_builtin_metrics(&AnalysisConfig::default())[0]
+            .name()
+            .to_string();
+
+        assert!(factory
+            .register_metric(custom(&builtin_name, "fresh"))
+            .is_err());
+        assert!(factory
+            .register_metric(custom("圈复杂度2", "cyclomatic_complexity"))
+            .is_err());
+        assert!(factory
+            .register_metric(custom("复杂度别名", "complexity"))
+            .is_err());
+
+        assert!(factory.register_metric(custom("自定义", "custom")).is_ok());
+        assert!(factory
+            .register_metric(custom("自定义", "custom_2"))
+            .is_err());
+        assert!(factory.register_metric(custom("另一个", "custom")).is_err());
+        assert!(factory
+            .register_metric(custom("另一个", "custom_2"))
+            .is_ok());
+    }
+
+    #[test]
+    fn registered_metric_is_reported_and_raises_the_score() {
+        let source = "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n";
+        let config = AnalysisConfig::default();
+
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let before = analyzer
+            .analyze_string(source, LanguageType::Rust, &config)
+            .unwrap();
+
+        analyzer
+            .register_metric(AlwaysWorst::new("自定义", "custom"))
+            .unwrap();
+        let after = analyzer
+            .analyze_string(source, LanguageType::Rust, &config)
+            .unwrap();
+
+        assert_eq!(after.metrics["自定义"].score, 1.0);
+        assert!(after.code_quality_score > before.code_quality_score);
+    }
+
+    /// 按标识查找指标的权重
+    fn weight_of(metrics: &[Box<dyn Metric + Send + Sync>], id: &str) -> f64 {
+        metrics.iter().find(|m| m.id() == id).unwrap().weight()
+    }
+
+    #[test]
+    fn weights_accept_aliases_ids_and_names() {
+        let factory = factory();
+        let naming_name = factory
+            .create_all_metrics(&AnalysisConfig::default())
+            .iter()
+            .find(|m| m.id() == "naming_convention")
+            .unwrap()
+            .name()
+            .to_string();
+
+        let mut config = AnalysisConfig::default();
+        config.weights.insert("Complexity".to_string(), 0.7);
+        config.weights.insert("error_handling".to_string(), 0.6);
+        config.weights.insert(naming_name, 0.5);
+        config.weights.insert("comment".to_string(), -1.0);
+        config.weights.insert("no_such_metric".to_string(), 0.4);
+
+        let metrics = factory.create_all_metrics(&config);
+        assert_eq!(weight_of(&metrics, "cyclomatic_complexity"), 0.7);
+        assert_eq!(weight_of(&metrics, "error_handling"), 0.6);
+        assert_eq!(weight_of(&metrics, "naming_convention"), 0.5);
+        assert_eq!(weight_of(&metrics, "comment_ratio"), 0.0);
+        assert_eq!(factory.unknown_weight_keys(&config), vec!["no_such_metric"]);
+    }
+
+    #[test]
+    fn exact_weight_key_takes_precedence_over_alias() {
+        let mut config = AnalysisConfig::default();
+        config.weights.insert("complexity".to_string(), 0.9);
+        config
+            .weights
+            .insert("cyclomatic_complexity".to_string(), 0.1);
+
+        let metrics = factory().create_all_metrics(&config);
+        assert_eq!(weight_of(&metrics, "cyclomatic_complexity"), 0.1);
+    }
+}
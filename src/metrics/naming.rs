@@ -1,6 +1,23 @@
+use crate::common::LanguageType;
 use crate::i18n::Translator;
-use crate::metrics::{Metric, MetricResult};
-use crate::parser::ParseResult;
+use crate::metrics::{Issue, IssueKind, Metric, MetricResult};
+use crate::parser::{Function, Identifier, IdentifierKind, ParseResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// 每个文件最多报告的不规范变量名数量
+const MAX_REPORTED_IDENTIFIERS: usize = 5;
+
+/// 惯用的单字母变量名（循环计数、坐标、异常等）
+const CONVENTIONAL_SHORT_NAMES: &[&str] = &["i", "j", "k", "n", "x", "y", "e"];
+
+/// 无意义的变量名
+const MEANINGLESS_NAMES: &[&str] = &["tmp", "temp", "foo", "bar", "baz", "xxx"];
+
+/// 预处理器的条件分支（如 `#else`）
+static PREPROCESSOR_BRANCH: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*#\s*(?:else|elif)\b").unwrap());
 
 pub struct NamingConventionMetric {
     translator: Translator,
@@ -17,6 +34,10 @@ impl Metric for NamingConventionMetric {
         "命名规范"
     }
 
+    fn id(&self) -> &str {
+        "naming_convention"
+    }
+
     fn description(&self) -> &str {
         "检测代码中的命名规范，良好的命名能提高代码可读性"
     }
@@ -32,14 +53,52 @@ impl Metric for NamingConventionMetric {
         let mut bad_names = 0;
 
         for func in functions {
-            if self.is_bad_name(&func.name) {
-                issues.push(format!("函数名 '{}' 不符合规范", func.name));
+            if !func.is_anonymous() && self.is_bad_name(&func.name) {
+                issues.push(
+                    Issue::new(
+                        IssueKind::BadNaming,
+                        format!("函数名 '{}' 不符合规范", func.name),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
                 bad_names += 1;
             }
         }
 
-        let bad_ratio = if !functions.is_empty() {
-            bad_names as f64 / functions.len() as f64
+        let duplicate_issues = self.find_duplicate_names(parse_result);
+        bad_names += duplicate_issues
+            .iter()
+            .filter(|issue| issue.kind == IssueKind::ShadowedFunction)
+            .count();
+        issues.extend(duplicate_issues);
+
+        let language = parse_result.get_language();
+        let identifiers = parse_result.get_identifiers();
+        let bad_identifiers: Vec<(&Identifier, &str)> = identifiers
+            .iter()
+            .filter_map(|identifier| {
+                self.identifier_problem(&identifier.name, language)
+                    .map(|problem| (identifier, problem))
+            })
+            .collect();
+        bad_names += bad_identifiers.len();
+        issues.extend(bad_identifiers.iter().take(MAX_REPORTED_IDENTIFIERS).map(
+            |(identifier, problem)| {
+                let kind = match identifier.kind {
+                    IdentifierKind::Variable => "变量名",
+                    IdentifierKind::Parameter => "参数名",
+                };
+                Issue::new(
+                    IssueKind::BadNaming,
+                    format!("{} '{}' {}", kind, identifier.name, problem),
+                )
+                .with_lines(identifier.line, identifier.line)
+            },
+        ));
+
+        let total_names = functions.len() + identifiers.len();
+        let bad_ratio = if total_names > 0 {
+            bad_names as f64 / total_names as f64
         } else {
             0.0
         };
@@ -68,17 +127,305 @@ impl NamingConventionMetric {
             || name.chars().all(|c| c == 'x' || c == 'y' || c == 'z')
     }
 
+    /// 检查变量名或参数名的问题
+    ///
+    /// 过短（惯用的单字母名除外，Go 允许短名）或无意义的名称，以及不符合语言大小写约定的名称：
+    /// Rust/Python 使用 snake_case，JavaScript/TypeScript/Java/Swift 使用 camelCase。
+    /// 全大写的常量名和首字母大写的类型别名不检查大小写。
+    ///
+    /// # Arguments
+    /// * `name` - 名称
+    /// * `language` - 语言类型
+    ///
+    /// # Returns
+    /// * `Option<&str>` - 问题描述，名称规范时为 None
+    fn identifier_problem(&self, name: &str, language: LanguageType) -> Option<&'static str> {
+        let is_short = name.chars().count() == 1
+            && !CONVENTIONAL_SHORT_NAMES.contains(&name)
+            && language != LanguageType::Go;
+        if is_short || MEANINGLESS_NAMES.contains(&name.to_lowercase().as_str()) {
+            return Some("过短或无意义");
+        }
+
+        let starts_lowercase = name.chars().next().is_some_and(char::is_lowercase);
+        let has_uppercase = name.chars().any(char::is_uppercase);
+        let has_lowercase = name.chars().any(char::is_lowercase);
+
+        match language {
+            LanguageType::Rust | LanguageType::Python if starts_lowercase && has_uppercase => {
+                Some("不符合 snake_case 命名规范")
+            }
+            LanguageType::JavaScript
+            | LanguageType::TypeScript
+            | LanguageType::Java
+            | LanguageType::Swift
+                if name.contains('_') && has_lowercase =>
+            {
+                Some("不符合 camelCase 命名规范")
+            }
+            _ => None,
+        }
+    }
+
+    /// 检测同一文件中的同名函数
+    ///
+    /// Java/C#/C++ 允许重载，同名函数仅作提示；Python/JavaScript/TypeScript/PHP
+    /// 中后面的定义会覆盖前面的，视为重复定义。其他语言要么由编译器拒绝重复定义，
+    /// 要么同名是惯用写法（如 Elixir 的多子句函数、Go 的不同接收者方法），不做检查。
+    /// 分属不同类、外层函数或条件分支的同名定义不算重复。
+    ///
+    /// # Arguments
+    /// * `parse_result` - 解析结果
+    ///
+    /// # Returns
+    /// * `Vec<Issue>` - 发现的问题
+    fn find_duplicate_names(&self, parse_result: &dyn ParseResult) -> Vec<Issue> {
+        let overloads_allowed = match parse_result.get_language() {
+            LanguageType::Java | LanguageType::CSharp | LanguageType::CPlusPlus => true,
+            LanguageType::Python
+            | LanguageType::JavaScript
+            | LanguageType::TypeScript
+            | LanguageType::PHP => false,
+            _ => return Vec::new(),
+        };
+
+        let lines: Vec<&str> = parse_result.get_source().lines().collect();
+        let mut groups: HashMap<&str, Vec<&Function>> = HashMap::new();
+        for func in parse_result.get_functions() {
+            if !func.is_anonymous() {
+                groups.entry(func.name.as_str()).or_default().push(func);
+            }
+        }
+
+        let mut issues = Vec::new();
+        for (name, mut group) in groups {
+            group.sort_by_key(|func| func.start_line);
+
+            // 按作用域边界拆分，只比较同一作用域内的定义
+            let mut scope: Vec<&Function> = Vec::new();
+            for func in group {
+                if let Some(previous) = scope.last() {
+                    if crosses_scope_boundary(&lines, previous, func) {
+                        issues.extend(self.duplicate_issue(name, &scope, overloads_allowed));
+                        scope.clear();
+                    }
+                }
+                scope.push(func);
+            }
+            issues.extend(self.duplicate_issue(name, &scope, overloads_allowed));
+        }
+
+        issues.sort_by_key(|issue| issue.line);
+        issues
+    }
+
+    /// 为同一作用域内的同名函数生成问题
+    ///
+    /// # Arguments
+    /// * `name` - 函数名
+    /// * `scope` - 同一作用域内的同名函数（按行号排序）
+    /// * `overloads_allowed` - 语言是否允许重载
+    ///
+    /// # Returns
+    /// * `Option<Issue>` - 同名函数不足两个时为 None
+    fn duplicate_issue(
+        &self,
+        name: &str,
+        scope: &[&Function],
+        overloads_allowed: bool,
+    ) -> Option<Issue> {
+        let (first, last) = match scope {
+            [first, .., last] => (first, last),
+            _ => return None,
+        };
+        let line_list = scope
+            .iter()
+            .map(|func| func.start_line.to_string())
+            .collect::<Vec<_>>()
+            .join("、");
+
+        let issue = if overloads_allowed {
+            Issue::new(
+                IssueKind::OverloadedFunction,
+                format!(
+                    "函数 '{}' 有 {} 个重载（第 {} 行）",
+                    name,
+                    scope.len(),
+                    line_list
+                ),
+            )
+            .with_lines(first.start_line, last.end_line)
+        } else {
+            Issue::new(
+                IssueKind::ShadowedFunction,
+                format!(
+                    "函数 '{}' 重复定义了 {} 次（第 {} 行），只有最后一个定义生效",
+                    name,
+                    scope.len(),
+                    line_list
+                ),
+            )
+            .with_lines(last.start_line, last.end_line)
+        };
+
+        Some(issue)
+    }
+
+    /// 计算得分
+    ///
+    /// 从基础分开始，随不良命名比例线性增长，全部不良时为满分 1.0
+    ///
+    /// # Arguments
+    /// * `bad_ratio` - 不良命名比例（同名函数可能重复计数，会超过 1）
+    ///
+    /// # Returns
+    /// * `f64` - 得分
     fn calculate_score(&self, bad_ratio: f64) -> f64 {
-        // 基础分0.4，每1%不良命名增加0.1分
         let base_score = 0.4;
-        let increase_per_percent = 10.0;
 
-        let score = base_score + (bad_ratio * increase_per_percent);
+        base_score + bad_ratio.min(1.0) * (1.0 - base_score)
+    }
+}
+
+/// 判断两个同名函数之间是否隔着作用域边界
+///
+/// 两个定义的缩进不同，或之间出现缩进更浅的代码行（类、外层函数或条件分支的边界）、
+/// 预处理器条件分支时，视为不同作用域
+///
+/// # Arguments
+/// * `lines` - 代码行
+/// * `previous` - 前一个定义
+/// * `current` - 后一个定义
+///
+/// # Returns
+/// * `bool` - 是否属于不同作用域
+fn crosses_scope_boundary(lines: &[&str], previous: &Function, current: &Function) -> bool {
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let (Some(previous_line), Some(current_line)) = (
+        lines.get(previous.start_line.saturating_sub(1)),
+        lines.get(current.start_line.saturating_sub(1)),
+    ) else {
+        return false;
+    };
 
-        if score > 1.0 {
-            1.0
-        } else {
-            score
+    let indent = indent_of(current_line);
+    if indent_of(previous_line) != indent {
+        return true;
+    }
+
+    lines[previous.start_line..current.start_line - 1]
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .any(|line| indent_of(line) < indent || PREPROCESSOR_BRANCH.is_match(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Language;
+    use crate::parser::BaseParseResult;
+
+    fn metric() -> NamingConventionMetric {
+        NamingConventionMetric::new(Translator::new(Language::ZhCN))
+    }
+
+    /// 构造 `total` 个函数，其中 `bad` 个函数名过短
+    fn functions(total: usize, bad: usize) -> BaseParseResult {
+        let functions = (0..total)
+            .map(|i| {
+                let name = if i < bad {
+                    format!("f{}", i % 10)
+                } else {
+                    format!("compute_value_{}", i)
+                };
+                Function::new(name, i * 3 + 1, i * 3 + 2, 1, 0)
+            })
+            .collect();
+
+        BaseParseResult {
+            functions,
+            comment_lines: 0,
+            total_lines: total * 3,
+            source: String::new(),
+            language: LanguageType::Rust,
         }
     }
+
+    #[test]
+    fn score_grows_monotonically_without_saturating() {
+        let metric = metric();
+        let scores: Vec<f64> = [0.0, 0.25, 0.5, 1.0]
+            .iter()
+            .map(|&ratio| metric.calculate_score(ratio))
+            .collect();
+
+        assert!(scores.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(scores[1] < 1.0 && scores[2] < 1.0);
+        assert_eq!(scores[3], 1.0);
+        assert_eq!(metric.calculate_score(3.0), 1.0);
+    }
+
+    #[test]
+    fn analyzed_score_scales_with_bad_name_ratio() {
+        let metric = metric();
+        let scores: Vec<f64> = [0, 2, 4, 8]
+            .iter()
+            .map(|&bad| metric.analyze(&functions(8, bad)).score)
+            .collect();
+
+        assert!(scores.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(scores[1] < 1.0 && scores[2] < 1.0);
+        assert_eq!(scores[3], 1.0);
+    }
+
+    /// 解析源码后返回同名函数相关的问题
+    fn duplicate_issues(file_name: &str, source: &str) -> Vec<Issue> {
+        let path = std::path::Path::new(file_name);
+        let parse_result = crate::parser::create_parser_for_file(path)
+            .parse(path, source)
+            .unwrap();
+        metric()
+            .analyze(&*parse_result)
+            .issues
+            .into_iter()
+            .filter(|issue| {
+                matches!(
+                    issue.kind,
+                    IssueKind::ShadowedFunction | IssueKind::OverloadedFunction
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_named_python_functions_are_shadowed() {
+        let source = "def load_config(path):\n    return open(path).read()\n\n\n\
+                      def save_config(path, data):\n    open(path, 'w').write(data)\n\n\n\
+                      def load_config(path, default=None):\n    return default\n";
+        let issues = duplicate_issues("config.py", source);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, IssueKind::ShadowedFunction);
+        assert_eq!(issues[0].line, Some(9));
+        assert!(issues[0].message.contains("load_config"));
+    }
+
+    #[test]
+    fn same_named_methods_in_different_classes_are_not_shadowed() {
+        let source = "class Reader:\n    def load(self):\n        return 1\n\n\n\
+                      class Writer:\n    def load(self):\n        return 2\n";
+        assert!(duplicate_issues("io.py", source).is_empty());
+    }
+
+    #[test]
+    fn java_overloads_are_not_reported_as_shadowing() {
+        let source = "public class Calc {\n\
+                      \x20   public int add(int a, int b) {\n        return a + b;\n    }\n\
+                      \x20   public double add(double a, double b) {\n        return a + b;\n    }\n\
+                      }\n";
+        let issues = duplicate_issues("Calc.java", source);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, IssueKind::OverloadedFunction);
+    }
 }
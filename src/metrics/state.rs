@@ -0,0 +1,351 @@
+//! # 状态管理度量
+//!
+//! 统计模块级可变全局状态，全局状态越多，状态变化越难追踪
+
+use crate::common::LanguageType;
+use crate::i18n::Translator;
+use crate::metrics::{Issue, IssueKind, Metric, MetricResult};
+use crate::parser::{strip_source, ParseResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// 每个可变全局变量增加的得分
+const GLOBAL_STATE_PENALTY: f64 = 0.08;
+
+/// 全局状态带来的最大得分
+const MAX_GLOBAL_STATE_SCORE: f64 = 0.5;
+
+/// 超过该数量的可变全局变量时报告问题
+const GLOBAL_STATE_ISSUE_THRESHOLD: usize = 3;
+
+/// Rust `static mut` 声明
+static RUST_STATIC_MUT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:pub(?:\([^)]*\))?\s+)?static\s+mut\s+(\w+)").unwrap());
+
+/// JavaScript/TypeScript 顶层 `let`/`var` 声明
+static JS_TOP_LEVEL_VAR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:export\s+)?(?:let|var)\s+(\w+)").unwrap());
+
+/// Go 顶层 `var` 声明
+static GO_TOP_LEVEL_VAR: Lazy<Regex> = Lazy::new(|| Regex::new(r"^var\s+(\w+)").unwrap());
+
+/// Python/PHP `global` 语句
+static GLOBAL_STATEMENT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^global\s+(.+)$").unwrap());
+
+/// 声明语句中的变量名（位于 `=`、`[` 或 `;` 之前的最后一个标识符）
+static DECLARED_NAME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\w+)\s*(?:\[[^\]]*\]\s*)*(?:=|;)").unwrap());
+
+pub struct StateManagementMetric {
+    translator: Translator,
+}
+
+impl StateManagementMetric {
+    pub fn new(translator: Translator) -> Self {
+        StateManagementMetric { translator }
+    }
+}
+
+impl Metric for StateManagementMetric {
+    fn name(&self) -> &str {
+        "状态管理"
+    }
+
+    fn id(&self) -> &str {
+        "state_management"
+    }
+
+    fn description(&self) -> &str {
+        "检测代码中状态变量的管理，良好的状态管理能提高代码可维护性和可预测性"
+    }
+
+    fn weight(&self) -> f64 {
+        0.1
+    }
+
+    fn analyze(&self, parse_result: &dyn ParseResult) -> MetricResult {
+        let mut issues = Vec::new();
+        let score = self.analyze_global_state(parse_result, &mut issues);
+
+        MetricResult::new(score, self.weight(), self.description().to_string(), issues)
+    }
+}
+
+impl StateManagementMetric {
+    /// 检查模块级可变全局状态
+    ///
+    /// # Arguments
+    /// * `parse_result` - 解析结果
+    /// * `issues` - 问题列表
+    ///
+    /// # Returns
+    /// * `f64` - 全局状态部分得分
+    fn analyze_global_state(&self, parse_result: &dyn ParseResult, issues: &mut Vec<Issue>) -> f64 {
+        let globals = find_mutable_globals(parse_result.get_source(), parse_result.get_language());
+
+        if globals.len() > GLOBAL_STATE_ISSUE_THRESHOLD {
+            issues.push(Issue::new(
+                IssueKind::GlobalState,
+                format!(
+                    "文件包含 {} 个可变全局变量 ({})，建议封装到结构体或模块内部",
+                    globals.len(),
+                    globals.join(", ")
+                ),
+            ));
+        }
+
+        (globals.len() as f64 * GLOBAL_STATE_PENALTY).min(MAX_GLOBAL_STATE_SCORE)
+    }
+}
+
+/// 查找文件中的可变全局变量
+///
+/// 基于剥离注释和字符串后的源码逐行匹配，各语言的规则：
+/// Rust 统计 `static mut`；Go 统计顶层 `var`；JavaScript/TypeScript 统计顶层 `let`/`var`；
+/// Python/PHP 统计 `global` 语句引用的变量；Java/C# 统计非 final/readonly 的静态字段；
+/// C/C++ 统计顶层非 const 的变量声明。
+///
+/// # Arguments
+/// * `source` - 源码
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `Vec<String>` - 去重后的变量名（按出现顺序）
+fn find_mutable_globals(source: &str, language: LanguageType) -> Vec<String> {
+    let stripped = strip_source(source, language);
+    let mut names: Vec<String> = Vec::new();
+    let mut depth: i32 = 0;
+    let mut in_var_block = false;
+
+    for raw_line in stripped.lines() {
+        let line = raw_line.trim();
+        let top_level = depth == 0;
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let found = match language {
+            LanguageType::Rust => capture_name(&RUST_STATIC_MUT, line),
+            LanguageType::JavaScript | LanguageType::TypeScript if top_level => {
+                capture_name(&JS_TOP_LEVEL_VAR, line)
+            }
+            LanguageType::Go if top_level => {
+                if in_var_block {
+                    in_var_block = !line.starts_with(')');
+                    line.split_whitespace()
+                        .next()
+                        .filter(|_| in_var_block)
+                        .map(str::to_string)
+                        .into_iter()
+                        .collect()
+                } else if line == "var (" {
+                    in_var_block = true;
+                    Vec::new()
+                } else {
+                    capture_name(&GO_TOP_LEVEL_VAR, line)
+                }
+            }
+            LanguageType::Python | LanguageType::PHP => GLOBAL_STATEMENT
+                .captures(line)
+                .map(|caps| {
+                    caps[1]
+                        .split(',')
+                        .map(|name| name.trim().trim_start_matches('$').trim_end_matches(';'))
+                        .filter(|name| !name.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            LanguageType::Java | LanguageType::CSharp if is_mutable_static_field(line) => {
+                capture_name(&DECLARED_NAME, line)
+            }
+            LanguageType::C | LanguageType::CPlusPlus if top_level && is_c_global(line) => {
+                capture_name(&DECLARED_NAME, line)
+            }
+            _ => Vec::new(),
+        };
+
+        for name in found {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+
+    names
+}
+
+/// 用正则的第一个捕获组提取变量名
+///
+/// # Arguments
+/// * `regex` - 正则
+/// * `line` - 代码行
+///
+/// # Returns
+/// * `Vec<String>` - 变量名（未匹配时为空）
+fn capture_name(regex: &Regex, line: &str) -> Vec<String> {
+    regex
+        .captures(line)
+        .map(|caps| vec![caps[1].to_string()])
+        .unwrap_or_default()
+}
+
+/// 判断 Java/C# 代码行是否为可变静态字段声明
+///
+/// # Arguments
+/// * `line` - 代码行
+///
+/// # Returns
+/// * `bool` - 是否为可变静态字段
+fn is_mutable_static_field(line: &str) -> bool {
+    let words: Vec<&str> = line
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .collect();
+    let has_word = |word: &str| words.contains(&word);
+
+    has_word("static")
+        && ![
+            "final",
+            "readonly",
+            "const",
+            "class",
+            "interface",
+            "enum",
+            "struct",
+        ]
+        .iter()
+        .any(|w| has_word(w))
+        && !line.contains('(')
+        && line.ends_with(';')
+}
+
+/// 判断 C/C++ 顶层代码行是否为可变全局变量声明
+///
+/// # Arguments
+/// * `line` - 代码行
+///
+/// # Returns
+/// * `bool` - 是否为可变全局变量
+fn is_c_global(line: &str) -> bool {
+    const NON_VARIABLE_PREFIXES: [&str; 12] = [
+        "#",
+        "}",
+        "typedef",
+        "extern",
+        "using",
+        "namespace",
+        "template",
+        "return",
+        "const ",
+        "constexpr",
+        "static_assert",
+        "enum",
+    ];
+
+    line.ends_with(';')
+        && !line.contains('(')
+        && !line.contains(" const ")
+        && line.split_whitespace().count() >= 2
+        && !NON_VARIABLE_PREFIXES.iter().any(|p| line.starts_with(p))
+        && !line.starts_with("static const")
+        && !is_forward_declaration(line)
+}
+
+/// 判断 C/C++ 代码行是否为类型的前置声明（如 `struct foo;`）
+///
+/// # Arguments
+/// * `line` - 代码行
+///
+/// # Returns
+/// * `bool` - 是否为前置声明
+fn is_forward_declaration(line: &str) -> bool {
+    let mut words = line.split_whitespace();
+    matches!(words.next(), Some("struct" | "class" | "union")) && words.count() == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Language;
+    use crate::parser::create_parser_for_file;
+    use std::path::Path;
+
+    fn analyze(file_name: &str, source: &str) -> MetricResult {
+        let path = Path::new(file_name);
+        let parse_result = create_parser_for_file(path).parse(path, source).unwrap();
+        StateManagementMetric::new(Translator::new(Language::ZhCN)).analyze(&*parse_result)
+    }
+
+    #[test]
+    fn global_mutable_state_scores_worse_than_none() {
+        let with_globals = "let count = 0;\n\
+                            var cache = {};\n\
+                            let lastUser = null;\n\
+                            let retries = 3;\n\
+                            export let debug = false;\n\
+                            \n\
+                            export function track(user) {\n\
+                            \x20 count += 1;\n\
+                            \x20 lastUser = user;\n\
+                            \x20 cache[user.id] = user;\n\
+                            }\n";
+        let without_globals = "const MAX_RETRIES = 3;\n\
+                               \n\
+                               export function track(state, user) {\n\
+                               \x20 let count = state.count + 1;\n\
+                               \x20 return { ...state, count, lastUser: user };\n\
+                               }\n";
+
+        let bad = analyze("tracker.js", with_globals);
+        let good = analyze("tracker.js", without_globals);
+
+        assert!(bad.score > good.score);
+        assert_eq!(good.score, 0.0);
+        let issue = bad
+            .issues
+            .iter()
+            .find(|issue| issue.kind == IssueKind::GlobalState)
+            .expect("global mutable state should be reported");
+        assert!(issue
+            .message
+            .contains("count, cache, lastUser, retries, debug"));
+        assert!(good.issues.is_empty());
+    }
+
+    #[test]
+    fn mutable_globals_are_found_per_language() {
+        let cases = [
+            (
+                LanguageType::Rust,
+                "static mut COUNTER: u32 = 0;\nstatic LIMIT: u32 = 5;\n",
+                vec!["COUNTER"],
+            ),
+            (
+                LanguageType::Go,
+                "var (\n\tcount int\n\tname string\n)\nvar ready bool\n",
+                vec!["count", "name", "ready"],
+            ),
+            (
+                LanguageType::Python,
+                "def bump():\n    global counter, total\n    counter += 1\n",
+                vec!["counter", "total"],
+            ),
+            (
+                LanguageType::Java,
+                "class A {\n    static int hits = 0;\n    static final int MAX = 3;\n}\n",
+                vec!["hits"],
+            ),
+        ];
+
+        for (language, source, expected) in cases {
+            assert_eq!(
+                find_mutable_globals(source, language),
+                expected,
+                "{:?}",
+                language
+            );
+        }
+    }
+}
@@ -1,14 +1,79 @@
+use crate::common::LanguageType;
 use crate::i18n::Translator;
-use crate::metrics::{Metric, MetricResult};
-use crate::parser::ParseResult;
+use crate::metrics::imports::{count_imports, DEFAULT_MAX_IMPORTS};
+use crate::metrics::{Issue, IssueKind, Metric, MetricResult};
+use crate::parser::{strip_source, Function, ParseResult};
+
+/// 少数派风格至少出现的次数，低于该值视为偶发写法
+const MIN_MIXED_BRACES: usize = 3;
+
+/// 少数派风格占比超过该值时视为混用
+const MIXED_BRACE_RATIO: f64 = 0.2;
+
+/// 默认单行条件表达式允许的最多逻辑运算符数
+pub const DEFAULT_MAX_BOOLEAN_OPERATORS: usize = 5;
+
+/// 每个过长条件表达式增加的分数
+const COMPLEX_CONDITION_PENALTY: f64 = 0.02;
+
+/// 过长条件表达式最多增加的分数
+const MAX_COMPLEX_CONDITION_PENALTY: f64 = 0.1;
+
+/// JS/TS 回调函数允许的最大嵌套层数
+const MAX_CALLBACK_DEPTH: usize = 2;
+
+/// JS/TS 单条 Promise 链允许的最多 `.then()`/`.catch()`/`.finally()` 调用数
+const MAX_PROMISE_CHAIN: usize = 3;
+
+/// 导入过多时增加的分数
+const TOO_MANY_IMPORTS_PENALTY: f64 = 0.05;
+
+/// 每个回调地狱问题增加的分数
+const CALLBACK_HELL_PENALTY: f64 = 0.03;
+
+/// 回调地狱问题最多增加的分数
+const MAX_CALLBACK_HELL_PENALTY: f64 = 0.15;
+
+/// Promise 链上的方法
+const PROMISE_METHODS: [&str; 3] = ["then", "catch", "finally"];
 
 pub struct StructureAnalysisMetric {
     translator: Translator,
+    max_boolean_operators: usize,
+    max_imports: usize,
 }
 
 impl StructureAnalysisMetric {
     pub fn new(translator: Translator) -> Self {
-        StructureAnalysisMetric { translator }
+        StructureAnalysisMetric {
+            translator,
+            max_boolean_operators: DEFAULT_MAX_BOOLEAN_OPERATORS,
+            max_imports: DEFAULT_MAX_IMPORTS,
+        }
+    }
+
+    /// 设置单行条件表达式允许的最多逻辑运算符数
+    ///
+    /// # Arguments
+    /// * `max` - 最多运算符数，超过即报告问题
+    ///
+    /// # Returns
+    /// * `Self` - 度量器实例
+    pub fn with_max_boolean_operators(mut self, max: usize) -> Self {
+        self.max_boolean_operators = max;
+        self
+    }
+
+    /// 设置每个文件允许的最多导入语句数
+    ///
+    /// # Arguments
+    /// * `max` - 最多导入语句数，超过即报告问题
+    ///
+    /// # Returns
+    /// * `Self` - 度量器实例
+    pub fn with_max_imports(mut self, max: usize) -> Self {
+        self.max_imports = max;
+        self
     }
 }
 
@@ -17,6 +82,10 @@ impl Metric for StructureAnalysisMetric {
         "代码结构"
     }
 
+    fn id(&self) -> &str {
+        "structure_analysis"
+    }
+
     fn description(&self) -> &str {
         "检测代码的嵌套深度和引用复杂度，评估结构清晰度"
     }
@@ -30,31 +99,87 @@ impl Metric for StructureAnalysisMetric {
         let functions = parse_result.get_functions();
         let mut issues = Vec::new();
 
-        // 分析嵌套深度（基于复杂度估算）
+        // 分析嵌套深度（由解析器记录的真实深度）
         let mut max_nesting_depth = 0;
 
         for func in functions {
-            // 使用复杂度作为嵌套深度的估算
-            let estimated_depth = (func.complexity as f64 / 3.0).ceil() as usize;
+            let depth = func.max_nesting_depth;
 
-            if estimated_depth > max_nesting_depth {
-                max_nesting_depth = estimated_depth;
+            if depth > max_nesting_depth {
+                max_nesting_depth = depth;
             }
 
-            if estimated_depth > 5 {
-                issues.push(format!(
-                    "函数 {} 嵌套深度过高 (估算 {} 层)，建议重构",
-                    func.name, estimated_depth
-                ));
-            } else if estimated_depth > 3 {
-                issues.push(format!(
-                    "函数 {} 嵌套深度较高 (估算 {} 层)，考虑简化",
-                    func.name, estimated_depth
-                ));
+            if depth > 5 {
+                issues.push(
+                    Issue::new(
+                        IssueKind::DeepNesting,
+                        format!("函数 {} 嵌套深度过高 ({} 层)，建议重构", func.name, depth),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
+            } else if depth > 3 {
+                issues.push(
+                    Issue::new(
+                        IssueKind::MediumNesting,
+                        format!("函数 {} 嵌套深度较高 ({} 层)，考虑简化", func.name, depth),
+                    )
+                    .with_lines(func.start_line, func.end_line),
+                );
             }
         }
 
-        let score = self.calculate_score(max_nesting_depth);
+        let mut score = self.calculate_score(max_nesting_depth);
+
+        // 检测大括号风格混用
+        let (trailing, own_line) = count_brace_styles(parse_result);
+        if is_mixed_brace_style(trailing, own_line) {
+            issues.push(Issue::new(
+                IssueKind::InconsistentBraceStyle,
+                format!(
+                    "大括号风格不一致：{} 处跟随语句，{} 处独占一行，建议统一风格",
+                    trailing, own_line
+                ),
+            ));
+            score = (score + 0.05).min(1.0);
+        }
+
+        // 检测过长的条件表达式
+        let complex_conditions = find_complex_conditions(parse_result, self.max_boolean_operators);
+        for &(line, operators) in &complex_conditions {
+            issues.push(
+                Issue::new(
+                    IssueKind::ComplexCondition,
+                    format!(
+                        "条件表达式过于复杂（{} 个逻辑运算符），建议提取为有名字的变量",
+                        operators
+                    ),
+                )
+                .with_line(line),
+            );
+        }
+        let penalty = (complex_conditions.len() as f64 * COMPLEX_CONDITION_PENALTY)
+            .min(MAX_COMPLEX_CONDITION_PENALTY);
+        score = (score + penalty).min(1.0);
+
+        // 检测导入过多的文件（耦合热点）
+        let imports = count_imports(parse_result.get_source(), parse_result.get_language());
+        if imports > self.max_imports {
+            issues.push(Issue::new(
+                IssueKind::TooManyImports,
+                format!(
+                    "文件导入了 {} 个模块（超过 {} 个），耦合度过高，建议按职责拆分",
+                    imports, self.max_imports
+                ),
+            ));
+            score = (score + TOO_MANY_IMPORTS_PENALTY).min(1.0);
+        }
+
+        // 检测 JS/TS 的回调地狱和过长的 Promise 链
+        let callback_issues = find_callback_hell(parse_result);
+        let penalty =
+            (callback_issues.len() as f64 * CALLBACK_HELL_PENALTY).min(MAX_CALLBACK_HELL_PENALTY);
+        score = (score + penalty).min(1.0);
+        issues.extend(callback_issues);
 
         MetricResult {
             score,
@@ -80,3 +205,553 @@ impl StructureAnalysisMetric {
         }
     }
 }
+
+/// 统计左大括号的两种风格：跟随语句末尾（K&R）与独占一行（Allman）
+///
+/// 只统计紧跟在语句（如 `if (x)`、`fn foo()`）之后的代码块大括号，
+/// 对象字面量、数组元素等其他大括号不计入。Python 等不使用大括号的语言直接返回 0。
+///
+/// # Arguments
+/// * `parse_result` - 解析结果
+///
+/// # Returns
+/// * `(usize, usize)` - (跟随语句的数量, 独占一行的数量)
+fn count_brace_styles(parse_result: &dyn ParseResult) -> (usize, usize) {
+    let language = parse_result.get_language();
+    if !uses_block_braces(language) {
+        return (0, 0);
+    }
+
+    let stripped = strip_source(parse_result.get_source(), language);
+    let mut trailing = 0;
+    let mut own_line = 0;
+    let mut previous = "";
+
+    for line in stripped.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if line == "{" {
+            if ends_like_statement(previous) {
+                own_line += 1;
+            }
+        } else if let Some(head) = line.strip_suffix('{') {
+            if ends_like_statement(head.trim_end()) {
+                trailing += 1;
+            }
+        }
+        previous = line;
+    }
+
+    (trailing, own_line)
+}
+
+/// 找出逻辑运算符过多的行
+///
+/// 在剥离注释和字符串后的源码上逐行统计 `&&`、`||`（Python 为 `and`、`or`），
+/// 字符串和注释中的运算符不计入。
+///
+/// # Arguments
+/// * `parse_result` - 解析结果
+/// * `max_operators` - 单行允许的最多运算符数
+///
+/// # Returns
+/// * `Vec<(usize, usize)>` - (行号, 运算符数量) 列表
+fn find_complex_conditions(
+    parse_result: &dyn ParseResult,
+    max_operators: usize,
+) -> Vec<(usize, usize)> {
+    let language = parse_result.get_language();
+    let stripped = strip_source(parse_result.get_source(), language);
+
+    stripped
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, count_boolean_operators(line, language)))
+        .filter(|&(_, operators)| operators > max_operators)
+        .collect()
+}
+
+/// 代码块扫描时的括号层级
+enum Frame {
+    /// 圆括号（函数调用参数或分组）
+    Paren,
+
+    /// 方括号
+    Bracket,
+
+    /// 大括号，记录是否为作为参数传入的回调函数体
+    Brace { callback: bool },
+}
+
+/// 找出 JS/TS 中嵌套过深的回调函数和过长的 Promise 链
+///
+/// 在剥离注释和字符串后的源码上按括号层级扫描：出现在调用参数中的函数体
+/// （`function (...) {` 或 `=> {`）视为回调；同一层级上连续的
+/// `.then()`/`.catch()`/`.finally()` 视为一条 Promise 链。
+/// 每个超过层数上限的回调金字塔报告一次，附带最深层数和所在的具名函数。
+///
+/// # Arguments
+/// * `parse_result` - 解析结果
+///
+/// # Returns
+/// * `Vec<Issue>` - 发现的问题
+fn find_callback_hell(parse_result: &dyn ParseResult) -> Vec<Issue> {
+    let language = parse_result.get_language();
+    if !matches!(
+        language,
+        LanguageType::JavaScript | LanguageType::TypeScript
+    ) {
+        return Vec::new();
+    }
+
+    let stripped = strip_source(parse_result.get_source(), language);
+    let bytes = stripped.as_bytes();
+    let mut issues = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    // 每个括号层级上正在统计的 Promise 链：(调用次数, 起始行)
+    let mut chains: Vec<(usize, usize)> = vec![(0, 0)];
+    // 正在统计的回调金字塔：(最深层数, 起始行)
+    let mut pyramid: Option<(usize, usize)> = None;
+    let mut line = 1;
+
+    for (i, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'\n' => {
+                line += 1;
+                // 换行后不是以 `.` 开头的续行，当前层级的链结束
+                let continues = stripped[i + 1..].trim_start().starts_with('.');
+                if !continues {
+                    finish_promise_chain(&mut chains, stack.len(), &mut issues);
+                }
+            }
+            b';' => finish_promise_chain(&mut chains, stack.len(), &mut issues),
+            b'.' if is_promise_method_call(&stripped[i + 1..]) => {
+                let chain = &mut chains[stack.len()];
+                if chain.0 == 0 {
+                    chain.1 = line;
+                }
+                chain.0 += 1;
+            }
+            b'(' | b'[' | b'{' => {
+                let frame = match byte {
+                    b'(' => Frame::Paren,
+                    b'[' => Frame::Bracket,
+                    _ => Frame::Brace {
+                        callback: matches!(stack.last(), Some(Frame::Paren))
+                            && opens_function_body(&stripped[..i]),
+                    },
+                };
+                if let Frame::Brace { callback: true } = frame {
+                    let depth = 1 + stack
+                        .iter()
+                        .filter(|f| matches!(f, Frame::Brace { callback: true }))
+                        .count();
+                    if depth > MAX_CALLBACK_DEPTH {
+                        let (peak, _) = pyramid.get_or_insert((depth, line));
+                        *peak = (*peak).max(depth);
+                    }
+                }
+                stack.push(frame);
+                chains.push((0, 0));
+            }
+            b')' | b']' | b'}' if !stack.is_empty() => {
+                stack.pop();
+                finish_promise_chain(&mut chains, stack.len() + 1, &mut issues);
+                chains.pop();
+
+                // 回到层数上限以内时，金字塔结束
+                let depth = stack
+                    .iter()
+                    .filter(|f| matches!(f, Frame::Brace { callback: true }))
+                    .count();
+                if depth <= MAX_CALLBACK_DEPTH {
+                    if let Some((peak, start_line)) = pyramid.take() {
+                        issues.push(callback_pyramid_issue(
+                            parse_result.get_functions(),
+                            peak,
+                            start_line,
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for depth in 0..chains.len() {
+        finish_promise_chain(&mut chains, depth, &mut issues);
+    }
+    if let Some((peak, start_line)) = pyramid {
+        issues.push(callback_pyramid_issue(
+            parse_result.get_functions(),
+            peak,
+            start_line,
+        ));
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    issues
+}
+
+/// 创建回调金字塔问题
+///
+/// # Arguments
+/// * `functions` - 文件中的函数
+/// * `depth` - 金字塔的最深层数
+/// * `line` - 超过层数上限的第一个回调所在行
+///
+/// # Returns
+/// * `Issue` - 问题
+fn callback_pyramid_issue(functions: &[Function], depth: usize, line: usize) -> Issue {
+    // 取包含该行的最内层具名函数，回调本身是匿名函数
+    let owner = functions
+        .iter()
+        .filter(|func| !func.is_anonymous() && func.start_line <= line && line <= func.end_line)
+        .max_by_key(|func| func.start_line);
+    let location = match owner {
+        Some(func) => format!("函数 {} 中", func.name),
+        None => "顶层代码中".to_string(),
+    };
+
+    Issue::new(
+        IssueKind::CallbackHell,
+        format!(
+            "{}的回调金字塔深度为 {} 层（上限 {}），建议改用 async/await 或拆分为具名函数",
+            location, depth, MAX_CALLBACK_DEPTH
+        ),
+    )
+    .with_line(line)
+}
+
+/// 结束某一层级上的 Promise 链，过长时报告问题
+///
+/// # Arguments
+/// * `chains` - 各层级的链统计
+/// * `depth` - 层级
+/// * `issues` - 问题列表
+fn finish_promise_chain(chains: &mut [(usize, usize)], depth: usize, issues: &mut Vec<Issue>) {
+    let Some(chain) = chains.get_mut(depth) else {
+        return;
+    };
+
+    let (calls, start_line) = *chain;
+    if calls > MAX_PROMISE_CHAIN {
+        issues.push(
+            Issue::new(
+                IssueKind::CallbackHell,
+                format!(
+                    "Promise 链过长（{} 次 then/catch/finally 调用），建议改用 async/await",
+                    calls
+                ),
+            )
+            .with_line(start_line),
+        );
+    }
+    *chain = (0, 0);
+}
+
+/// 判断 `.` 之后是否为 Promise 链方法调用
+///
+/// # Arguments
+/// * `rest` - `.` 之后的代码
+///
+/// # Returns
+/// * `bool` - 是否为 `then`/`catch`/`finally` 调用
+fn is_promise_method_call(rest: &str) -> bool {
+    PROMISE_METHODS.iter().any(|method| {
+        rest.strip_prefix(method)
+            .is_some_and(|after| after.trim_start().starts_with('('))
+    })
+}
+
+/// 判断大括号前的代码是否为函数头（`function (...)` 或箭头函数 `=>`）
+///
+/// # Arguments
+/// * `before` - 大括号之前的代码
+///
+/// # Returns
+/// * `bool` - 是否为函数体
+fn opens_function_body(before: &str) -> bool {
+    let before = before.trim_end();
+    before.ends_with("=>") || before.ends_with(')')
+}
+
+/// 统计一行中的逻辑运算符数量
+///
+/// # Arguments
+/// * `line` - 剥离后的代码行
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `usize` - 运算符数量
+fn count_boolean_operators(line: &str, language: LanguageType) -> usize {
+    if language == LanguageType::Python {
+        return line
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|word| *word == "and" || *word == "or")
+            .count();
+    }
+
+    line.matches("&&").count() + line.matches("||").count()
+}
+
+/// 判断两种风格是否被大量混用
+///
+/// # Arguments
+/// * `trailing` - 跟随语句的数量
+/// * `own_line` - 独占一行的数量
+///
+/// # Returns
+/// * `bool` - 是否混用
+fn is_mixed_brace_style(trailing: usize, own_line: usize) -> bool {
+    let minority = trailing.min(own_line);
+    let total = trailing + own_line;
+
+    minority >= MIN_MIXED_BRACES && minority as f64 / total as f64 >= MIXED_BRACE_RATIO
+}
+
+/// 判断语言是否使用大括号表示代码块
+///
+/// # Arguments
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `bool` - 是否使用大括号
+fn uses_block_braces(language: LanguageType) -> bool {
+    matches!(
+        language,
+        LanguageType::Rust
+            | LanguageType::Go
+            | LanguageType::JavaScript
+            | LanguageType::TypeScript
+            | LanguageType::Java
+            | LanguageType::C
+            | LanguageType::CPlusPlus
+            | LanguageType::CSharp
+            | LanguageType::PHP
+            | LanguageType::Zig
+            | LanguageType::Swift
+    )
+}
+
+/// 判断一段代码是否以语句头结尾（后面可以跟代码块）
+///
+/// # Arguments
+/// * `code` - 代码片段
+///
+/// # Returns
+/// * `bool` - 是否为语句头
+fn ends_like_statement(code: &str) -> bool {
+    match code.chars().last() {
+        Some(c) => c == ')' || c == '>' || c.is_alphanumeric() || c == '_',
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Language;
+    use crate::parser::create_parser_for_file;
+    use std::path::Path;
+
+    /// 用对应语言的解析器解析源码后运行代码结构指标
+    fn analyze(file_name: &str, source: &str) -> MetricResult {
+        let path = Path::new(file_name);
+        let parse_result = create_parser_for_file(path).parse(path, source).unwrap();
+        StructureAnalysisMetric::new(Translator::new(Language::ZhCN)).analyze(&*parse_result)
+    }
+
+    fn count_kind(result: &MetricResult, kind: IssueKind) -> usize {
+        result
+            .issues
+            .iter()
+            .filter(|issue| issue.kind == kind)
+            .count()
+    }
+
+    #[test]
+    fn mixed_brace_styles_are_reported() {
+        let source = "int a(int x) {\n  return x;\n}\n\
+                      int b(int x) {\n  return x;\n}\n\
+                      int c(int x) {\n  return x;\n}\n\
+                      int d(int x)\n{\n  return x;\n}\n\
+                      int e(int x)\n{\n  return x;\n}\n\
+                      int f(int x)\n{\n  if (x)\n  {\n    return 1;\n  }\n  return x;\n}\n";
+        let result = analyze("mixed.c", source);
+        assert_eq!(count_kind(&result, IssueKind::InconsistentBraceStyle), 1);
+    }
+
+    #[test]
+    fn consistent_brace_style_is_not_reported() {
+        let source = "function a(x) {\n  if (x) {\n    return 1;\n  }\n  return x;\n}\n\
+                      function b(x) {\n  for (const y of x) {\n    log(y);\n  }\n}\n\
+                      function c() {\n  const s = \"if (x)\\n{\";\n  return s;\n}\n";
+        let result = analyze("consistent.js", source);
+        assert_eq!(count_kind(&result, IssueKind::InconsistentBraceStyle), 0);
+    }
+
+    #[test]
+    fn braces_inside_strings_and_comments_are_ignored() {
+        let source = "fn a() {\n    let s = \"x\n{\n\";\n}\n\
+                      fn b() {\n    // y\n    // {\n}\n\
+                      fn c() {\n    /* z\n{\n */\n}\n\
+                      fn d() {\n}\n";
+        let (trailing, own_line) = count_brace_styles(
+            &*create_parser_for_file(Path::new("strings.rs"))
+                .parse(Path::new("strings.rs"), source)
+                .unwrap(),
+        );
+        assert_eq!((trailing, own_line), (4, 0));
+    }
+
+    #[test]
+    fn python_is_skipped() {
+        let source = "def a(x):\n    d = {\n        'k': 1,\n    }\n    return d\n";
+        assert_eq!(
+            count_kind(
+                &analyze("dicts.py", source),
+                IssueKind::InconsistentBraceStyle
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn triple_nested_callbacks_are_callback_hell() {
+        let source = "function loadProfile(id, done) {\n\
+                      \x20 readUser(id, function (err, user) {\n\
+                      \x20   readPosts(user, (err, posts) => {\n\
+                      \x20     readComments(posts, function (err, comments) {\n\
+                      \x20       done(null, { user, posts, comments });\n\
+                      \x20     });\n\
+                      \x20   });\n\
+                      \x20 });\n\
+                      }\n";
+        let result = analyze("profile.js", source);
+        let issues: Vec<&Issue> = result
+            .issues
+            .iter()
+            .filter(|issue| issue.kind == IssueKind::CallbackHell)
+            .collect();
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(4));
+        assert!(issues[0].message.contains("loadProfile"));
+        assert!(issues[0].message.contains("3 层"));
+    }
+
+    #[test]
+    fn async_await_version_is_not_callback_hell() {
+        let source = "async function loadProfile(id) {\n\
+                      \x20 const user = await readUser(id);\n\
+                      \x20 const posts = await readPosts(user);\n\
+                      \x20 const comments = await readComments(posts);\n\
+                      \x20 return { user, posts, comments };\n\
+                      }\n";
+        let result = analyze("profile.ts", source);
+        assert_eq!(count_kind(&result, IssueKind::CallbackHell), 0);
+    }
+
+    #[test]
+    fn long_promise_chains_are_reported() {
+        let source = "function load(id) {\n\
+                      \x20 return fetchUser(id)\n\
+                      \x20   .then((user) => fetchPosts(user))\n\
+                      \x20   .then((posts) => fetchComments(posts))\n\
+                      \x20   .then((comments) => render(comments))\n\
+                      \x20   .catch((err) => report(err));\n\
+                      }\n\
+                      function short(id) {\n\
+                      \x20 return fetchUser(id).then(render).catch(report);\n\
+                      }\n";
+        let result = analyze("chain.js", source);
+        let lines: Vec<Option<usize>> = result
+            .issues
+            .iter()
+            .filter(|issue| issue.kind == IssueKind::CallbackHell)
+            .map(|issue| issue.line)
+            .collect();
+        assert_eq!(lines, [Some(3)]);
+    }
+
+    /// 生成包含 `count` 条导入语句的 Python 文件
+    fn python_imports(count: usize) -> String {
+        let imports: String = (0..count)
+            .map(|i| format!("from package.module_{} import helper_{}\n", i, i))
+            .collect();
+        format!("{}\n\ndef main():\n    return 0\n", imports)
+    }
+
+    #[test]
+    fn forty_imports_are_too_many() {
+        let result = analyze("hub.py", &python_imports(40));
+        let issues: Vec<&Issue> = result
+            .issues
+            .iter()
+            .filter(|issue| issue.kind == IssueKind::TooManyImports)
+            .collect();
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("40"));
+        assert!(result.score > 0.0);
+    }
+
+    #[test]
+    fn few_imports_are_fine_and_the_limit_is_configurable() {
+        let few = python_imports(3);
+        assert_eq!(
+            count_kind(&analyze("leaf.py", &few), IssueKind::TooManyImports),
+            0
+        );
+
+        let path = Path::new("leaf.py");
+        let parse_result = create_parser_for_file(path).parse(path, &few).unwrap();
+        let strict = StructureAnalysisMetric::new(Translator::new(Language::ZhCN))
+            .with_max_imports(2)
+            .analyze(&*parse_result);
+        assert_eq!(count_kind(&strict, IssueKind::TooManyImports), 1);
+    }
+
+    #[test]
+    fn seven_operator_condition_exceeds_the_default_limit() {
+        let source = "function canShip(o) {\n\
+                      \x20 if (o.paid && o.packed && !o.held && (o.local || o.express) && o.weight < 30 && o.address && o.phone) {\n\
+                      \x20   return true;\n\
+                      \x20 }\n\
+                      \x20 const ok = o.a && o.b && o.c && o.d && o.e && o.f;\n\
+                      \x20 const note = \"a && b && c && d && e && f && g\";\n\
+                      \x20 return ok;\n\
+                      }\n";
+        let result = analyze("ship.js", source);
+        let issues: Vec<&Issue> = result
+            .issues
+            .iter()
+            .filter(|issue| issue.kind == IssueKind::ComplexCondition)
+            .collect();
+
+        // 第 5 行恰好 5 个运算符不超限，字符串里的运算符不计数
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(2));
+        assert!(issues[0].message.contains("7 个逻辑运算符"));
+        assert_eq!(DEFAULT_MAX_BOOLEAN_OPERATORS, 5);
+    }
+
+    #[test]
+    fn python_boolean_keywords_are_counted_and_the_limit_is_configurable() {
+        let source = "def check(a, b, c, d):\n    return a and b or c and not d\n";
+        assert_eq!(
+            count_boolean_operators("    return a and b or c and not d", LanguageType::Python),
+            3
+        );
+        assert_eq!(
+            count_kind(&analyze("check.py", source), IssueKind::ComplexCondition),
+            0
+        );
+
+        let path = Path::new("check.py");
+        let parse_result = create_parser_for_file(path).parse(path, source).unwrap();
+        let strict = StructureAnalysisMetric::new(Translator::new(Language::ZhCN))
+            .with_max_boolean_operators(2)
+            .analyze(&*parse_result);
+        assert_eq!(count_kind(&strict, IssueKind::ComplexCondition), 1);
+    }
+}
@@ -0,0 +1,194 @@
+//! # 匿名函数检测
+//!
+//! 统一检测各语言的匿名函数（箭头函数、闭包、lambda 等），
+//! 并以 `<anon>@文件名:行号` 的形式命名，使其可以和普通函数一起参与度量
+
+use crate::common::LanguageType;
+use crate::parser::{measure_nesting_depth, strip_source, Function};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// 匿名函数名称前缀
+pub const ANONYMOUS_PREFIX: &str = "<anon>";
+
+/// JavaScript/TypeScript 作为参数、属性值或返回值的箭头函数和函数表达式
+static JS_ANONYMOUS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:[(,:?]|\breturn)\s*(?:async\s+)?(?:\([^()]*\)\s*=>|\w+\s*=>|function\s*\()")
+        .unwrap()
+});
+
+/// Rust 闭包
+static RUST_CLOSURE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:[=(,]|\bmove|\breturn)\s*(?:move\s+)?\|[^|]*\|").unwrap());
+
+/// Python lambda
+static PYTHON_LAMBDA: Lazy<Regex> = Lazy::new(|| Regex::new(r"\blambda\b").unwrap());
+
+/// Java lambda
+static JAVA_LAMBDA: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:\([^()]*\)|\b\w+)\s*->").unwrap());
+
+/// C# lambda
+static CSHARP_LAMBDA: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[(,=]\s*(?:async\s+)?(?:\([^()]*\)|\w+)\s*=>").unwrap());
+
+/// Go 匿名函数
+static GO_FUNC_LITERAL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:[=(,]|\bgo|\bdefer|\breturn)\s*func\s*\(").unwrap());
+
+/// PHP 匿名函数和箭头函数
+static PHP_CLOSURE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:[=(,]|\breturn)\s*(?:static\s+)?(?:function|fn)\s*\(").unwrap());
+
+/// C++ lambda
+static CPP_LAMBDA: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:[=(,]|\breturn)\s*\[[^\]]*\]\s*\(").unwrap());
+
+/// 分支关键字与运算符
+static DECISION_POINT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:if|elif|for|while|case|catch|except)\b|&&|\|\||\?").unwrap());
+
+/// 检测源码中的匿名函数
+///
+/// 每行最多记录一个匿名函数；函数体以大括号包围时统计到匹配的右括号，否则视为单行。
+///
+/// # Arguments
+/// * `file_path` - 文件路径（用于命名）
+/// * `content` - 源码
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `Vec<Function>` - 匿名函数列表
+pub fn detect_anonymous_functions(
+    file_path: &Path,
+    content: &str,
+    language: LanguageType,
+) -> Vec<Function> {
+    let Some(regex) = anonymous_regex(language) else {
+        return Vec::new();
+    };
+
+    let file_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stripped = strip_source(content, language);
+    let lines: Vec<&str> = stripped.lines().collect();
+
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let found = regex.find(line)?;
+            let end = find_body_end(&lines, index, found.end());
+            let complexity = 1 + lines[index..=end]
+                .iter()
+                .map(|l| DECISION_POINT.find_iter(l).count())
+                .sum::<usize>();
+            let parameters = count_parameters(&line[found.start()..], found.len());
+
+            let mut function = Function::new(
+                format!("{}@{}:{}", ANONYMOUS_PREFIX, file_name, index + 1),
+                index + 1,
+                end + 1,
+                complexity,
+                parameters,
+            );
+            function.max_nesting_depth = measure_nesting_depth(&lines[index..=end], language);
+            Some(function)
+        })
+        .collect()
+}
+
+/// 获取语言对应的匿名函数正则
+///
+/// # Arguments
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `Option<&Regex>` - 正则，不支持匿名函数的语言为 None
+fn anonymous_regex(language: LanguageType) -> Option<&'static Regex> {
+    match language {
+        LanguageType::JavaScript | LanguageType::TypeScript => Some(&JS_ANONYMOUS),
+        LanguageType::Rust => Some(&RUST_CLOSURE),
+        LanguageType::Python => Some(&PYTHON_LAMBDA),
+        LanguageType::Java => Some(&JAVA_LAMBDA),
+        LanguageType::CSharp => Some(&CSHARP_LAMBDA),
+        LanguageType::Go => Some(&GO_FUNC_LITERAL),
+        LanguageType::PHP => Some(&PHP_CLOSURE),
+        LanguageType::CPlusPlus => Some(&CPP_LAMBDA),
+        _ => None,
+    }
+}
+
+/// 查找匿名函数体的结束行
+///
+/// # Arguments
+/// * `lines` - 剥离后的代码行
+/// * `start` - 起始行索引
+/// * `column` - 匹配结束的字节位置
+///
+/// # Returns
+/// * `usize` - 结束行索引
+fn find_body_end(lines: &[&str], start: usize, column: usize) -> usize {
+    let rest = &lines[start][column..];
+    let Some(open) = rest.find('{') else {
+        return start;
+    };
+
+    let mut depth = 0i32;
+    let first = &rest[open..];
+    for (offset, line) in std::iter::once(first)
+        .chain(lines[start + 1..].iter().copied())
+        .enumerate()
+    {
+        for c in line.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return start + offset;
+            }
+        }
+    }
+
+    lines.len() - 1
+}
+
+/// 统计匿名函数的参数数量
+///
+/// # Arguments
+/// * `text` - 从匹配位置开始的代码行
+/// * `matched_len` - 匹配文本的长度
+///
+/// # Returns
+/// * `usize` - 参数数量
+fn count_parameters(text: &str, matched_len: usize) -> usize {
+    let matched = &text[..matched_len];
+
+    let params = if let Some(rest) = matched.find("lambda").map(|i| &text[i + 6..]) {
+        // Python: lambda a, b: ...
+        rest.split(':').next().unwrap_or("")
+    } else if let Some(open) = matched.find('|') {
+        // Rust: |a, b| ...
+        let inner = &matched[open + 1..];
+        inner.split('|').next().unwrap_or("")
+    } else if matched.ends_with('(') {
+        // function (a, b) / func(a, b) / [&](a, b)
+        text[matched_len..].split(')').next().unwrap_or("")
+    } else {
+        // (a, b) => / x => / x ->
+        let head = matched.trim_end_matches(['=', '>', '-']).trim_end();
+        match head.strip_suffix(')') {
+            Some(head) => head.rsplit('(').next().unwrap_or(""),
+            None => head
+                .rsplit(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()
+                .unwrap_or(""),
+        }
+    };
+
+    params.split(',').filter(|p| !p.trim().is_empty()).count()
+}
@@ -3,10 +3,26 @@
 //! 定义解析器的基础接口和数据结构
 
 use crate::common::LanguageType;
+use crate::parser::{collect_identifiers, strip_source, Identifier};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+/// Shell 中开启或关闭代码块的关键字
+static SHELL_BLOCK_KEYWORD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:if|for|while|until|select|case|fi|done|esac)\b").unwrap());
+
+/// 认知复杂度关注的记号：代码块括号、控制结构关键字、布尔运算符和三元运算符
+static COGNITIVE_TOKEN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\{|\}|;|\b(?:else\s+if|elif|elsif|elseif|else|if|unless|for|foreach|while|until|loop|switch|match|select|case|cond|catch|except|rescue|guard|fi|done|esac|and|or)\b|&&|\|\||\s\?\s",
+    )
+    .unwrap()
+});
+
 /// 函数信息结构
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Function {
     /// 函数名称
     pub name: String,
@@ -22,6 +38,18 @@ pub struct Function {
 
     /// 参数数量
     pub parameters: usize,
+
+    /// 最大嵌套深度（函数体本身为 0 层，每进入一层代码块加 1）
+    pub max_nesting_depth: usize,
+
+    /// 是否对外导出（公开）
+    pub exported: bool,
+
+    /// 函数定义处是否有文档注释
+    pub documented: bool,
+
+    /// 函数体源码（仅在启用 `capture_function_bodies` 时填充，默认不保存以节省内存）
+    pub body: Option<String>,
 }
 
 impl Function {
@@ -49,9 +77,47 @@ impl Function {
             end_line,
             complexity,
             parameters,
+            max_nesting_depth: 0,
+            exported: false,
+            documented: false,
+            body: None,
         }
     }
 
+    /// 根据源代码填充函数体
+    ///
+    /// # Arguments
+    /// * `source` - 函数所在文件的源代码
+    ///
+    /// # Returns
+    /// * `Self` - 带函数体的函数信息
+    pub fn with_body(mut self, source: &str) -> Self {
+        let lines: Vec<&str> = source.lines().collect();
+        self.body = Some(self.source_lines(&lines).join("\n"));
+        self
+    }
+
+    /// 从文件的代码行中截取函数所在的行
+    ///
+    /// # Arguments
+    /// * `lines` - 函数所在文件的代码行
+    ///
+    /// # Returns
+    /// * `&[&str]` - 函数定义行到结束行（超出文件范围的部分被截断）
+    pub fn source_lines<'a>(&self, lines: &'a [&'a str]) -> &'a [&'a str] {
+        let start = self.start_line.saturating_sub(1).min(lines.len());
+        let end = (start + self.line_count()).min(lines.len());
+        &lines[start..end]
+    }
+
+    /// 判断是否为匿名函数（箭头函数、闭包、lambda 等）
+    ///
+    /// # Returns
+    /// * `bool` - 是否为匿名函数
+    pub fn is_anonymous(&self) -> bool {
+        self.name.starts_with(super::ANONYMOUS_PREFIX)
+    }
+
     /// 获取函数行数
     ///
     /// # Returns
@@ -78,6 +144,19 @@ pub trait ParseResult {
 
     /// 获取语言类型
     fn get_language(&self) -> LanguageType;
+
+    /// 获取原始源代码
+    fn get_source(&self) -> &str;
+
+    /// 获取声明的变量名和参数名
+    ///
+    /// 默认从源码中按语言的声明语法收集
+    ///
+    /// # Returns
+    /// * `Vec<Identifier>` - 标识符列表
+    fn get_identifiers(&self) -> Vec<Identifier> {
+        collect_identifiers(self.get_source(), self.get_language())
+    }
 }
 
 /// 解析器trait
@@ -115,6 +194,9 @@ pub struct BaseParseResult {
     /// 总行数
     pub total_lines: usize,
 
+    /// 原始源代码
+    pub source: String,
+
     /// 语言类型
     pub language: LanguageType,
 }
@@ -139,4 +221,327 @@ impl ParseResult for BaseParseResult {
     fn get_language(&self) -> LanguageType {
         self.language
     }
+
+    /// 获取原始源代码
+    fn get_source(&self) -> &str {
+        &self.source
+    }
+}
+
+/// 统计参数列表中的参数数量
+///
+/// 只按最外层括号内的顶层逗号分割，`fn (u8, u8) void`、`%{a: 1, b: 2}` 之类的嵌套结构不会被拆开
+///
+/// # Arguments
+/// * `text` - 参数列表左括号之后的文本
+///
+/// # Returns
+/// * `usize` - 参数数量
+pub fn count_top_level_parameters(text: &str) -> usize {
+    let mut depth = 0usize;
+    let mut count = 0;
+    let mut current_has_content = false;
+
+    for ch in text.chars() {
+        match ch {
+            '(' | '[' | '{' => {
+                depth += 1;
+                current_has_content = true;
+            }
+            ')' | ']' | '}' if depth == 0 => break,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                if current_has_content {
+                    count += 1;
+                }
+                current_has_content = false;
+            }
+            c if !c.is_whitespace() => current_has_content = true,
+            _ => {}
+        }
+    }
+
+    // 允许尾随逗号
+    if current_has_content {
+        count += 1;
+    }
+
+    count
+}
+
+/// 统计函数签名中的参数数量
+///
+/// 从 `offset` 之后的第一个左括号开始，按顶层逗号计数；
+/// 解构参数 `({a, b})`、默认值 `a = 5` 与类型参数 `Map<K, V>` 中的逗号都不会拆开参数。
+///
+/// # Arguments
+/// * `lines` - 代码行
+/// * `start` - 函数定义所在行
+/// * `end` - 函数结束行
+/// * `offset` - 定义行中开始查找左括号的字节位置（通常是函数名之后）
+///
+/// # Returns
+/// * `usize` - 参数数量，找不到参数列表时为 0
+pub fn count_signature_parameters(
+    lines: &[&str],
+    start: usize,
+    end: usize,
+    offset: usize,
+) -> usize {
+    let signature = lines[start..=end].join("\n");
+    let Some(open) = signature.get(offset..).and_then(|rest| rest.find('(')) else {
+        return 0;
+    };
+
+    count_top_level_parameters(&blank_type_argument_commas(&signature[offset + open + 1..]))
+}
+
+/// 把类型参数 `<...>` 内的逗号替换为空格
+///
+/// 只有紧跟在标识符后的 `<` 才视为类型参数开始，`=>` 中的 `>` 不会关闭类型参数。
+///
+/// # Arguments
+/// * `text` - 参数列表文本
+///
+/// # Returns
+/// * `String` - 处理后的文本
+fn blank_type_argument_commas(text: &str) -> String {
+    let mut depth = 0usize;
+    let mut prev = ' ';
+    let mut out = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '<' if prev.is_alphanumeric() || prev == '_' || prev == '$' => depth += 1,
+            '>' if depth > 0 && prev != '=' => depth -= 1,
+            ',' if depth > 0 => {
+                out.push(' ');
+                prev = ch;
+                continue;
+            }
+            _ => {}
+        }
+        out.push(ch);
+        prev = ch;
+    }
+
+    out
+}
+
+/// 计算函数体的最大嵌套深度
+///
+/// Python 和 Elixir 按缩进层级计算；Shell 按 `if`/`fi`、`do`/`done` 等配对关键字计算；
+/// 其他语言按大括号计数的峰值计算，函数体自身的大括号不计入。注释与字符串中的括号会先被剥离。
+///
+/// # Arguments
+/// * `lines` - 函数的代码行（从定义行到结束行）
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `usize` - 最大嵌套深度
+pub fn measure_nesting_depth(lines: &[&str], language: LanguageType) -> usize {
+    let stripped = strip_source(&lines.join("\n"), language);
+
+    match language {
+        LanguageType::Python | LanguageType::Elixir => indent_nesting_depth(&stripped),
+        LanguageType::Shell => shell_nesting_depth(&stripped),
+        _ => brace_nesting_depth(&stripped),
+    }
+}
+
+/// 计算函数的认知复杂度（参照 SonarSource 的规则）
+///
+/// 每个条件、循环、`switch`/`match` 和异常捕获加 1，嵌套在其他结构中时再加上嵌套层级；
+/// `else`/`elif` 只加 1；同一条件中每段连续相同的布尔运算符（`&&`、`||`、`and`、`or`）加 1。
+/// 嵌套层级的计算方式与 [`measure_nesting_depth`] 相同，注释与字符串会先被剥离。
+///
+/// # Arguments
+/// * `lines` - 函数的代码行（从定义行到结束行）
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `usize` - 认知复杂度
+pub fn measure_cognitive_complexity(lines: &[&str], language: LanguageType) -> usize {
+    let stripped = strip_source(&lines.join("\n"), language);
+    let indent_based = matches!(language, LanguageType::Python | LanguageType::Elixir);
+    let keyword_blocks = language == LanguageType::Shell;
+    let brace_blocks = !indent_based && !keyword_blocks;
+    // C 系语言的 `case` 是 switch 的分支标签，不单独计分
+    let counts_case = keyword_blocks || language == LanguageType::Elixir;
+
+    let mut score = 0;
+    let mut depth = 0usize;
+    let mut indents: Vec<usize> = Vec::new();
+    let mut bracket_depth = 0usize;
+    let mut last_operator = None;
+
+    for (index, line) in stripped.lines().enumerate() {
+        // 缩进语言按缩进层级确定嵌套（定义行与函数体第一行为第 0 层，括号内的续行沿用上一行）
+        if indent_based && index > 0 && bracket_depth == 0 && !line.trim().is_empty() {
+            let indent = line.len() - line.trim_start().len();
+            while indents.last().is_some_and(|&top| top > indent) {
+                indents.pop();
+            }
+            if indents.last().is_none_or(|&top| top < indent) {
+                indents.push(indent);
+            }
+        }
+        if indent_based {
+            bracket_depth += line.matches(['(', '[', '{']).count();
+            bracket_depth = bracket_depth.saturating_sub(line.matches([')', ']', '}']).count());
+        }
+        if !brace_blocks {
+            last_operator = None;
+        }
+
+        for token in COGNITIVE_TOKEN.find_iter(line) {
+            // `x.match(...)` 之类的方法调用不是控制结构
+            if line[..token.start()].ends_with('.') {
+                continue;
+            }
+
+            let nesting = if indent_based {
+                indents.len().saturating_sub(1)
+            } else {
+                depth.saturating_sub(usize::from(brace_blocks))
+            };
+            let text = token.as_str().trim();
+
+            match text {
+                "{" | "}" | ";" => {
+                    last_operator = None;
+                    if brace_blocks && text == "{" {
+                        depth += 1;
+                    } else if brace_blocks && text == "}" {
+                        depth = depth.saturating_sub(1);
+                    }
+                }
+                "&&" | "||" | "and" | "or" => {
+                    let operator = if text == "and" {
+                        "&&"
+                    } else if text == "or" {
+                        "||"
+                    } else {
+                        text
+                    };
+                    if last_operator != Some(operator) {
+                        score += 1;
+                    }
+                    last_operator = Some(operator);
+                }
+                "fi" | "done" | "esac" => {
+                    if keyword_blocks {
+                        depth = depth.saturating_sub(1);
+                    }
+                }
+                "?" => {
+                    if brace_blocks {
+                        score += 1 + nesting;
+                    }
+                }
+                "case" | "cond" if !counts_case => {}
+                _ if text.starts_with("el") => {
+                    last_operator = None;
+                    score += 1;
+                }
+                _ => {
+                    last_operator = None;
+                    score += 1 + nesting;
+                    if keyword_blocks {
+                        depth += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    score
+}
+
+/// 按 Shell 的配对关键字（`if`…`fi`、`for`/`while`/`until`/`select`…`done`、`case`…`esac`）计算嵌套深度
+///
+/// # Arguments
+/// * `stripped` - 剥离后的函数源码
+///
+/// # Returns
+/// * `usize` - 最大嵌套深度
+fn shell_nesting_depth(stripped: &str) -> usize {
+    let mut depth = 0usize;
+    let mut peak = 0;
+
+    for keyword in SHELL_BLOCK_KEYWORD.find_iter(stripped) {
+        match keyword.as_str() {
+            "fi" | "done" | "esac" => depth = depth.saturating_sub(1),
+            _ => {
+                depth += 1;
+                peak = peak.max(depth);
+            }
+        }
+    }
+
+    peak
+}
+
+/// 按大括号计数的峰值计算嵌套深度
+///
+/// # Arguments
+/// * `stripped` - 剥离后的函数源码
+///
+/// # Returns
+/// * `usize` - 最大嵌套深度
+fn brace_nesting_depth(stripped: &str) -> usize {
+    let mut depth = 0usize;
+    let mut peak = 0;
+
+    for ch in stripped.chars() {
+        match ch {
+            '{' => {
+                depth += 1;
+                peak = peak.max(depth);
+            }
+            '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    peak.saturating_sub(1)
+}
+
+/// 按缩进层级计算嵌套深度，括号内的续行不计入
+///
+/// # Arguments
+/// * `stripped` - 剥离后的函数源码
+///
+/// # Returns
+/// * `usize` - 最大嵌套深度
+fn indent_nesting_depth(stripped: &str) -> usize {
+    let mut indents: Vec<usize> = Vec::new();
+    let mut bracket_depth = 0usize;
+    let mut peak = 0;
+
+    // 跳过定义行，以函数体第一行的缩进作为第 0 层
+    for line in stripped.lines().skip(1) {
+        let continuation = bracket_depth > 0;
+        for ch in line.chars() {
+            match ch {
+                '(' | '[' | '{' => bracket_depth += 1,
+                ')' | ']' | '}' => bracket_depth = bracket_depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        if continuation || line.trim().is_empty() {
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        while indents.last().is_some_and(|&top| top > indent) {
+            indents.pop();
+        }
+        if indents.last().is_none_or(|&top| top < indent) {
+            indents.push(indent);
+        }
+        peak = peak.max(indents.len().saturating_sub(1));
+    }
+
+    peak
 }
@@ -1,8 +1,31 @@
 use crate::common::LanguageType;
-use crate::parser::{BaseParseResult, Function, ParseResult, Parser};
+use crate::parser::{
+    count_comment_lines, count_top_level_parameters, has_doc_comment, is_exported,
+    measure_nesting_depth, strip_source, BaseParseResult, Function, ParseResult, Parser,
+};
+use once_cell::sync::Lazy;
 use regex::Regex;
 use std::path::Path;
 
+/// 函数定义的开头（返回类型、函数名和参数列表左括号，C++ 的函数名可以带 `Class::` 限定）
+static FUNCTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*((?:[\w*&]+[\s*&]+)+)((?:[A-Za-z_]\w*::)*~?[A-Za-z_]\w*)\s*\(").unwrap()
+});
+
+/// 独占一行的返回类型（GNU 风格把函数名写在下一行行首）
+static RETURN_TYPE_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*((?:[\w*&]+[\s*&]*)+)$").unwrap());
+
+/// 行首的函数名和参数列表左括号（返回类型在上一行）
+static NAME_LINE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*((?:[A-Za-z_]\w*::)*~?[A-Za-z_]\w*)\s*\(").unwrap());
+
+/// 会出现在返回类型或函数名位置、但不构成函数定义的关键字
+const NON_TYPE_KEYWORDS: &[&str] = &[
+    "return", "else", "if", "for", "while", "switch", "do", "case", "goto", "new", "delete",
+    "throw", "sizeof",
+];
+
 pub struct CParser;
 
 impl CParser {
@@ -22,14 +45,17 @@ impl Parser for CParser {
 
         let lines: Vec<&str> = content.lines().collect();
         let total_lines = lines.len();
+        let stripped = strip_source(content, language);
+        let code: Vec<&str> = stripped.lines().collect();
 
-        let comment_lines = self.count_comment_lines(&lines);
-        let functions = self.detect_functions(&lines);
+        let comment_lines = count_comment_lines(content, language);
+        let functions = self.detect_functions(&lines, &code, language);
 
         Ok(Box::new(BaseParseResult {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language,
         }))
     }
@@ -40,82 +66,190 @@ impl Parser for CParser {
 }
 
 impl CParser {
-    fn count_comment_lines(&self, lines: &[&str]) -> usize {
-        let mut count = 0;
-        let mut in_block_comment = false;
-
-        for line in lines {
-            let trimmed = line.trim();
+    /// 检测函数
+    ///
+    /// 签名可以跨多行，函数体的 `{` 可以在参数列表之后的任意一行，
+    /// 函数体也可以与签名写在同一行（如 `int add(int a, int b) { return a + b; }`）。
+    ///
+    /// # Arguments
+    /// * `lines` - 原始代码行
+    /// * `code` - 剥离注释和字符串后的代码行
+    /// * `language` - 语言类型（C 或 C++）
+    ///
+    /// # Returns
+    /// * `Vec<Function>` - 函数列表
+    fn detect_functions(
+        &self,
+        lines: &[&str],
+        code: &[&str],
+        language: LanguageType,
+    ) -> Vec<Function> {
+        let mut functions = Vec::new();
+        let mut i = 0;
 
-            if in_block_comment {
-                count += 1;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-                continue;
+        while i < code.len() {
+            if let Some(func) = self.try_parse_function(lines, code, i, language) {
+                i = func.end_line; // 跳到函数结束
+                functions.push(func);
+            } else {
+                i += 1;
             }
+        }
 
-            if trimmed.starts_with("//") {
-                count += 1;
-                continue;
-            }
+        functions
+    }
 
-            if trimmed.starts_with("/*") {
-                count += 1;
-                in_block_comment = true;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-            }
+    /// 尝试解析函数
+    ///
+    /// # Arguments
+    /// * `lines` - 原始代码行
+    /// * `code` - 剥离后的代码行
+    /// * `start` - 起始位置
+    /// * `language` - 语言类型
+    ///
+    /// # Returns
+    /// * `Option<Function>` - 函数信息（声明、调用和控制语句返回 None）
+    fn try_parse_function(
+        &self,
+        lines: &[&str],
+        code: &[&str],
+        start: usize,
+        language: LanguageType,
+    ) -> Option<Function> {
+        let (return_type, name, name_line, params_start) = self.match_signature(code, start)?;
+        if return_type
+            .split_whitespace()
+            .next()
+            .is_some_and(|word| NON_TYPE_KEYWORDS.contains(&word))
+            || NON_TYPE_KEYWORDS.contains(&name)
+        {
+            return None;
+        }
+
+        let (open_line, open_col) = self.find_body_start(code, name_line, params_start)?;
+        let end_line = self.find_function_end(code, open_line, open_col);
+
+        let signature = code[name_line..=open_line].join("\n");
+        let params = &signature[params_start..];
+        let params_end = params.find(')').unwrap_or(params.len());
+        let parameters = if params[..params_end].trim() == "void" {
+            0
+        } else {
+            count_top_level_parameters(params)
+        };
+
+        let mut function = Function::new(
+            name.to_string(),
+            start + 1,    // 转换为1索引
+            end_line + 1, // 转换为1索引
+            self.calculate_complexity(&lines[start..=end_line]),
+            parameters,
+        );
+        function.exported = is_exported(lines[start], &function.name, language);
+        function.documented = has_doc_comment(lines, start, language);
+        function.max_nesting_depth = measure_nesting_depth(&lines[start..=end_line], language);
+
+        Some(function)
+    }
+
+    /// 匹配函数签名的开头
+    ///
+    /// 返回类型可以和函数名在同一行，也可以独占函数名的上一行
+    ///
+    /// # Arguments
+    /// * `code` - 剥离后的代码行
+    /// * `start` - 起始位置
+    ///
+    /// # Returns
+    /// * `Option<(&str, &str, usize, usize)>` - 返回类型、函数名、函数名所在行和参数列表左括号之后的字节位置
+    fn match_signature<'a>(
+        &self,
+        code: &[&'a str],
+        start: usize,
+    ) -> Option<(&'a str, &'a str, usize, usize)> {
+        if let Some(captures) = FUNCTION_REGEX.captures(code[start]) {
+            return Some((
+                captures.get(1)?.as_str(),
+                captures.get(2)?.as_str(),
+                start,
+                captures.get(0)?.end(),
+            ));
         }
 
-        count
+        let return_type = RETURN_TYPE_LINE.captures(code[start])?.get(1)?.as_str();
+        let captures = NAME_LINE.captures(code.get(start + 1)?)?;
+        Some((
+            return_type,
+            captures.get(1)?.as_str(),
+            start + 1,
+            captures.get(0)?.end(),
+        ))
     }
 
-    fn detect_functions(&self, lines: &[&str]) -> Vec<Function> {
-        let mut functions = Vec::new();
-        let func_regex =
-            Regex::new(r"([\w\*]+\s+)+([a-zA-Z_][a-zA-Z0-9_]*)\s*\(([^;]*)\)\s*\{").unwrap();
-
-        for (i, line) in lines.iter().enumerate() {
-            let trimmed = line.trim();
-            if trimmed.ends_with("{") && !trimmed.starts_with("{") {
-                if let Some(captures) = func_regex.captures(line) {
-                    let func_name = captures.get(2).unwrap().as_str().to_string();
-                    let params_str = captures.get(3).unwrap().as_str();
-
-                    let params = if params_str.trim().is_empty() || params_str.trim() == "void" {
-                        0
-                    } else {
-                        params_str.split(',').count()
-                    };
-
-                    let end_line = self.find_function_end(lines, i);
-                    let complexity = self.calculate_complexity(&lines[i..=end_line]);
-
-                    functions.push(Function {
-                        name: func_name,
-                        start_line: i + 1,
-                        end_line: end_line + 1,
-                        complexity,
-                        parameters: params,
-                    });
+    /// 查找函数体的左大括号
+    ///
+    /// 先跳过参数列表，参数列表之后到 `{` 之间只能出现 `const`、`noexcept`、
+    /// 尾置返回类型之类的修饰；遇到 `;`、`=` 或 `}` 说明这是声明或表达式。
+    ///
+    /// # Arguments
+    /// * `code` - 剥离后的代码行
+    /// * `start` - 起始位置
+    /// * `params_start` - 定义行中参数列表左括号之后的字节位置
+    ///
+    /// # Returns
+    /// * `Option<(usize, usize)>` - `{` 所在的行和字节位置（不是函数定义时为 None）
+    fn find_body_start(
+        &self,
+        code: &[&str],
+        start: usize,
+        params_start: usize,
+    ) -> Option<(usize, usize)> {
+        let mut paren_depth = 1usize;
+
+        for (i, line) in code.iter().enumerate().skip(start) {
+            let offset = if i == start { params_start } else { 0 };
+
+            for (col, ch) in line[offset..].char_indices() {
+                match ch {
+                    '(' => paren_depth += 1,
+                    ')' => paren_depth = paren_depth.saturating_sub(1),
+                    '{' if paren_depth == 0 => return Some((i, offset + col)),
+                    ';' | '=' | '}' if paren_depth == 0 => return None,
+                    '{' | ';' | '}' => return None,
+                    _ => {}
                 }
             }
         }
 
-        functions
+        None
     }
 
-    fn find_function_end(&self, lines: &[&str], start: usize) -> usize {
-        let mut brace_count = 1;
+    /// 查找函数结束位置
+    ///
+    /// 从函数体的 `{` 开始计数，右大括号可以与左大括号在同一行
+    ///
+    /// # Arguments
+    /// * `code` - 剥离后的代码行
+    /// * `open_line` - `{` 所在的行
+    /// * `open_col` - `{` 在该行中的字节位置
+    ///
+    /// # Returns
+    /// * `usize` - 结束位置（没有闭合时为最后一行）
+    fn find_function_end(&self, code: &[&str], open_line: usize, open_col: usize) -> usize {
+        let mut brace_count = 0usize;
 
-        for i in (start + 1)..lines.len() {
-            for ch in lines[i].chars() {
+        for (i, line) in code.iter().enumerate().skip(open_line) {
+            let text = if i == open_line {
+                &line[open_col..]
+            } else {
+                line
+            };
+
+            for ch in text.chars() {
                 match ch {
                     '{' => brace_count += 1,
                     '}' => {
-                        brace_count -= 1;
+                        brace_count = brace_count.saturating_sub(1);
                         if brace_count == 0 {
                             return i;
                         }
@@ -125,7 +259,7 @@ impl CParser {
             }
         }
 
-        lines.len() - 1
+        code.len() - 1
     }
 
     fn calculate_complexity(&self, function_lines: &[&str]) -> usize {
@@ -147,3 +281,88 @@ impl CParser {
         complexity
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<Function> {
+        CParser::new()
+            .parse(Path::new("main.c"), source)
+            .unwrap()
+            .get_functions()
+            .to_vec()
+    }
+
+    fn spans(functions: &[Function]) -> Vec<(&str, usize, usize, usize)> {
+        functions
+            .iter()
+            .map(|f| (&f.name[..], f.start_line, f.end_line, f.parameters))
+            .collect()
+    }
+
+    #[test]
+    fn brace_on_next_line_is_detected() {
+        let functions = parse(
+            "static int\n\
+             clamp(int value,\n\
+             \x20     int low, int high)\n\
+             {\n\
+             \x20   if (value < low) {\n\
+             \x20       return low;\n\
+             \x20   }\n\
+             \x20   return value > high ? high : value;\n\
+             }\n\
+             \n\
+             int main(void)\n\
+             {\n\
+             \x20   return clamp(5, 0, 3);\n\
+             }\n",
+        );
+
+        assert_eq!(spans(&functions), [("clamp", 1, 9, 3), ("main", 11, 14, 0)]);
+        assert_eq!(functions[0].complexity, 3);
+    }
+
+    #[test]
+    fn single_line_bodies_end_on_their_own_line() {
+        let functions = parse(
+            "int add(int a,int b){return a+b;}\n\
+             int sub(int a, int b) { return a - b; }\n\
+             void noop(void) {}\n\
+             int twice(int x)\n\
+             {\n\
+             \x20   return add(x, x);\n\
+             }\n",
+        );
+
+        assert_eq!(
+            spans(&functions),
+            [
+                ("add", 1, 1, 2),
+                ("sub", 2, 2, 2),
+                ("noop", 3, 3, 0),
+                ("twice", 4, 7, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn declarations_and_calls_are_not_functions() {
+        let functions = parse(
+            "int add(int a, int b);\n\
+             extern void log_line(const char *line);\n\
+             int (*handler)(int);\n\
+             \n\
+             void run(void) {\n\
+             \x20   log_line(\"{\");\n\
+             \x20   if (add(1, 2)) {\n\
+             \x20       return;\n\
+             \x20   }\n\
+             }\n",
+        );
+
+        assert_eq!(spans(&functions), [("run", 5, 10, 0)]);
+        assert_eq!(functions[0].complexity, 2);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::common::LanguageType;
-use crate::parser::{BaseParseResult, Function, ParseResult, Parser};
+use crate::parser::{
+    count_comment_lines, has_doc_comment, is_exported, measure_nesting_depth, BaseParseResult,
+    Function, ParseResult, Parser,
+};
 use regex::Regex;
 use std::path::Path;
 
@@ -20,13 +23,14 @@ impl Parser for CSharpParser {
         let lines: Vec<&str> = content.lines().collect();
         let total_lines = lines.len();
 
-        let comment_lines = self.count_comment_lines(&lines);
+        let comment_lines = count_comment_lines(content, LanguageType::CSharp);
         let functions = self.detect_functions(&lines);
 
         Ok(Box::new(BaseParseResult {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language: LanguageType::CSharp,
         }))
     }
@@ -37,53 +41,6 @@ impl Parser for CSharpParser {
 }
 
 impl CSharpParser {
-    fn count_comment_lines(&self, lines: &[&str]) -> usize {
-        let mut count = 0;
-        let mut in_block_comment = false;
-        let mut in_xml_doc = false;
-
-        for line in lines {
-            let trimmed = line.trim();
-
-            if in_block_comment {
-                count += 1;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-                continue;
-            }
-
-            if in_xml_doc {
-                count += 1;
-                if !trimmed.starts_with("///") && !trimmed.starts_with("*") && !trimmed.is_empty() {
-                    in_xml_doc = false;
-                }
-                continue;
-            }
-
-            if trimmed.starts_with("//") {
-                count += 1;
-                continue;
-            }
-
-            if trimmed.starts_with("///") {
-                count += 1;
-                in_xml_doc = true;
-                continue;
-            }
-
-            if trimmed.starts_with("/*") {
-                count += 1;
-                in_block_comment = true;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-            }
-        }
-
-        count
-    }
-
     fn detect_functions(&self, lines: &[&str]) -> Vec<Function> {
         let mut functions = Vec::new();
         let method_regex = Regex::new(
@@ -103,6 +60,7 @@ impl CSharpParser {
 
                 let end_line = self.find_method_end(lines, i);
                 let complexity = self.calculate_complexity(&lines[i..=end_line]);
+                let exported = is_exported(line, &func_name, LanguageType::CSharp);
 
                 functions.push(Function {
                     name: func_name,
@@ -110,6 +68,13 @@ impl CSharpParser {
                     end_line: end_line + 1,
                     complexity,
                     parameters: params,
+                    max_nesting_depth: measure_nesting_depth(
+                        &lines[i..=end_line],
+                        LanguageType::CSharp,
+                    ),
+                    exported,
+                    documented: has_doc_comment(lines, i, LanguageType::CSharp),
+                    body: None,
                 });
             }
         }
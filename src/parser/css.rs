@@ -274,6 +274,7 @@ impl Parser for CSSParser {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language: LanguageType::CSS,
         }))
     }
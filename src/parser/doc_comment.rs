@@ -0,0 +1,261 @@
+//! # 文档注释检测
+//!
+//! 判断函数定义前（Python 还包括函数体开头的 docstring）是否有文档注释，
+//! 供各语言解析器在识别函数时记录
+
+use crate::common::LanguageType;
+
+/// 注释开头的任务标记（这类注释不是文档）
+const TASK_MARKERS: &[&str] = &["TODO", "FIXME", "XXX", "HACK"];
+
+/// 判断函数是否带有文档注释
+///
+/// 从函数定义的上一行向上查找紧邻的注释块，跳过属性、注解和装饰器；
+/// 遇到空行或其他代码即停止。只有文档注释才算数：
+///
+/// - Python: 函数体开头的 docstring（`#` 注释不算）
+/// - Rust 和 C 风格语言: `///`、`//!`、`/** */`（Rust 还包括 `#[doc]`）
+/// - Go、Shell: 没有专门的文档注释语法，紧邻的普通注释即文档，但 TODO 等任务标记不算
+/// - Elixir: `@doc`
+///
+/// # Arguments
+/// * `lines` - 代码行
+/// * `start` - 函数定义所在行（0 索引）
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `bool` - 是否有文档注释
+pub fn has_doc_comment(lines: &[&str], start: usize, language: LanguageType) -> bool {
+    if language == LanguageType::Python {
+        return has_docstring(lines, start);
+    }
+
+    let mut index = start.min(lines.len());
+    while index > 0 {
+        index -= 1;
+        let trimmed = lines[index].trim();
+        if trimmed.is_empty() {
+            return false;
+        }
+
+        // 多行块注释：从结尾找到开头，按开头判断
+        if trimmed.ends_with("*/") && language_has_block_comments(language) {
+            let Some(open) = (0..=index).rev().find(|&i| lines[i].contains("/*")) else {
+                return false;
+            };
+            if is_doc_block_start(lines[open].trim(), language) {
+                return true;
+            }
+            index = open;
+            continue;
+        }
+
+        match classify_line(trimmed, language) {
+            LineKind::DocComment => return true,
+            LineKind::Comment | LineKind::Decoration => continue,
+            LineKind::Code => return false,
+        }
+    }
+
+    false
+}
+
+/// 函数定义前一行的类别
+enum LineKind {
+    /// 文档注释
+    DocComment,
+
+    /// 普通注释
+    Comment,
+
+    /// 属性、注解或装饰器
+    Decoration,
+
+    /// 其他代码
+    Code,
+}
+
+/// 判断一行（非块注释结尾）的类别
+fn classify_line(trimmed: &str, language: LanguageType) -> LineKind {
+    let hash_comment = trimmed.starts_with('#') && !trimmed.starts_with("#[");
+
+    let is_doc = match language {
+        LanguageType::Shell => hash_comment && !is_task_comment(&trimmed[1..]),
+        LanguageType::Go => trimmed.starts_with("//") && !is_task_comment(&trimmed[2..]),
+        // `@doc` 单行文档或 `@doc """..."""` 的结尾
+        LanguageType::Elixir => trimmed.starts_with("@doc") || trimmed.starts_with("\"\"\""),
+        LanguageType::Rust => is_doc_line_comment(trimmed) || trimmed.starts_with("#[doc"),
+        LanguageType::Python => false,
+        _ => is_doc_line_comment(trimmed),
+    };
+    if is_doc {
+        return LineKind::DocComment;
+    }
+
+    let is_comment = match language {
+        LanguageType::Python | LanguageType::Shell | LanguageType::Elixir => hash_comment,
+        LanguageType::PHP => trimmed.starts_with("//") || hash_comment,
+        _ => trimmed.starts_with("//"),
+    };
+    if is_comment {
+        LineKind::Comment
+    } else if is_decoration_line(trimmed, language) {
+        LineKind::Decoration
+    } else {
+        LineKind::Code
+    }
+}
+
+/// 判断一行是否为 `///` 或 `//!` 文档注释（`////` 是普通注释）
+fn is_doc_line_comment(trimmed: &str) -> bool {
+    (trimmed.starts_with("///") && !trimmed.starts_with("////")) || trimmed.starts_with("//!")
+}
+
+/// 判断块注释的开头是否为文档注释
+fn is_doc_block_start(trimmed: &str, language: LanguageType) -> bool {
+    let Some(block) = trimmed.find("/*").map(|i| &trimmed[i..]) else {
+        return false;
+    };
+
+    match language {
+        // Go 没有专门的文档块注释，紧邻的块注释即文档
+        LanguageType::Go => !is_task_comment(&block[2..]),
+        _ => block.starts_with("/**") && !block.starts_with("/**/"),
+    }
+}
+
+/// 判断语言是否使用 `/* */` 块注释
+fn language_has_block_comments(language: LanguageType) -> bool {
+    !matches!(
+        language,
+        LanguageType::Python | LanguageType::Shell | LanguageType::Elixir | LanguageType::Zig
+    )
+}
+
+/// 判断注释内容是否以 TODO 等任务标记开头
+fn is_task_comment(text: &str) -> bool {
+    let text = text.trim_start_matches(['/', '*', '#']).trim_start();
+    TASK_MARKERS.iter().any(|marker| {
+        text.strip_prefix(marker)
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+    })
+}
+
+/// 判断一行是否为夹在注释和函数定义之间的属性、注解或装饰器
+fn is_decoration_line(trimmed: &str, language: LanguageType) -> bool {
+    match language {
+        LanguageType::Rust | LanguageType::PHP => trimmed.starts_with("#["),
+        LanguageType::CSharp => trimmed.starts_with('['),
+        LanguageType::Java
+        | LanguageType::Python
+        | LanguageType::JavaScript
+        | LanguageType::TypeScript
+        | LanguageType::Swift
+        | LanguageType::Elixir => trimmed.starts_with('@'),
+        _ => false,
+    }
+}
+
+/// 判断 Python 函数体是否以 docstring 开头
+fn has_docstring(lines: &[&str], start: usize) -> bool {
+    // 函数签名可能跨多行，以第一个以冒号结尾的行作为签名结束
+    let Some(signature_end) = lines
+        .iter()
+        .skip(start)
+        .position(|line| line.trim_end().ends_with(':'))
+    else {
+        return false;
+    };
+
+    lines
+        .iter()
+        .skip(start + signature_end + 1)
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .is_some_and(|line| {
+            line.starts_with("\"\"\"")
+                || line.starts_with("'''")
+                || line.starts_with("r\"\"\"")
+                || line.starts_with("r'''")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 判断源码最后一行的函数定义是否带有文档注释
+    fn documented(source: &str, language: LanguageType) -> bool {
+        let lines: Vec<&str> = source.lines().collect();
+        let start = lines
+            .iter()
+            .rposition(|line| {
+                let line = line.trim_start();
+                ["def ", "fn ", "pub fn ", "func ", "int ", "function "]
+                    .iter()
+                    .any(|prefix| line.starts_with(prefix))
+            })
+            .unwrap();
+        has_doc_comment(&lines, start, language)
+    }
+
+    #[test]
+    fn python_requires_a_docstring() {
+        let python = LanguageType::Python;
+        assert!(!documented(
+            "# TODO: fix\ndef is_valid(x):\n    return x\n",
+            python
+        ));
+        assert!(!documented(
+            "# Checks x.\n@cache\ndef is_valid(x):\n    return x\n",
+            python
+        ));
+        assert!(documented(
+            "# TODO: fix\ndef is_valid(x):\n    \"\"\"Check x.\"\"\"\n    return x\n",
+            python
+        ));
+    }
+
+    #[test]
+    fn rust_and_c_style_require_doc_comment_syntax() {
+        for language in [
+            LanguageType::Rust,
+            LanguageType::C,
+            LanguageType::JavaScript,
+        ] {
+            assert!(!documented("// check the value\nfn f() {}", language));
+            assert!(!documented("//// banner\nfn f() {}", language));
+            assert!(!documented("/* plain\n * block\n */\nfn f() {}", language));
+            assert!(documented("/// Checks the value.\nfn f() {}", language));
+            assert!(documented("//! Module docs.\nfn f() {}", language));
+            assert!(documented(
+                "/**\n * Checks the value.\n */\nfn f() {}",
+                language
+            ));
+            assert!(documented("/** Checks the value. */\nfn f() {}", language));
+            // 文档注释和函数之间夹着普通注释仍然算有文档
+            assert!(documented("/// Docs.\n// TODO: tidy\nfn f() {}", language));
+        }
+
+        let rust = LanguageType::Rust;
+        assert!(documented("/// Docs.\n#[inline]\npub fn f() {}", rust));
+        assert!(documented("#[doc = \"Docs.\"]\npub fn f() {}", rust));
+        assert!(!documented("/// Docs.\n\nfn f() {}", rust));
+    }
+
+    #[test]
+    fn go_comments_are_docs_unless_they_are_task_markers() {
+        let go = LanguageType::Go;
+        assert!(documented("// Parse reads the input.\nfunc Parse() {}", go));
+        assert!(documented(
+            "/* Parse reads the input. */\nfunc Parse() {}",
+            go
+        ));
+        assert!(!documented("// TODO: handle errors\nfunc Parse() {}", go));
+        assert!(!documented("// FIXME\nfunc Parse() {}", go));
+        assert!(documented(
+            "// TODOList holds tasks.\nfunc TODOList() {}",
+            go
+        ));
+    }
+}
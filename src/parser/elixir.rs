@@ -0,0 +1,284 @@
+//! # Elixir语言解析器
+//!
+//! 专门用于解析Elixir源代码文件
+
+use crate::common::LanguageType;
+use crate::parser::{
+    count_top_level_parameters, has_doc_comment, is_exported, measure_nesting_depth, strip_source,
+    BaseParseResult, Function, ParseResult, Parser,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// 函数定义（`def`/`defp`/`defmacro`/`defmacrop`）
+static FUNCTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:defp|defmacrop|def|defmacro)\s+([a-z_][a-zA-Z0-9_]*[?!]?)\s*(\()?").unwrap()
+});
+
+/// 打开或关闭代码块的关键字（`do:` 关键字写法不开启代码块）
+static BLOCK_KEYWORD: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(do|fn|end)\b(:?)").unwrap());
+
+/// 单行 `do:` 写法
+static INLINE_DO: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bdo:").unwrap());
+
+/// 分支关键字与运算符
+static BRANCH_KEYWORD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:if|unless|cond|case|with|and|or)\b|&&|\|\||->").unwrap());
+
+/// Elixir解析器
+pub struct ElixirParser;
+
+impl ElixirParser {
+    /// 创建新的Elixir解析器
+    ///
+    /// # Returns
+    /// * `Self` - 解析器实例
+    pub fn new() -> Self {
+        ElixirParser
+    }
+
+    /// 计数注释行（`#` 注释以及 `@doc`/`@moduledoc` 文档）
+    ///
+    /// # Arguments
+    /// * `lines` - 代码行
+    ///
+    /// # Returns
+    /// * `usize` - 注释行数
+    fn count_comment_lines(&self, lines: &[&str]) -> usize {
+        let mut count = 0;
+        let mut in_heredoc = false;
+
+        for line in lines {
+            let trimmed = line.trim();
+
+            if in_heredoc {
+                count += 1;
+                if trimmed.starts_with("\"\"\"") {
+                    in_heredoc = false;
+                }
+                continue;
+            }
+
+            if trimmed.starts_with('#') {
+                count += 1;
+                continue;
+            }
+
+            if trimmed.starts_with("@doc") || trimmed.starts_with("@moduledoc") {
+                count += 1;
+                in_heredoc = trimmed.ends_with("\"\"\"");
+            }
+        }
+
+        count
+    }
+
+    /// 检测函数
+    ///
+    /// # Arguments
+    /// * `lines` - 代码行
+    /// * `stripped` - 剥离注释和字符串后的代码行
+    ///
+    /// # Returns
+    /// * `Vec<Function>` - 函数列表
+    fn detect_functions(&self, lines: &[&str], stripped: &[&str]) -> Vec<Function> {
+        let mut functions = Vec::new();
+
+        for (i, line) in stripped.iter().enumerate() {
+            let Some(captures) = FUNCTION_REGEX.captures(line) else {
+                continue;
+            };
+            let Some(end_line) = self.find_function_end(stripped, i) else {
+                continue;
+            };
+
+            let name = captures[1].to_string();
+            let parameters = match captures.get(2) {
+                Some(open) => {
+                    let signature = stripped[i..=end_line].join("\n");
+                    count_top_level_parameters(&signature[open.end()..])
+                }
+                None => 0,
+            };
+            let complexity = self.calculate_complexity(&stripped[i..=end_line]);
+
+            let mut function = Function::new(name, i + 1, end_line + 1, complexity, parameters);
+            function.exported = is_exported(line, &function.name, LanguageType::Elixir);
+            function.documented = has_doc_comment(lines, i, LanguageType::Elixir);
+            function.max_nesting_depth =
+                measure_nesting_depth(&lines[i..=end_line], LanguageType::Elixir);
+            functions.push(function);
+        }
+
+        functions
+    }
+
+    /// 查找函数结束位置（与 `do` 匹配的 `end`，单行 `do:` 写法在当前行结束）
+    ///
+    /// # Arguments
+    /// * `stripped` - 剥离后的代码行
+    /// * `start` - 起始位置
+    ///
+    /// # Returns
+    /// * `Option<usize>` - 结束位置（没有函数体的声明为 None）
+    fn find_function_end(&self, stripped: &[&str], start: usize) -> Option<usize> {
+        let mut depth = 0usize;
+        let mut found_body = false;
+
+        for (i, line) in stripped.iter().enumerate().skip(start) {
+            for captures in BLOCK_KEYWORD.captures_iter(line) {
+                if !captures[2].is_empty() {
+                    continue;
+                }
+                if &captures[1] == "end" {
+                    depth = depth.saturating_sub(1);
+                    if found_body && depth == 0 {
+                        return Some(i);
+                    }
+                } else {
+                    depth += 1;
+                    found_body = true;
+                }
+            }
+
+            if !found_body && INLINE_DO.is_match(line) {
+                return Some(i);
+            }
+            if !found_body && i > start && FUNCTION_REGEX.is_match(line) {
+                // 只有函数头（如默认参数声明），没有函数体
+                return None;
+            }
+        }
+
+        None
+    }
+
+    /// 计算循环复杂度
+    ///
+    /// # Arguments
+    /// * `function_lines` - 剥离后的函数代码行
+    ///
+    /// # Returns
+    /// * `usize` - 复杂度
+    fn calculate_complexity(&self, function_lines: &[&str]) -> usize {
+        // 每个 `->` 子句各算一条路径
+        1 + function_lines
+            .iter()
+            .map(|line| BRANCH_KEYWORD.find_iter(line).count())
+            .sum::<usize>()
+    }
+}
+
+impl Parser for ElixirParser {
+    /// 解析Elixir文件
+    ///
+    /// # Arguments
+    /// * `_file_path` - 文件路径
+    /// * `content` - 文件内容
+    ///
+    /// # Returns
+    /// * `Result<Box<dyn ParseResult>, Box<dyn std::error::Error>>` - 解析结果
+    fn parse(
+        &self,
+        _file_path: &Path,
+        content: &str,
+    ) -> Result<Box<dyn ParseResult>, Box<dyn std::error::Error>> {
+        let lines: Vec<&str> = content.lines().collect();
+        let total_lines = lines.len();
+
+        let stripped_source = strip_source(content, LanguageType::Elixir);
+        let stripped: Vec<&str> = stripped_source.lines().collect();
+
+        let comment_lines = self.count_comment_lines(&lines);
+        let functions = self.detect_functions(&lines, &stripped);
+
+        Ok(Box::new(BaseParseResult {
+            functions,
+            comment_lines,
+            total_lines,
+            source: content.to_string(),
+            language: LanguageType::Elixir,
+        }))
+    }
+
+    /// 获取支持的语言
+    ///
+    /// # Returns
+    /// * `Vec<LanguageType>` - 语言列表
+    fn supported_languages(&self) -> Vec<LanguageType> {
+        vec![LanguageType::Elixir]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 生成包含指定数量 `case` 子句的函数
+    fn case_function(clauses: usize) -> String {
+        let arms: String = (0..clauses)
+            .map(|i| format!("      {} -> :r{}\n", i, i))
+            .collect();
+        format!(
+            "defmodule M do\n  def pick(value) do\n    case value do\n{}    end\n  end\nend\n",
+            arms
+        )
+    }
+
+    fn complexity(source: &str) -> usize {
+        ElixirParser::new()
+            .parse(Path::new("m.ex"), source)
+            .unwrap()
+            .get_functions()[0]
+            .complexity
+    }
+
+    #[test]
+    fn each_case_clause_adds_complexity() {
+        // 基础 1 + case 1 + 每个子句 1
+        assert_eq!(complexity(&case_function(2)), 4);
+        assert_eq!(complexity(&case_function(3)), 5);
+        assert_eq!(complexity(&case_function(6)), 8);
+    }
+
+    #[test]
+    fn detects_blocks_one_liners_and_docs() {
+        let source = "defmodule Shop do\n\
+                      \x20 @moduledoc \"\"\"\n\
+                      \x20 Shop helpers.\n\
+                      \x20 \"\"\"\n\
+                      \n\
+                      \x20 @doc \"Totals the cart.\"\n\
+                      \x20 def total(items, discount) do\n\
+                      \x20   Enum.reduce(items, 0, fn item, acc -> acc + item end) - discount\n\
+                      \x20 end\n\
+                      \n\
+                      \x20 # 内部工具\n\
+                      \x20 defp empty?(items), do: items == []\n\
+                      end\n";
+        let result = ElixirParser::new()
+            .parse(Path::new("shop.ex"), source)
+            .unwrap();
+        let functions = result.get_functions();
+
+        let summary: Vec<(&str, usize, usize, usize, bool)> = functions
+            .iter()
+            .map(|f| {
+                (
+                    f.name.as_str(),
+                    f.start_line,
+                    f.end_line,
+                    f.parameters,
+                    f.exported,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [("total", 7, 9, 2, true), ("empty?", 12, 12, 1, false)]
+        );
+        assert!(functions[0].documented);
+        assert_eq!(result.get_comment_lines(), 5);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::common::LanguageType;
-use crate::parser::{BaseParseResult, Function, ParseResult, Parser};
+use crate::parser::{
+    count_signature_parameters, has_doc_comment, is_exported, measure_nesting_depth,
+    BaseParseResult, Function, ParseResult, Parser,
+};
 use regex::Regex;
 use std::path::Path;
 
@@ -35,6 +38,7 @@ impl Parser for GenericParser {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language,
         }))
     }
@@ -155,13 +159,18 @@ impl GenericParser {
             if let Some(func_info) = self.extract_function_info(line, &regex) {
                 let end_line = self.find_function_end(lines, i, language);
                 let complexity = self.calculate_complexity(&lines[i..=end_line]);
+                let exported = is_exported(line, &func_info.name, language);
 
                 functions.push(Function {
                     name: func_info.name,
                     start_line: i + 1,
                     end_line: end_line + 1,
                     complexity,
-                    parameters: func_info.param_count,
+                    parameters: count_signature_parameters(lines, i, end_line, func_info.name_end),
+                    max_nesting_depth: measure_nesting_depth(&lines[i..=end_line], language),
+                    exported,
+                    documented: has_doc_comment(lines, i, language),
+                    body: None,
                 });
             }
         }
@@ -170,6 +179,10 @@ impl GenericParser {
     }
 
     /// Get regex pattern for function detection based on language
+    ///
+    /// Only the first non-empty capture group (the name) is used; parameters are
+    /// counted from the signature after the name, so every pattern gets real
+    /// parameter counts whether or not it captures the parameter list.
     fn get_function_pattern(&self, language: LanguageType) -> &'static str {
         match language {
             LanguageType::JavaScript | LanguageType::TypeScript => {
@@ -177,7 +190,7 @@ impl GenericParser {
             }
             LanguageType::Python => r"^\s*def\s+([a-zA-Z_][a-zA-Z0-9_]*)\s*\(([^)]*)\)",
             LanguageType::Java => {
-                r"(?:public|private|protected|static|\s)+[\w\<\>\[\]]+\s+([\w]+)\s*\(([^\)]*)\)\s*(?:\{|throws)"
+                r"(?:public|private|protected|static|\s)+[\w<>\[\]]+\s+([\w]+)\s*\(([^\)]*)\)\s*(?:\{|throws)"
             }
             LanguageType::Go => r"func\s+(?:\([^)]*\)\s+)?([a-zA-Z_][a-zA-Z0-9_]*)\s*\(([^)]*)\)",
             _ => {
@@ -190,17 +203,15 @@ impl GenericParser {
     fn extract_function_info(&self, line: &str, regex: &Regex) -> Option<FunctionInfo> {
         regex.captures(line).map(|captures| {
             // Find the first non-None capture group for the name
-            let name = captures
-                .iter()
-                .skip(1)
-                .flatten()
-                .next()
+            let name_match = captures.iter().skip(1).flatten().next();
+            let name = name_match
                 .map(|m| m.as_str().to_string())
                 .unwrap_or_else(|| "anonymous".to_string());
 
             FunctionInfo {
                 name,
-                param_count: 0, // Simplified
+                // Parameters are counted from the first '(' after the name
+                name_end: name_match.map_or(0, |m| m.end()),
             }
         })
     }
@@ -305,5 +316,107 @@ impl GenericParser {
 /// Information about a detected function
 struct FunctionInfo {
     name: String,
-    param_count: usize,
+    name_end: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Detects functions in `source` and returns (name, parameters) pairs
+    fn detect(source: &str, language: LanguageType) -> Vec<(String, usize)> {
+        let lines: Vec<&str> = source.lines().collect();
+        GenericParser::new()
+            .detect_functions(&lines, language)
+            .into_iter()
+            .map(|func| (func.name, func.parameters))
+            .collect()
+    }
+
+    fn expected(pairs: &[(&str, usize)]) -> Vec<(String, usize)> {
+        pairs
+            .iter()
+            .map(|(name, params)| (name.to_string(), *params))
+            .collect()
+    }
+
+    #[test]
+    fn detects_javascript_functions() {
+        let source = "function add(a, b) {\n  return a + b;\n}\n\
+                      var neg = function(x) {\n  return -x;\n};\n\
+                      const obj = {\n  greet: function(name, greeting, punctuation) {\n    return name;\n  }\n};\n\
+                      const noop = () => {\n};\n";
+        assert_eq!(
+            detect(source, LanguageType::JavaScript),
+            expected(&[("add", 2), ("neg", 1), ("greet", 3), ("noop", 0)])
+        );
+    }
+
+    #[test]
+    fn detects_typescript_functions() {
+        let source =
+            "function scale(v: number, factor: number): number {\n  return v * factor;\n}\n\
+                      const total = (items: number[]) => {\n  return items.length;\n};\n";
+        assert_eq!(
+            detect(source, LanguageType::TypeScript),
+            expected(&[("scale", 2), ("total", 1)])
+        );
+    }
+
+    #[test]
+    fn detects_python_functions() {
+        let source = "def area(width, height, depth=1):\n    return width * height * depth\n\n\
+                      class Shape:\n    def describe(self):\n        return 'shape'\n";
+        assert_eq!(
+            detect(source, LanguageType::Python),
+            expected(&[("area", 3), ("describe", 1)])
+        );
+    }
+
+    #[test]
+    fn detects_java_methods() {
+        let source = "public class Calc {\n\
+                      \x20   public static void main(String[] args) {\n        run();\n    }\n\
+                      \x20   private Map<String, Integer> count(List<String> words, int limit) throws IOException {\n        return null;\n    }\n\
+                      \x20   int[] empty() {\n        return new int[0];\n    }\n}\n";
+        assert_eq!(
+            detect(source, LanguageType::Java),
+            expected(&[("main", 1), ("count", 2), ("empty", 0)])
+        );
+    }
+
+    #[test]
+    fn detects_go_functions_and_methods() {
+        let source = "func Sum(a int, b int) int {\n\treturn a + b\n}\n\
+                      func (s *Server) Start() error {\n\treturn nil\n}\n";
+        assert_eq!(
+            detect(source, LanguageType::Go),
+            expected(&[("Sum", 2), ("Start", 0)])
+        );
+    }
+
+    #[test]
+    fn fallback_pattern_detects_typed_functions() {
+        let source = "int clamp(int value, int low, int high) {\n  return value;\n}\n\
+                      void reset() {\n}\n";
+        assert_eq!(
+            detect(source, LanguageType::Unsupported),
+            expected(&[("clamp", 3), ("reset", 0)])
+        );
+    }
+
+    #[test]
+    fn every_pattern_compiles() {
+        let parser = GenericParser::new();
+        for language in [
+            LanguageType::JavaScript,
+            LanguageType::TypeScript,
+            LanguageType::Python,
+            LanguageType::Java,
+            LanguageType::Go,
+            LanguageType::Unsupported,
+        ] {
+            assert!(Regex::new(parser.get_function_pattern(language)).is_ok());
+        }
+    }
 }
@@ -1,5 +1,8 @@
 use crate::common::LanguageType;
-use crate::parser::{BaseParseResult, Function, ParseResult, Parser};
+use crate::parser::{
+    count_comment_lines, has_doc_comment, is_exported, measure_nesting_depth, BaseParseResult,
+    Function, ParseResult, Parser,
+};
 use regex::Regex;
 use std::path::Path;
 
@@ -20,13 +23,14 @@ impl Parser for GoParser {
         let lines: Vec<&str> = content.lines().collect();
         let total_lines = lines.len();
 
-        let comment_lines = self.count_comment_lines(&lines);
+        let comment_lines = count_comment_lines(content, LanguageType::Go);
         let functions = self.detect_functions(&lines);
 
         Ok(Box::new(BaseParseResult {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language: LanguageType::Go,
         }))
     }
@@ -37,38 +41,6 @@ impl Parser for GoParser {
 }
 
 impl GoParser {
-    fn count_comment_lines(&self, lines: &[&str]) -> usize {
-        let mut count = 0;
-        let mut in_block_comment = false;
-
-        for line in lines {
-            let trimmed = line.trim();
-
-            if in_block_comment {
-                count += 1;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-                continue;
-            }
-
-            if trimmed.starts_with("//") {
-                count += 1;
-                continue;
-            }
-
-            if trimmed.starts_with("/*") {
-                count += 1;
-                in_block_comment = true;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-            }
-        }
-
-        count
-    }
-
     fn detect_functions(&self, lines: &[&str]) -> Vec<Function> {
         let mut functions = Vec::new();
         let func_regex =
@@ -87,6 +59,7 @@ impl GoParser {
 
                 let end_line = self.find_function_end(lines, i);
                 let complexity = self.calculate_complexity(&lines[i..=end_line]);
+                let exported = is_exported(line, &func_name, LanguageType::Go);
 
                 functions.push(Function {
                     name: func_name,
@@ -94,6 +67,13 @@ impl GoParser {
                     end_line: end_line + 1,
                     complexity,
                     parameters: params,
+                    max_nesting_depth: measure_nesting_depth(
+                        &lines[i..=end_line],
+                        LanguageType::Go,
+                    ),
+                    exported,
+                    documented: has_doc_comment(lines, i, LanguageType::Go),
+                    body: None,
                 });
             }
         }
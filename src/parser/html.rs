@@ -318,6 +318,7 @@ impl Parser for HTMLParser {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language: LanguageType::HTML,
         }))
     }
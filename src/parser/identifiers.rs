@@ -0,0 +1,276 @@
+//! # 标识符收集
+//!
+//! 统一收集各语言中声明的变量名和参数名，供命名规范等度量使用。
+//! 基于剥离注释和字符串后的源码逐行匹配声明语法，只做近似识别。
+
+use crate::common::LanguageType;
+use crate::parser::strip_source;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashSet;
+
+/// 标识符种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierKind {
+    /// 局部变量（let/var/const、赋值、循环变量等）
+    Variable,
+
+    /// 函数参数
+    Parameter,
+}
+
+/// 声明的标识符
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identifier {
+    /// 名称（PHP 不含 `$`）
+    pub name: String,
+
+    /// 首次声明的行号（从 1 开始）
+    pub line: usize,
+
+    /// 种类
+    pub kind: IdentifierKind,
+}
+
+/// Rust: `let`/`let mut` 和 `for x in`
+static RUST_VARIABLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\blet\s+(?:mut\s+)?([A-Za-z_]\w*)|\bfor\s+([A-Za-z_]\w*)\s+in\b").unwrap()
+});
+
+/// Python: 行首赋值（可带类型注解）和 `for x in`
+static PYTHON_VARIABLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*([A-Za-z_]\w*)\s*(?::[^=]+)?=[^=]|\bfor\s+([A-Za-z_]\w*)\s+in\b").unwrap()
+});
+
+/// JavaScript/TypeScript: `let`/`var`/`const`
+static JS_VARIABLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:let|var|const)\s+([A-Za-z_$][\w$]*)").unwrap());
+
+/// Go: 短变量声明 `x :=` 和 `var x`
+static GO_VARIABLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b([A-Za-z_]\w*)\s*:=|\bvar\s+([A-Za-z_]\w*)").unwrap());
+
+/// Java/C#/C/C++: 以常见类型或 `var`/`auto` 开头的声明
+static TYPED_VARIABLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(?:var|auto|int|long|short|byte|char|float|double|bool|boolean|String|string|size_t|unsigned)\s+\**\s*([A-Za-z_]\w*)\s*[=;:,]",
+    )
+    .unwrap()
+});
+
+/// PHP: `$x =` 和 `as $x`
+static PHP_VARIABLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$([A-Za-z_]\w*)\s*=[^=>]|\bas\s+\$([A-Za-z_]\w*)").unwrap());
+
+/// Swift: `let`/`var`（含 `if let`/`guard let` 绑定）和 `for x in`
+static SWIFT_VARIABLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:let|var)\s+([A-Za-z_]\w*)|\bfor\s+([A-Za-z_]\w*)\s+in\b").unwrap()
+});
+
+/// Zig: `var`/`const`
+static ZIG_VARIABLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:var|const)\s+([A-Za-z_]\w*)").unwrap());
+
+/// Rust/Zig 函数签名（参数列表在同一行闭合）
+static FN_SIGNATURE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bfn\s+\w+\s*(?:<[^(]*>)?\s*\(([^)]*)\)").unwrap());
+
+/// Swift 函数签名（`外部标签 名称: 类型` 取名称）
+static SWIFT_SIGNATURE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bfunc\s+[^\s(<]+\s*(?:<[^(]*>)?\s*\(([^)]*)\)").unwrap());
+
+/// Python 函数签名
+static PYTHON_SIGNATURE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:async\s+)?def\s+\w+\s*\(([^)]*)\)").unwrap());
+
+/// JavaScript/TypeScript/PHP 函数签名和箭头函数
+static JS_SIGNATURE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bfunction\b\s*\*?\s*&?[\w$]*\s*\(([^)]*)\)|\(([^()]*)\)\s*=>").unwrap()
+});
+
+/// Go 函数签名（可带接收者）
+static GO_SIGNATURE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*func\s*(?:\([^)]*\)\s*)?\w+\s*\(([^)]*)\)").unwrap());
+
+/// Java/C#/C/C++ 方法签名（以 `{` 结尾或行尾，排除调用语句）
+static C_LIKE_SIGNATURE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:[\w<>\[\],.*&?]+\s+)+(?P<name>\w+)\s*\(([^)]*)\)\s*(?:throws\s+[\w.,\s]+)?\{?\s*$",
+    )
+    .unwrap()
+});
+
+/// 与方法签名形状相同的控制语句关键字
+const CONTROL_KEYWORDS: &[&str] = &["if", "for", "while", "switch", "catch", "return", "new"];
+
+/// 会被声明语法误匹配的关键字和接收者名称
+const IGNORED_NAMES: &[&str] = &[
+    "if", "elif", "else", "while", "for", "with", "try", "except", "finally", "lambda", "return",
+    "enum", "void", "mut", "self", "this", "cls",
+];
+
+/// 判断语言的参数是否写作 `名称: 类型`
+///
+/// # Arguments
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `bool` - 是否在名称之后标注类型
+fn annotates_after_name(language: LanguageType) -> bool {
+    matches!(
+        language,
+        LanguageType::Rust
+            | LanguageType::Python
+            | LanguageType::TypeScript
+            | LanguageType::Zig
+            | LanguageType::Swift
+    )
+}
+
+/// 收集源码中声明的变量名和参数名
+///
+/// 同名标识符只保留第一次出现；以下划线开头的名称（约定为未使用）、
+/// `self`/`this` 等接收者和被误匹配的关键字会被忽略。不支持的语言返回空列表。
+///
+/// # Arguments
+/// * `source` - 源码
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `Vec<Identifier>` - 标识符列表（按行号排序）
+pub fn collect_identifiers(source: &str, language: LanguageType) -> Vec<Identifier> {
+    let (variable, signature): (&Regex, &Regex) = match language {
+        LanguageType::Rust => (&RUST_VARIABLE, &FN_SIGNATURE),
+        LanguageType::Zig => (&ZIG_VARIABLE, &FN_SIGNATURE),
+        LanguageType::Python => (&PYTHON_VARIABLE, &PYTHON_SIGNATURE),
+        LanguageType::JavaScript | LanguageType::TypeScript => (&JS_VARIABLE, &JS_SIGNATURE),
+        LanguageType::PHP => (&PHP_VARIABLE, &JS_SIGNATURE),
+        LanguageType::Go => (&GO_VARIABLE, &GO_SIGNATURE),
+        LanguageType::Swift => (&SWIFT_VARIABLE, &SWIFT_SIGNATURE),
+        LanguageType::Java | LanguageType::CSharp | LanguageType::C | LanguageType::CPlusPlus => {
+            (&TYPED_VARIABLE, &C_LIKE_SIGNATURE)
+        }
+        _ => return Vec::new(),
+    };
+
+    let stripped = strip_source(source, language);
+    let mut seen = HashSet::new();
+    let mut identifiers = Vec::new();
+    let mut push = |name: &str, line: usize, kind: IdentifierKind| {
+        if is_collectable(name) && seen.insert(name.to_string()) {
+            identifiers.push(Identifier {
+                name: name.to_string(),
+                line,
+                kind,
+            });
+        }
+    };
+
+    for (index, line) in stripped.lines().enumerate() {
+        for captures in signature.captures_iter(line) {
+            let is_control = captures
+                .name("name")
+                .is_some_and(|name| CONTROL_KEYWORDS.contains(&name.as_str()));
+            if is_control {
+                continue;
+            }
+
+            let params = captures.iter().skip(1).flatten().last();
+            for param in params
+                .map(|m| split_parameters(m.as_str()))
+                .unwrap_or_default()
+            {
+                if let Some(name) = parameter_name(param, language) {
+                    push(name, index + 1, IdentifierKind::Parameter);
+                }
+            }
+        }
+
+        for captures in variable.captures_iter(line) {
+            if let Some(name) = captures.iter().skip(1).flatten().next() {
+                push(name.as_str(), index + 1, IdentifierKind::Variable);
+            }
+        }
+    }
+
+    identifiers
+}
+
+/// 判断名称是否需要收集
+///
+/// # Arguments
+/// * `name` - 名称
+///
+/// # Returns
+/// * `bool` - 是否收集
+fn is_collectable(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('_')
+        && !IGNORED_NAMES.contains(&name)
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// 按顶层逗号拆分参数列表
+///
+/// # Arguments
+/// * `text` - 括号内的参数文本
+///
+/// # Returns
+/// * `Vec<&str>` - 各参数文本
+fn split_parameters(text: &str) -> Vec<&str> {
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut params = Vec::new();
+
+    for (i, ch) in text.char_indices() {
+        match ch {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' | '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                params.push(&text[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    params.push(&text[start..]);
+
+    params
+}
+
+/// 从单个参数文本中取出参数名
+///
+/// 解构参数（`{ a, b }`、`(a, b)`）不处理
+///
+/// # Arguments
+/// * `param` - 参数文本，如 `mut count: usize`、`int count`、`$count = 0`
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `Option<&str>` - 参数名
+fn parameter_name(param: &str, language: LanguageType) -> Option<&str> {
+    let declaration = param.split('=').next()?.trim();
+    if declaration.starts_with(['{', '[', '(']) {
+        return None;
+    }
+
+    let name_part = if annotates_after_name(language) {
+        declaration.split(':').next()?
+    } else {
+        declaration
+    };
+    let mut words = name_part
+        .split(|c: char| c.is_whitespace() || matches!(c, '*' | '&' | '.' | '?'))
+        .filter(|word| !word.is_empty());
+
+    // Go 的参数名在类型之前，其他语言取最后一个单词（跳过修饰符和类型）
+    let word = if language == LanguageType::Go {
+        words.next()?
+    } else {
+        words.next_back()?
+    };
+
+    Some(word.trim_start_matches('$'))
+}
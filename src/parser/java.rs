@@ -1,5 +1,8 @@
 use crate::common::LanguageType;
-use crate::parser::{BaseParseResult, Function, ParseResult, Parser};
+use crate::parser::{
+    count_comment_lines, has_doc_comment, is_exported, measure_nesting_depth, BaseParseResult,
+    Function, ParseResult, Parser,
+};
 use regex::Regex;
 use std::path::Path;
 
@@ -20,13 +23,14 @@ impl Parser for JavaParser {
         let lines: Vec<&str> = content.lines().collect();
         let total_lines = lines.len();
 
-        let comment_lines = self.count_comment_lines(&lines);
+        let comment_lines = count_comment_lines(content, LanguageType::Java);
         let functions = self.detect_functions(&lines);
 
         Ok(Box::new(BaseParseResult {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language: LanguageType::Java,
         }))
     }
@@ -37,46 +41,6 @@ impl Parser for JavaParser {
 }
 
 impl JavaParser {
-    fn count_comment_lines(&self, lines: &[&str]) -> usize {
-        let mut count = 0;
-        let mut in_block_comment = false;
-        let mut in_javadoc = false;
-
-        for line in lines {
-            let trimmed = line.trim();
-
-            if in_block_comment || in_javadoc {
-                count += 1;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                    in_javadoc = false;
-                }
-                continue;
-            }
-
-            if trimmed.starts_with("//") {
-                count += 1;
-                continue;
-            }
-
-            if trimmed.starts_with("/**") {
-                count += 1;
-                in_javadoc = true;
-                if trimmed.contains("*/") {
-                    in_javadoc = false;
-                }
-            } else if trimmed.starts_with("/*") {
-                count += 1;
-                in_block_comment = true;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-            }
-        }
-
-        count
-    }
-
     fn detect_functions(&self, lines: &[&str]) -> Vec<Function> {
         let mut functions = Vec::new();
         let method_regex = Regex::new(
@@ -96,6 +60,7 @@ impl JavaParser {
 
                 let end_line = self.find_method_end(lines, i);
                 let complexity = self.calculate_complexity(&lines[i..=end_line]);
+                let exported = is_exported(line, &func_name, LanguageType::Java);
 
                 functions.push(Function {
                     name: func_name,
@@ -103,6 +68,13 @@ impl JavaParser {
                     end_line: end_line + 1,
                     complexity,
                     parameters: params,
+                    max_nesting_depth: measure_nesting_depth(
+                        &lines[i..=end_line],
+                        LanguageType::Java,
+                    ),
+                    exported,
+                    documented: has_doc_comment(lines, i, LanguageType::Java),
+                    body: None,
                 });
             }
         }
@@ -1,5 +1,8 @@
 use crate::common::LanguageType;
-use crate::parser::{BaseParseResult, Function, ParseResult, Parser};
+use crate::parser::{
+    count_comment_lines, count_signature_parameters, has_doc_comment, is_exported,
+    measure_nesting_depth, BaseParseResult, Function, ParseResult, Parser,
+};
 use regex::Regex;
 use std::path::Path;
 
@@ -22,13 +25,14 @@ impl Parser for JavaScriptParser {
         let lines: Vec<&str> = content.lines().collect();
         let total_lines = lines.len();
 
-        let comment_lines = self.count_comment_lines(&lines);
+        let comment_lines = count_comment_lines(content, LanguageType::JavaScript);
         let functions = self.detect_functions(&lines);
 
         Ok(Box::new(BaseParseResult {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language: LanguageType::JavaScript,
         }))
     }
@@ -39,44 +43,6 @@ impl Parser for JavaScriptParser {
 }
 
 impl JavaScriptParser {
-    /// Count comment lines (both // and /* */ style)
-    fn count_comment_lines(&self, lines: &[&str]) -> usize {
-        let mut count = 0;
-        let mut in_block_comment = false;
-
-        for line in lines {
-            let trimmed = line.trim();
-
-            // Handle block comment continuation
-            if in_block_comment {
-                count += 1;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-                continue;
-            }
-
-            // Single line comment
-            if trimmed.starts_with("//") {
-                count += 1;
-                continue;
-            }
-
-            // Block comment start
-            if trimmed.starts_with("/*") {
-                count += 1;
-                in_block_comment = true;
-
-                // Check if it ends on same line
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-            }
-        }
-
-        count
-    }
-
     /// Detect all types of JavaScript functions
     fn detect_functions(&self, lines: &[&str]) -> Vec<Function> {
         let mut functions = Vec::new();
@@ -99,16 +65,25 @@ impl JavaScriptParser {
 
         for (i, line) in lines.iter().enumerate() {
             if let Some(captures) = regex.captures(line) {
-                let name = captures.get(1).unwrap().as_str().to_string();
+                let name_match = captures.get(1).unwrap();
+                let name = name_match.as_str().to_string();
                 let end_line = self.find_function_end(lines, i);
                 let complexity = self.calculate_complexity(&lines[i..=end_line]);
+                let exported = is_exported(line, &name, LanguageType::JavaScript);
 
                 functions.push(Function {
                     name,
                     start_line: i + 1,
                     end_line: end_line + 1,
                     complexity,
-                    parameters: 0, // Simplified
+                    parameters: count_signature_parameters(lines, i, end_line, name_match.end()),
+                    max_nesting_depth: measure_nesting_depth(
+                        &lines[i..=end_line],
+                        LanguageType::JavaScript,
+                    ),
+                    exported,
+                    documented: has_doc_comment(lines, i, LanguageType::JavaScript),
+                    body: None,
                 });
             }
         }
@@ -129,16 +104,30 @@ impl JavaScriptParser {
 
             for (i, line) in lines.iter().enumerate() {
                 if let Some(captures) = regex.captures(line) {
-                    let name = captures.get(1).unwrap().as_str().to_string();
+                    let name_match = captures.get(1).unwrap();
+                    let name = name_match.as_str().to_string();
                     let end_line = self.find_function_end(lines, i);
                     let complexity = self.calculate_complexity(&lines[i..=end_line]);
+                    let exported = is_exported(line, &name, LanguageType::JavaScript);
 
                     functions.push(Function {
                         name,
                         start_line: i + 1,
                         end_line: end_line + 1,
                         complexity,
-                        parameters: 0,
+                        parameters: count_signature_parameters(
+                            lines,
+                            i,
+                            end_line,
+                            name_match.end(),
+                        ),
+                        max_nesting_depth: measure_nesting_depth(
+                            &lines[i..=end_line],
+                            LanguageType::JavaScript,
+                        ),
+                        exported,
+                        documented: has_doc_comment(lines, i, LanguageType::JavaScript),
+                        body: None,
                     });
                 }
             }
@@ -171,19 +160,33 @@ impl JavaScriptParser {
             // Look for methods inside classes
             if in_class {
                 if let Some(captures) = regex.captures(line) {
-                    let name = captures.get(1).unwrap().as_str().to_string();
+                    let name_match = captures.get(1).unwrap();
+                    let name = name_match.as_str().to_string();
 
                     // Skip constructor and common lifecycle methods
                     if name != "constructor" {
                         let end_line = self.find_function_end(lines, i);
                         let complexity = self.calculate_complexity(&lines[i..=end_line]);
+                        let exported = is_exported(line, &name, LanguageType::JavaScript);
 
                         functions.push(Function {
                             name,
                             start_line: i + 1,
                             end_line: end_line + 1,
                             complexity,
-                            parameters: 0,
+                            parameters: count_signature_parameters(
+                                lines,
+                                i,
+                                end_line,
+                                name_match.end(),
+                            ),
+                            max_nesting_depth: measure_nesting_depth(
+                                &lines[i..=end_line],
+                                LanguageType::JavaScript,
+                            ),
+                            exported,
+                            documented: has_doc_comment(lines, i, LanguageType::JavaScript),
+                            body: None,
                         });
                     }
                 }
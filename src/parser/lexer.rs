@@ -0,0 +1,574 @@
+//! # 源码预处理
+//!
+//! 将注释和字符串字面量的内容替换为空格，得到"剥离后的源码"，
+//! 便于基于文本的检查避开注释和字符串中的干扰字符。
+//! 剥离后的源码与原文逐字符对齐，换行保持不变，因此行号和列号仍然有效。
+
+use crate::common::LanguageType;
+use std::collections::HashSet;
+
+/// 语言的词法特征
+struct LexRules {
+    /// 是否支持 `//` 单行注释
+    slash_comment: bool,
+
+    /// 是否支持 `#` 单行注释
+    hash_comment: bool,
+
+    /// `#` 是否只在词首（行首或空白、分隔符之后）开始注释，如 Shell 的 `$#` 不是注释
+    hash_comment_at_word_start: bool,
+
+    /// 是否支持 `/* */` 块注释
+    block_comment: bool,
+
+    /// 块注释是否可以嵌套（如 Swift 的 `/* /* */ */`）
+    nested_block_comment: bool,
+
+    /// 单引号是否表示字符串（否则按字符字面量处理）
+    single_quote_string: bool,
+
+    /// 是否支持反引号字符串
+    backtick_string: bool,
+
+    /// 是否支持三引号字符串
+    triple_quote_string: bool,
+
+    /// 是否支持 Rust 原始字符串 `r#"..."#`
+    raw_string: bool,
+
+    /// 是否支持 JavaScript 正则字面量 `/.../`
+    regex_literal: bool,
+}
+
+impl LexRules {
+    /// 获取语言对应的词法特征
+    ///
+    /// # Arguments
+    /// * `language` - 语言类型
+    ///
+    /// # Returns
+    /// * `Self` - 词法特征
+    fn for_language(language: LanguageType) -> Self {
+        let mut rules = LexRules {
+            slash_comment: true,
+            hash_comment: false,
+            hash_comment_at_word_start: false,
+            block_comment: true,
+            nested_block_comment: false,
+            single_quote_string: false,
+            backtick_string: false,
+            triple_quote_string: false,
+            raw_string: false,
+            regex_literal: false,
+        };
+
+        match language {
+            LanguageType::Python => {
+                rules.slash_comment = false;
+                rules.block_comment = false;
+                rules.hash_comment = true;
+                rules.single_quote_string = true;
+                rules.triple_quote_string = true;
+            }
+            LanguageType::PHP => {
+                rules.hash_comment = true;
+                rules.single_quote_string = true;
+            }
+            LanguageType::JavaScript | LanguageType::TypeScript => {
+                rules.single_quote_string = true;
+                rules.backtick_string = true;
+                rules.regex_literal = true;
+            }
+            LanguageType::Go => rules.backtick_string = true,
+            LanguageType::Elixir => {
+                rules.slash_comment = false;
+                rules.block_comment = false;
+                rules.hash_comment = true;
+                rules.single_quote_string = true;
+                rules.triple_quote_string = true;
+            }
+            LanguageType::Zig => {
+                rules.block_comment = false;
+                rules.single_quote_string = true;
+            }
+            LanguageType::Shell => {
+                rules.slash_comment = false;
+                rules.block_comment = false;
+                rules.hash_comment = true;
+                rules.hash_comment_at_word_start = true;
+                rules.single_quote_string = true;
+            }
+            LanguageType::Swift => {
+                rules.nested_block_comment = true;
+                rules.triple_quote_string = true;
+            }
+            LanguageType::Rust => rules.raw_string = true,
+            LanguageType::CSS | LanguageType::HTML => rules.single_quote_string = true,
+            _ => {}
+        }
+
+        rules
+    }
+}
+
+/// 剥离注释与字符串内容
+///
+/// 注释整体替换为空格；字符串保留两端引号，内容替换为空格。
+///
+/// # Arguments
+/// * `content` - 原始源码
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `String` - 剥离后的源码
+pub fn strip_source(content: &str, language: LanguageType) -> String {
+    lex(content, language, |_, _| {})
+}
+
+/// 统计含有注释的行数
+///
+/// 只把字符串和正则字面量之外的 `//`、`/*`、`#` 视为注释起点，
+/// 代码行末尾的行内注释同样计入。
+///
+/// # Arguments
+/// * `content` - 原始源码
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `usize` - 注释行数
+pub fn count_comment_lines(content: &str, language: LanguageType) -> usize {
+    let mut comment_lines = HashSet::new();
+    lex(content, language, |start, end| {
+        comment_lines.extend(start..=end);
+    });
+    comment_lines.len()
+}
+
+/// 扫描源码，剥离注释与字符串，并报告每段注释覆盖的行范围
+///
+/// # Arguments
+/// * `content` - 原始源码
+/// * `language` - 语言类型
+/// * `on_comment` - 注释回调，参数为起止行号（从 0 开始，含两端）
+///
+/// # Returns
+/// * `String` - 剥离后的源码
+fn lex(content: &str, language: LanguageType, mut on_comment: impl FnMut(usize, usize)) -> String {
+    let rules = LexRules::for_language(language);
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    let mut line = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let written = out.len();
+        let mut is_comment = false;
+
+        if (rules.slash_comment && c == '/' && next == Some('/'))
+            || (rules.hash_comment && c == '#' && is_hash_comment_start(&rules, &chars, i))
+        {
+            i = blank_until(&chars, i, &mut out, |_, ch| ch == '\n');
+            is_comment = true;
+        } else if rules.block_comment && c == '/' && next == Some('*') {
+            i = skip_block_comment(&chars, i, rules.nested_block_comment, &mut out);
+            is_comment = true;
+        } else if rules.triple_quote_string && is_triple_quote(&chars, i) {
+            i = skip_triple_quote(&chars, i, &mut out);
+        } else if rules.raw_string && c == 'r' && is_raw_string_start(&chars, i) {
+            i = skip_raw_string(&chars, i, &mut out);
+        } else if rules.regex_literal && c == '/' && is_regex_start(&out) {
+            i = skip_regex(&chars, i, &mut out);
+        } else if is_quote_start(&rules, &chars, i) {
+            i = skip_quoted(&chars, i, c, &mut out);
+        } else {
+            out.push(c);
+            i += 1;
+        }
+
+        let start_line = line;
+        line += out[written..].matches('\n').count();
+        if is_comment {
+            on_comment(start_line, line);
+        }
+    }
+
+    out
+}
+
+/// 判断 `#` 是否开始注释
+///
+/// # Arguments
+/// * `rules` - 词法特征
+/// * `chars` - 源码字符
+/// * `i` - `#` 的位置
+///
+/// # Returns
+/// * `bool` - 是否为注释起点
+fn is_hash_comment_start(rules: &LexRules, chars: &[char], i: usize) -> bool {
+    if !rules.hash_comment_at_word_start || i == 0 {
+        return true;
+    }
+
+    let prev = chars[i - 1];
+    prev.is_whitespace() || ";|&(){}".contains(prev)
+}
+
+/// 判断 `/` 是否开始一个正则字面量（而不是除号）
+///
+/// 前一个非空白字符是运算符、分隔符或位于开头时视为正则。
+///
+/// # Arguments
+/// * `out` - 已输出的剥离源码
+///
+/// # Returns
+/// * `bool` - 是否为正则字面量
+fn is_regex_start(out: &str) -> bool {
+    match out.trim_end().chars().last() {
+        None => true,
+        Some(prev) => "(,=:[!&|?{};+-*%<>~^".contains(prev),
+    }
+}
+
+/// 跳过正则字面量（支持转义与字符类），不跨行
+///
+/// # Arguments
+/// * `chars` - 源码字符
+/// * `start` - 起始 `/` 的位置
+/// * `out` - 输出缓冲
+///
+/// # Returns
+/// * `usize` - 正则结束后的位置
+fn skip_regex(chars: &[char], start: usize, out: &mut String) -> usize {
+    out.push('/');
+    let mut i = start + 1;
+    let mut in_class = false;
+
+    while i < chars.len() && chars[i] != '\n' {
+        let c = chars[i];
+        if c == '\\' && i + 1 < chars.len() && chars[i + 1] != '\n' {
+            out.push_str("  ");
+            i += 2;
+            continue;
+        }
+        match c {
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => {
+                out.push('/');
+                return i + 1;
+            }
+            _ => {}
+        }
+        out.push(blank(c));
+        i += 1;
+    }
+
+    i
+}
+
+/// 判断当前位置是否开始一个普通引号字符串或字符字面量
+///
+/// # Arguments
+/// * `rules` - 词法特征
+/// * `chars` - 源码字符
+/// * `i` - 位置
+///
+/// # Returns
+/// * `bool` - 是否为引号开头
+fn is_quote_start(rules: &LexRules, chars: &[char], i: usize) -> bool {
+    match chars[i] {
+        '"' => true,
+        '`' => rules.backtick_string,
+        '\'' => rules.single_quote_string || is_char_literal(chars, i),
+        _ => false,
+    }
+}
+
+/// 把字符替换为空格（换行保持不变）
+///
+/// # Arguments
+/// * `c` - 原字符
+///
+/// # Returns
+/// * `char` - 替换后的字符
+fn blank(c: char) -> char {
+    if c == '\n' || c == '\r' {
+        c
+    } else {
+        ' '
+    }
+}
+
+/// 从 `start` 开始逐字符替换为空格，直到 `stop` 返回 true（不含该字符）
+///
+/// # Arguments
+/// * `chars` - 源码字符
+/// * `start` - 起始位置
+/// * `out` - 输出缓冲
+/// * `stop` - 结束条件，参数为位置和字符
+///
+/// # Returns
+/// * `usize` - 结束位置
+fn blank_until(
+    chars: &[char],
+    start: usize,
+    out: &mut String,
+    stop: impl Fn(usize, char) -> bool,
+) -> usize {
+    let mut i = start;
+    while i < chars.len() && !stop(i, chars[i]) {
+        out.push(blank(chars[i]));
+        i += 1;
+    }
+    i
+}
+
+/// 跳过块注释
+///
+/// # Arguments
+/// * `chars` - 源码字符
+/// * `start` - `/*` 的位置
+/// * `nested` - 块注释是否可以嵌套（内层的 `/*` 需要各自的 `*/` 闭合）
+/// * `out` - 输出缓冲
+///
+/// # Returns
+/// * `usize` - 注释结束后的位置
+fn skip_block_comment(chars: &[char], start: usize, nested: bool, out: &mut String) -> usize {
+    out.push_str("  ");
+    let mut depth = 1;
+    let mut i = start + 2;
+
+    while i < chars.len() {
+        let next = chars.get(i + 1).copied();
+        if chars[i] == '*' && next == Some('/') {
+            depth -= 1;
+        } else if nested && chars[i] == '/' && next == Some('*') {
+            depth += 1;
+        } else {
+            out.push(blank(chars[i]));
+            i += 1;
+            continue;
+        }
+
+        out.push_str("  ");
+        i += 2;
+        if depth == 0 {
+            break;
+        }
+    }
+
+    i
+}
+
+/// 跳过普通引号字符串（支持反斜杠转义）
+///
+/// # Arguments
+/// * `chars` - 源码字符
+/// * `start` - 起始引号位置
+/// * `quote` - 引号字符
+/// * `out` - 输出缓冲
+///
+/// # Returns
+/// * `usize` - 字符串结束后的位置
+fn skip_quoted(chars: &[char], start: usize, quote: char, out: &mut String) -> usize {
+    out.push(quote);
+    let mut i = start + 1;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\\' && i + 1 < chars.len() {
+            out.push(' ');
+            out.push(blank(chars[i + 1]));
+            i += 2;
+            continue;
+        }
+        if c == quote {
+            out.push(quote);
+            return i + 1;
+        }
+        // 普通引号字符串不跨行（反引号除外），遇到换行视为未闭合
+        if c == '\n' && quote != '`' {
+            return i;
+        }
+        out.push(blank(c));
+        i += 1;
+    }
+
+    i
+}
+
+/// 判断单引号是否开始一个字符字面量（而不是 Rust 生命周期等）
+///
+/// # Arguments
+/// * `chars` - 源码字符
+/// * `start` - 单引号位置
+///
+/// # Returns
+/// * `bool` - 是否为字符字面量
+fn is_char_literal(chars: &[char], start: usize) -> bool {
+    match chars.get(start + 1) {
+        Some('\\') => true,
+        Some(_) => chars.get(start + 2) == Some(&'\''),
+        None => false,
+    }
+}
+
+/// 判断是否为三引号
+///
+/// # Arguments
+/// * `chars` - 源码字符
+/// * `i` - 位置
+///
+/// # Returns
+/// * `bool` - 是否为三引号
+fn is_triple_quote(chars: &[char], i: usize) -> bool {
+    let c = chars[i];
+    (c == '"' || c == '\'') && chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&c)
+}
+
+/// 跳过三引号字符串
+///
+/// # Arguments
+/// * `chars` - 源码字符
+/// * `start` - 起始位置
+/// * `out` - 输出缓冲
+///
+/// # Returns
+/// * `usize` - 字符串结束后的位置
+fn skip_triple_quote(chars: &[char], start: usize, out: &mut String) -> usize {
+    let quote = chars[start];
+    out.extend([quote; 3]);
+    let i = blank_until(chars, start + 3, out, |i, _| {
+        is_triple_quote(chars, i) && chars[i] == quote
+    });
+
+    if i < chars.len() {
+        out.extend([quote; 3]);
+        i + 3
+    } else {
+        i
+    }
+}
+
+/// 判断是否为 Rust 原始字符串的开头（`r"`、`r#"`、`br"` 中的 `r`）
+///
+/// # Arguments
+/// * `chars` - 源码字符
+/// * `i` - `r` 的位置
+///
+/// # Returns
+/// * `bool` - 是否为原始字符串
+fn is_raw_string_start(chars: &[char], i: usize) -> bool {
+    // `r` 前面不能是标识符字符（`br` 除外）
+    if i > 0 {
+        let prev = chars[i - 1];
+        let is_byte_prefix = prev == 'b' && (i < 2 || !is_ident_char(chars[i - 2]));
+        if is_ident_char(prev) && !is_byte_prefix {
+            return false;
+        }
+    }
+
+    let mut j = i + 1;
+    while chars.get(j) == Some(&'#') {
+        j += 1;
+    }
+    chars.get(j) == Some(&'"')
+}
+
+/// 跳过 Rust 原始字符串
+///
+/// # Arguments
+/// * `chars` - 源码字符
+/// * `start` - `r` 的位置
+/// * `out` - 输出缓冲
+///
+/// # Returns
+/// * `usize` - 字符串结束后的位置
+fn skip_raw_string(chars: &[char], start: usize, out: &mut String) -> usize {
+    let mut i = start + 1;
+    let mut hashes = 0;
+    while chars[i] == '#' {
+        hashes += 1;
+        i += 1;
+    }
+    // 输出 r、# 与起始引号
+    out.extend(&chars[start..=i]);
+    i += 1;
+
+    let closes_at =
+        |i: usize| chars[i] == '"' && (1..=hashes).all(|k| chars.get(i + k) == Some(&'#'));
+    let i = blank_until(chars, i, out, |i, _| closes_at(i));
+
+    if i < chars.len() {
+        out.extend(&chars[i..=i + hashes]);
+        i + hashes + 1
+    } else {
+        i
+    }
+}
+
+/// 判断是否为标识符字符
+///
+/// # Arguments
+/// * `c` - 字符
+///
+/// # Returns
+/// * `bool` - 是否为标识符字符
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_and_comment_markers_inside_strings_are_not_comments() {
+        let source = "const url = \"http://example.com\";\n\
+                      const a = 'not /* a comment';\n\
+                      const b = `template // text ${x}`;\n\
+                      const c = 1;\n\
+                      const d = \"*/ still code\";\n";
+        assert_eq!(count_comment_lines(source, LanguageType::JavaScript), 0);
+    }
+
+    #[test]
+    fn regex_literals_are_not_comments() {
+        let source = "const slashes = /\\/\\/+/g;\n\
+                      const star = /a\\/*b/;\n\
+                      const ratio = total / count; // 平均值\n\
+                      const next = 1;\n";
+        assert_eq!(count_comment_lines(source, LanguageType::JavaScript), 1);
+    }
+
+    #[test]
+    fn trailing_and_block_comments_are_counted_once_per_line() {
+        let source = "let x = 1; // 行尾注释\n\
+                      /* 块注释\n\
+                      \x20  第二行 */ let y = 2;\n\
+                      let z = 3; /* a */ /* b */\n\
+                      let w = 4;\n";
+        assert_eq!(count_comment_lines(source, LanguageType::JavaScript), 4);
+        assert_eq!(count_comment_lines(source, LanguageType::C), 4);
+    }
+
+    #[test]
+    fn stripped_source_keeps_line_structure() {
+        let source = "call(\"a // b\"); // c\n/* d\n e */ done();\n";
+        let stripped = strip_source(source, LanguageType::JavaScript);
+
+        assert_eq!(stripped.lines().count(), source.lines().count());
+        assert!(stripped.contains("call("));
+        assert!(stripped.contains("done();"));
+        assert!(!stripped.contains("//"));
+        assert!(!stripped.contains("/*"));
+    }
+
+    #[test]
+    fn hash_comments_respect_strings() {
+        let source = "color = \"#fff\"  # 白色\nurl = 'http://x#anchor'\nvalue = 1\n";
+        assert_eq!(count_comment_lines(source, LanguageType::Python), 1);
+    }
+}
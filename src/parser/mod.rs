@@ -2,51 +2,160 @@
 //!
 //! 提供各种编程语言的代码解析功能
 
+mod anonymous;
 mod base;
 mod c;
 mod csharp;
 mod css;
+mod doc_comment;
+mod elixir;
 mod generic;
 mod go;
 mod html;
+mod identifiers;
 mod java;
 mod javascript;
+mod lexer;
 mod php;
 mod python;
 mod rust;
+mod shell;
+mod swift;
 mod typescript;
+mod visibility;
+mod zig;
 
 use crate::common::LanguageType;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::{Arc, RwLock};
 
-pub use base::{BaseParseResult, Function, ParseResult, Parser};
+pub use anonymous::{detect_anonymous_functions, ANONYMOUS_PREFIX};
+pub use base::{
+    count_signature_parameters, count_top_level_parameters, measure_cognitive_complexity,
+    measure_nesting_depth, BaseParseResult, Function, ParseResult, Parser,
+};
 pub use c::CParser;
 pub use csharp::CSharpParser;
 pub use css::CSSParser;
+pub use doc_comment::has_doc_comment;
+pub use elixir::ElixirParser;
 pub use generic::GenericParser;
 pub use go::GoParser;
 pub use html::HTMLParser;
+pub use identifiers::{collect_identifiers, Identifier, IdentifierKind};
 pub use java::JavaParser;
 pub use javascript::JavaScriptParser;
+pub use lexer::{count_comment_lines, strip_source};
 pub use php::PHPParser;
 pub use python::PythonParser;
 pub use rust::RustParser;
+pub use shell::ShellParser;
+pub use swift::SwiftParser;
 pub use typescript::TypeScriptParser;
+pub use visibility::is_exported;
+pub use zig::ZigParser;
+
+/// 解析器构造函数
+pub type ParserConstructor = Arc<dyn Fn() -> Box<dyn Parser> + Send + Sync>;
+
+/// 自定义解析器注册表（扩展名 -> 构造函数）
+static PARSER_REGISTRY: Lazy<RwLock<HashMap<String, ParserConstructor>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 为指定扩展名注册自定义解析器
+///
+/// 注册后该扩展名的文件会被纳入分析，并优先使用自定义解析器（包括覆盖内置语言）。
+/// 重复注册同一扩展名时，后注册的解析器生效。
+///
+/// # Arguments
+/// * `extension` - 文件扩展名（不含点，不区分大小写）
+/// * `constructor` - 解析器构造函数
+pub fn register_parser<F>(extension: &str, constructor: F)
+where
+    F: Fn() -> Box<dyn Parser> + Send + Sync + 'static,
+{
+    let key = normalize_extension(extension);
+    let mut registry = PARSER_REGISTRY
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    registry.insert(key, Arc::new(constructor));
+}
+
+/// 判断文件是否有已注册的自定义解析器
+///
+/// # Arguments
+/// * `file_path` - 文件路径
+///
+/// # Returns
+/// * `bool` - 是否已注册
+pub fn has_registered_parser(file_path: &Path) -> bool {
+    registered_parser(file_path).is_some()
+}
+
+/// 查找文件对应的自定义解析器构造函数
+///
+/// # Arguments
+/// * `file_path` - 文件路径
+///
+/// # Returns
+/// * `Option<ParserConstructor>` - 构造函数
+fn registered_parser(file_path: &Path) -> Option<ParserConstructor> {
+    let extension = file_path.extension()?.to_str()?;
+    let registry = PARSER_REGISTRY
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    registry.get(&normalize_extension(extension)).cloned()
+}
+
+/// 规范化扩展名（去掉开头的点并转为小写）
+///
+/// # Arguments
+/// * `extension` - 扩展名
+///
+/// # Returns
+/// * `String` - 规范化后的扩展名
+fn normalize_extension(extension: &str) -> String {
+    extension.trim_start_matches('.').to_lowercase()
+}
 
 /// 根据文件创建对应的解析器
 ///
+/// 优先使用已注册的自定义解析器，否则按语言类型选择内置解析器。
+///
 /// # Arguments
 /// * `file_path` - 文件路径
 ///
 /// # Returns
 /// * `Box<dyn Parser>` - 解析器实例
 pub fn create_parser_for_file(file_path: &Path) -> Box<dyn Parser> {
+    if let Some(constructor) = registered_parser(file_path) {
+        return constructor();
+    }
+
     let detector = crate::common::LanguageDetector::new();
     let language = detector.detect_language(file_path);
 
     create_parser_for_language(language)
 }
 
+/// 判断文件是否会回退到通用解析器
+///
+/// 既没有注册自定义解析器、也不属于内置语言的文件只能用通用解析器做近似分析。
+///
+/// # Arguments
+/// * `file_path` - 文件路径
+///
+/// # Returns
+/// * `bool` - 是否使用通用解析器
+pub fn uses_generic_parser(file_path: &Path) -> bool {
+    registered_parser(file_path).is_none()
+        && !crate::common::LanguageDetector::new().is_supported_file(file_path)
+}
+
 /// 根据语言类型创建解析器
 ///
 /// # Arguments
@@ -58,6 +167,8 @@ pub fn create_parser_for_language(language: LanguageType) -> Box<dyn Parser> {
     match language {
         LanguageType::Rust => Box::new(RustParser::new()),
         LanguageType::Go => Box::new(GoParser::new()),
+        LanguageType::Zig => Box::new(ZigParser::new()),
+        LanguageType::Elixir => Box::new(ElixirParser::new()),
         LanguageType::JavaScript => Box::new(JavaScriptParser::new()),
         LanguageType::TypeScript => Box::new(TypeScriptParser::new()),
         LanguageType::Python => Box::new(PythonParser::new()),
@@ -67,6 +178,78 @@ pub fn create_parser_for_language(language: LanguageType) -> Box<dyn Parser> {
         LanguageType::PHP => Box::new(PHPParser::new()),
         LanguageType::HTML => Box::new(HTMLParser::new()),
         LanguageType::CSS => Box::new(CSSParser::new()),
+        LanguageType::Shell => Box::new(ShellParser::new()),
+        LanguageType::Swift => Box::new(SwiftParser::new()),
         _ => Box::new(GenericParser::new()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 把每个非空行当作一个函数的测试解析器
+    struct LineParser;
+
+    impl Parser for LineParser {
+        fn parse(
+            &self,
+            _file_path: &Path,
+            content: &str,
+        ) -> Result<Box<dyn ParseResult>, Box<dyn std::error::Error>> {
+            let functions = content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(i, line)| Function::new(line.trim().to_string(), i + 1, i + 1, 1, 0))
+                .collect();
+
+            Ok(Box::new(BaseParseResult {
+                functions,
+                comment_lines: 0,
+                total_lines: content.lines().count(),
+                source: content.to_string(),
+                language: LanguageType::Unsupported,
+            }))
+        }
+
+        fn supported_languages(&self) -> Vec<LanguageType> {
+            vec![LanguageType::Unsupported]
+        }
+    }
+
+    #[test]
+    fn registered_parser_is_used_for_its_extension() {
+        let path = Path::new("script.fscx");
+        assert!(uses_generic_parser(path));
+        assert!(!has_registered_parser(path));
+
+        register_parser(".FSCX", || Box::new(LineParser));
+
+        assert!(has_registered_parser(path));
+        assert!(has_registered_parser(Path::new("OTHER.Fscx")));
+        assert!(!uses_generic_parser(path));
+
+        let result = create_parser_for_file(path)
+            .parse(path, "alpha\n\nbeta\n")
+            .unwrap();
+        let names: Vec<&str> = result
+            .get_functions()
+            .iter()
+            .map(|func| func.name.as_str())
+            .collect();
+        assert_eq!(names, ["alpha", "beta"]);
+    }
+
+    #[test]
+    fn builtin_languages_are_not_affected_by_other_registrations() {
+        register_parser("fscy", || Box::new(LineParser));
+
+        let path = Path::new("lib.rs");
+        let result = create_parser_for_file(path)
+            .parse(path, "fn alpha() {}\n\nbeta\n")
+            .unwrap();
+        assert_eq!(result.get_functions().len(), 1);
+        assert_eq!(result.get_language(), LanguageType::Rust);
+    }
+}
@@ -3,7 +3,10 @@
 //! 专门用于解析PHP源代码文件
 
 use crate::common::LanguageType;
-use crate::parser::{BaseParseResult, Function, ParseResult, Parser};
+use crate::parser::{
+    count_comment_lines, has_doc_comment, is_exported, measure_nesting_depth, BaseParseResult,
+    Function, ParseResult, Parser,
+};
 use regex::Regex;
 use std::path::Path;
 
@@ -35,68 +38,6 @@ impl PHPParser {
         }
     }
 
-    /// 计数注释行
-    ///
-    /// # Arguments
-    /// * `lines` - 代码行
-    ///
-    /// # Returns
-    /// * `usize` - 注释行数
-    fn count_comment_lines(&self, lines: &[&str]) -> usize {
-        let mut count = 0;
-        let mut in_block_comment = false;
-        let mut in_doc_comment = false;
-
-        for line in lines {
-            let trimmed = line.trim();
-
-            // 处理块注释
-            if in_block_comment {
-                count += 1;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-                continue;
-            }
-
-            // 处理文档注释
-            if in_doc_comment {
-                count += 1;
-                if trimmed.contains("*/") {
-                    in_doc_comment = false;
-                }
-                continue;
-            }
-
-            // 检查单行注释
-            if self.is_single_line_comment(trimmed) {
-                count += 1;
-                continue;
-            }
-
-            // 检查文档注释开始
-            if trimmed.starts_with("/**") {
-                count += 1;
-                in_doc_comment = true;
-                if trimmed.contains("*/") {
-                    in_doc_comment = false;
-                }
-                continue;
-            }
-
-            // 检查块注释开始
-            if trimmed.starts_with("/*") {
-                count += 1;
-                in_block_comment = true;
-                if trimmed.contains("*/") {
-                    in_block_comment = false;
-                }
-            }
-        }
-
-        count
-    }
-
     /// 判断是否为单行注释
     ///
     /// # Arguments
@@ -168,13 +109,21 @@ impl PHPParser {
         // 计算复杂度
         let complexity = self.calculate_complexity(&lines[start..=end_line.min(lines.len() - 1)]);
 
-        Some(Function::new(
+        let mut function = Function::new(
             name,
             start + 1,    // 转换为1索引
             end_line + 1, // 转换为1索引
             complexity,
             parameters,
-        ))
+        );
+        function.exported = is_exported(lines[start], &function.name, LanguageType::PHP);
+        function.documented = has_doc_comment(lines, start, LanguageType::PHP);
+        function.max_nesting_depth = measure_nesting_depth(
+            &lines[start..=end_line.min(lines.len() - 1)],
+            LanguageType::PHP,
+        );
+
+        Some(function)
     }
 
     /// 计数参数
@@ -338,7 +287,7 @@ impl Parser for PHPParser {
         let total_lines = lines.len();
 
         // 计算注释行数
-        let comment_lines = self.count_comment_lines(&lines);
+        let comment_lines = count_comment_lines(content, LanguageType::PHP);
 
         // 检测函数
         let functions = self.detect_functions(&lines);
@@ -347,6 +296,7 @@ impl Parser for PHPParser {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language: LanguageType::PHP,
         }))
     }
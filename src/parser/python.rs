@@ -1,5 +1,8 @@
 use crate::common::LanguageType;
-use crate::parser::{BaseParseResult, Function, ParseResult, Parser};
+use crate::parser::{
+    has_doc_comment, is_exported, measure_nesting_depth, BaseParseResult, Function, ParseResult,
+    Parser,
+};
 use regex::Regex;
 use std::path::Path;
 
@@ -27,6 +30,7 @@ impl Parser for PythonParser {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language: LanguageType::Python,
         }))
     }
@@ -95,6 +99,7 @@ impl PythonParser {
 
                 let end_line = self.find_function_end(lines, i);
                 let complexity = self.calculate_complexity(&lines[i..=end_line]);
+                let exported = is_exported(line, &func_name, LanguageType::Python);
 
                 functions.push(Function {
                     name: func_name,
@@ -102,6 +107,13 @@ impl PythonParser {
                     end_line: end_line + 1,
                     complexity,
                     parameters: params,
+                    max_nesting_depth: measure_nesting_depth(
+                        &lines[i..=end_line],
+                        LanguageType::Python,
+                    ),
+                    exported,
+                    documented: has_doc_comment(lines, i, LanguageType::Python),
+                    body: None,
                 });
             }
         }
@@ -3,7 +3,10 @@
 //! 专门用于解析Rust源代码文件
 
 use crate::common::LanguageType;
-use crate::parser::{BaseParseResult, Function, ParseResult, Parser};
+use crate::parser::{
+    has_doc_comment, is_exported, measure_nesting_depth, BaseParseResult, Function, ParseResult,
+    Parser,
+};
 use regex::Regex;
 use std::path::Path;
 
@@ -20,7 +23,7 @@ impl RustParser {
     /// * `Self` - 解析器实例
     pub fn new() -> Self {
         let function_regex = Regex::new(
-            r"^\s*(pub\s+)?(async\s+)?(unsafe\s+)?(extern\s+.*\s+)?fn\s+([a-zA-Z_][a-zA-Z0-9_]*)\s*(?:<[^>]*>)?\s*\(([^)]*)\)"
+            r"^\s*(pub(?:\([^)]*\))?\s+)?(?:const\s+)?(async\s+)?(unsafe\s+)?(extern\s+.*\s+)?fn\s+([a-zA-Z_][a-zA-Z0-9_]*)\s*(?:<[^>]*>)?\s*\(([^)]*)\)"
         ).unwrap();
 
         RustParser { function_regex }
@@ -150,13 +153,21 @@ impl RustParser {
         // 计算复杂度
         let complexity = self.calculate_complexity(&lines[start..=end_line.min(lines.len() - 1)]);
 
-        Some(Function::new(
+        let mut function = Function::new(
             name,
             start + 1,    // 转换为1索引
             end_line + 1, // 转换为1索引
             complexity,
             parameters,
-        ))
+        );
+        function.exported = is_exported(lines[start], &function.name, LanguageType::Rust);
+        function.documented = has_doc_comment(lines, start, LanguageType::Rust);
+        function.max_nesting_depth = measure_nesting_depth(
+            &lines[start..=end_line.min(lines.len() - 1)],
+            LanguageType::Rust,
+        );
+
+        Some(function)
     }
 
     /// 计数参数
@@ -333,6 +344,7 @@ impl Parser for RustParser {
             functions,
             comment_lines,
             total_lines,
+            source: content.to_string(),
             language: LanguageType::Rust,
         }))
     }
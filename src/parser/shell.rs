@@ -0,0 +1,185 @@
+//! # Shell脚本解析器
+//!
+//! 专门用于解析 sh/bash/zsh 脚本
+
+use crate::common::LanguageType;
+use crate::parser::{
+    count_comment_lines, has_doc_comment, measure_nesting_depth, strip_source, BaseParseResult,
+    Function, ParseResult, Parser,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// 函数定义（`name() {` 或 `function name {`，函数体的 `{` 可以在下一行）
+static FUNCTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:function\s+([A-Za-z_][\w:.-]*)\s*(?:\(\s*\))?|([A-Za-z_][\w:.-]*)\s*\(\s*\))\s*(?:\{|$)",
+    )
+    .unwrap()
+});
+
+/// 分支关键字与运算符（`||` 整体计一次）
+static BRANCH_KEYWORD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:if|elif|for|while|case)\b|&&|\|\||\|").unwrap());
+
+/// Shell解析器
+pub struct ShellParser;
+
+impl ShellParser {
+    /// 创建新的Shell解析器
+    ///
+    /// # Returns
+    /// * `Self` - 解析器实例
+    pub fn new() -> Self {
+        ShellParser
+    }
+
+    /// 检测函数
+    ///
+    /// # Arguments
+    /// * `lines` - 原始代码行
+    /// * `code` - 剥离注释和字符串后的代码行
+    ///
+    /// # Returns
+    /// * `Vec<Function>` - 函数列表
+    fn detect_functions(&self, lines: &[&str], code: &[&str]) -> Vec<Function> {
+        let mut functions = Vec::new();
+        let mut i = 0;
+
+        while i < code.len() {
+            if let Some(func) = self.try_parse_function(lines, code, i) {
+                i = func.end_line; // 跳到函数结束
+                functions.push(func);
+            } else {
+                i += 1;
+            }
+        }
+
+        functions
+    }
+
+    /// 尝试解析函数
+    ///
+    /// # Arguments
+    /// * `lines` - 原始代码行
+    /// * `code` - 剥离后的代码行
+    /// * `start` - 起始位置
+    ///
+    /// # Returns
+    /// * `Option<Function>` - 函数信息
+    fn try_parse_function(&self, lines: &[&str], code: &[&str], start: usize) -> Option<Function> {
+        let captures = FUNCTION_REGEX.captures(code[start])?;
+        let name = captures.get(1).or_else(|| captures.get(2))?.as_str();
+
+        let end_line = self.find_function_end(code, start)?;
+        let complexity = self.calculate_complexity(&code[start..=end_line]);
+
+        // Shell 函数不声明参数，通过 $1、$@ 等访问
+        let mut function = Function::new(
+            name.to_string(),
+            start + 1,    // 转换为1索引
+            end_line + 1, // 转换为1索引
+            complexity,
+            0,
+        );
+        function.documented = has_doc_comment(lines, start, LanguageType::Shell);
+        function.max_nesting_depth =
+            measure_nesting_depth(&lines[start..=end_line], LanguageType::Shell);
+
+        Some(function)
+    }
+
+    /// 查找函数结束位置
+    ///
+    /// # Arguments
+    /// * `code` - 剥离后的代码行
+    /// * `start` - 起始位置
+    ///
+    /// # Returns
+    /// * `Option<usize>` - 结束位置（定义行和下一行都没有 `{` 时为 None）
+    fn find_function_end(&self, code: &[&str], start: usize) -> Option<usize> {
+        let mut depth = 0usize;
+        let mut found_body = false;
+
+        for (i, line) in code.iter().enumerate().skip(start) {
+            if !found_body && i > start + 1 {
+                return None;
+            }
+
+            for ch in line.chars() {
+                match ch {
+                    '{' => {
+                        depth += 1;
+                        found_body = true;
+                    }
+                    '}' => {
+                        depth = depth.saturating_sub(1);
+                        if found_body && depth == 0 {
+                            return Some(i);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        None
+    }
+
+    /// 计算循环复杂度
+    ///
+    /// # Arguments
+    /// * `function_lines` - 剥离后的函数代码行
+    ///
+    /// # Returns
+    /// * `usize` - 复杂度
+    fn calculate_complexity(&self, function_lines: &[&str]) -> usize {
+        1 + function_lines
+            .iter()
+            .map(|line| BRANCH_KEYWORD.find_iter(line).count())
+            .sum::<usize>()
+    }
+}
+
+impl Parser for ShellParser {
+    /// 解析Shell脚本
+    ///
+    /// # Arguments
+    /// * `_file_path` - 文件路径
+    /// * `content` - 文件内容
+    ///
+    /// # Returns
+    /// * `Result<Box<dyn ParseResult>, Box<dyn std::error::Error>>` - 解析结果
+    fn parse(
+        &self,
+        _file_path: &Path,
+        content: &str,
+    ) -> Result<Box<dyn ParseResult>, Box<dyn std::error::Error>> {
+        let lines: Vec<&str> = content.lines().collect();
+        let stripped = strip_source(content, LanguageType::Shell);
+        let code: Vec<&str> = stripped.lines().collect();
+
+        // shebang 不是注释
+        let shebang = usize::from(content.starts_with("#!"));
+        let comment_lines =
+            count_comment_lines(content, LanguageType::Shell).saturating_sub(shebang);
+        let functions = self.detect_functions(&lines, &code);
+
+        Ok(Box::new(BaseParseResult {
+            functions,
+            comment_lines,
+            total_lines: lines.len(),
+            source: content.to_string(),
+            language: LanguageType::Shell,
+        }))
+    }
+
+    /// 获取支持的语言
+    ///
+    /// # Returns
+    /// * `Vec<LanguageType>` - 语言列表
+    fn supported_languages(&self) -> Vec<LanguageType> {
+        vec![LanguageType::Shell]
+    }
+}
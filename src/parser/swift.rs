@@ -0,0 +1,212 @@
+//! # Swift语言解析器
+//!
+//! 专门用于解析Swift源代码文件
+
+use crate::common::LanguageType;
+use crate::parser::{
+    count_comment_lines, count_top_level_parameters, has_doc_comment, is_exported,
+    measure_nesting_depth, strip_source, BaseParseResult, Function, ParseResult, Parser,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// 函数定义（`func name(`，可带属性、访问控制和其他修饰符，运算符函数的名称为运算符本身）
+static FUNCTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:@\w+(?:\([^)]*\))?\s+|(?:(?:public|private|fileprivate|internal|open)(?:\(set\))?|static|class|final|override|mutating|nonmutating|convenience|required|dynamic|nonisolated)\s+)*func\s+([A-Za-z_]\w*|[^\s\w(<]+)\s*(?:<[^(]*>)?\s*\(",
+    )
+    .unwrap()
+});
+
+/// 分支关键字与运算符（三元运算符的 `?` 两侧必须有空格，可与可选类型的 `?` 区分）
+static BRANCH_KEYWORD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:if|guard|for|while|switch|case)\b|&&|\|\||\?\?|\s\?\s").unwrap()
+});
+
+/// 签名跨行时，参数列表之后可以出现的续行开头
+const SIGNATURE_CONTINUATIONS: &[&str] = &["->", "where", "throws", "rethrows", "async", "{"];
+
+/// Swift解析器
+pub struct SwiftParser;
+
+impl SwiftParser {
+    /// 创建新的Swift解析器
+    ///
+    /// # Returns
+    /// * `Self` - 解析器实例
+    pub fn new() -> Self {
+        SwiftParser
+    }
+
+    /// 检测函数
+    ///
+    /// # Arguments
+    /// * `lines` - 原始代码行
+    /// * `code` - 剥离注释和字符串后的代码行
+    ///
+    /// # Returns
+    /// * `Vec<Function>` - 函数列表
+    fn detect_functions(&self, lines: &[&str], code: &[&str]) -> Vec<Function> {
+        let mut functions = Vec::new();
+        let mut i = 0;
+
+        while i < code.len() {
+            if let Some(func) = self.try_parse_function(lines, code, i) {
+                i = func.end_line; // 跳到函数结束
+                functions.push(func);
+            } else {
+                i += 1;
+            }
+        }
+
+        functions
+    }
+
+    /// 尝试解析函数
+    ///
+    /// # Arguments
+    /// * `lines` - 原始代码行
+    /// * `code` - 剥离后的代码行
+    /// * `start` - 起始位置
+    ///
+    /// # Returns
+    /// * `Option<Function>` - 函数信息（协议中没有函数体的声明返回 None）
+    fn try_parse_function(&self, lines: &[&str], code: &[&str], start: usize) -> Option<Function> {
+        let captures = FUNCTION_REGEX.captures(code[start])?;
+        let name = captures.get(1)?.as_str().to_string();
+        let params_start = captures.get(0)?.end();
+
+        let end_line = self.find_function_end(code, start, params_start)?;
+        let signature = code[start..=end_line].join("\n");
+        let parameters = count_top_level_parameters(&signature[params_start..]);
+        let complexity = self.calculate_complexity(&code[start..=end_line]);
+
+        let mut function = Function::new(
+            name,
+            start + 1,    // 转换为1索引
+            end_line + 1, // 转换为1索引
+            complexity,
+            parameters,
+        );
+        function.exported = is_exported(lines[start], &function.name, LanguageType::Swift);
+        function.documented = has_doc_comment(lines, start, LanguageType::Swift);
+        function.max_nesting_depth =
+            measure_nesting_depth(&lines[start..=end_line], LanguageType::Swift);
+
+        Some(function)
+    }
+
+    /// 查找函数结束位置
+    ///
+    /// 先跳过参数列表，函数体的 `{` 必须出现在参数列表结束的行，
+    /// 或以 `->`、`where`、`throws` 等开头的签名续行中。
+    ///
+    /// # Arguments
+    /// * `code` - 剥离后的代码行
+    /// * `start` - 起始位置
+    /// * `params_start` - 定义行中参数列表左括号之后的字节位置
+    ///
+    /// # Returns
+    /// * `Option<usize>` - 结束位置（没有函数体时为 None）
+    fn find_function_end(&self, code: &[&str], start: usize, params_start: usize) -> Option<usize> {
+        let mut paren_depth = 1usize;
+        let mut brace_depth = 0usize;
+        let mut params_closed_line = None;
+
+        for (i, line) in code.iter().enumerate().skip(start) {
+            let text = if i == start {
+                &line[params_start..]
+            } else {
+                line
+            };
+
+            // 参数列表结束后的新行只能是签名续行
+            let is_continuation = SIGNATURE_CONTINUATIONS
+                .iter()
+                .any(|prefix| text.trim_start().starts_with(prefix));
+            if brace_depth == 0 && params_closed_line.is_some_and(|l| l < i) && !is_continuation {
+                return None;
+            }
+
+            for ch in text.chars() {
+                match ch {
+                    '(' if params_closed_line.is_none() => paren_depth += 1,
+                    ')' if params_closed_line.is_none() => {
+                        paren_depth -= 1;
+                        if paren_depth == 0 {
+                            params_closed_line = Some(i);
+                        }
+                    }
+                    '{' if params_closed_line.is_some() => brace_depth += 1,
+                    '}' if params_closed_line.is_some() => {
+                        if brace_depth == 0 {
+                            return None;
+                        }
+                        brace_depth -= 1;
+                        if brace_depth == 0 {
+                            return Some(i);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        None
+    }
+
+    /// 计算循环复杂度
+    ///
+    /// # Arguments
+    /// * `function_lines` - 剥离后的函数代码行
+    ///
+    /// # Returns
+    /// * `usize` - 复杂度
+    fn calculate_complexity(&self, function_lines: &[&str]) -> usize {
+        1 + function_lines
+            .iter()
+            .map(|line| BRANCH_KEYWORD.find_iter(line).count())
+            .sum::<usize>()
+    }
+}
+
+impl Parser for SwiftParser {
+    /// 解析Swift代码
+    ///
+    /// # Arguments
+    /// * `_file_path` - 文件路径
+    /// * `content` - 文件内容
+    ///
+    /// # Returns
+    /// * `Result<Box<dyn ParseResult>, Box<dyn std::error::Error>>` - 解析结果
+    fn parse(
+        &self,
+        _file_path: &Path,
+        content: &str,
+    ) -> Result<Box<dyn ParseResult>, Box<dyn std::error::Error>> {
+        let lines: Vec<&str> = content.lines().collect();
+        let stripped = strip_source(content, LanguageType::Swift);
+        let code: Vec<&str> = stripped.lines().collect();
+
+        // Swift 的块注释可以嵌套，由词法扫描按嵌套层级匹配 `*/`
+        let comment_lines = count_comment_lines(content, LanguageType::Swift);
+        let functions = self.detect_functions(&lines, &code);
+
+        Ok(Box::new(BaseParseResult {
+            functions,
+            comment_lines,
+            total_lines: lines.len(),
+            source: content.to_string(),
+            language: LanguageType::Swift,
+        }))
+    }
+
+    /// 获取支持的语言
+    ///
+    /// # Returns
+    /// * `Vec<LanguageType>` - 语言列表
+    fn supported_languages(&self) -> Vec<LanguageType> {
+        vec![LanguageType::Swift]
+    }
+}
@@ -1,39 +1,479 @@
+//! # TypeScript语言解析器
+//!
+//! 在 JavaScript 函数形式的基础上识别泛型、参数和返回值的类型注解、访问修饰符与装饰器；
+//! 注释统计与 JavaScript 使用相同的词法规则
+
 use crate::common::LanguageType;
-use crate::parser::{BaseParseResult, JavaScriptParser, ParseResult, Parser};
+use crate::parser::{
+    count_comment_lines, count_signature_parameters, has_doc_comment, is_exported,
+    measure_nesting_depth, strip_source, BaseParseResult, Function, ParseResult, Parser,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use std::path::Path;
 
+/// 函数声明（`function name<T>(`，可带 `export`/`async`/生成器 `*`）
+static FUNCTION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bfunction\s*\*?\s*([A-Za-z_$][\w$]*)\s*(?:<[^>]*>)?\s*\(").unwrap());
+
+/// 变量形式的箭头函数（`const name: Type = async <T>(`）
+static ARROW_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*(?::[^=]*)?=\s*(?:async\s+)?(?:<[^>]*>\s*)?\(",
+    )
+    .unwrap()
+});
+
+/// 函数表达式（`name = function(`）
+static FUNCTION_EXPRESSION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([A-Za-z_$][\w$]*)\s*=\s*(?:async\s+)?function\s*\*?\s*\(").unwrap());
+
+/// 类方法（可带装饰器、访问修饰符、可选标记 `?` 和泛型）
+static METHOD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:@[\w.]+(?:\([^)]*\))?\s+)*(?:(?:public|private|protected|static|async|readonly|override|abstract|declare|get|set)\s+)*\*?\s*([A-Za-z_$][\w$]*)\s*\??\s*(?:<[^>]*>)?\s*\(",
+    )
+    .unwrap()
+});
+
+/// 以箭头函数赋值的类属性（`handle = (e: Event): void => {`）
+static PROPERTY_ARROW_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:public|private|protected|static|readonly|override)\s+)*([A-Za-z_$][\w$]*)\s*(?::[^=]*)?=\s*(?:async\s+)?(?:<[^>]*>\s*)?\(",
+    )
+    .unwrap()
+});
+
+/// 类声明
+static CLASS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bclass\s+[A-Za-z_$][\w$]*").unwrap());
+
+/// 分支关键字与运算符（`?.` 可选链和 `name?:` 可选标记不计入）
+static DECISION_POINT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:if|for|while|case|catch)\b|&&|\|\||\?\?|\?(?:[^.:?]|$)").unwrap()
+});
+
+/// 形似方法调用但不是方法定义的关键字
+const NON_METHOD_KEYWORDS: &[&str] = &[
+    "if", "for", "while", "switch", "catch", "return", "function", "new", "typeof", "super",
+];
+
+/// 函数定义的形式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DefinitionKind {
+    /// 参数列表之后是函数体（函数声明、方法）
+    Block,
+
+    /// 参数列表之后是 `=>`（箭头函数）
+    Arrow,
+}
+
+/// TypeScript解析器
 pub struct TypeScriptParser;
 
 impl TypeScriptParser {
+    /// 创建新的TypeScript解析器
+    ///
+    /// # Returns
+    /// * `Self` - 解析器实例
     pub fn new() -> Self {
         TypeScriptParser
     }
+
+    /// 检测函数
+    ///
+    /// 在剥离注释和字符串后的源码上匹配，每行最多记录一个函数；
+    /// 只有直接位于类体中的定义才按方法识别。
+    ///
+    /// # Arguments
+    /// * `lines` - 原始代码行
+    /// * `code` - 剥离后的代码行
+    ///
+    /// # Returns
+    /// * `Vec<Function>` - 函数列表
+    fn detect_functions(&self, lines: &[&str], code: &[&str]) -> Vec<Function> {
+        let mut functions = Vec::new();
+        let mut class_bodies: Vec<usize> = Vec::new();
+        let mut depth = 0usize;
+
+        for (i, line) in code.iter().enumerate() {
+            // 离开已经结束的类
+            while class_bodies.last().is_some_and(|&body| depth < body) {
+                class_bodies.pop();
+            }
+
+            let in_class_body = class_bodies.last() == Some(&depth);
+            if let Some(function) = self.try_parse_function(lines, code, i, in_class_body) {
+                functions.push(function);
+            }
+
+            if CLASS_REGEX.is_match(line) {
+                class_bodies.push(depth + 1);
+            }
+            depth = brace_depth_after(line, depth);
+        }
+
+        functions
+    }
+
+    /// 尝试在指定行解析函数
+    ///
+    /// # Arguments
+    /// * `lines` - 原始代码行
+    /// * `code` - 剥离后的代码行
+    /// * `start` - 行号（0 索引）
+    /// * `in_class_body` - 该行是否直接位于类体中
+    ///
+    /// # Returns
+    /// * `Option<Function>` - 函数信息（重载签名、抽象方法等没有函数体时为 None）
+    fn try_parse_function(
+        &self,
+        lines: &[&str],
+        code: &[&str],
+        start: usize,
+        in_class_body: bool,
+    ) -> Option<Function> {
+        let line = code[start];
+        let method = in_class_body.then(|| match_method(line)).flatten();
+        let (name, name_end, kind) = method.or_else(|| match_function(line))?;
+
+        let end_line = find_definition_end(code, start, name_end, kind)?;
+        let body = &code[start..=end_line];
+        let complexity = 1 + body
+            .iter()
+            .map(|line| DECISION_POINT.find_iter(line).count())
+            .sum::<usize>();
+
+        let mut function = Function::new(
+            name,
+            start + 1,    // 转换为1索引
+            end_line + 1, // 转换为1索引
+            complexity,
+            count_signature_parameters(code, start, end_line, name_end),
+        );
+        function.exported = is_exported(lines[start], &function.name, LanguageType::TypeScript);
+        function.documented = has_doc_comment(lines, start, LanguageType::TypeScript);
+        function.max_nesting_depth =
+            measure_nesting_depth(&lines[start..=end_line], LanguageType::TypeScript);
+
+        Some(function)
+    }
 }
 
 impl Parser for TypeScriptParser {
+    /// 解析TypeScript文件
+    ///
+    /// # Arguments
+    /// * `_file_path` - 文件路径
+    /// * `content` - 文件内容
+    ///
+    /// # Returns
+    /// * `Result<Box<dyn ParseResult>, Box<dyn std::error::Error>>` - 解析结果
     fn parse(
         &self,
-        file_path: &Path,
+        _file_path: &Path,
         content: &str,
     ) -> Result<Box<dyn ParseResult>, Box<dyn std::error::Error>> {
-        // TypeScript和JavaScript解析逻辑相似，复用JavaScript解析器
-        let js_parser = JavaScriptParser::new();
-        let result = js_parser.parse(file_path, content)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let stripped = strip_source(content, LanguageType::TypeScript);
+        let code: Vec<&str> = stripped.lines().collect();
 
-        // 创建一个新的结果，修改语言类型
-        let functions = result.get_functions().to_vec();
-        let comment_lines = result.get_comment_lines();
-        let total_lines = result.get_total_lines();
+        let comment_lines = count_comment_lines(content, LanguageType::TypeScript);
+        let functions = self.detect_functions(&lines, &code);
 
         Ok(Box::new(BaseParseResult {
             functions,
             comment_lines,
-            total_lines,
+            total_lines: lines.len(),
+            source: content.to_string(),
             language: LanguageType::TypeScript,
         }))
     }
 
+    /// 获取支持的语言
+    ///
+    /// # Returns
+    /// * `Vec<LanguageType>` - 语言列表
     fn supported_languages(&self) -> Vec<LanguageType> {
         vec![LanguageType::TypeScript]
     }
 }
+
+/// 匹配类体中的方法或箭头函数属性
+///
+/// # Arguments
+/// * `line` - 剥离后的代码行
+///
+/// # Returns
+/// * `Option<(String, usize, DefinitionKind)>` - (名称, 名称结束位置, 定义形式)，构造函数不计入
+fn match_method(line: &str) -> Option<(String, usize, DefinitionKind)> {
+    let (captures, kind) = match METHOD_REGEX.captures(line) {
+        Some(captures) => (captures, DefinitionKind::Block),
+        None => (PROPERTY_ARROW_REGEX.captures(line)?, DefinitionKind::Arrow),
+    };
+    let name = captures.get(1)?;
+
+    if name.as_str() == "constructor" || NON_METHOD_KEYWORDS.contains(&name.as_str()) {
+        return None;
+    }
+
+    Some((name.as_str().to_string(), name.end(), kind))
+}
+
+/// 匹配函数声明、箭头函数或函数表达式
+///
+/// # Arguments
+/// * `line` - 剥离后的代码行
+///
+/// # Returns
+/// * `Option<(String, usize, DefinitionKind)>` - (名称, 名称结束位置, 定义形式)
+fn match_function(line: &str) -> Option<(String, usize, DefinitionKind)> {
+    let patterns = [
+        (&FUNCTION_REGEX, DefinitionKind::Block),
+        (&ARROW_REGEX, DefinitionKind::Arrow),
+        (&FUNCTION_EXPRESSION_REGEX, DefinitionKind::Block),
+    ];
+
+    patterns.iter().find_map(|(regex, kind)| {
+        let name = regex.captures(line)?.get(1)?;
+        Some((name.as_str().to_string(), name.end(), *kind))
+    })
+}
+
+/// 查找函数定义的结束行
+///
+/// 参数列表之后只允许返回值类型注解：函数体形式需要在 `;` 之前遇到 `{`；
+/// 箭头形式需要遇到 `=>`，其后为 `{` 时统计到匹配的右括号，否则视为单行表达式。
+///
+/// # Arguments
+/// * `code` - 剥离后的代码行
+/// * `start` - 定义所在行
+/// * `name_end` - 名称在该行中的结束位置
+/// * `kind` - 定义形式
+///
+/// # Returns
+/// * `Option<usize>` - 结束行，没有函数体时为 None
+fn find_definition_end(
+    code: &[&str],
+    start: usize,
+    name_end: usize,
+    kind: DefinitionKind,
+) -> Option<usize> {
+    let (params_line, params_end) = find_params_end(code, start, name_end)?;
+    let opening = match kind {
+        DefinitionKind::Block => "{",
+        DefinitionKind::Arrow => "=>",
+    };
+
+    // 参数列表之后紧跟的必须是类型注解或函数体，否则是普通的调用或表达式
+    let mut head_checked = false;
+    let mut prev = ' ';
+
+    for (i, line) in code.iter().enumerate().skip(params_line) {
+        let offset = if i == params_line { params_end } else { 0 };
+        let text = &line[offset..];
+
+        let head = text.trim_start();
+        if !head_checked && !head.is_empty() {
+            if !head.starts_with(':') && !head.starts_with(opening) {
+                return None;
+            }
+            head_checked = true;
+        }
+
+        for (pos, ch) in text.char_indices() {
+            match ch {
+                '{' if kind == DefinitionKind::Block => {
+                    return Some(find_block_end(code, i, offset + pos));
+                }
+                '>' if kind == DefinitionKind::Arrow && prev == '=' => {
+                    let body = text[pos + 1..].trim_start();
+                    if body.starts_with('{') {
+                        return Some(find_block_end(code, i, line.len() - body.len()));
+                    }
+                    return Some(i);
+                }
+                ';' => return None,
+                _ => {}
+            }
+            prev = ch;
+        }
+    }
+
+    None
+}
+
+/// 查找参数列表的结束位置
+///
+/// # Arguments
+/// * `code` - 剥离后的代码行
+/// * `start` - 定义所在行
+/// * `name_end` - 名称在该行中的结束位置（之后的第一个 `(` 为参数列表开始）
+///
+/// # Returns
+/// * `Option<(usize, usize)>` - 右括号所在行及其后一个字节的位置
+fn find_params_end(code: &[&str], start: usize, name_end: usize) -> Option<(usize, usize)> {
+    let mut depth = 0usize;
+
+    for (i, line) in code.iter().enumerate().skip(start) {
+        let offset = if i == start { name_end } else { 0 };
+
+        for (pos, ch) in line[offset..].char_indices() {
+            match ch {
+                '(' => depth += 1,
+                ')' if depth == 1 => return Some((i, offset + pos + 1)),
+                ')' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+
+    None
+}
+
+/// 查找从指定位置的 `{` 开始的代码块的结束行
+///
+/// # Arguments
+/// * `code` - 剥离后的代码行
+/// * `line` - `{` 所在行
+/// * `column` - `{` 在该行中的字节位置
+///
+/// # Returns
+/// * `usize` - 匹配的 `}` 所在行，未闭合时为最后一行
+fn find_block_end(code: &[&str], line: usize, column: usize) -> usize {
+    let mut depth = 0usize;
+
+    for (i, text) in code.iter().enumerate().skip(line) {
+        let text = if i == line { &text[column..] } else { text };
+        for ch in text.chars() {
+            match ch {
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return i;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    code.len().saturating_sub(1)
+}
+
+/// 计算一行结束后的大括号深度
+///
+/// # Arguments
+/// * `line` - 剥离后的代码行
+/// * `depth` - 行首的深度
+///
+/// # Returns
+/// * `usize` - 行尾的深度
+fn brace_depth_after(line: &str, depth: usize) -> usize {
+    line.chars().fold(depth, |depth, ch| match ch {
+        '{' => depth + 1,
+        '}' => depth.saturating_sub(1),
+        _ => depth,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANGULAR_SERVICE: &str = r#"import { Injectable, HostListener } from '@angular/core';
+import { HttpClient } from '@angular/common/http';
+
+export interface User {
+  id: number;
+  greet(other: User): string;
+}
+
+export type UserId = number | string;
+
+@Injectable({
+  providedIn: 'root',
+})
+export class UserService {
+  private cache = new Map<number, User>();
+
+  constructor(private http: HttpClient) {}
+
+  /** Loads a user, using the cache unless forced. */
+  public getUser(id: number, force: boolean = false): Observable<User> {
+    if (!force && this.cache.has(id)) {
+      return of(this.cache.get(id)!);
+    }
+    return this.http.get<User>(`/api/users/${id}`);
+  }
+
+  @HostListener('window:resize', ['$event'])
+  onResize(event: UIEvent): void {
+    this.width = (event.target as Window).innerWidth;
+  }
+
+  private handle = (error: HttpErrorResponse): Observable<never> => {
+    return throwError(() => error);
+  };
+
+  format(value: string): string;
+  format(value: number): string;
+  format(value: string | number): string {
+    return String(value);
+  }
+
+  static create<T extends User>(items: Array<Map<string, T>>, cmp: (a: T, b: T) => number): UserService {
+    return new UserService(null);
+  }
+}
+"#;
+
+    #[test]
+    fn parses_an_angular_style_decorated_service() {
+        let result = TypeScriptParser::new()
+            .parse(Path::new("user.service.ts"), ANGULAR_SERVICE)
+            .unwrap();
+        let summary: Vec<(&str, usize, usize, usize, usize)> = result
+            .get_functions()
+            .iter()
+            .map(|f| {
+                (
+                    f.name.as_str(),
+                    f.start_line,
+                    f.end_line,
+                    f.parameters,
+                    f.complexity,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                ("getUser", 20, 25, 2, 3),
+                ("onResize", 28, 30, 1, 1),
+                ("handle", 32, 34, 1, 1),
+                ("format", 38, 40, 1, 1),
+                ("create", 42, 44, 2, 1),
+            ]
+        );
+        assert!(result.get_functions()[0].documented);
+        assert_eq!(result.get_comment_lines(), 1);
+    }
+
+    #[test]
+    fn type_annotations_do_not_count_as_decisions() {
+        let source = "export function pick<T>(a?: T, b?: T | null): T | undefined {\n\
+                      \x20 return a?.valueOf() ? a : b ?? undefined;\n\
+                      }\n";
+        let result = TypeScriptParser::new()
+            .parse(Path::new("pick.ts"), source)
+            .unwrap();
+        let pick = &result.get_functions()[0];
+
+        assert_eq!(pick.parameters, 2);
+        // 基础 1 + 三元 1 + `??` 1
+        assert_eq!(pick.complexity, 3);
+        assert!(pick.exported);
+    }
+}
@@ -0,0 +1,142 @@
+//! # 可见性检测
+//!
+//! 按各语言的规则判断函数是否对外导出（公开），供文档注释等检查使用
+
+use crate::common::LanguageType;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `public` 关键字
+static PUBLIC_KEYWORD: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bpublic\b").unwrap());
+
+/// Swift 对模块外可见的访问控制关键字
+static SWIFT_PUBLIC_KEYWORD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:public|open)\b").unwrap());
+
+/// 非公开的可见性关键字
+static NON_PUBLIC_KEYWORD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:private|protected)\b").unwrap());
+
+/// 判断函数是否对外导出
+///
+/// - Rust: `pub`（不含 `pub(crate)` 等受限可见性）
+/// - Zig: `pub` 或 `export`
+/// - Elixir: `def`/`defmacro`（不含 `defp`/`defmacrop`）
+/// - Go: 函数名首字母大写
+/// - JavaScript/TypeScript: `export` 声明或 TypeScript 的 `public` 方法
+/// - Java/C#: `public`
+/// - Swift: `public` 或 `open`
+/// - PHP: 未声明为 `private`/`protected`
+/// - Python: 函数名不以下划线开头
+/// - C/C++: 未声明为 `static`
+///
+/// # Arguments
+/// * `signature` - 函数定义所在行
+/// * `name` - 函数名
+/// * `language` - 语言类型
+///
+/// # Returns
+/// * `bool` - 是否导出
+pub fn is_exported(signature: &str, name: &str, language: LanguageType) -> bool {
+    let trimmed = signature.trim_start();
+
+    match language {
+        LanguageType::Rust => trimmed.starts_with("pub "),
+        LanguageType::Zig => trimmed.starts_with("pub ") || trimmed.starts_with("export "),
+        LanguageType::Elixir => trimmed.starts_with("def ") || trimmed.starts_with("defmacro "),
+        LanguageType::Go => name.chars().next().is_some_and(char::is_uppercase),
+        LanguageType::JavaScript | LanguageType::TypeScript => {
+            trimmed.starts_with("export ") || PUBLIC_KEYWORD.is_match(trimmed)
+        }
+        LanguageType::Java | LanguageType::CSharp => PUBLIC_KEYWORD.is_match(trimmed),
+        LanguageType::Swift => SWIFT_PUBLIC_KEYWORD.is_match(trimmed),
+        LanguageType::PHP => !NON_PUBLIC_KEYWORD.is_match(trimmed),
+        LanguageType::Python => !name.starts_with('_'),
+        LanguageType::C | LanguageType::CPlusPlus => !trimmed.starts_with("static "),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{create_parser_for_file, Function};
+    use std::path::Path;
+
+    fn parse(file_name: &str, source: &str) -> Vec<Function> {
+        let path = Path::new(file_name);
+        create_parser_for_file(path)
+            .parse(path, source)
+            .unwrap()
+            .get_functions()
+            .to_vec()
+    }
+
+    /// (函数名, 是否导出, 是否有文档注释)
+    fn flags(functions: &[Function]) -> Vec<(&str, bool, bool)> {
+        functions
+            .iter()
+            .map(|f| (&f.name[..], f.exported, f.documented))
+            .collect()
+    }
+
+    #[test]
+    fn rust_pub_fn_is_exported() {
+        assert!(is_exported("pub fn load() {", "load", LanguageType::Rust));
+        assert!(!is_exported(
+            "pub(crate) fn load() {",
+            "load",
+            LanguageType::Rust
+        ));
+        assert!(!is_exported("fn load() {", "load", LanguageType::Rust));
+
+        let functions = parse(
+            "lib.rs",
+            "/// Loads the config.\n\
+             pub fn load() {}\n\
+             \n\
+             // load without validation\n\
+             pub fn load_raw() {}\n\
+             \n\
+             pub(crate) fn helper() {}\n",
+        );
+        assert_eq!(
+            flags(&functions),
+            [
+                ("load", true, true),
+                ("load_raw", true, false),
+                ("helper", false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn go_capitalized_func_is_exported() {
+        assert!(is_exported("func Parse() {", "Parse", LanguageType::Go));
+        assert!(!is_exported("func parse() {", "parse", LanguageType::Go));
+
+        let functions = parse(
+            "parse.go",
+            "package parse\n\
+             \n\
+             // Parse reads the input.\n\
+             func Parse() {\n\
+             }\n\
+             \n\
+             // TODO: handle errors\n\
+             func Load() {\n\
+             }\n\
+             \n\
+             func parse() {\n\
+             }\n",
+        );
+        assert_eq!(
+            flags(&functions),
+            [
+                ("Parse", true, true),
+                ("Load", true, false),
+                ("parse", false, false),
+            ]
+        );
+    }
+}
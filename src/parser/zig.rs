@@ -0,0 +1,268 @@
+//! # Zig语言解析器
+//!
+//! 专门用于解析Zig源代码文件
+
+use crate::common::LanguageType;
+use crate::parser::{
+    count_top_level_parameters, has_doc_comment, is_exported, measure_nesting_depth,
+    BaseParseResult, Function, ParseResult, Parser,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// 函数定义（`fn name(`，可带 `pub`/`export`/`extern`/`inline` 等修饰）
+static FUNCTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:pub\s+)?(?:export\s+|extern\s+(?:\x22[^\x22]*\x22\s+)?|inline\s+|noinline\s+)*fn\s+([a-zA-Z_][a-zA-Z0-9_]*)\s*\(",
+    )
+    .unwrap()
+});
+
+/// 分支关键字
+static BRANCH_KEYWORD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:if|else|while|for|switch|catch|and|or|orelse)\b").unwrap());
+
+/// switch 分支（`=>`，不含 `else =>` 默认分支）
+static SWITCH_PRONG: Lazy<Regex> = Lazy::new(|| Regex::new(r"=>").unwrap());
+
+/// Zig解析器
+pub struct ZigParser;
+
+impl ZigParser {
+    /// 创建新的Zig解析器
+    ///
+    /// # Returns
+    /// * `Self` - 解析器实例
+    pub fn new() -> Self {
+        ZigParser
+    }
+
+    /// 计数注释行（Zig 只有 `//`、`///` 和 `//!` 行注释）
+    ///
+    /// # Arguments
+    /// * `lines` - 代码行
+    ///
+    /// # Returns
+    /// * `usize` - 注释行数
+    fn count_comment_lines(&self, lines: &[&str]) -> usize {
+        lines
+            .iter()
+            .filter(|line| line.trim_start().starts_with("//"))
+            .count()
+    }
+
+    /// 检测函数
+    ///
+    /// # Arguments
+    /// * `lines` - 代码行
+    ///
+    /// # Returns
+    /// * `Vec<Function>` - 函数列表
+    fn detect_functions(&self, lines: &[&str]) -> Vec<Function> {
+        let mut functions = Vec::new();
+        let mut i = 0;
+
+        while i < lines.len() {
+            if let Some(func) = self.try_parse_function(lines, i) {
+                i = func.end_line; // 跳到函数结束
+                functions.push(func);
+            } else {
+                i += 1;
+            }
+        }
+
+        functions
+    }
+
+    /// 尝试解析函数
+    ///
+    /// # Arguments
+    /// * `lines` - 代码行
+    /// * `start` - 起始位置
+    ///
+    /// # Returns
+    /// * `Option<Function>` - 函数信息（没有函数体的声明返回 None）
+    fn try_parse_function(&self, lines: &[&str], start: usize) -> Option<Function> {
+        let line = lines[start];
+        let captures = FUNCTION_REGEX.captures(line)?;
+        let name = captures.get(1)?.as_str().to_string();
+        let params_start = captures.get(0)?.end();
+
+        let end_line = self.find_function_end(lines, start)?;
+        let signature = lines[start..=end_line].join("\n");
+        let parameters = count_top_level_parameters(&signature[params_start..]);
+        let complexity = self.calculate_complexity(&lines[start..=end_line]);
+
+        let mut function = Function::new(
+            name,
+            start + 1,    // 转换为1索引
+            end_line + 1, // 转换为1索引
+            complexity,
+            parameters,
+        );
+        function.exported = is_exported(line, &function.name, LanguageType::Zig);
+        function.documented = has_doc_comment(lines, start, LanguageType::Zig);
+        function.max_nesting_depth =
+            measure_nesting_depth(&lines[start..=end_line], LanguageType::Zig);
+
+        Some(function)
+    }
+
+    /// 查找函数结束位置
+    ///
+    /// # Arguments
+    /// * `lines` - 代码行
+    /// * `start` - 起始位置
+    ///
+    /// # Returns
+    /// * `Option<usize>` - 结束位置（外部函数声明等没有函数体时为 None）
+    fn find_function_end(&self, lines: &[&str], start: usize) -> Option<usize> {
+        let mut depth = 0usize;
+        let mut found_body = false;
+
+        for (i, line) in lines.iter().enumerate().skip(start) {
+            let code = line.split("//").next().unwrap_or("");
+            for ch in code.chars() {
+                match ch {
+                    '{' => {
+                        depth += 1;
+                        found_body = true;
+                    }
+                    '}' => {
+                        depth = depth.saturating_sub(1);
+                        if found_body && depth == 0 {
+                            return Some(i);
+                        }
+                    }
+                    ';' if !found_body => return None,
+                    _ => {}
+                }
+            }
+        }
+
+        None
+    }
+
+    /// 计算循环复杂度
+    ///
+    /// # Arguments
+    /// * `function_lines` - 函数代码行
+    ///
+    /// # Returns
+    /// * `usize` - 复杂度
+    fn calculate_complexity(&self, function_lines: &[&str]) -> usize {
+        let mut complexity = 1;
+
+        for line in function_lines {
+            let code = line.split("//").next().unwrap_or("");
+            complexity += BRANCH_KEYWORD.find_iter(code).count();
+
+            // switch 的每个分支各算一条路径，默认分支已由 else 计入
+            if !code.trim_start().starts_with("else") {
+                complexity += SWITCH_PRONG.find_iter(code).count();
+            }
+        }
+
+        complexity
+    }
+}
+
+impl Parser for ZigParser {
+    /// 解析Zig文件
+    ///
+    /// # Arguments
+    /// * `_file_path` - 文件路径
+    /// * `content` - 文件内容
+    ///
+    /// # Returns
+    /// * `Result<Box<dyn ParseResult>, Box<dyn std::error::Error>>` - 解析结果
+    fn parse(
+        &self,
+        _file_path: &Path,
+        content: &str,
+    ) -> Result<Box<dyn ParseResult>, Box<dyn std::error::Error>> {
+        let lines: Vec<&str> = content.lines().collect();
+        let total_lines = lines.len();
+
+        let comment_lines = self.count_comment_lines(&lines);
+        let functions = self.detect_functions(&lines);
+
+        Ok(Box::new(BaseParseResult {
+            functions,
+            comment_lines,
+            total_lines,
+            source: content.to_string(),
+            language: LanguageType::Zig,
+        }))
+    }
+
+    /// 获取支持的语言
+    ///
+    /// # Returns
+    /// * `Vec<LanguageType>` - 语言列表
+    fn supported_languages(&self) -> Vec<LanguageType> {
+        vec![LanguageType::Zig]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<Function> {
+        ZigParser::new()
+            .parse(Path::new("main.zig"), source)
+            .unwrap()
+            .get_functions()
+            .to_vec()
+    }
+
+    #[test]
+    fn switch_prongs_add_complexity() {
+        let functions = parse(
+            "fn classify(kind: Kind) u8 {\n\
+             \x20   return switch (kind) {\n\
+             \x20       .a => 1,\n\
+             \x20       .b, .c => 2,\n\
+             \x20       .d => 3,\n\
+             \x20       else => 0,\n\
+             \x20   };\n\
+             }\n",
+        );
+        assert_eq!(functions.len(), 1);
+        // 基础 1 + switch 1 + 三个分支 3 + else 1
+        assert_eq!(functions[0].complexity, 6);
+        assert_eq!(functions[0].parameters, 1);
+        assert_eq!((functions[0].start_line, functions[0].end_line), (1, 8));
+    }
+
+    #[test]
+    fn error_union_function_with_catch() {
+        let source = "const std = @import(\"std\");\n\
+                      \n\
+                      /// Opens the file.\n\
+                      pub fn load(path: []const u8, allocator: std.mem.Allocator) !u32 {\n\
+                      \x20   const file = std.fs.cwd().openFile(path, .{}) catch |err| {\n\
+                      \x20       return err;\n\
+                      \x20   };\n\
+                      \x20   defer file.close();\n\
+                      \x20   return 0;\n\
+                      }\n\
+                      \n\
+                      extern \"c\" fn puts(s: [*:0]const u8) c_int;\n";
+        let result = ZigParser::new()
+            .parse(Path::new("load.zig"), source)
+            .unwrap();
+        let functions = result.get_functions();
+
+        assert_eq!(functions.len(), 1);
+        let load = &functions[0];
+        assert_eq!(load.name, "load");
+        assert_eq!(load.complexity, 2);
+        assert_eq!(load.parameters, 2);
+        assert!(load.exported);
+        assert!(load.documented);
+        assert_eq!(result.get_comment_lines(), 1);
+    }
+}
@@ -2,9 +2,13 @@
 //!
 //! 生成格式化的控制台输出报告
 
-use crate::analyzer::AnalysisResult;
+use crate::analyzer::{AnalysisResult, BaselineDiff, FileDelta, ParameterDistribution};
 use crate::i18n::Translator;
-use crate::report::ReportOptions;
+use crate::metrics::Issue;
+use crate::report::{
+    compare_scores, file_metric_scores, fix_suggestion, grade_headline, group_issues,
+    quality_level, ReportOptions,
+};
 use colored::*;
 
 /// 控制台报告生成器
@@ -17,6 +21,9 @@ pub struct ConsoleReport<'a> {
 
     /// 报告选项
     options: &'a ReportOptions,
+
+    /// 基线对比结果
+    baseline: Option<&'a BaselineDiff>,
 }
 
 impl<'a> ConsoleReport<'a> {
@@ -26,6 +33,7 @@ impl<'a> ConsoleReport<'a> {
     /// * `result` - 分析结果
     /// * `translator` - 翻译器
     /// * `options` - 报告选项
+    /// * `baseline` - 基线对比结果
     ///
     /// # Returns
     /// * `Self` - 生成器实例
@@ -33,21 +41,27 @@ impl<'a> ConsoleReport<'a> {
         result: &'a AnalysisResult,
         translator: &'a Translator,
         options: &'a ReportOptions,
+        baseline: Option<&'a BaselineDiff>,
     ) -> Self {
         ConsoleReport {
             result,
             translator,
             options,
+            baseline,
         }
     }
 
     /// 生成报告
     pub fn generate(&self) {
         self.print_header();
+        self.print_grade_headline();
         self.print_score_summary();
+        self.print_generic_parser_warning();
+        self.print_baseline_comparison();
 
         if !self.options.summary_only {
             self.print_metrics();
+            self.print_languages();
             self.print_files();
         }
 
@@ -70,18 +84,35 @@ impl<'a> ConsoleReport<'a> {
         self.print_divider();
     }
 
+    /// 打印评级标题行
+    fn print_grade_headline(&self) {
+        println!(
+            "\n  {}",
+            grade_headline(
+                self.translator,
+                self.options,
+                self.result.code_quality_score
+            )
+            .bold()
+        );
+    }
+
     /// 打印分数摘要
     fn print_score_summary(&self) {
         println!();
 
         // 打印总分
-        let display_score = self.result.code_quality_score * 100.0;
+        let display_score = self
+            .options
+            .polarity
+            .display(self.result.code_quality_score);
         print!(
-            "  {}: {:.2} / 100",
+            "  {}: {:.*} / 100",
             self.translator
                 .translate("report.quality_score")
                 .cyan()
                 .bold(),
+            self.options.precision,
             display_score
         );
 
@@ -90,21 +121,211 @@ impl<'a> ConsoleReport<'a> {
         println!();
 
         // 打印质量等级
-        let level = self.get_quality_level(self.result.code_quality_score);
-        println!(
+        println!("{}", self.quality_level_line());
+
+        if let Some(percentile) = self.options.percentile_of(self.result.code_quality_score) {
+            println!(
+                "  📈 {}",
+                self.translator
+                    .translate_with_args("report.percentile", vec![format!("{:.0}", percentile)])
+                    .cyan()
+            );
+        }
+
+        println!();
+    }
+
+    /// 生成质量等级行
+    ///
+    /// # Returns
+    /// * `String` - 形如 `质量等级: 等级 - 描述` 的带颜色的行
+    pub(crate) fn quality_level_line(&self) -> String {
+        let level = quality_level(self.result.code_quality_score, &self.options.level_cuts);
+        format!(
             "  {} - {}",
             format!(
                 "{}: {}",
                 self.translator.translate("report.quality_level"),
-                self.translator.translate(&level.0)
+                self.translator.translate(level.0)
             )
             .cyan(),
-            self.translator.translate(&level.1).cyan()
+            self.translator.translate(level.1).cyan()
+        )
+    }
+
+    /// 通用解析器分析的文件过多时打印警告
+    fn print_generic_parser_warning(&self) {
+        if !self.result.is_approximate() {
+            return;
+        }
+
+        println!(
+            "  ⚠️  {}\n",
+            self.translator
+                .translate_with_args(
+                    "report.generic_parser_warning",
+                    vec![
+                        self.result.generic_parser_files().to_string(),
+                        self.result.files_analyzed.len().to_string(),
+                    ],
+                )
+                .yellow()
+                .bold()
+        );
+    }
+
+    /// 打印与基线的对比
+    fn print_baseline_comparison(&self) {
+        let Some(diff) = self.baseline else {
+            return;
+        };
+
+        println!(
+            "◆ {}\n",
+            self.translator
+                .translate("report.baseline_comparison")
+                .magenta()
+                .bold()
+        );
+
+        println!(
+            "  {:<20} {:.*} → {:.*} ({})",
+            self.translator.translate("report.quality_score"),
+            self.options.precision,
+            self.options.polarity.display(diff.overall.baseline),
+            self.options.precision,
+            self.options.polarity.display(diff.overall.current),
+            self.format_delta(diff.overall.delta())
         );
 
+        for (name, delta) in &diff.metrics {
+            println!(
+                "  {:<20} {:.*} → {:.*} ({})",
+                name,
+                self.options.precision,
+                self.options.polarity.display(delta.baseline),
+                self.options.precision,
+                self.options.polarity.display(delta.current),
+                self.format_delta(delta.delta())
+            );
+        }
+
+        if !diff.files.is_empty() {
+            println!(
+                "\n  {}",
+                self.translator.translate("report.file_changes").bold()
+            );
+        }
+        for (path, delta) in &diff.files {
+            println!(
+                "    {}  {}",
+                self.shorten_path(path),
+                self.format_file_delta(delta)
+            );
+        }
+
+        self.print_new_issues(diff);
+
         println!();
     }
 
+    /// 打印问题变化数量和新出现的问题
+    ///
+    /// # Arguments
+    /// * `diff` - 对比结果
+    fn print_new_issues(&self, diff: &BaselineDiff) {
+        println!(
+            "\n  {}",
+            self.translator
+                .translate_with_args(
+                    "report.issue_changes",
+                    vec![
+                        diff.new_issues.len().to_string(),
+                        diff.resolved_issues.len().to_string(),
+                    ],
+                )
+                .bold()
+        );
+
+        let limit = self.options.issue_limit(diff.new_issues.len());
+        for (path, issue) in &diff.new_issues[..limit] {
+            let location = match issue.line {
+                Some(line) => format!("{}:{}", self.shorten_path(path), line),
+                None => self.shorten_path(path),
+            };
+            println!(
+                "    {} {}",
+                "+".red(),
+                format!("{}  {}", location, issue).dimmed()
+            );
+        }
+
+        if diff.new_issues.len() > limit {
+            println!(
+                "    {}",
+                format!("...还有 {} 个问题", diff.new_issues.len() - limit).yellow()
+            );
+        }
+    }
+
+    /// 格式化文件得分变化（只存在于一侧的文件标注新增或删除）
+    ///
+    /// # Arguments
+    /// * `delta` - 文件得分变化
+    ///
+    /// # Returns
+    /// * `String` - 格式化后的文本
+    fn format_file_delta(&self, delta: &FileDelta) -> String {
+        let display = |score: f64| {
+            format!(
+                "{:.*}",
+                self.options.precision,
+                self.options.polarity.display(score)
+            )
+        };
+
+        match (delta.baseline, delta.current) {
+            (Some(baseline), Some(current)) => format!(
+                "{} → {} ({})",
+                display(baseline),
+                display(current),
+                self.format_delta(current - baseline)
+            ),
+            (None, Some(current)) => format!(
+                "{} ({})",
+                display(current),
+                self.translator.translate("report.file_added").yellow()
+            ),
+            (Some(baseline), None) => format!(
+                "{} ({})",
+                display(baseline),
+                self.translator.translate("report.file_removed").cyan()
+            ),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// 格式化得分变化（变差为红色，变好为绿色）
+    ///
+    /// # Arguments
+    /// * `delta` - 内部得分变化量（0-1，正数表示变差）
+    ///
+    /// # Returns
+    /// * `ColoredString` - 格式化后的变化量
+    fn format_delta(&self, delta: f64) -> ColoredString {
+        let text = format!(
+            "{:+.*}",
+            self.options.precision,
+            self.options.polarity.display_delta(delta)
+        );
+
+        match delta {
+            d if d > 0.0 => text.red(),
+            d if d < 0.0 => text.green(),
+            _ => text.normal(),
+        }
+    }
+
     /// 打印分数评语
     ///
     /// # Arguments
@@ -151,7 +372,7 @@ impl<'a> ConsoleReport<'a> {
 
         // 排序指标
         let mut metrics: Vec<_> = self.result.metrics.iter().collect();
-        metrics.sort_by(|a, b| a.1.score.partial_cmp(&b.1.score).unwrap());
+        metrics.sort_by(|a, b| compare_scores(a.1.score, b.1.score, false));
 
         // 打印每个指标
         for (name, result) in &metrics {
@@ -161,6 +382,41 @@ impl<'a> ConsoleReport<'a> {
         println!();
     }
 
+    /// 打印按语言汇总的得分（只有一种语言时不打印）
+    fn print_languages(&self) {
+        if self.result.languages.len() < 2 {
+            return;
+        }
+
+        println!(
+            "◆ {}\n",
+            self.translator
+                .translate("report.languages")
+                .magenta()
+                .bold()
+        );
+
+        for language in &self.result.languages {
+            let name = format!(
+                "{} {}",
+                self.get_status_emoji(language.score * 100.0),
+                language.language.display_name()
+            );
+            println!(
+                "  {:<30} {:.*}分\t  {}: {}, {}: {}",
+                name.color(self.get_score_color(language.score)),
+                self.options.precision,
+                self.options.polarity.display(language.score),
+                self.translator.translate("report.file_count"),
+                language.files,
+                self.translator.translate("report.total_lines"),
+                language.lines
+            );
+        }
+
+        println!();
+    }
+
     /// 打印单个指标
     ///
     /// # Arguments
@@ -171,12 +427,17 @@ impl<'a> ConsoleReport<'a> {
 
         let status_emoji = self.get_status_emoji(score_percentage);
         let status_color = self.get_status_color(name, status_emoji, score_percentage);
-        let comment = self.get_metric_comment(name, score_percentage);
+        let comment = match self.detect_metric_type(name) {
+            // 自定义指标没有内置评语，直接显示其描述
+            "unknown" => result.description.clone(),
+            _ => self.get_metric_comment(name, score_percentage),
+        };
 
         println!(
-            "  {:<30} {:.2}分\t  {}",
+            "  {:<30} {:.*}分\t  {}",
             status_color,
-            score_percentage,
+            self.options.precision,
+            self.options.polarity.display(result.score),
             comment.cyan()
         );
     }
@@ -254,9 +515,15 @@ impl<'a> ConsoleReport<'a> {
     /// # Returns
     /// * `&str` - 类型标识
     fn detect_metric_type(&self, metric_name: &str) -> &str {
-        if metric_name.contains("复杂度") || metric_name.contains("complexity") {
+        if metric_name.contains("认知") || metric_name.contains("cognitive") {
+            "cognitive"
+        } else if metric_name.contains("复杂度") || metric_name.contains("complexity") {
             "complexity"
-        } else if metric_name.contains("状态") || metric_name.contains("长度") {
+        } else if metric_name.contains("状态") {
+            "state"
+        } else if metric_name.contains("行长度") {
+            "line_length"
+        } else if metric_name.contains("长度") {
             "length"
         } else if metric_name.contains("注释") {
             "comment"
@@ -292,9 +559,8 @@ impl<'a> ConsoleReport<'a> {
                 .bold()
         );
 
-        // 排序文件
-        let mut files = self.result.files_analyzed.clone();
-        files.sort_by(|a, b| b.file_score.partial_cmp(&a.file_score).unwrap());
+        // 排序并按阈值过滤文件
+        let files = self.options.rank_files(&self.result.files_analyzed);
 
         if files.is_empty() {
             println!(
@@ -305,9 +571,9 @@ impl<'a> ConsoleReport<'a> {
         }
 
         // 打印前N个文件
-        let max_files = self.options.top_files.min(files.len());
+        let max_files = self.options.file_limit(files.len());
         for i in 0..max_files {
-            self.print_file_item(i, &files[i]);
+            self.print_file_item(i, &files[i], max_files);
         }
     }
 
@@ -316,39 +582,104 @@ impl<'a> ConsoleReport<'a> {
     /// # Arguments
     /// * `index` - 索引
     /// * `file` - 文件分析结果
-    fn print_file_item(&self, index: usize, file: &crate::analyzer::FileAnalysisResult) {
+    /// * `count` - 本次显示的文件数量
+    fn print_file_item(
+        &self,
+        index: usize,
+        file: &crate::analyzer::FileAnalysisResult,
+        count: usize,
+    ) {
         let score_color = self.get_score_color(file.file_score);
 
         println!(
             "  {}. {} ({})",
             (index + 1).to_string().white().bold(),
             self.shorten_path(&file.file_path).magenta(),
-            format!("屎气指数: {:.2}", file.file_score * 100.0).color(score_color)
+            format!(
+                "{}: {:.*}",
+                self.options.polarity.file_score_label(),
+                self.options.precision,
+                self.options.polarity.display(file.file_score)
+            )
+            .color(score_color)
         );
 
+        if self.options.verbose {
+            self.print_file_metrics(file);
+        }
+
         // 显示问题
         self.print_file_issues(file);
 
-        if index < self.options.top_files - 1 {
+        if index + 1 < count {
             println!();
         }
     }
 
+    /// 打印文件的各项指标得分（从差到好）
+    ///
+    /// # Arguments
+    /// * `file` - 文件分析结果
+    fn print_file_metrics(&self, file: &crate::analyzer::FileAnalysisResult) {
+        let scores: Vec<String> = file_metric_scores(file)
+            .into_iter()
+            .map(|(name, score)| {
+                format!(
+                    "{} {:.*}",
+                    name,
+                    self.options.precision,
+                    self.options.polarity.display(score)
+                )
+            })
+            .collect();
+
+        if !scores.is_empty() {
+            println!("     {}", scores.join(" · ").dimmed());
+        }
+    }
+
     /// 打印文件问题
     ///
     /// # Arguments
     /// * `file` - 文件分析结果
     fn print_file_issues(&self, file: &crate::analyzer::FileAnalysisResult) {
-        let max_issues = self.options.max_issues.min(file.issues.len());
+        let visible = self.options.visible_issues(&file.issues);
+        let max_issues = self.options.issue_limit(visible.len());
+        let shown: Vec<&Issue> = visible.iter().copied().take(max_issues).collect();
+
+        for (category, issues) in group_issues(&shown) {
+            println!("     {}", self.translator.translate(&category).bold());
+
+            for issue in issues {
+                // 带行号的问题以 `路径:行号:` 开头，便于在编辑器中跳转
+                match issue.line {
+                    Some(line) => println!(
+                        "       {} {}",
+                        format!("{}:{}:", file.file_path, line).dimmed(),
+                        issue.message.yellow()
+                    ),
+                    None => println!("       {}", issue.message.yellow()),
+                }
+
+                if self.options.verbose {
+                    if let Some(fix) = fix_suggestion(self.translator, issue) {
+                        println!("          💡 {}", fix.cyan());
+                    }
+                }
+            }
+        }
 
-        for i in 0..max_issues {
-            println!("     {}", file.issues[i].yellow());
+        if visible.len() > max_issues {
+            println!(
+                "     🔍 {}",
+                format!("...还有 {} 个问题", visible.len() - max_issues).yellow()
+            );
         }
 
-        if file.issues.len() > max_issues {
+        if file.dropped_issues > 0 {
             println!(
                 "     🔍 {}",
-                format!("...还有 {} 个问题", file.issues.len() - max_issues).yellow()
+                format!("...另有 {} 个问题超出保留上限未记录", file.dropped_issues).yellow()
             );
         }
     }
@@ -363,8 +694,7 @@ impl<'a> ConsoleReport<'a> {
                 .bold()
         );
 
-        let mut files = self.result.files_analyzed.clone();
-        files.sort_by(|a, b| b.file_score.partial_cmp(&a.file_score).unwrap());
+        let files = self.options.rank_files(&self.result.files_analyzed);
 
         if files.is_empty() {
             println!(
@@ -378,7 +708,7 @@ impl<'a> ConsoleReport<'a> {
         }
 
         for (i, file) in files.iter().enumerate() {
-            self.print_file_item(i, file);
+            self.print_file_item(i, file, files.len());
         }
     }
 
@@ -392,7 +722,7 @@ impl<'a> ConsoleReport<'a> {
                 .bold()
         );
 
-        let level = self.get_quality_level(self.result.code_quality_score);
+        let level = quality_level(self.result.code_quality_score, &self.options.level_cuts);
 
         println!(
             "  🌸 {} - {}\n",
@@ -427,6 +757,7 @@ impl<'a> ConsoleReport<'a> {
         );
 
         self.print_statistics();
+        self.print_parameter_distribution();
         self.print_metric_details();
     }
 
@@ -457,6 +788,53 @@ impl<'a> ConsoleReport<'a> {
             self.translator.translate("verbose.total_issues"),
             self.get_total_issues()
         );
+
+        println!(
+            "    {:<15} {}",
+            self.translator.translate("verbose.run_id"),
+            self.result.run_id
+        );
+    }
+
+    /// 打印函数参数数量分布直方图
+    fn print_parameter_distribution(&self) {
+        let distribution = &self.result.parameter_distribution;
+        let total = distribution.total();
+        if total == 0 {
+            return;
+        }
+
+        println!(
+            "\n  📐 {}",
+            self.translator
+                .translate("verbose.parameter_distribution")
+                .blue()
+                .bold()
+        );
+
+        let max = distribution
+            .buckets
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for (index, (label, &count)) in ParameterDistribution::LABELS
+            .iter()
+            .zip(distribution.buckets.iter())
+            .enumerate()
+        {
+            // 4 个及以上参数的区间标红
+            let bar = "█".repeat((count * 30).div_ceil(max));
+            let bar = if index >= 3 { bar.red() } else { bar.green() };
+            println!(
+                "    {:<4} {} {} ({:.1}%)",
+                label,
+                bar,
+                count,
+                count as f64 * 100.0 / total as f64
+            );
+        }
     }
 
     /// 打印指标详情
@@ -494,46 +872,13 @@ impl<'a> ConsoleReport<'a> {
         );
 
         println!(
-            "      {} {:.2}/100",
+            "      {} {:.*}/100",
             self.translator.translate("verbose.score"),
-            result.score * 100.0
+            self.options.precision,
+            self.options.polarity.display(result.score)
         );
     }
 
-    /// 获取质量等级
-    ///
-    /// # Arguments
-    /// * `score` - 分数
-    ///
-    /// # Returns
-    /// * `(&str, &str)` - (等级键, 描述键)
-    fn get_quality_level(&self, score: f64) -> (&'static str, &'static str) {
-        let adjusted_score = score * 100.0;
-
-        match adjusted_score {
-            s if s < 5.0 => ("level.clean", "level.clean.description"),
-            s if s < 15.0 => ("level.mild", "level.mild.description"),
-            s if s < 25.0 => ("level.moderate", "level.moderate.description"),
-            s if s < 40.0 => ("level.bad", "level.bad.description"),
-            s if s < 55.0 => ("level.terrible", "level.terrible.description"),
-            s if s < 65.0 => ("level.disaster", "level.disaster.description"),
-            s if s < 75.0 => ("level.disaster.severe", "level.disaster.severe.description"),
-            s if s < 85.0 => (
-                "level.disaster.very_bad",
-                "level.disaster.very_bad.description",
-            ),
-            s if s < 95.0 => (
-                "level.disaster.extreme",
-                "level.disaster.extreme.description",
-            ),
-            s if s < 100.0 => ("level.disaster.worst", "level.disaster.worst.description"),
-            _ => (
-                "level.disaster.ultimate",
-                "level.disaster.ultimate.description",
-            ),
-        }
-    }
-
     /// 获取分数颜色
     ///
     /// # Arguments
@@ -0,0 +1,121 @@
+//! # CSV报告生成
+//!
+//! 每个分析过的文件输出一行得分数据，便于导入表格和看板
+
+use crate::analyzer::AnalysisResult;
+use crate::report::{compare_scores, ReportOptions};
+use std::collections::BTreeSet;
+
+/// CSV报告生成器
+pub struct CsvReport<'a> {
+    /// 分析结果
+    result: &'a AnalysisResult,
+
+    /// 报告选项
+    options: &'a ReportOptions,
+}
+
+impl<'a> CsvReport<'a> {
+    /// 创建新的CSV报告生成器
+    ///
+    /// # Arguments
+    /// * `result` - 分析结果
+    /// * `options` - 报告选项
+    ///
+    /// # Returns
+    /// * `Self` - 生成器实例
+    pub fn new(result: &'a AnalysisResult, options: &'a ReportOptions) -> Self {
+        CsvReport { result, options }
+    }
+
+    /// 生成报告
+    pub fn generate(&self) {
+        print!("{}", self.render());
+    }
+
+    /// 渲染 CSV 文本
+    ///
+    /// 列依次为路径、文件得分、问题数和各项指标得分（按指标名称排序），
+    /// 行按文件得分从差到好排列；空项目只输出表头。
+    ///
+    /// # Returns
+    /// * `String` - CSV 文本
+    pub(crate) fn render(&self) -> String {
+        let metric_names: BTreeSet<&str> = self
+            .result
+            .files_analyzed
+            .iter()
+            .flat_map(|file| file.metrics.keys().map(String::as_str))
+            .collect();
+
+        let mut header = vec!["path", "file_score", "issues"];
+        header.extend(metric_names.iter().copied());
+        let mut csv = csv_row(header.into_iter().map(str::to_string));
+
+        let mut files: Vec<_> = self.result.files_analyzed.iter().collect();
+        files.sort_by(|a, b| compare_scores(a.file_score, b.file_score, true));
+
+        for file in files {
+            let mut row = vec![
+                file.file_path.clone(),
+                self.format_score(file.file_score),
+                (file.issues.len() + file.dropped_issues).to_string(),
+            ];
+            row.extend(metric_names.iter().map(|name| {
+                file.metrics
+                    .get(*name)
+                    .map(|metric| self.format_score(metric.score))
+                    .unwrap_or_default()
+            }));
+            csv.push_str(&csv_row(row.into_iter()));
+        }
+
+        csv
+    }
+
+    /// 按分数方向和精度格式化得分（0-100）
+    ///
+    /// # Arguments
+    /// * `score` - 内部得分（0-1，越高越差）
+    ///
+    /// # Returns
+    /// * `String` - 格式化后的得分
+    fn format_score(&self, score: f64) -> String {
+        format!(
+            "{:.*}",
+            self.options.precision,
+            self.options.polarity.display(score)
+        )
+    }
+}
+
+/// 拼接一行 CSV（以换行结尾）
+///
+/// # Arguments
+/// * `fields` - 各列的值
+///
+/// # Returns
+/// * `String` - 一行 CSV
+fn csv_row(fields: impl Iterator<Item = String>) -> String {
+    let mut row = fields
+        .map(|field| escape(&field))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push('\n');
+    row
+}
+
+/// 转义 CSV 字段：包含逗号、引号或换行时用双引号包裹，内部引号写两次
+///
+/// # Arguments
+/// * `field` - 原始值
+///
+/// # Returns
+/// * `String` - 转义后的值
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,336 @@
+//! # HTML报告生成
+//!
+//! 生成单文件的 HTML 报告，样式全部内联，离线即可打开
+
+use crate::analyzer::{AnalysisResult, FileAnalysisResult};
+use crate::i18n::{Language, Translator};
+use crate::metrics::MetricResult;
+use crate::report::{compare_scores, fix_suggestion, quality_level, ReportOptions};
+use std::fmt::Write;
+
+/// 内联样式
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', 'PingFang SC', 'Microsoft YaHei', sans-serif;
+       max-width: 960px; margin: 2em auto; padding: 0 1em; color: #2c3e50; background: #fafafa; }
+h1 { text-align: center; }
+.overview { display: flex; align-items: center; gap: 2em; flex-wrap: wrap; }
+.gauge { width: 160px; height: 160px; border-radius: 50%; display: flex;
+         align-items: center; justify-content: center; }
+.gauge-inner { width: 120px; height: 120px; border-radius: 50%; background: #fafafa;
+               display: flex; flex-direction: column; align-items: center; justify-content: center; }
+.gauge-value { font-size: 2em; font-weight: bold; }
+table { border-collapse: collapse; width: 100%; margin: 1em 0; }
+th, td { border: 1px solid #ddd; padding: 0.5em; text-align: left; }
+th { background: #f0f0f0; }
+.dot { display: inline-block; width: 0.8em; height: 0.8em; border-radius: 50%; }
+details { background: #fff; border: 1px solid #ddd; border-radius: 6px; margin: 0.5em 0; padding: 0.5em 1em; }
+summary { cursor: pointer; font-weight: bold; }
+.fix { color: #7f8c8d; }
+";
+
+/// HTML报告生成器
+pub struct HtmlReport<'a> {
+    /// 分析结果
+    result: &'a AnalysisResult,
+
+    /// 翻译器
+    translator: &'a Translator,
+
+    /// 报告选项
+    options: &'a ReportOptions,
+}
+
+impl<'a> HtmlReport<'a> {
+    /// 创建新的HTML报告生成器
+    ///
+    /// # Arguments
+    /// * `result` - 分析结果
+    /// * `translator` - 翻译器
+    /// * `options` - 报告选项
+    ///
+    /// # Returns
+    /// * `Self` - 生成器实例
+    pub fn new(
+        result: &'a AnalysisResult,
+        translator: &'a Translator,
+        options: &'a ReportOptions,
+    ) -> Self {
+        HtmlReport {
+            result,
+            translator,
+            options,
+        }
+    }
+
+    /// 生成报告
+    pub fn generate(&self) {
+        println!("{}", self.render());
+    }
+
+    /// 渲染完整的 HTML 页面
+    ///
+    /// # Returns
+    /// * `String` - HTML 文本
+    pub(crate) fn render(&self) -> String {
+        let lang = match self.translator.get_language() {
+            Language::ZhCN => "zh-CN",
+            Language::EnUS => "en",
+        };
+        let title = escape(&self.translator.translate("report.title"));
+
+        let mut html = String::new();
+        let _ = writeln!(html, "<!DOCTYPE html>");
+        let _ = writeln!(html, "<html lang=\"{}\">", lang);
+        let _ = writeln!(html, "<head>");
+        let _ = writeln!(html, "<meta charset=\"utf-8\">");
+        let _ = writeln!(html, "<title>{}</title>", title);
+        let _ = writeln!(html, "<style>{}</style>", STYLE);
+        let _ = writeln!(html, "</head>");
+        let _ = writeln!(html, "<body>");
+        let _ = writeln!(html, "<h1>🌸 {} 🌸</h1>", title);
+
+        self.write_overview(&mut html);
+        self.write_metrics_table(&mut html);
+
+        if !self.options.summary_only {
+            self.write_problem_files(&mut html);
+        }
+
+        let _ = writeln!(html, "</body>");
+        let _ = writeln!(html, "</html>");
+        html
+    }
+
+    /// 写入总体评估（得分仪表和摘要）
+    ///
+    /// # Arguments
+    /// * `html` - 输出缓冲
+    fn write_overview(&self, html: &mut String) {
+        let score = self.result.code_quality_score;
+        let display = self.options.polarity.display(score);
+        let color = score_color(score);
+        let level = quality_level(score, &self.options.level_cuts);
+
+        let _ = writeln!(
+            html,
+            "<h2>{}</h2>",
+            escape(&self.translator.translate("report.overall_assessment"))
+        );
+        let _ = writeln!(html, "<div class=\"overview\">");
+        let _ = writeln!(
+            html,
+            "<div class=\"gauge\" style=\"background: conic-gradient({} {:.1}%, #e0e0e0 0);\">\
+             <div class=\"gauge-inner\"><span class=\"gauge-value\" style=\"color: {};\">{:.*}</span>\
+             <span>/ 100</span></div></div>",
+            color,
+            display.clamp(0.0, 100.0),
+            color,
+            self.options.precision,
+            display
+        );
+
+        let _ = writeln!(html, "<ul>");
+        let _ = writeln!(
+            html,
+            "<li><strong>{}</strong>: {} - {}</li>",
+            escape(&self.translator.translate("report.quality_level")),
+            escape(&self.translator.translate(level.0)),
+            escape(&self.translator.translate(level.1))
+        );
+        if let Some(percentile) = self.options.percentile_of(score) {
+            let _ =
+                writeln!(
+                    html,
+                    "<li><strong>{}</strong>: {}</li>",
+                    escape(&self.translator.translate("report.percentile_label")),
+                    escape(&self.translator.translate_with_args(
+                        "report.percentile",
+                        vec![format!("{:.0}", percentile)]
+                    ))
+                );
+        }
+        let _ = writeln!(
+            html,
+            "<li><strong>{}</strong>: {}</li>",
+            escape(&self.translator.translate("report.analyzed_files")),
+            self.result.total_files
+        );
+        let _ = writeln!(
+            html,
+            "<li><strong>{}</strong>: {}</li>",
+            escape(&self.translator.translate("report.total_lines")),
+            self.result.total_lines
+        );
+        let _ = writeln!(html, "</ul>");
+        let _ = writeln!(html, "</div>");
+    }
+
+    /// 写入指标表格
+    ///
+    /// # Arguments
+    /// * `html` - 输出缓冲
+    fn write_metrics_table(&self, html: &mut String) {
+        let _ = writeln!(
+            html,
+            "<h2>{}</h2>",
+            escape(&self.translator.translate("report.quality_metrics"))
+        );
+        let _ = writeln!(html, "<table>");
+        let _ = writeln!(
+            html,
+            "<tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+            escape(&self.translator.translate("report.metric")),
+            escape(&self.translator.translate("report.score")),
+            escape(&self.translator.translate("report.weight")),
+            escape(&self.translator.translate("report.issue_count")),
+            escape(&self.translator.translate("report.status"))
+        );
+
+        let mut metrics: Vec<_> = self.result.metrics.iter().collect();
+        metrics.sort_by(|a, b| compare_scores(a.1.score, b.1.score, false));
+
+        for (name, result) in metrics {
+            self.write_metric_row(html, name, result);
+        }
+
+        let _ = writeln!(html, "</table>");
+    }
+
+    /// 写入指标行
+    ///
+    /// # Arguments
+    /// * `html` - 输出缓冲
+    /// * `name` - 指标名称
+    /// * `result` - 指标结果
+    fn write_metric_row(&self, html: &mut String, name: &str, result: &MetricResult) {
+        let issue_count = self
+            .result
+            .files_analyzed
+            .iter()
+            .flat_map(|file| &file.issues)
+            .filter(|issue| issue.metric.as_deref() == Some(name))
+            .count();
+
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{:.*}</td><td>{:.2}</td><td>{}</td>\
+             <td><span class=\"dot\" style=\"background: {};\"></span></td></tr>",
+            escape(name),
+            self.options.precision,
+            self.options.polarity.display(result.score),
+            result.weight,
+            issue_count,
+            score_color(result.score)
+        );
+    }
+
+    /// 写入问题文件（每个文件一个可折叠区块）
+    ///
+    /// # Arguments
+    /// * `html` - 输出缓冲
+    fn write_problem_files(&self, html: &mut String) {
+        let files = self.options.rank_files(&self.result.files_analyzed);
+
+        let _ = writeln!(
+            html,
+            "<h2>{}</h2>",
+            escape(&self.translator.translate("report.problem_files"))
+        );
+
+        if files.is_empty() {
+            let _ = writeln!(
+                html,
+                "<p>🎉 {}</p>",
+                escape(&self.translator.translate("report.no_issues"))
+            );
+            return;
+        }
+
+        for file in &files[..self.options.file_limit(files.len())] {
+            self.write_file_section(html, file);
+        }
+    }
+
+    /// 写入单个文件的可折叠区块
+    ///
+    /// # Arguments
+    /// * `html` - 输出缓冲
+    /// * `file` - 文件分析结果
+    fn write_file_section(&self, html: &mut String, file: &FileAnalysisResult) {
+        let _ = writeln!(html, "<details>");
+        let _ = writeln!(
+            html,
+            "<summary><span class=\"dot\" style=\"background: {};\"></span> {} ({}: {:.*})</summary>",
+            score_color(file.file_score),
+            escape(&file.file_path),
+            escape(&self.translator.translate("report.score")),
+            self.options.precision,
+            self.options.polarity.display(file.file_score)
+        );
+
+        let visible = self.options.visible_issues(&file.issues);
+        if !visible.is_empty() {
+            let _ = writeln!(html, "<ul>");
+            for issue in visible {
+                let _ = write!(html, "<li>{}", escape(&issue.to_string()));
+                if let Some(fix) = fix_suggestion(self.translator, issue) {
+                    let _ = write!(html, "<br><span class=\"fix\">💡 {}</span>", escape(&fix));
+                }
+                let _ = writeln!(html, "</li>");
+            }
+            let _ = writeln!(html, "</ul>");
+        }
+
+        if file.dropped_issues > 0 {
+            let _ = writeln!(
+                html,
+                "<p>...另有 {} 个问题超出保留上限未记录</p>",
+                file.dropped_issues
+            );
+        }
+
+        let _ = writeln!(html, "</details>");
+    }
+}
+
+/// 按得分区间获取颜色（与控制台报告的分段一致）
+///
+/// # Arguments
+/// * `score` - 内部得分（0-1，越高越差）
+///
+/// # Returns
+/// * `&str` - CSS 颜色
+fn score_color(score: f64) -> &'static str {
+    match score {
+        s if s < 0.2 => "#2ecc71",
+        s if s < 0.35 => "#27ae60",
+        s if s < 0.5 => "#1abc9c",
+        s if s < 0.6 => "#3498db",
+        s if s < 0.7 => "#f1c40f",
+        s if s < 0.8 => "#f39c12",
+        s if s < 0.9 => "#e74c3c",
+        _ => "#c0392b",
+    }
+}
+
+/// 转义 HTML 特殊字符
+///
+/// # Arguments
+/// * `text` - 原始文本
+///
+/// # Returns
+/// * `String` - 转义后的文本
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
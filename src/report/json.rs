@@ -0,0 +1,384 @@
+//! # JSON报告生成
+//!
+//! 将分析结果输出为机器可读的 JSON
+
+use crate::analyzer::{AnalysisResult, BaselineDiff, FileDelta, MetricDelta};
+use crate::metrics::Issue;
+use crate::report::{grade, Polarity, DEFAULT_LEVEL_CUTS, LEVEL_CUT_COUNT};
+use serde_json::{json, Map, Value};
+
+/// JSON报告生成器
+pub struct JsonReport<'a> {
+    /// 分析结果
+    result: &'a AnalysisResult,
+
+    /// 分数方向
+    polarity: Polarity,
+
+    /// 质量等级分界点（用于计算字母评级）
+    level_cuts: [f64; LEVEL_CUT_COUNT],
+
+    /// 总体得分在参考分布中的百分位
+    percentile: Option<f64>,
+
+    /// 是否输出紧凑的单行 JSON
+    compact: bool,
+
+    /// 与基线的对比结果
+    baseline: Option<&'a BaselineDiff>,
+}
+
+impl<'a> JsonReport<'a> {
+    /// 创建新的JSON报告生成器
+    ///
+    /// # Arguments
+    /// * `result` - 分析结果
+    /// * `polarity` - 分数方向
+    ///
+    /// # Returns
+    /// * `Self` - 生成器实例
+    pub fn new(result: &'a AnalysisResult, polarity: Polarity) -> Self {
+        JsonReport {
+            result,
+            polarity,
+            level_cuts: DEFAULT_LEVEL_CUTS,
+            percentile: None,
+            compact: false,
+            baseline: None,
+        }
+    }
+
+    /// 设置质量等级分界点
+    ///
+    /// # Arguments
+    /// * `level_cuts` - 升序排列的等级分界点（0-100）
+    ///
+    /// # Returns
+    /// * `Self` - 生成器实例
+    pub fn with_level_cuts(mut self, level_cuts: [f64; LEVEL_CUT_COUNT]) -> Self {
+        self.level_cuts = level_cuts;
+        self
+    }
+
+    /// 设置总体得分在参考分布中的百分位
+    ///
+    /// # Arguments
+    /// * `percentile` - 百分位（0-100）
+    ///
+    /// # Returns
+    /// * `Self` - 生成器实例
+    pub fn with_percentile(mut self, percentile: Option<f64>) -> Self {
+        self.percentile = percentile;
+        self
+    }
+
+    /// 设置是否输出紧凑的单行 JSON（否则缩进排版）
+    ///
+    /// # Arguments
+    /// * `compact` - 是否紧凑输出
+    ///
+    /// # Returns
+    /// * `Self` - 生成器实例
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// 设置与基线的对比结果（输出为 `baseline_diff` 字段）
+    ///
+    /// # Arguments
+    /// * `baseline` - 基线对比结果
+    ///
+    /// # Returns
+    /// * `Self` - 生成器实例
+    pub fn with_baseline(mut self, baseline: Option<&'a BaselineDiff>) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// 生成报告
+    pub fn generate(&self) {
+        match self.render() {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("生成JSON报告失败: {}", e),
+        }
+    }
+
+    /// 渲染 JSON 文本
+    ///
+    /// # Returns
+    /// * `serde_json::Result<String>` - JSON 文本
+    pub(crate) fn render(&self) -> serde_json::Result<String> {
+        let result = self.apply_polarity();
+
+        let mut value = serde_json::to_value(&result)?;
+        if let Some(object) = value.as_object_mut() {
+            object.insert("polarity".to_string(), json!(self.polarity));
+            let grade = grade(self.result.code_quality_score, &self.level_cuts);
+            object.insert(
+                "grade".to_string(),
+                json!({ "letter": grade.letter, "emoji": grade.emoji }),
+            );
+            if let Some(percentile) = self.percentile {
+                object.insert("percentile".to_string(), json!(percentile));
+            }
+            if let Some(diff) = self.baseline {
+                object.insert("baseline_diff".to_string(), self.baseline_value(diff));
+            }
+        }
+
+        if self.compact {
+            serde_json::to_string(&value)
+        } else {
+            serde_json::to_string_pretty(&value)
+        }
+    }
+
+    /// 把基线对比结果转换为 JSON（得分按分数方向转换，仍为 0-1）
+    ///
+    /// # Arguments
+    /// * `diff` - 基线对比结果
+    ///
+    /// # Returns
+    /// * `Value` - JSON 值
+    fn baseline_value(&self, diff: &BaselineDiff) -> Value {
+        let metrics: Map<String, Value> = diff
+            .metrics
+            .iter()
+            .map(|(name, delta)| (name.clone(), self.metric_delta_value(delta)))
+            .collect();
+        let files: Map<String, Value> = diff
+            .files
+            .iter()
+            .map(|(path, delta)| (path.clone(), self.file_delta_value(delta)))
+            .collect();
+
+        json!({
+            "overall": self.metric_delta_value(&diff.overall),
+            "metrics": metrics,
+            "files": files,
+            "new_issues": issue_values(&diff.new_issues),
+            "resolved_issues": issue_values(&diff.resolved_issues),
+        })
+    }
+
+    /// 把指标得分变化转换为 JSON
+    ///
+    /// # Arguments
+    /// * `delta` - 指标得分变化
+    ///
+    /// # Returns
+    /// * `Value` - JSON 值
+    fn metric_delta_value(&self, delta: &MetricDelta) -> Value {
+        json!({
+            "baseline": self.polarity.display(delta.baseline) / 100.0,
+            "current": self.polarity.display(delta.current) / 100.0,
+            "delta": self.polarity.display_delta(delta.delta()) / 100.0,
+        })
+    }
+
+    /// 把文件得分变化转换为 JSON，`status` 为 new、removed 或 changed
+    ///
+    /// # Arguments
+    /// * `delta` - 文件得分变化
+    ///
+    /// # Returns
+    /// * `Value` - JSON 值
+    fn file_delta_value(&self, delta: &FileDelta) -> Value {
+        let status = match (delta.baseline, delta.current) {
+            (None, _) => "new",
+            (_, None) => "removed",
+            _ => "changed",
+        };
+
+        json!({
+            "status": status,
+            "baseline": delta.baseline.map(|score| self.polarity.display(score) / 100.0),
+            "current": delta.current.map(|score| self.polarity.display(score) / 100.0),
+            "delta": delta.delta().map(|d| self.polarity.display_delta(d) / 100.0),
+        })
+    }
+
+    /// 按分数方向转换所有得分（仍为 0-1），与控制台和 Markdown 展示保持一致
+    ///
+    /// # Returns
+    /// * `AnalysisResult` - 转换后的结果
+    fn apply_polarity(&self) -> AnalysisResult {
+        let mut result = self.result.clone();
+        let convert = |score: f64| self.polarity.display(score) / 100.0;
+
+        result.code_quality_score = convert(result.code_quality_score);
+        for metric in result.metrics.values_mut() {
+            metric.score = convert(metric.score);
+        }
+        for language in &mut result.languages {
+            language.score = convert(language.score);
+        }
+        for file in &mut result.files_analyzed {
+            file.file_score = convert(file.file_score);
+            for metric in file.metrics.values_mut() {
+                metric.score = convert(metric.score);
+            }
+        }
+
+        result
+    }
+}
+
+/// 把 (文件路径, 问题) 列表转换为 JSON 数组，每项为问题字段加上 `file_path`
+///
+/// # Arguments
+/// * `issues` - (文件路径, 问题) 列表
+///
+/// # Returns
+/// * `Vec<Value>` - JSON 数组
+fn issue_values(issues: &[(String, Issue)]) -> Vec<Value> {
+    issues
+        .iter()
+        .map(|(path, issue)| {
+            let mut value = serde_json::to_value(issue).unwrap_or_default();
+            if let Some(object) = value.as_object_mut() {
+                object.insert("file_path".to_string(), json!(path));
+            }
+            value
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{CodeAnalyzer, FileAnalysisResult};
+    use crate::common::testing::TempDir;
+    use crate::common::LanguageType;
+    use crate::config::AnalysisConfig;
+    use crate::metrics::MetricResult;
+    use std::collections::HashMap;
+
+    fn metrics(score: f64) -> HashMap<String, MetricResult> {
+        HashMap::from([(
+            "复杂度".to_string(),
+            MetricResult::new(score, 1.0, String::new(), Vec::new()),
+        )])
+    }
+
+    fn result() -> AnalysisResult {
+        AnalysisResult {
+            code_quality_score: 0.3,
+            metrics: metrics(0.25),
+            files_analyzed: vec![FileAnalysisResult {
+                file_path: "a.rs".to_string(),
+                file_score: 0.8,
+                lines: 10,
+                metrics: metrics(0.6),
+                issues: Vec::new(),
+                dropped_issues: 0,
+                generic_parser: false,
+                language: LanguageType::Rust,
+            }],
+            total_files: 1,
+            total_lines: 10,
+            is_empty: false,
+            run_id: String::new(),
+            parameter_distribution: Default::default(),
+            analyzed_files: vec!["a.rs".to_string()],
+            skipped_files: Vec::new(),
+            languages: Vec::new(),
+            cache_stats: None,
+        }
+    }
+
+    #[test]
+    fn quality_polarity_inverts_every_score() {
+        let result = result();
+        let converted = JsonReport::new(&result, Polarity::Quality).apply_polarity();
+
+        assert!((converted.code_quality_score - 0.7).abs() < 1e-9);
+        assert!((converted.metrics["复杂度"].score - 0.75).abs() < 1e-9);
+        let file = &converted.files_analyzed[0];
+        assert!((file.file_score - 0.2).abs() < 1e-9);
+        assert!((file.metrics["复杂度"].score - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn smell_polarity_keeps_scores() {
+        let result = result();
+        let converted = JsonReport::new(&result, Polarity::Smell).apply_polarity();
+
+        assert!((converted.code_quality_score - 0.3).abs() < 1e-9);
+        assert!((converted.files_analyzed[0].file_score - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn json_lists_analyzed_and_skipped_files_with_reasons() {
+        let dir = TempDir::new("json-coverage");
+        dir.write("main.py", "def main():\n    return 1\n");
+        dir.write("util.py", "def util():\n    return 2\n");
+        dir.write("big.py", &"x = 1\n".repeat(200));
+        dir.write("lib.rs", "fn lib() {}\n");
+        let config = AnalysisConfig {
+            max_file_size: 512,
+            extensions: vec!["py".to_string()],
+            ..AnalysisConfig::default()
+        };
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let result = analyzer.analyze_with_config(dir.path(), &config).unwrap();
+
+        let json = JsonReport::new(&result, Polarity::Smell).render().unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        let name = |path: &Value| {
+            let path = std::path::Path::new(path.as_str().unwrap());
+            path.file_name().unwrap().to_str().unwrap().to_string()
+        };
+
+        let mut analyzed: Vec<String> = value["analyzed_files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(name)
+            .collect();
+        analyzed.sort();
+        assert_eq!(analyzed, ["main.py", "util.py"]);
+
+        let mut skipped: Vec<(String, String)> = value["skipped_files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| {
+                (
+                    name(&entry["path"]),
+                    entry["reason"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        skipped.sort();
+        assert_eq!(
+            skipped,
+            [
+                ("big.py".to_string(), "too_large".to_string()),
+                ("lib.rs".to_string(), "extension_filter".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn compact_is_one_line_and_pretty_is_not_but_both_parse_the_same() {
+        let result = result();
+        let render = |compact| {
+            JsonReport::new(&result, Polarity::Smell)
+                .with_compact(compact)
+                .render()
+                .unwrap()
+        };
+        let compact = render(true);
+        let pretty = render(false);
+
+        assert_eq!(compact.lines().count(), 1);
+        assert!(pretty.lines().count() > 1);
+        assert_eq!(
+            serde_json::from_str::<Value>(&compact).unwrap(),
+            serde_json::from_str::<Value>(&pretty).unwrap()
+        );
+    }
+}
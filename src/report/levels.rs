@@ -0,0 +1,108 @@
+//! # 质量等级
+//!
+//! 按分界点把总体得分映射为质量等级和字母评级，供各报告格式共用
+
+/// 等级分界点的数量（比等级数少一个）
+pub const LEVEL_CUT_COUNT: usize = 10;
+
+/// 默认的等级分界点（屎气指数，0-100）
+pub const DEFAULT_LEVEL_CUTS: [f64; LEVEL_CUT_COUNT] =
+    [5.0, 15.0, 25.0, 40.0, 55.0, 65.0, 75.0, 85.0, 95.0, 100.0];
+
+/// 从干净到最差的等级（等级键, 描述键）
+const LEVELS: [(&str, &str); LEVEL_CUT_COUNT + 1] = [
+    ("level.clean", "level.clean.description"),
+    ("level.mild", "level.mild.description"),
+    ("level.moderate", "level.moderate.description"),
+    ("level.bad", "level.bad.description"),
+    ("level.terrible", "level.terrible.description"),
+    ("level.disaster", "level.disaster.description"),
+    ("level.disaster.severe", "level.disaster.severe.description"),
+    (
+        "level.disaster.very_bad",
+        "level.disaster.very_bad.description",
+    ),
+    (
+        "level.disaster.extreme",
+        "level.disaster.extreme.description",
+    ),
+    ("level.disaster.worst", "level.disaster.worst.description"),
+    (
+        "level.disaster.ultimate",
+        "level.disaster.ultimate.description",
+    ),
+];
+
+/// 各质量等级对应的字母评级（A 最好，F 最差）
+const GRADE_LETTERS: [&str; LEVEL_CUT_COUNT + 1] =
+    ["A", "A", "B", "C", "D", "D", "D", "F", "F", "F", "F"];
+
+/// 各质量等级对应的表情
+const LEVEL_EMOJIS: [&str; LEVEL_CUT_COUNT + 1] = [
+    "🌱", "🌸", "😐", "😷", "💩", "🤕", "☣️", "🧟", "☢️", "🪦", "👑💩",
+];
+
+/// 字母评级
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grade {
+    /// 字母（A、B、C、D、F）
+    pub letter: &'static str,
+
+    /// 表情
+    pub emoji: &'static str,
+}
+
+impl Grade {
+    /// 获取评级说明的 i18n 键
+    ///
+    /// # Returns
+    /// * `String` - 形如 `grade.a` 的键
+    pub fn label_key(&self) -> String {
+        format!("grade.{}", self.letter.to_lowercase())
+    }
+}
+
+/// 获取得分所在的等级序号
+///
+/// # Arguments
+/// * `score` - 内部得分（0-1，越高越差）
+/// * `cuts` - 升序排列的等级分界点（0-100）
+///
+/// # Returns
+/// * `usize` - 等级序号（0 为最干净）
+fn level_index(score: f64, cuts: &[f64; LEVEL_CUT_COUNT]) -> usize {
+    let adjusted_score = score * 100.0;
+    cuts.iter()
+        .position(|&cut| adjusted_score < cut)
+        .unwrap_or(LEVEL_CUT_COUNT)
+}
+
+/// 获取质量等级
+///
+/// # Arguments
+/// * `score` - 内部得分（0-1，越高越差）
+/// * `cuts` - 升序排列的等级分界点（0-100），得分低于第 n 个分界点即属于第 n 级
+///
+/// # Returns
+/// * `(&str, &str)` - (等级键, 描述键)
+pub fn quality_level(score: f64, cuts: &[f64; LEVEL_CUT_COUNT]) -> (&'static str, &'static str) {
+    LEVELS[level_index(score, cuts)]
+}
+
+/// 获取字母评级
+///
+/// 与 [`quality_level`] 使用相同的分界点，保证各报告格式的评级和等级一致
+///
+/// # Arguments
+/// * `score` - 内部得分（0-1，越高越差）
+/// * `cuts` - 升序排列的等级分界点（0-100）
+///
+/// # Returns
+/// * `Grade` - 字母评级
+pub fn grade(score: f64, cuts: &[f64; LEVEL_CUT_COUNT]) -> Grade {
+    let index = level_index(score, cuts);
+    Grade {
+        letter: GRADE_LETTERS[index],
+        emoji: LEVEL_EMOJIS[index],
+    }
+}
@@ -2,9 +2,12 @@
 //!
 //! 生成Markdown格式的报告
 
-use crate::analyzer::AnalysisResult;
+use crate::analyzer::{AnalysisResult, BaselineDiff};
 use crate::i18n::Translator;
-use crate::report::ReportOptions;
+use crate::report::{
+    compare_scores, file_metric_scores, fix_suggestion, grade_headline, group_issues,
+    quality_level, ReportOptions,
+};
 
 /// Markdown报告生成器
 pub struct MarkdownReport<'a> {
@@ -16,6 +19,9 @@ pub struct MarkdownReport<'a> {
 
     /// 报告选项
     options: &'a ReportOptions,
+
+    /// 基线对比结果
+    baseline: Option<&'a BaselineDiff>,
 }
 
 impl<'a> MarkdownReport<'a> {
@@ -25,6 +31,7 @@ impl<'a> MarkdownReport<'a> {
     /// * `result` - 分析结果
     /// * `translator` - 翻译器
     /// * `options` - 报告选项
+    /// * `baseline` - 基线对比结果
     ///
     /// # Returns
     /// * `Self` - 生成器实例
@@ -32,11 +39,13 @@ impl<'a> MarkdownReport<'a> {
         result: &'a AnalysisResult,
         translator: &'a Translator,
         options: &'a ReportOptions,
+        baseline: Option<&'a BaselineDiff>,
     ) -> Self {
         MarkdownReport {
             result,
             translator,
             options,
+            baseline,
         }
     }
 
@@ -64,7 +73,10 @@ impl<'a> MarkdownReport<'a> {
     fn generate_full_report(&self) {
         self.print_title();
         self.print_summary();
+        self.print_generic_parser_warning();
+        self.print_baseline_comparison();
         self.print_metrics_table();
+        self.print_language_table();
 
         if !self.options.summary_only {
             self.print_problem_files();
@@ -76,6 +88,14 @@ impl<'a> MarkdownReport<'a> {
     /// 打印标题
     fn print_title(&self) {
         println!("# 🌸 {} 🌸\n", self.translator.translate("report.title"));
+        println!(
+            "**{}**\n",
+            grade_headline(
+                self.translator,
+                self.options,
+                self.result.code_quality_score
+            )
+        );
     }
 
     /// 打印摘要
@@ -86,30 +106,212 @@ impl<'a> MarkdownReport<'a> {
         );
 
         println!(
-            "- **{}**: {:.2}/100",
+            "- **{}**: {:.*}/100",
             self.translator.translate("report.quality_score"),
-            self.result.code_quality_score * 100.0
+            self.options.precision,
+            self.options
+                .polarity
+                .display(self.result.code_quality_score)
+        );
+
+        println!("{}", self.quality_level_line());
+
+        if let Some(percentile) = self.options.percentile_of(self.result.code_quality_score) {
+            println!(
+                "- **{}**: {}",
+                self.translator.translate("report.percentile_label"),
+                self.translator
+                    .translate_with_args("report.percentile", vec![format!("{:.0}", percentile)])
+            );
+        }
+
+        println!(
+            "- **{}**: {}",
+            self.translator.translate("report.analyzed_files"),
+            self.result.total_files
+        );
+
+        println!(
+            "- **{}**: {}",
+            self.translator.translate("report.total_lines"),
+            self.result.total_lines
         );
 
-        let level = self.get_quality_level(self.result.code_quality_score);
         println!(
+            "- **{}**: `{}`\n",
+            self.translator.translate("report.run_id"),
+            self.result.run_id
+        );
+    }
+
+    /// 生成质量等级行
+    ///
+    /// # Returns
+    /// * `String` - 形如 `- **质量等级**: 等级 - 描述` 的行
+    pub(crate) fn quality_level_line(&self) -> String {
+        let level = quality_level(self.result.code_quality_score, &self.options.level_cuts);
+        format!(
             "- **{}**: {} - {}",
             self.translator.translate("report.quality_level"),
             self.translator.translate(level.0),
             self.translator.translate(level.1)
+        )
+    }
+
+    /// 通用解析器分析的文件过多时打印警告
+    fn print_generic_parser_warning(&self) {
+        if !self.result.is_approximate() {
+            return;
+        }
+
+        println!(
+            "> ⚠️ {}\n",
+            self.translator.translate_with_args(
+                "report.generic_parser_warning",
+                vec![
+                    self.result.generic_parser_files().to_string(),
+                    self.result.files_analyzed.len().to_string(),
+                ],
+            )
         );
+    }
+
+    /// 打印与基线的对比
+    fn print_baseline_comparison(&self) {
+        let Some(diff) = self.baseline else {
+            return;
+        };
 
         println!(
-            "- **{}**: {}",
-            self.translator.translate("report.analyzed_files"),
-            self.result.total_files
+            "## {}\n",
+            self.translator.translate("report.baseline_comparison")
         );
 
         println!(
-            "- **{}**: {}\n",
-            self.translator.translate("report.total_lines"),
-            self.result.total_lines
+            "| {} | {} | {} | {} |",
+            self.translator.translate("report.metric"),
+            self.translator.translate("report.baseline"),
+            self.translator.translate("report.current"),
+            self.translator.translate("report.change")
+        );
+
+        println!("|------|------|------|------|");
+
+        println!(
+            "| {} | {:.*} | {:.*} | {:+.*} |",
+            self.translator.translate("report.quality_score"),
+            self.options.precision,
+            self.options.polarity.display(diff.overall.baseline),
+            self.options.precision,
+            self.options.polarity.display(diff.overall.current),
+            self.options.precision,
+            self.options.polarity.display_delta(diff.overall.delta())
+        );
+
+        for (name, delta) in &diff.metrics {
+            println!(
+                "| {} | {:.*} | {:.*} | {:+.*} |",
+                name,
+                self.options.precision,
+                self.options.polarity.display(delta.baseline),
+                self.options.precision,
+                self.options.polarity.display(delta.current),
+                self.options.precision,
+                self.options.polarity.display_delta(delta.delta())
+            );
+        }
+
+        println!();
+
+        if !diff.files.is_empty() {
+            self.print_file_changes(diff);
+        }
+
+        self.print_new_issues(diff);
+    }
+
+    /// 打印问题变化数量和新出现的问题列表
+    ///
+    /// # Arguments
+    /// * `diff` - 对比结果
+    fn print_new_issues(&self, diff: &BaselineDiff) {
+        println!(
+            "**{}**\n",
+            self.translator.translate_with_args(
+                "report.issue_changes",
+                vec![
+                    diff.new_issues.len().to_string(),
+                    diff.resolved_issues.len().to_string(),
+                ],
+            )
         );
+
+        if diff.new_issues.is_empty() {
+            return;
+        }
+
+        let limit = self.options.issue_limit(diff.new_issues.len());
+        for (path, issue) in &diff.new_issues[..limit] {
+            match issue.line {
+                Some(line) => println!("- `{}:{}` {}", path, line, issue),
+                None => println!("- `{}` {}", path, issue),
+            }
+        }
+
+        if diff.new_issues.len() > limit {
+            println!("- ...还有 {} 个问题", diff.new_issues.len() - limit);
+        }
+
+        println!();
+    }
+
+    /// 打印文件得分变化表格
+    ///
+    /// # Arguments
+    /// * `diff` - 对比结果
+    fn print_file_changes(&self, diff: &BaselineDiff) {
+        println!("### {}\n", self.translator.translate("report.file_changes"));
+
+        println!(
+            "| {} | {} | {} | {} |",
+            self.translator.translate("report.file"),
+            self.translator.translate("report.baseline"),
+            self.translator.translate("report.current"),
+            self.translator.translate("report.change")
+        );
+
+        println!("|------|------|------|------|");
+
+        let display = |score: Option<f64>| match score {
+            Some(score) => format!(
+                "{:.*}",
+                self.options.precision,
+                self.options.polarity.display(score)
+            ),
+            None => "-".to_string(),
+        };
+
+        for (path, delta) in &diff.files {
+            let change = match (delta.delta(), delta.baseline) {
+                (Some(d), _) => format!(
+                    "{:+.*}",
+                    self.options.precision,
+                    self.options.polarity.display_delta(d)
+                ),
+                (None, None) => self.translator.translate("report.file_added"),
+                (None, Some(_)) => self.translator.translate("report.file_removed"),
+            };
+
+            println!(
+                "| {} | {} | {} | {} |",
+                path,
+                display(delta.baseline),
+                display(delta.current),
+                change
+            );
+        }
+
+        println!();
     }
 
     /// 打印指标表格
@@ -124,7 +326,7 @@ impl<'a> MarkdownReport<'a> {
 
         // 排序并打印指标
         let mut metrics: Vec<_> = self.result.metrics.iter().collect();
-        metrics.sort_by(|a, b| a.1.score.partial_cmp(&b.1.score).unwrap());
+        metrics.sort_by(|a, b| compare_scores(a.1.score, b.1.score, false));
 
         for (name, result) in metrics {
             self.print_metric_row(name, result);
@@ -133,17 +335,50 @@ impl<'a> MarkdownReport<'a> {
         println!();
     }
 
+    /// 打印按语言汇总的得分表格（只有一种语言时不打印）
+    fn print_language_table(&self) {
+        if self.result.languages.len() < 2 {
+            return;
+        }
+
+        println!("## {}\n", self.translator.translate("report.languages"));
+        println!(
+            "| {} | {} | {} | {} | {} |",
+            self.translator.translate("report.language"),
+            self.translator.translate("report.score"),
+            self.translator.translate("report.file_count"),
+            self.translator.translate("report.total_lines"),
+            self.translator.translate("report.status")
+        );
+        println!("|------|------|------|------|------|");
+
+        for language in &self.result.languages {
+            println!(
+                "| {} | {:.*} | {} | {} | {} |",
+                language.language.display_name(),
+                self.options.precision,
+                self.options.polarity.display(language.score),
+                language.files,
+                language.lines,
+                self.get_status_emoji(language.score * 100.0)
+            );
+        }
+
+        println!();
+    }
+
     /// 打印表格头部
     fn print_table_header(&self) {
         println!(
-            "| {} | {} | {} | {} |",
+            "| {} | {} | {} | {} | {} |",
             self.translator.translate("report.metric"),
             self.translator.translate("report.score"),
             self.translator.translate("report.weight"),
+            self.translator.translate("report.issue_count"),
             self.translator.translate("report.status")
         );
 
-        println!("|------|------|------|------|");
+        println!("|------|------|------|------|------|");
     }
 
     /// 打印指标行
@@ -156,11 +391,32 @@ impl<'a> MarkdownReport<'a> {
         let status_emoji = self.get_status_emoji(score_percentage);
 
         println!(
-            "| {} | {:.2} | {:.2} | {} |",
-            name, score_percentage, result.weight, status_emoji
+            "| {} | {:.*} | {:.2} | {} | {} |",
+            name,
+            self.options.precision,
+            self.options.polarity.display(result.score),
+            result.weight,
+            self.count_metric_issues(name),
+            status_emoji
         );
     }
 
+    /// 统计某个指标在所有文件中产生的问题数
+    ///
+    /// # Arguments
+    /// * `name` - 指标名称
+    ///
+    /// # Returns
+    /// * `usize` - 问题数
+    fn count_metric_issues(&self, name: &str) -> usize {
+        self.result
+            .files_analyzed
+            .iter()
+            .flat_map(|file| &file.issues)
+            .filter(|issue| issue.metric.as_deref() == Some(name))
+            .count()
+    }
+
     /// 获取状态表情
     ///
     /// # Arguments
@@ -183,21 +439,24 @@ impl<'a> MarkdownReport<'a> {
 
     /// 打印问题文件
     fn print_problem_files(&self) {
+        let files = self.options.rank_files(&self.result.files_analyzed);
+        let top = match self.options.top_files {
+            0 => files.len(),
+            top => top,
+        };
+
         println!(
             "## {} (Top {})\n",
             self.translator.translate("report.problem_files"),
-            self.options.top_files
+            top
         );
 
-        let mut files = self.result.files_analyzed.clone();
-        files.sort_by(|a, b| b.file_score.partial_cmp(&a.file_score).unwrap());
-
         if files.is_empty() {
             println!("🎉 {}\n", self.translator.translate("report.no_issues"));
             return;
         }
 
-        let max_files = self.options.top_files.min(files.len());
+        let max_files = self.options.file_limit(files.len());
 
         for i in 0..max_files {
             self.print_file_section(i, &files[i]);
@@ -211,21 +470,67 @@ impl<'a> MarkdownReport<'a> {
     /// * `file` - 文件分析结果
     fn print_file_section(&self, index: usize, file: &crate::analyzer::FileAnalysisResult) {
         println!(
-            "### {}. {} ({}: {:.2})",
+            "### {}. {} ({}: {:.*})",
             index + 1,
             file.file_path,
             self.translator.translate("report.score"),
-            file.file_score * 100.0
+            self.options.precision,
+            self.options.polarity.display(file.file_score)
         );
 
-        if !file.issues.is_empty() {
+        self.print_file_metrics(file);
+
+        let visible = self.options.visible_issues(&file.issues);
+        if !visible.is_empty() {
             println!("**{}**:", self.translator.translate("report.main_issues"));
 
-            for issue in &file.issues {
-                println!("- {}", issue);
+            for (category, issues) in group_issues(&visible) {
+                println!("- **{}**", self.translator.translate(&category));
+
+                for issue in issues {
+                    match issue.line {
+                        Some(line) => println!("  - `{}:{}` {}", file.file_path, line, issue),
+                        None => println!("  - {}", issue),
+                    }
+
+                    if let Some(fix) = fix_suggestion(self.translator, issue) {
+                        println!("    - 💡 {}", fix);
+                    }
+                }
             }
         }
 
+        if file.dropped_issues > 0 {
+            println!("- ...另有 {} 个问题超出保留上限未记录", file.dropped_issues);
+        }
+
+        println!();
+    }
+
+    /// 打印文件的指标得分表格（从差到好）
+    ///
+    /// # Arguments
+    /// * `file` - 文件分析结果
+    fn print_file_metrics(&self, file: &crate::analyzer::FileAnalysisResult) {
+        let scores = file_metric_scores(file);
+        if scores.is_empty() {
+            return;
+        }
+
+        println!(
+            "| {} | {} |",
+            self.translator.translate("report.metric"),
+            self.translator.translate("report.score")
+        );
+        println!("|------|------|");
+        for (name, score) in scores {
+            println!(
+                "| {} | {:.*} |",
+                name,
+                self.options.precision,
+                self.options.polarity.display(score)
+            );
+        }
         println!();
     }
 
@@ -304,38 +609,55 @@ impl<'a> MarkdownReport<'a> {
         println!("- {}", self.translator.translate("advice.bad.duplication"));
         println!("- {}\n", self.translator.translate("advice.bad.comments"));
     }
+}
 
-    /// 获取质量等级
-    ///
-    /// # Arguments
-    /// * `score` - 分数
-    ///
-    /// # Returns
-    /// * `(&str, &str)` - (等级键, 描述键)
-    fn get_quality_level(&self, score: f64) -> (&'static str, &'static str) {
-        let adjusted_score = score * 100.0;
-
-        match adjusted_score {
-            s if s < 5.0 => ("level.clean", "level.clean.description"),
-            s if s < 15.0 => ("level.mild", "level.mild.description"),
-            s if s < 25.0 => ("level.moderate", "level.moderate.description"),
-            s if s < 40.0 => ("level.bad", "level.bad.description"),
-            s if s < 55.0 => ("level.terrible", "level.terrible.description"),
-            s if s < 65.0 => ("level.disaster", "level.disaster.description"),
-            s if s < 75.0 => ("level.disaster.severe", "level.disaster.severe.description"),
-            s if s < 85.0 => (
-                "level.disaster.very_bad",
-                "level.disaster.very_bad.description",
-            ),
-            s if s < 95.0 => (
-                "level.disaster.extreme",
-                "level.disaster.extreme.description",
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::CodeAnalyzer;
+    use crate::common::testing::TempDir;
+    use crate::config::AnalysisConfig;
+    use crate::i18n::Language;
+    use crate::report::tests::options;
+
+    #[test]
+    fn metric_issue_counts_sum_to_the_total_issue_count() {
+        let project = TempDir::new("markdown-issue-counts");
+        let branches: String = (0..15)
+            .map(|i| format!("    if value == {}:\n        return {}\n", i, i))
+            .collect();
+        project.write(
+            "messy.py",
+            &format!(
+                "counter = 0\n\ndef classify(value, a, b, c, d, e, f, g):\n{}    return -1\n",
+                branches
             ),
-            s if s < 100.0 => ("level.disaster.worst", "level.disaster.worst.description"),
-            _ => (
-                "level.disaster.ultimate",
-                "level.disaster.ultimate.description",
-            ),
-        }
+        );
+        project.write(
+            "clean.py",
+            "def add(a, b):\n    \"\"\"Add.\"\"\"\n    return a + b\n",
+        );
+        let mut analyzer = CodeAnalyzer::new();
+        analyzer.set_silent(true);
+        let result = analyzer
+            .analyze_with_config(project.path(), &AnalysisConfig::default())
+            .unwrap();
+        let translator = Translator::new(Language::ZhCN);
+        let options = options();
+        let report = MarkdownReport::new(&result, &translator, &options, None);
+
+        let counts: Vec<usize> = result
+            .metrics
+            .keys()
+            .map(|name| report.count_metric_issues(name))
+            .collect();
+        let total: usize = result
+            .files_analyzed
+            .iter()
+            .map(|file| file.issues.len())
+            .sum();
+
+        assert!(counts.iter().filter(|&&count| count > 0).count() >= 2);
+        assert_eq!(counts.iter().sum::<usize>(), total);
     }
 }
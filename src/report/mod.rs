@@ -3,10 +3,21 @@
 //! 负责生成分析报告
 
 mod console;
+mod csv;
+mod html;
+mod json;
+mod levels;
 mod markdown;
+mod percentile;
 
-use crate::analyzer::AnalysisResult;
+pub use levels::{grade, quality_level, Grade, DEFAULT_LEVEL_CUTS, LEVEL_CUT_COUNT};
+pub use percentile::ReferenceDistribution;
+
+use crate::analyzer::{AnalysisResult, BaselineDiff, FileAnalysisResult};
 use crate::i18n::Translator;
+use crate::metrics::{Issue, Severity};
+use serde::Serialize;
+use std::cmp::Ordering;
 
 /// 报告生成器
 pub struct Report {
@@ -15,6 +26,141 @@ pub struct Report {
 
     /// 翻译器
     translator: Translator,
+
+    /// 基线对比结果
+    baseline_diff: Option<BaselineDiff>,
+}
+
+/// 分数方向
+///
+/// 内部始终使用"越高越差"的屎气分数，展示时按方向统一转换
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Polarity {
+    /// 越高越差（屎气指数，默认）
+    #[default]
+    Smell,
+
+    /// 越高越好（质量分 = 100 - 屎气指数）
+    Quality,
+}
+
+impl Polarity {
+    /// 将内部分数（0-1，越高越差）转换为展示分数（0-100）
+    ///
+    /// # Arguments
+    /// * `score` - 内部分数
+    ///
+    /// # Returns
+    /// * `f64` - 展示分数
+    pub fn display(self, score: f64) -> f64 {
+        match self {
+            Polarity::Smell => score * 100.0,
+            Polarity::Quality => (1.0 - score) * 100.0,
+        }
+    }
+
+    /// 将内部分数变化量转换为展示变化量
+    ///
+    /// # Arguments
+    /// * `delta` - 内部分数变化量（0-1）
+    ///
+    /// # Returns
+    /// * `f64` - 展示变化量（0-100）
+    pub fn display_delta(self, delta: f64) -> f64 {
+        match self {
+            Polarity::Smell => delta * 100.0,
+            Polarity::Quality => -delta * 100.0,
+        }
+    }
+
+    /// 文件得分的标签
+    ///
+    /// # Returns
+    /// * `&str` - 标签
+    pub fn file_score_label(self) -> &'static str {
+        match self {
+            Polarity::Smell => "屎气指数",
+            Polarity::Quality => "质量分",
+        }
+    }
+}
+
+/// 问题文件的排序方式
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SortKey {
+    /// 按文件得分（默认）
+    #[default]
+    Score,
+
+    /// 按问题数
+    Issues,
+
+    /// 按代码行数
+    Lines,
+
+    /// 按指定指标的得分（指标名称）
+    Metric(String),
+}
+
+impl std::str::FromStr for SortKey {
+    type Err = String;
+
+    /// 解析 `score`、`issues`、`lines` 或 `metric:<指标>`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "score" => Ok(SortKey::Score),
+            "issues" => Ok(SortKey::Issues),
+            "lines" => Ok(SortKey::Lines),
+            other => match other.strip_prefix("metric:") {
+                Some(metric) if !metric.trim().is_empty() => {
+                    Ok(SortKey::Metric(metric.trim().to_string()))
+                }
+                _ => Err(format!(
+                    "未知的排序方式: {}（可选 score、issues、lines、metric:<指标>）",
+                    value
+                )),
+            },
+        }
+    }
+}
+
+impl SortKey {
+    /// 比较两个文件（从差到好、从多到少）
+    ///
+    /// # Arguments
+    /// * `a` - 文件分析结果
+    /// * `b` - 文件分析结果
+    ///
+    /// # Returns
+    /// * `Ordering` - 比较结果
+    fn compare(&self, a: &FileAnalysisResult, b: &FileAnalysisResult) -> Ordering {
+        match self {
+            SortKey::Score => compare_scores(a.file_score, b.file_score, true),
+            SortKey::Issues => file_issue_count(b).cmp(&file_issue_count(a)),
+            SortKey::Lines => b.lines.cmp(&a.lines),
+            SortKey::Metric(name) => {
+                // 没有该指标的文件排在最后
+                let score = |file: &FileAnalysisResult| {
+                    file.metrics
+                        .get(name)
+                        .map_or(f64::NAN, |metric| metric.score)
+                };
+                compare_scores(score(a), score(b), true)
+            }
+        }
+    }
+}
+
+/// 文件的问题总数（含超出保留上限的问题）
+///
+/// # Arguments
+/// * `file` - 文件分析结果
+///
+/// # Returns
+/// * `usize` - 问题数
+fn file_issue_count(file: &FileAnalysisResult) -> usize {
+    file.issues.len() + file.dropped_issues
 }
 
 /// 报告选项
@@ -23,17 +169,300 @@ pub struct ReportOptions {
     /// 是否详细模式
     pub verbose: bool,
 
-    /// 显示的文件数量
+    /// 显示的文件数量（0 表示全部）
     pub top_files: usize,
 
-    /// 每个文件显示的问题数
+    /// 每个文件显示的问题数（0 表示全部）
     pub max_issues: usize,
 
     /// 是否只显示摘要
     pub summary_only: bool,
 
+    /// 是否只输出总体得分
+    pub quiet: bool,
+
     /// 是否输出markdown
     pub markdown_output: bool,
+
+    /// 是否输出JSON
+    pub json_output: bool,
+
+    /// 是否输出HTML
+    pub html_output: bool,
+
+    /// 是否输出CSV
+    pub csv_output: bool,
+
+    /// JSON 是否输出为紧凑的单行
+    pub json_compact: bool,
+
+    /// 只显示得分高于该值的文件（0-100）
+    pub min_file_score: Option<f64>,
+
+    /// 问题文件的排序方式
+    pub sort_by: SortKey,
+
+    /// 报告中显示的问题的最低严重程度（JSON 输出不受影响）
+    pub min_severity: Severity,
+
+    /// 是否反转排序（从好到差、从少到多）
+    pub reverse: bool,
+
+    /// 分数展示方向
+    pub polarity: Polarity,
+
+    /// 参考分布（设置后报告总体得分在其中的百分位）
+    pub percentile: Option<ReferenceDistribution>,
+
+    /// 得分显示的小数位数（JSON 输出始终保留完整精度）
+    pub precision: usize,
+
+    /// 质量等级分界点（屎气指数，0-100，升序）
+    pub level_cuts: [f64; LEVEL_CUT_COUNT],
+}
+
+impl ReportOptions {
+    /// 计算总体得分在参考分布中的百分位
+    ///
+    /// # Arguments
+    /// * `score` - 内部得分（0-1，越高越差）
+    ///
+    /// # Returns
+    /// * `Option<f64>` - 比该得分更干净的参考项目比例（0-100），未启用时为 None
+    pub fn percentile_of(&self, score: f64) -> Option<f64> {
+        self.percentile
+            .as_ref()
+            .map(|reference| reference.percentile(score))
+    }
+
+    /// 计算实际显示的文件数量
+    ///
+    /// # Arguments
+    /// * `total` - 可显示的文件总数
+    ///
+    /// # Returns
+    /// * `usize` - 显示数量，`top_files` 为 0 时显示全部
+    pub fn file_limit(&self, total: usize) -> usize {
+        show_limit(self.top_files, total)
+    }
+
+    /// 计算每个文件实际显示的问题数量
+    ///
+    /// # Arguments
+    /// * `total` - 文件的问题总数
+    ///
+    /// # Returns
+    /// * `usize` - 显示数量，`max_issues` 为 0 时显示全部
+    pub fn issue_limit(&self, total: usize) -> usize {
+        show_limit(self.max_issues, total)
+    }
+
+    /// 按展示顺序列出文件中达到最低严重程度的问题
+    ///
+    /// # Arguments
+    /// * `issues` - 问题列表
+    ///
+    /// # Returns
+    /// * `Vec<&Issue>` - 排序和过滤后的问题
+    pub fn visible_issues<'a>(&self, issues: &'a [Issue]) -> Vec<&'a Issue> {
+        ordered_issues(issues)
+            .into_iter()
+            .filter(|issue| issue.kind.severity() >= self.min_severity)
+            .collect()
+    }
+
+    /// 按排序方式排序文件（默认得分从高到低），并过滤掉不高于阈值的文件
+    ///
+    /// # Arguments
+    /// * `files` - 文件分析结果
+    ///
+    /// # Returns
+    /// * `Vec<FileAnalysisResult>` - 排序和过滤后的文件
+    pub fn rank_files(&self, files: &[FileAnalysisResult]) -> Vec<FileAnalysisResult> {
+        let mut ranked: Vec<FileAnalysisResult> = files
+            .iter()
+            .filter(|f| {
+                self.min_file_score
+                    .is_none_or(|min| f.file_score * 100.0 > min)
+            })
+            .cloned()
+            .collect();
+
+        ranked.sort_by(|a, b| {
+            let ordering = self.sort_by.compare(a, b);
+            let ordering = if self.reverse {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            // 并行分析的文件顺序不固定，相同时按路径排序保证输出稳定
+            ordering.then_with(|| a.file_path.cmp(&b.file_path))
+        });
+        ranked
+    }
+}
+
+/// 比较两个得分，NaN 始终排在最后
+///
+/// # Arguments
+/// * `a` - 得分
+/// * `b` - 得分
+/// * `descending` - 是否从高到低排序
+///
+/// # Returns
+/// * `Ordering` - 比较结果
+fn compare_scores(a: f64, b: f64, descending: bool) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        _ if descending => b.total_cmp(&a),
+        _ => a.total_cmp(&b),
+    }
+}
+
+/// 按得分从差到好排列单个文件的指标，便于看出拖累文件得分的指标
+///
+/// # Arguments
+/// * `file` - 文件分析结果
+///
+/// # Returns
+/// * `Vec<(&str, f64)>` - (指标名称, 内部得分)
+fn file_metric_scores(file: &FileAnalysisResult) -> Vec<(&str, f64)> {
+    let mut scores: Vec<(&str, f64)> = file
+        .metrics
+        .iter()
+        .map(|(name, result)| (name.as_str(), result.score))
+        .collect();
+    scores.sort_by(|a, b| compare_scores(a.1, b.1, true).then_with(|| a.0.cmp(b.0)));
+    scores
+}
+
+/// 按展示顺序排列文件中的问题
+///
+/// 先按严重程度从高到低，再按行号从小到大（文件级问题在后），再按指标名称，
+/// 最后按描述排序，保证报告不受指标遍历顺序影响。
+///
+/// # Arguments
+/// * `issues` - 问题列表
+///
+/// # Returns
+/// * `Vec<&Issue>` - 排序后的问题
+fn ordered_issues(issues: &[Issue]) -> Vec<&Issue> {
+    let mut ordered: Vec<&Issue> = issues.iter().collect();
+    ordered.sort_by(|a, b| {
+        b.kind
+            .severity()
+            .cmp(&a.kind.severity())
+            .then_with(|| compare_optional(a.line, b.line))
+            .then_with(|| compare_optional(a.metric.as_ref(), b.metric.as_ref()))
+            .then_with(|| a.message.cmp(&b.message))
+    });
+    ordered
+}
+
+/// 问题分类的展示顺序
+const ISSUE_CATEGORIES: &[&str] = &[
+    "complexity",
+    "structure",
+    "error",
+    "duplication",
+    "naming",
+    "comment",
+    "other",
+];
+
+/// 按分类分组问题（分组按固定顺序排列，组内保持原有顺序）
+///
+/// # Arguments
+/// * `issues` - 已排序的问题
+///
+/// # Returns
+/// * `Vec<(String, Vec<&Issue>)>` - （分类的 i18n 键、该分类的问题），不含空分组
+fn group_issues<'a>(issues: &[&'a Issue]) -> Vec<(String, Vec<&'a Issue>)> {
+    ISSUE_CATEGORIES
+        .iter()
+        .map(|category| {
+            let group: Vec<&Issue> = issues
+                .iter()
+                .copied()
+                .filter(|issue| issue.kind.category() == *category)
+                .collect();
+            (format!("issue.category.{}", category), group)
+        })
+        .filter(|(_, group)| !group.is_empty())
+        .collect()
+}
+
+/// 比较两个可选值，None 排在最后
+///
+/// # Arguments
+/// * `a` - 值
+/// * `b` - 值
+///
+/// # Returns
+/// * `Ordering` - 比较结果
+fn compare_optional<T: Ord>(a: Option<T>, b: Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// 计算显示数量，上限为 0 表示不限制
+///
+/// # Arguments
+/// * `limit` - 上限
+/// * `total` - 总数
+///
+/// # Returns
+/// * `usize` - 显示数量
+fn show_limit(limit: usize, total: usize) -> usize {
+    if limit == 0 {
+        total
+    } else {
+        limit.min(total)
+    }
+}
+
+/// 获取问题对应的本地化修复建议
+///
+/// # Arguments
+/// * `translator` - 翻译器
+/// * `issue` - 问题
+///
+/// # Returns
+/// * `Option<String>` - 修复建议，该类型没有建议时为 None
+fn fix_suggestion(translator: &Translator, issue: &Issue) -> Option<String> {
+    let key = issue.kind.fix_key();
+    let suggestion = translator.translate(&key);
+
+    (suggestion != key).then_some(suggestion)
+}
+
+/// 生成评级标题行，如 `评级: D 较差 (质量评分 73.00/100) 💩`
+///
+/// # Arguments
+/// * `translator` - 翻译器
+/// * `options` - 报告选项
+/// * `score` - 内部总体得分（0-1，越高越差）
+///
+/// # Returns
+/// * `String` - 标题行
+fn grade_headline(translator: &Translator, options: &ReportOptions, score: f64) -> String {
+    let grade = grade(score, &options.level_cuts);
+    format!(
+        "{}: {} {} ({} {:.*}/100) {}",
+        translator.translate("report.grade"),
+        grade.letter,
+        translator.translate(&grade.label_key()),
+        translator.translate("report.quality_score"),
+        options.precision,
+        options.polarity.display(score),
+        grade.emoji
+    )
 }
 
 impl Report {
@@ -48,6 +477,7 @@ impl Report {
         Report {
             result,
             translator: Translator::new(crate::i18n::Language::ZhCN),
+            baseline_diff: None,
         }
     }
 
@@ -59,30 +489,109 @@ impl Report {
         self.translator = translator;
     }
 
+    /// 设置基线对比结果
+    ///
+    /// # Arguments
+    /// * `baseline_diff` - 基线对比结果
+    pub fn set_baseline_diff(&mut self, baseline_diff: Option<BaselineDiff>) {
+        self.baseline_diff = baseline_diff;
+    }
+
     /// 生成控制台报告
     ///
     /// # Arguments
     /// * `options` - 报告选项
     pub fn generate_console_report(&self, options: &ReportOptions) {
+        // 安静模式只输出总体得分，JSON 格式下只输出数字
+        if options.quiet {
+            let score = options.polarity.display(self.result.code_quality_score);
+            if options.json_output {
+                println!("{:.*}", options.precision, score);
+            } else {
+                println!("score: {:.*}", options.precision, score);
+            }
+            return;
+        }
+
+        // JSON 输出即使是空项目也需要给出结果
+        if options.json_output {
+            json::JsonReport::new(&self.result, options.polarity)
+                .with_level_cuts(options.level_cuts)
+                .with_percentile(options.percentile_of(self.result.code_quality_score))
+                .with_compact(options.json_compact)
+                .with_baseline(self.baseline_diff.as_ref())
+                .generate();
+            return;
+        }
+
+        // CSV 输出即使是空项目也需要给出表头
+        if options.csv_output {
+            csv::CsvReport::new(&self.result, options).generate();
+            return;
+        }
+
         // 检查是否为空项目
         if self.result.is_empty {
             return;
         }
 
         // 选择输出格式
-        if options.markdown_output {
+        if options.html_output {
+            html::HtmlReport::new(&self.result, &self.translator, options).generate();
+        } else if options.markdown_output {
             self.generate_markdown_output(options);
         } else {
             self.generate_console_output(options);
         }
     }
 
+    /// 渲染写入文件的 JSON、CSV 或 HTML 报告
+    ///
+    /// 与标准输出的内容一致（JSON 末尾带换行）；安静模式不影响文件内容。
+    ///
+    /// # Arguments
+    /// * `options` - 报告选项
+    ///
+    /// # Returns
+    /// * `Option<String>` - 报告文本，未选择这三种格式时为 None
+    pub fn render_file_report(&self, options: &ReportOptions) -> Option<String> {
+        if options.json_output {
+            let json = json::JsonReport::new(&self.result, options.polarity)
+                .with_level_cuts(options.level_cuts)
+                .with_percentile(options.percentile_of(self.result.code_quality_score))
+                .with_compact(options.json_compact)
+                .with_baseline(self.baseline_diff.as_ref())
+                .render();
+            return match json {
+                Ok(json) => Some(json + "\n"),
+                Err(e) => {
+                    eprintln!("生成JSON报告失败: {}", e);
+                    None
+                }
+            };
+        }
+        if options.csv_output {
+            return Some(csv::CsvReport::new(&self.result, options).render());
+        }
+        if options.html_output {
+            return Some(
+                html::HtmlReport::new(&self.result, &self.translator, options).render() + "\n",
+            );
+        }
+        None
+    }
+
     /// 生成控制台输出
     ///
     /// # Arguments
     /// * `options` - 报告选项
     fn generate_console_output(&self, options: &ReportOptions) {
-        let console_report = console::ConsoleReport::new(&self.result, &self.translator, options);
+        let console_report = console::ConsoleReport::new(
+            &self.result,
+            &self.translator,
+            options,
+            self.baseline_diff.as_ref(),
+        );
 
         console_report.generate();
     }
@@ -92,9 +601,316 @@ impl Report {
     /// # Arguments
     /// * `options` - 报告选项
     fn generate_markdown_output(&self, options: &ReportOptions) {
-        let markdown_report =
-            markdown::MarkdownReport::new(&self.result, &self.translator, options);
+        let markdown_report = markdown::MarkdownReport::new(
+            &self.result,
+            &self.translator,
+            options,
+            self.baseline_diff.as_ref(),
+        );
 
         markdown_report.generate();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::LanguageType;
+    use crate::i18n::Language;
+    use crate::metrics::IssueKind;
+    use std::collections::HashMap;
+
+    /// 默认报告选项
+    pub(super) fn options() -> ReportOptions {
+        ReportOptions {
+            verbose: false,
+            top_files: 5,
+            max_issues: 5,
+            summary_only: false,
+            quiet: false,
+            markdown_output: false,
+            json_output: false,
+            html_output: false,
+            csv_output: false,
+            json_compact: false,
+            min_file_score: None,
+            sort_by: SortKey::Score,
+            min_severity: Severity::Low,
+            reverse: false,
+            polarity: Polarity::Smell,
+            percentile: None,
+            precision: 2,
+            level_cuts: DEFAULT_LEVEL_CUTS,
+        }
+    }
+
+    /// 构造指定得分的文件结果
+    fn file(path: &str, file_score: f64) -> FileAnalysisResult {
+        FileAnalysisResult {
+            file_path: path.to_string(),
+            file_score,
+            lines: 100,
+            metrics: HashMap::new(),
+            issues: Vec::new(),
+            dropped_issues: 0,
+            generic_parser: false,
+            language: LanguageType::Rust,
+        }
+    }
+
+    /// 构造包含指定文件的分析结果
+    fn result(files: Vec<FileAnalysisResult>) -> AnalysisResult {
+        AnalysisResult {
+            code_quality_score: 0.5,
+            metrics: HashMap::new(),
+            total_files: files.len(),
+            total_lines: files.iter().map(|f| f.lines).sum(),
+            is_empty: files.is_empty(),
+            run_id: String::new(),
+            parameter_distribution: Default::default(),
+            analyzed_files: files.iter().map(|f| f.file_path.clone()).collect(),
+            skipped_files: Vec::new(),
+            languages: Vec::new(),
+            files_analyzed: files,
+            cache_stats: None,
+        }
+    }
+
+    fn paths(files: &[FileAnalysisResult]) -> Vec<&str> {
+        files.iter().map(|f| f.file_path.as_str()).collect()
+    }
+
+    #[test]
+    fn min_file_score_lists_only_files_above_the_threshold() {
+        let files = vec![
+            file("clean.rs", 0.1),
+            file("meh.rs", 0.4),
+            file("edge.rs", 0.5),
+            file("bad.rs", 0.6),
+            file("awful.rs", 0.9),
+        ];
+        let filtered = ReportOptions {
+            min_file_score: Some(50.0),
+            ..options()
+        };
+
+        assert_eq!(paths(&filtered.rank_files(&files)), ["awful.rs", "bad.rs"]);
+        assert_eq!(options().rank_files(&files).len(), 5);
+    }
+
+    #[test]
+    fn zero_limits_show_everything() {
+        let unlimited = ReportOptions {
+            top_files: 0,
+            max_issues: 0,
+            ..options()
+        };
+        assert_eq!(unlimited.file_limit(12), 12);
+        assert_eq!(unlimited.issue_limit(7), 7);
+        assert_eq!(unlimited.file_limit(0), 0);
+
+        assert_eq!(options().file_limit(12), 5);
+        assert_eq!(options().file_limit(3), 3);
+    }
+
+    #[test]
+    fn zero_limits_render_without_panicking() {
+        let mut noisy = file("noisy.rs", 0.7);
+        noisy.issues = (1..=8)
+            .map(|line| Issue::new(crate::metrics::IssueKind::FunctionLong, "过长").with_line(line))
+            .collect();
+        let files = vec![noisy, file("a.rs", 0.2), file("b.rs", 0.4)];
+
+        for markdown_output in [false, true] {
+            for verbose in [false, true] {
+                let options = ReportOptions {
+                    top_files: 0,
+                    max_issues: 0,
+                    markdown_output,
+                    verbose,
+                    ..options()
+                };
+                Report::new(result(files.clone())).generate_console_report(&options);
+                Report::new(result(vec![file("only.rs", 0.9)])).generate_console_report(&options);
+            }
+        }
+    }
+
+    #[test]
+    fn nan_scores_sort_last_in_both_directions() {
+        let mut scores = [0.3, f64::NAN, 0.9, 0.1, f64::NAN];
+        scores.sort_by(|a, b| compare_scores(*a, *b, true));
+        assert_eq!(scores[..3], [0.9, 0.3, 0.1]);
+        assert!(scores[3..].iter().all(|score| score.is_nan()));
+
+        scores.sort_by(|a, b| compare_scores(*a, *b, false));
+        assert_eq!(scores[..3], [0.1, 0.3, 0.9]);
+        assert!(scores[3..].iter().all(|score| score.is_nan()));
+    }
+
+    #[test]
+    fn ranking_files_with_nan_scores_does_not_panic() {
+        let mut broken = file("broken.rs", f64::NAN);
+        broken.metrics.insert(
+            "复杂度".to_string(),
+            crate::metrics::MetricResult::new(f64::NAN, 1.0, String::new(), Vec::new()),
+        );
+        broken.metrics.insert(
+            "命名".to_string(),
+            crate::metrics::MetricResult::new(0.4, 1.0, String::new(), Vec::new()),
+        );
+        let files = vec![file("a.rs", 0.2), broken, file("b.rs", 0.6)];
+
+        let ranked = options().rank_files(&files);
+        assert_eq!(paths(&ranked), ["b.rs", "a.rs", "broken.rs"]);
+
+        let by_metric = ReportOptions {
+            sort_by: SortKey::Metric("命名".to_string()),
+            ..options()
+        };
+        assert_eq!(paths(&by_metric.rank_files(&files))[0], "broken.rs");
+
+        let metric_names: Vec<&str> = file_metric_scores(&files[1])
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(metric_names, ["命名", "复杂度"]);
+
+        Report::new(result(files)).generate_console_report(&options());
+    }
+
+    #[test]
+    fn issues_are_ordered_by_severity_then_line_then_metric() {
+        use crate::metrics::IssueKind;
+
+        let issue = |kind: IssueKind, line: Option<usize>, metric: &str| {
+            let issue = Issue::new(kind, format!("{:?}", kind)).with_metric(metric);
+            match line {
+                Some(line) => issue.with_line(line),
+                None => issue,
+            }
+        };
+        let expected = vec![
+            issue(IssueKind::HighComplexity, Some(3), "complexity"),
+            issue(IssueKind::DeepNesting, Some(3), "structure"),
+            issue(IssueKind::TooManyParameters, Some(9), "parameters"),
+            issue(IssueKind::CommentVeryLow, None, "comment"),
+            issue(IssueKind::BadNaming, Some(1), "naming"),
+            issue(IssueKind::FunctionLong, Some(20), "length"),
+            issue(IssueKind::LongLine, Some(2), "structure"),
+            issue(IssueKind::StaleTodo, None, "comment"),
+        ];
+
+        // 不同的输入顺序（模拟指标遍历顺序不固定）得到相同的输出
+        let mut reversed = expected.clone();
+        reversed.reverse();
+        let mut rotated = expected.clone();
+        rotated.rotate_left(3);
+        for input in [expected.clone(), reversed, rotated] {
+            let ordered: Vec<Issue> = ordered_issues(&input).into_iter().cloned().collect();
+            assert_eq!(ordered, expected);
+        }
+
+        let warnings = ReportOptions {
+            min_severity: Severity::Medium,
+            ..options()
+        };
+        assert_eq!(warnings.visible_issues(&expected).len(), 6);
+    }
+
+    #[test]
+    fn console_and_markdown_report_the_same_quality_level() {
+        let translator = Translator::new(crate::i18n::Language::ZhCN);
+        let custom_cuts = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+        let cases = [
+            (0.0, DEFAULT_LEVEL_CUTS, "level.clean"),
+            (0.3, DEFAULT_LEVEL_CUTS, "level.bad"),
+            (0.5, DEFAULT_LEVEL_CUTS, "level.terrible"),
+            (0.97, DEFAULT_LEVEL_CUTS, "level.disaster.worst"),
+            (1.0, DEFAULT_LEVEL_CUTS, "level.disaster.ultimate"),
+            (0.015, custom_cuts, "level.mild"),
+            (0.3, custom_cuts, "level.disaster.ultimate"),
+        ];
+
+        for (score, level_cuts, expected) in cases {
+            let mut result = result(vec![file("a.rs", score)]);
+            result.code_quality_score = score;
+            let options = ReportOptions {
+                level_cuts,
+                ..options()
+            };
+
+            let console = console::ConsoleReport::new(&result, &translator, &options, None)
+                .quality_level_line();
+            let markdown = markdown::MarkdownReport::new(&result, &translator, &options, None)
+                .quality_level_line();
+
+            let level = translator.translate(expected);
+            let description = translator.translate(&format!("{}.description", expected));
+            for line in [&console, &markdown] {
+                assert!(line.contains(&level), "{} -> {}", score, line);
+                assert!(line.contains(&description), "{} -> {}", score, line);
+            }
+        }
+    }
+
+    #[test]
+    fn quality_polarity_inverts_displayed_scores() {
+        assert!((Polarity::Smell.display(0.73) - 73.0).abs() < 1e-9);
+        assert!((Polarity::Quality.display(0.73) - 27.0).abs() < 1e-9);
+        assert_eq!(Polarity::Quality.display(0.0), 100.0);
+        assert_eq!(Polarity::Quality.display(1.0), 0.0);
+
+        // 变差（内部得分上升）在质量分下表现为下降
+        assert!((Polarity::Smell.display_delta(0.1) - 10.0).abs() < 1e-9);
+        assert!((Polarity::Quality.display_delta(0.1) + 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn grade_headline_follows_polarity_but_keeps_the_grade() {
+        let translator = Translator::new(crate::i18n::Language::ZhCN);
+        let quality = ReportOptions {
+            polarity: Polarity::Quality,
+            ..options()
+        };
+
+        let smell_line = grade_headline(&translator, &options(), 0.73);
+        let quality_line = grade_headline(&translator, &quality, 0.73);
+        assert!(smell_line.contains("73.00/100"), "{}", smell_line);
+        assert!(quality_line.contains("27.00/100"), "{}", quality_line);
+
+        let letter = grade(0.73, &DEFAULT_LEVEL_CUTS).letter;
+        assert!(smell_line.contains(&format!(": {} ", letter)));
+        assert!(quality_line.contains(&format!(": {} ", letter)));
+    }
+
+    #[test]
+    fn complexity_issue_has_a_fix_suggestion_in_both_locales() {
+        let issue = Issue::new(IssueKind::HighComplexity, "复杂度过高").with_line(3);
+
+        assert_eq!(
+            fix_suggestion(&Translator::new(Language::ZhCN), &issue).as_deref(),
+            Some("把嵌套的条件分支提取为独立的辅助函数，或用提前返回减少分支")
+        );
+        assert_eq!(
+            fix_suggestion(&Translator::new(Language::EnUS), &issue).as_deref(),
+            Some("Extract the nested conditionals into helper functions, or use early returns to cut branches")
+        );
+    }
+
+    #[test]
+    fn every_issue_kind_has_a_fix_suggestion_in_both_locales() {
+        use clap::ValueEnum;
+
+        for language in [Language::ZhCN, Language::EnUS] {
+            let translator = Translator::new(language);
+            let missing: Vec<&str> = IssueKind::value_variants()
+                .iter()
+                .filter(|kind| **kind != IssueKind::Custom)
+                .filter(|kind| fix_suggestion(&translator, &Issue::new(**kind, "")).is_none())
+                .map(|kind| kind.key())
+                .collect();
+            assert!(missing.is_empty(), "{:?}: {:?}", language, missing);
+        }
+    }
+}
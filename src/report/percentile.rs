@@ -0,0 +1,129 @@
+//! # 参考分布百分位
+//!
+//! 把总体屎气指数放到参考语料的得分分布中，给出"比 X% 的项目更屎"的相对位置
+
+use crate::error::{AppError, AppResult};
+use std::fs;
+use std::path::Path;
+
+/// 内置参考分布（若干开源项目的屎气指数，0-100，越高越差）
+const DEFAULT_REFERENCE_SCORES: &[f64] = &[
+    12.4, 15.8, 18.3, 20.1, 22.7, 24.5, 26.0, 27.9, 29.3, 30.8, 32.2, 33.9, 35.4, 37.1, 39.0, 41.6,
+    44.2, 47.5, 51.3, 56.8, 63.0,
+];
+
+/// 参考得分分布
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceDistribution {
+    /// 已排序的参考屎气指数（0-100）
+    scores: Vec<f64>,
+}
+
+impl Default for ReferenceDistribution {
+    /// 使用内置参考分布
+    fn default() -> Self {
+        Self::from_scores(DEFAULT_REFERENCE_SCORES.to_vec())
+    }
+}
+
+impl ReferenceDistribution {
+    /// 根据参考得分创建分布（忽略非有限值）
+    ///
+    /// # Arguments
+    /// * `scores` - 参考屎气指数（0-100）
+    ///
+    /// # Returns
+    /// * `Self` - 参考分布
+    pub fn from_scores(mut scores: Vec<f64>) -> Self {
+        scores.retain(|score| score.is_finite());
+        scores.sort_by(f64::total_cmp);
+        ReferenceDistribution { scores }
+    }
+
+    /// 从 JSON 文件读取参考分布（屎气指数数组，0-100）
+    ///
+    /// # Arguments
+    /// * `path` - 分布文件路径
+    ///
+    /// # Returns
+    /// * `AppResult<Self>` - 参考分布
+    pub fn load(path: &Path) -> AppResult<Self> {
+        if !path.is_file() {
+            return Err(AppError::FileNotFound(path.to_path_buf()));
+        }
+
+        let content = fs::read_to_string(path)?;
+        let scores: Vec<f64> = serde_json::from_str(&content).map_err(|e| {
+            AppError::ConfigError(format!("参考分布 {} 格式无效: {}", path.display(), e))
+        })?;
+
+        let distribution = Self::from_scores(scores);
+        if distribution.scores.is_empty() {
+            return Err(AppError::ConfigError(format!(
+                "参考分布 {} 中没有有效得分",
+                path.display()
+            )));
+        }
+
+        Ok(distribution)
+    }
+
+    /// 计算屎气指数在参考分布中的百分位
+    ///
+    /// # Arguments
+    /// * `score` - 内部得分（0-1，越高越差）
+    ///
+    /// # Returns
+    /// * `f64` - 参考项目中得分严格低于该得分（即比它干净）的比例（0-100）
+    pub fn percentile(&self, score: f64) -> f64 {
+        if self.scores.is_empty() {
+            return 0.0;
+        }
+
+        let smell = score * 100.0;
+        let cleaner = self.scores.partition_point(|&reference| reference < smell);
+        cleaner as f64 * 100.0 / self.scores.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_map_to_expected_percentiles() {
+        let distribution = ReferenceDistribution::from_scores(vec![40.0, 10.0, 30.0, 20.0]);
+        let table = [
+            (0.0, 0.0),
+            (0.05, 0.0),
+            // 与参考得分相等时不计入"更干净"
+            (0.1, 0.0),
+            (0.15, 25.0),
+            (0.3, 50.0),
+            (0.35, 75.0),
+            (0.5, 100.0),
+            (1.0, 100.0),
+        ];
+
+        for (score, expected) in table {
+            assert_eq!(distribution.percentile(score), expected, "score {}", score);
+        }
+    }
+
+    #[test]
+    fn default_distribution_places_a_known_score() {
+        // 内置分布 21 个得分中有 9 个低于 30
+        let percentile = ReferenceDistribution::default().percentile(0.3);
+        assert!((percentile - 9.0 * 100.0 / 21.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn non_finite_reference_scores_are_ignored() {
+        let distribution = ReferenceDistribution::from_scores(vec![f64::NAN, 20.0, f64::INFINITY]);
+        assert_eq!(distribution.percentile(0.3), 100.0);
+        assert_eq!(
+            ReferenceDistribution::from_scores(Vec::new()).percentile(0.3),
+            0.0
+        );
+    }
+}
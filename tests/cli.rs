@@ -0,0 +1,434 @@
+//! # 命令行集成测试
+//!
+//! 运行编译好的可执行文件，检查退出状态和输出
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// 在系统临时目录下创建包含指定文件的目录，测试结束时删除
+struct Fixture {
+    root: PathBuf,
+}
+
+impl Fixture {
+    fn new(label: &str, files: &[(&str, &str)]) -> Self {
+        let root = std::env::temp_dir().join(format!("fsc-cli-{}-{}", label, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (relative, content) in files {
+            let path = root.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        Fixture { root }
+    }
+
+    fn path(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// 运行命令行工具（参数原样传入）
+fn run_args(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_fuck-shit-code"))
+        .args(args)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap()
+}
+
+/// 运行命令行工具分析指定路径
+fn run(args: &[&str], path: &Path) -> Output {
+    let path = path.to_str().unwrap();
+    run_args(&[args, &[path]].concat())
+}
+
+/// 生成包含 `branches` 个 if 分支的 Python 函数
+fn branchy_function(branches: usize) -> String {
+    let body: String = (0..branches)
+        .map(|i| format!("    if value == {}:\n        return {}\n", i, i))
+        .collect();
+    format!(
+        "def classify(value):\n    \"\"\"Classify the value.\"\"\"\n{}    return -1\n",
+        body
+    )
+}
+
+const CLEAN: &str = "def add(a, b):\n    \"\"\"Add two numbers.\"\"\"\n    return a + b\n";
+
+#[test]
+fn deny_fails_and_lists_denied_issue_locations() {
+    let fixture = Fixture::new(
+        "deny",
+        &[("clean.py", CLEAN), ("branchy.py", &branchy_function(20))],
+    );
+
+    let output = run(&["--deny", "high_complexity", "--quiet"], fixture.path());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(
+        stderr.contains("branchy.py:1 [high_complexity]"),
+        "{}",
+        stderr
+    );
+    assert!(!stderr.contains("clean.py"), "{}", stderr);
+}
+
+#[test]
+fn deny_passes_when_no_denied_issue_is_present() {
+    let fixture = Fixture::new("deny-clean", &[("clean.py", CLEAN)]);
+
+    let output = run(
+        &["--deny", "high_complexity,deep_nesting", "--quiet"],
+        fixture.path(),
+    );
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn bench_parse_reports_non_zero_throughput() {
+    let fixture = Fixture::new(
+        "bench",
+        &[
+            ("clean.py", CLEAN),
+            ("branchy.py", &branchy_function(5)),
+            ("util.js", "function twice(x) {\n  return x * 2;\n}\n"),
+        ],
+    );
+
+    let output = run(&["--bench-parse", "5", "--lang", "en-US"], fixture.path());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Parsed 3 files"), "{}", stdout);
+    assert!(stdout.contains(" 5 times"), "{}", stdout);
+
+    let throughput = stdout
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Throughput: "))
+        .unwrap_or_else(|| panic!("{}", stdout));
+    let lines_per_second: f64 = throughput
+        .split_whitespace()
+        .next()
+        .and_then(|value| value.parse().ok())
+        .unwrap();
+    assert!(lines_per_second > 0.0, "{}", stdout);
+}
+
+#[test]
+fn max_metric_and_fail_under_gate_independently() {
+    let fixture = Fixture::new(
+        "max-metric",
+        &[("clean.py", CLEAN), ("branchy.py", &branchy_function(20))],
+    );
+
+    let passing = run(
+        &[
+            "--quiet",
+            "--fail-under",
+            "100",
+            "--max-metric",
+            "complexity=100",
+        ],
+        fixture.path(),
+    );
+    assert_eq!(
+        passing.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&passing.stderr)
+    );
+
+    let overall = run(
+        &[
+            "--quiet",
+            "--fail-under",
+            "0",
+            "--max-metric",
+            "complexity=100",
+        ],
+        fixture.path(),
+    );
+    let stderr = String::from_utf8_lossy(&overall.stderr);
+    assert_eq!(overall.status.code(), Some(1), "{}", stderr);
+    assert!(stderr.contains("--fail-under"), "{}", stderr);
+    assert!(!stderr.contains("--max-metric"), "{}", stderr);
+
+    let per_metric = run(
+        &[
+            "--quiet",
+            "--fail-under",
+            "100",
+            "--max-metric",
+            "complexity=0",
+        ],
+        fixture.path(),
+    );
+    let stderr = String::from_utf8_lossy(&per_metric.stderr);
+    assert_eq!(per_metric.status.code(), Some(1), "{}", stderr);
+    assert!(stderr.contains("循环复杂度"), "{}", stderr);
+    assert!(!stderr.contains("--fail-under"), "{}", stderr);
+}
+
+#[test]
+fn output_flag_writes_csv_report_to_file() {
+    let fixture = Fixture::new(
+        "output",
+        &[("clean.py", CLEAN), ("branchy.py", &branchy_function(20))],
+    );
+    let report = fixture.path().join("report.csv");
+
+    let output = run(&["--csv", "-o", report.to_str().unwrap()], fixture.path());
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(output.stdout.is_empty());
+
+    let csv = fs::read_to_string(&report).unwrap();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert!(rows[0].starts_with("path,file_score,issues"));
+    assert_eq!(rows.len(), 3);
+    assert!(rows[1].contains("branchy.py"), "{}", csv);
+
+    // -o 只支持写入文件的格式
+    let rejected = run(&["-o", report.to_str().unwrap()], fixture.path());
+    assert!(!rejected.status.success());
+}
+
+#[test]
+fn version_matches_the_crate_version() {
+    let output = run_args(&["--version"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        format!("fuck-shit-code {}", env!("CARGO_PKG_VERSION"))
+    );
+
+    let info = run_args(&["info"]);
+    assert!(info.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&info.stdout).lines().next(),
+        Some(format!("fuck-shit-code {}", env!("CARGO_PKG_VERSION")).as_str())
+    );
+}
+
+#[test]
+fn merge_sums_issues_and_weights_the_score_by_lines() {
+    let fixture = Fixture::new(
+        "merge",
+        &[
+            (
+                "small/params.py",
+                "def f(a, b, c, d, e, f, g, h):\n    return 1\n",
+            ),
+            ("large/branchy.py", &branchy_function(20)),
+            ("large/clean.py", CLEAN),
+        ],
+    );
+    let json_of = |name: &str| fixture.path().join(name).to_str().unwrap().to_string();
+    let load = |name: &str| -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(fixture.path().join(name)).unwrap()).unwrap()
+    };
+    let issue_count = |result: &serde_json::Value| -> usize {
+        result["files_analyzed"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|file| file["issues"].as_array().unwrap().len())
+            .sum()
+    };
+
+    for part in ["small", "large"] {
+        let output = run(
+            &["--json", "-o", &json_of(&format!("{part}.json"))],
+            &fixture.path().join(part),
+        );
+        assert!(output.status.success());
+    }
+    let output = run_args(&[
+        "--json",
+        "-o",
+        &json_of("merged.json"),
+        "merge",
+        &json_of("small.json"),
+        &json_of("large.json"),
+    ]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let (small, large, merged) = (load("small.json"), load("large.json"), load("merged.json"));
+    assert_eq!(
+        issue_count(&merged),
+        issue_count(&small) + issue_count(&large)
+    );
+
+    let lines = |result: &serde_json::Value| result["total_lines"].as_f64().unwrap();
+    let score = |result: &serde_json::Value| result["code_quality_score"].as_f64().unwrap();
+    assert_eq!(lines(&merged), lines(&small) + lines(&large));
+    let weighted = (score(&small) * lines(&small) + score(&large) * lines(&large)) / lines(&merged);
+    assert!((score(&merged) - weighted).abs() < 1e-9);
+    // 行数不同，加权平均与简单平均不同
+    assert!((score(&merged) - (score(&small) + score(&large)) / 2.0).abs() > 1e-6);
+}
+
+#[test]
+fn mostly_unsupported_files_trigger_the_generic_parser_warning() {
+    let ruby = "def greet(name)\n  if name\n    puts name\n  end\nend\n";
+    let fixture = Fixture::new(
+        "generic-parser",
+        &[
+            ("a.rb", ruby),
+            ("b.rb", ruby),
+            ("c.rb", ruby),
+            ("clean.py", CLEAN),
+        ],
+    );
+    let path = |name: &str| fixture.path().join(name).to_str().unwrap().to_string();
+    let warning = "files could only be analyzed by the generic parser";
+
+    let mostly_unsupported = run_args(&[
+        "--lang",
+        "en-US",
+        &path("a.rb"),
+        &path("b.rb"),
+        &path("c.rb"),
+        &path("clean.py"),
+    ]);
+    let stdout = String::from_utf8_lossy(&mostly_unsupported.stdout);
+    assert!(stdout.contains(&format!("3/4 {}", warning)), "{}", stdout);
+
+    let supported = run_args(&["--lang", "en-US", &path("clean.py")]);
+    let stdout = String::from_utf8_lossy(&supported.stdout);
+    assert!(!stdout.contains(warning), "{}", stdout);
+}
+
+#[test]
+fn ext_filter_analyzes_only_matching_files() {
+    let fixture = Fixture::new(
+        "ext",
+        &[
+            ("clean.py", CLEAN),
+            ("pkg/branchy.py", &branchy_function(3)),
+            (
+                "lib.rs",
+                "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+            ),
+            ("app.js", "function add(a, b) {\n  return a + b;\n}\n"),
+        ],
+    );
+
+    let output = run(&["--json", "--ext", "py"], fixture.path());
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let root = format!("{}/", fixture.path().display());
+    let relative = |value: &serde_json::Value| {
+        value
+            .as_str()
+            .unwrap()
+            .trim_start_matches(&root)
+            .to_string()
+    };
+
+    let mut analyzed: Vec<String> = report["analyzed_files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(relative)
+        .collect();
+    analyzed.sort();
+    assert_eq!(analyzed, ["clean.py", "pkg/branchy.py"]);
+    for entry in report["files_analyzed"].as_array().unwrap() {
+        assert!(relative(&entry["file_path"]).ends_with(".py"));
+    }
+
+    let mut skipped: Vec<(String, &str)> = report["skipped_files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| (relative(&entry["path"]), entry["reason"].as_str().unwrap()))
+        .collect();
+    skipped.sort();
+    assert_eq!(
+        skipped,
+        [
+            ("app.js".to_string(), "extension_filter"),
+            ("lib.rs".to_string(), "extension_filter"),
+        ]
+    );
+}
+
+#[test]
+fn precision_controls_the_decimal_places_of_scores() {
+    let fixture = Fixture::new("precision", &[("branchy.py", &branchy_function(6))]);
+    let overall_score = |precision: &str| {
+        let output = run(
+            &["--lang", "en-US", "--markdown", "--precision", precision],
+            fixture.path(),
+        );
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        let line = stdout
+            .lines()
+            .find(|line| line.starts_with("- **Quality Score**: "))
+            .unwrap_or_else(|| panic!("{}", stdout))
+            .to_string();
+        line.trim_start_matches("- **Quality Score**: ")
+            .trim_end_matches("/100")
+            .to_string()
+    };
+
+    let integer = overall_score("0");
+    assert!(integer.parse::<u32>().is_ok(), "{}", integer);
+
+    let four = overall_score("4");
+    let (whole, decimals) = four.split_once('.').unwrap();
+    assert!(whole.parse::<u32>().is_ok(), "{}", four);
+    assert_eq!(decimals.len(), 4, "{}", four);
+    assert_eq!(
+        four.parse::<f64>().unwrap().round(),
+        integer.parse::<f64>().unwrap()
+    );
+}
+
+#[test]
+fn baseline_auto_rejects_multiple_paths() {
+    let fixture = Fixture::new(
+        "baseline-auto",
+        &[("a/clean.py", CLEAN), ("b/clean.py", CLEAN)],
+    );
+    let a = fixture.path().join("a");
+    let b = fixture.path().join("b");
+
+    let output = run_args(&[
+        "--baseline-auto",
+        "--quiet",
+        a.to_str().unwrap(),
+        b.to_str().unwrap(),
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("--baseline-auto"), "{}", stderr);
+}